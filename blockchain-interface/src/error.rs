@@ -69,6 +69,8 @@ pub enum BlockchainError {
     InvalidEpoch,
     #[error("Accounts diff not found")]
     AccountsDiffNotFound,
+    #[error("State at block {0} is not available, it has been pruned or was never stored")]
+    StateNotAvailable(u32),
     #[error(
         "A full genesis config is required for initializing a history node on \
         the mainnet for the first time. Obtain the full genesis config (it's \
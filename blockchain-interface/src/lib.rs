@@ -5,8 +5,10 @@ pub use error::{
     BlockchainError, BlockchainEvent, ChunksPushError, ChunksPushResult, Direction, ForkEvent,
     PushError, PushResult,
 };
+pub use state_proof::{StateProof, StateProofError};
 
 mod abstract_blockchain;
 mod chain_info;
 mod chain_ordering;
 mod error;
+mod state_proof;
@@ -0,0 +1,133 @@
+use std::collections::BTreeMap;
+
+use nimiq_account::{Account, Staker, StakingContractStore, Validator};
+use nimiq_block::{Block, MacroBlock, MicroBlock};
+use nimiq_hash::Blake2bHash;
+use nimiq_keys::Address;
+use nimiq_primitives::{key_nibbles::KeyNibbles, policy::Policy, trie::trie_proof::TrieProof};
+use nimiq_serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// A proof of the values of some keys in the accounts trie of a block.
+///
+/// It contains the header of the block, such that a client that trusts the block hash can verify
+/// the values without knowing the state root.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct StateProof {
+    /// The block the proof was created against, without body and justification.
+    pub block: Block,
+    pub proof: TrieProof,
+}
+
+#[derive(Clone, Debug, Error, PartialEq, Eq)]
+pub enum StateProofError {
+    #[error("Proof is for block {actual}, expected block {expected}")]
+    BlockHashMismatch {
+        expected: Blake2bHash,
+        actual: Blake2bHash,
+    },
+    #[error("Invalid trie proof: {0}")]
+    InvalidProof(&'static str),
+    #[error("Proven value can't be deserialized")]
+    InvalidValue,
+}
+
+impl StateProof {
+    /// Creates a state proof for the given block, dropping its body and justification.
+    pub fn new(block: &Block, proof: TrieProof) -> Self {
+        let block = match block {
+            Block::Micro(block) => Block::Micro(MicroBlock {
+                header: block.header.clone(),
+                justification: None,
+                body: None,
+            }),
+            Block::Macro(block) => Block::Macro(MacroBlock {
+                header: block.header.clone(),
+                body: None,
+                justification: None,
+            }),
+        };
+        StateProof { block, proof }
+    }
+
+    /// Returns the key of the account with the given address in the accounts trie.
+    pub fn account_key(address: &Address) -> KeyNibbles {
+        KeyNibbles::from(address)
+    }
+
+    /// Returns the key of the staker with the given address in the accounts trie.
+    pub fn staker_key(address: &Address) -> KeyNibbles {
+        &KeyNibbles::from(&Policy::STAKING_CONTRACT_ADDRESS)
+            + &StakingContractStore::staker_key(address)
+    }
+
+    /// Returns the key of the validator with the given address in the accounts trie.
+    pub fn validator_key(address: &Address) -> KeyNibbles {
+        &KeyNibbles::from(&Policy::STAKING_CONTRACT_ADDRESS)
+            + &StakingContractStore::validator_key(address)
+    }
+
+    /// Verifies the proof against a trusted block hash and returns the serialized values of the
+    /// given keys, `None` for keys that are proven to be absent.
+    pub fn verify(
+        self,
+        block_hash: &Blake2bHash,
+        keys: &[KeyNibbles],
+    ) -> Result<BTreeMap<KeyNibbles, Option<Vec<u8>>>, StateProofError> {
+        let actual = self.block.hash();
+        if actual != *block_hash {
+            return Err(StateProofError::BlockHashMismatch {
+                expected: block_hash.clone(),
+                actual,
+            });
+        }
+
+        self.proof
+            .verify_values(self.block.state_root(), &keys.iter().collect::<Vec<_>>())
+            .map_err(|error| StateProofError::InvalidProof(error.0))
+    }
+
+    /// Verifies the proof against a trusted block hash and returns the proven account. Accounts
+    /// that don't exist are returned as empty basic accounts, like the node does.
+    pub fn verify_account(
+        self,
+        block_hash: &Blake2bHash,
+        address: &Address,
+    ) -> Result<Account, StateProofError> {
+        Ok(self
+            .verify_value(block_hash, Self::account_key(address))?
+            .unwrap_or_default())
+    }
+
+    /// Verifies the proof against a trusted block hash and returns the proven staker, if any.
+    pub fn verify_staker(
+        self,
+        block_hash: &Blake2bHash,
+        address: &Address,
+    ) -> Result<Option<Staker>, StateProofError> {
+        self.verify_value(block_hash, Self::staker_key(address))
+    }
+
+    /// Verifies the proof against a trusted block hash and returns the proven validator, if any.
+    pub fn verify_validator(
+        self,
+        block_hash: &Blake2bHash,
+        address: &Address,
+    ) -> Result<Option<Validator>, StateProofError> {
+        self.verify_value(block_hash, Self::validator_key(address))
+    }
+
+    fn verify_value<T: Deserialize>(
+        self,
+        block_hash: &Blake2bHash,
+        key: KeyNibbles,
+    ) -> Result<Option<T>, StateProofError> {
+        let mut values = self.verify(block_hash, &[key.clone()])?;
+        values
+            .remove(&key)
+            .flatten()
+            .map(|value| T::deserialize_from_vec(&value))
+            .transpose()
+            .map_err(|_| StateProofError::InvalidValue)
+    }
+}
@@ -1,9 +1,12 @@
+use std::collections::BTreeMap;
+
 use nimiq_account::{
     Account, Accounts, BlockLogger, BlockState, RevertInfo, TransactionOperationReceipt,
 };
 use nimiq_block::{Block, BlockError, SkipBlockInfo};
-use nimiq_blockchain_interface::PushError;
+use nimiq_blockchain_interface::{AbstractBlockchain, BlockchainError, PushError, StateProof};
 use nimiq_database::{mdbx::MdbxReadTransaction, traits::Database};
+use nimiq_hash::Blake2bHash;
 use nimiq_keys::Address;
 use nimiq_primitives::{
     key_nibbles::KeyNibbles,
@@ -211,6 +214,204 @@ impl Blockchain {
         self.state.accounts.get_proof(Some(&txn), keys)
     }
 
+    /// Produces a proof of the values of the given keys against the state root of the current
+    /// head block.
+    pub fn get_state_proof(&self, keys: Vec<&KeyNibbles>) -> Result<StateProof, IncompleteTrie> {
+        let proof = self.get_accounts_proof(keys)?;
+
+        Ok(StateProof::new(&self.state.main_chain.head, proof))
+    }
+
+    /// Produces a proof of the values of the given keys against the state root of a past main
+    /// chain block, e.g. the latest election block. The accounts trie of that block is rebuilt in
+    /// memory on top of a read transaction by reverting the changes of all later blocks, the
+    /// database is never written to. The same restrictions as for
+    /// [`Blockchain::get_historical_value`] apply.
+    pub fn get_historical_state_proof(
+        &self,
+        keys: Vec<&KeyNibbles>,
+        block_hash: &Blake2bHash,
+    ) -> Result<StateProof, BlockchainError> {
+        let txn = self.read_transaction();
+        let chain_info = self
+            .chain_store
+            .get_chain_info(block_hash, false, Some(&txn))?;
+        let block_number = chain_info.head.block_number();
+        if !chain_info.on_main_chain {
+            return Err(BlockchainError::StateNotAvailable(block_number));
+        }
+        if *block_hash == self.head_hash() {
+            return self
+                .get_state_proof(keys)
+                .map_err(|_| BlockchainError::StateNotAvailable(block_number));
+        }
+
+        let diffs = self.get_accounts_revert_diffs(&txn, block_hash, block_number)?;
+
+        let proof = self
+            .state
+            .accounts
+            .tree
+            .get_proof_with_diffs(&txn, diffs, keys)
+            .map_err(|_| BlockchainError::StateNotAvailable(block_number))?;
+
+        Ok(StateProof::new(&chain_info.head, proof))
+    }
+
+    /// Gets an accounts chunk given a start key and a limit as it was after the main chain block
+    /// with the given hash. The chunk is read from the current state with the changes of all later
+    /// blocks reverted on top, so it might contain more or fewer accounts than the limit. The same
+    /// restrictions as for [`Blockchain::get_historical_value`] apply.
+    pub fn get_historical_accounts_chunk(
+        &self,
+        start: KeyNibbles,
+        limit: usize,
+        block_hash: &Blake2bHash,
+    ) -> Result<AccountsChunk, BlockchainError> {
+        let txn = self.read_transaction();
+        let chain_info = self
+            .chain_store
+            .get_chain_info(block_hash, false, Some(&txn))?;
+        let block_number = chain_info.head.block_number();
+        if !chain_info.on_main_chain || !self.state.accounts.is_complete(Some(&txn)) {
+            return Err(BlockchainError::StateNotAvailable(block_number));
+        }
+
+        let diffs = self.get_accounts_revert_diffs(&txn, block_hash, block_number)?;
+        let trie_chunk = self
+            .state
+            .accounts
+            .get_chunk(start.clone(), limit, Some(&txn));
+        let end_key = trie_chunk.end_key;
+        let mut items: BTreeMap<KeyNibbles, Vec<u8>> = trie_chunk
+            .items
+            .into_iter()
+            .map(|item| (item.key, item.value))
+            .collect();
+
+        // The diffs are ordered from the head backwards, so the values of older diffs win.
+        let mut reverted_values = BTreeMap::new();
+        for diff in diffs {
+            reverted_values.extend(diff.0);
+        }
+        for (key, value) in reverted_values {
+            if key < start || end_key.as_ref().is_some_and(|end_key| key >= *end_key) {
+                continue;
+            }
+            match value {
+                Some(value) => items.insert(key, value),
+                None => items.remove(&key),
+            };
+        }
+
+        let accounts = items
+            .into_iter()
+            .filter_map(|(key, value)| Some((key.to_address()?, value)))
+            .map(|(address, value)| {
+                Account::deserialize_from_vec(&value)
+                    .map(|account| (address, account))
+                    .map_err(|_| BlockchainError::InconsistentState)
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(AccountsChunk { end_key, accounts })
+    }
+
+    /// Collects the revert diffs that rebuild the accounts trie of the main chain block with the
+    /// given hash and number from the current state, starting with the diff of the head.
+    fn get_accounts_revert_diffs(
+        &self,
+        txn: &MdbxReadTransaction,
+        block_hash: &Blake2bHash,
+        block_number: u32,
+    ) -> Result<Vec<TrieDiff>, BlockchainError> {
+        if self.block_number() - block_number > self.config.max_historical_state_distance {
+            return Err(BlockchainError::StateNotAvailable(block_number));
+        }
+
+        let mut diffs = vec![];
+        let mut hash = self.head_hash();
+        while hash != *block_hash {
+            let diff = self
+                .chain_store
+                .get_accounts_revert_diff(&hash, Some(txn))
+                .ok_or(BlockchainError::StateNotAvailable(block_number))?;
+            diffs.push(diff);
+
+            hash = self
+                .chain_store
+                .get_chain_info(&hash, false, Some(txn))?
+                .head
+                .parent_hash()
+                .clone();
+        }
+        Ok(diffs)
+    }
+
+    /// Returns the value of a key in the accounts trie as it was after the main chain block at
+    /// `block_number`. The value is reconstructed from the current state by reverting the changes
+    /// of all later blocks, which only works as long as their revert diffs are stored. These are
+    /// only kept by history nodes and pruned together with the blocks. States further behind the
+    /// head than the configured `max_historical_state_distance` are not reconstructed.
+    pub fn get_historical_value<T: Deserialize>(
+        &self,
+        key: &KeyNibbles,
+        block_number: u32,
+    ) -> Result<Option<T>, BlockchainError> {
+        let head_number = self.block_number();
+        if block_number > head_number {
+            return Err(BlockchainError::BlockNotFound(block_number));
+        }
+        if head_number - block_number > self.config.max_historical_state_distance {
+            return Err(BlockchainError::StateNotAvailable(block_number));
+        }
+
+        let txn = self.read_transaction();
+        let mut hash = self.head_hash();
+        let mut reverted_value = None;
+        for _ in block_number..head_number {
+            let diff = self
+                .chain_store
+                .get_accounts_revert_diff(&hash, Some(&txn))
+                .ok_or(BlockchainError::StateNotAvailable(block_number))?;
+            if let Some(value) = diff.0.get(key) {
+                reverted_value = Some(value.clone());
+            }
+
+            hash = self
+                .chain_store
+                .get_chain_info(&hash, false, Some(&txn))?
+                .head
+                .parent_hash()
+                .clone();
+        }
+
+        match reverted_value {
+            Some(value) => value
+                .map(|value| T::deserialize_from_vec(&value))
+                .transpose()
+                .map_err(|_| BlockchainError::InconsistentState),
+            None => self
+                .state
+                .accounts
+                .tree
+                .get(&txn, key)
+                .map_err(|_| BlockchainError::StateNotAvailable(block_number)),
+        }
+    }
+
+    /// Returns the account at the given address as it was after the main chain block at
+    /// `block_number`. See [`Blockchain::get_historical_value`].
+    pub fn get_historical_account(
+        &self,
+        address: &Address,
+        block_number: u32,
+    ) -> Result<Account, BlockchainError> {
+        Ok(self
+            .get_historical_value(&KeyNibbles::from(address), block_number)?
+            .unwrap_or_default())
+    }
+
     /// Gets an accounts chunk given a start key and a limit
     pub fn get_accounts_chunk(
         &self,
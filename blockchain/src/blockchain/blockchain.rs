@@ -71,6 +71,9 @@ pub struct BlockchainConfig {
     pub max_epochs_stored: u32,
     /// Enables/Disables indices in the history store.
     pub index_history: bool,
+    /// Maximum number of blocks behind the head for which past states of the accounts trie are
+    /// reconstructed. Older states are reported as not available.
+    pub max_historical_state_distance: u32,
 }
 
 impl Default for BlockchainConfig {
@@ -79,6 +82,7 @@ impl Default for BlockchainConfig {
             keep_history: true,
             max_epochs_stored: Policy::MIN_EPOCHS_STORED,
             index_history: true,
+            max_historical_state_distance: Policy::blocks_per_epoch(),
         }
     }
 }
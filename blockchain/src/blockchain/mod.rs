@@ -9,6 +9,7 @@ pub mod inherents;
 pub mod push;
 pub(super) mod rebranch_utils;
 pub mod slots;
+pub mod snapshot;
 pub mod verify;
 pub mod wrappers;
 pub mod zkp_sync;
@@ -627,7 +627,16 @@ impl Blockchain {
                 self.metrics.note_invalid_block();
             })?;
             if is_complete {
-                let recorded_diff = txn.stop_recording().into_forward_diff();
+                let recorded_changes = txn.stop_recording();
+                if self.config.keep_history {
+                    // Allows history nodes to reconstruct past states of the accounts trie.
+                    self.chain_store.put_accounts_revert_diff(
+                        txn.raw(),
+                        &block.hash(),
+                        &recorded_changes.clone().into_backward_diff(),
+                    );
+                }
+                let recorded_diff = recorded_changes.into_forward_diff();
                 self.chain_store
                     .put_accounts_diff(txn.raw(), &block.hash(), &recorded_diff);
             }
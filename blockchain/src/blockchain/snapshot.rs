@@ -0,0 +1,244 @@
+use nimiq_account::AccountsTrieTable;
+use nimiq_block::{Block, MacroBlock};
+use nimiq_blockchain_interface::BlockchainError;
+use nimiq_database::{mdbx::MdbxReadTransaction, traits::Table};
+use nimiq_hash::Blake2bHash;
+use nimiq_primitives::policy::Policy;
+use nimiq_zkp::{verify::verify, NanoProof, ZKP_VERIFYING_DATA};
+
+use crate::{
+    chain_store::{
+        AccountsDiffTable, AccountsRevertDiffTable, ChainTable, HeadTable, HeightIndex,
+        PushedBlockTable, RevertTable, StoredBlockTable,
+    },
+    history::{
+        AddressTable, BlockTxnsTable, HistoricTransactionTable, HistoryTreeTable,
+        IndexVersionTable, InherentTable, LastLeafTable, TxHashTable, TxnHashesTable,
+    },
+    interface::HistoryInterface,
+    Blockchain,
+};
+
+/// Implements methods to support database snapshots.
+impl Blockchain {
+    /// The tables that make up the chain state and are included in database snapshots.
+    /// Node-local data, such as the wallet or the validator state, is not part of a snapshot.
+    pub const SNAPSHOT_TABLES: &'static [&'static str] = &[
+        HeadTable::NAME,
+        ChainTable::NAME,
+        PushedBlockTable::NAME,
+        StoredBlockTable::NAME,
+        HeightIndex::NAME,
+        RevertTable::NAME,
+        AccountsDiffTable::NAME,
+        AccountsRevertDiffTable::NAME,
+        HistoryTreeTable::NAME,
+        HistoricTransactionTable::NAME,
+        LastLeafTable::NAME,
+        TxHashTable::NAME,
+        AddressTable::NAME,
+        InherentTable::NAME,
+        IndexVersionTable::NAME,
+        TxnHashesTable::NAME,
+        BlockTxnsTable::NAME,
+        AccountsTrieTable::NAME,
+    ];
+
+    /// Verifies the chain state as seen by the given transaction, e.g. after a snapshot has been
+    /// imported. The chain must link to our genesis block (see `verify_snapshot_linkage`), the
+    /// accounts tree must be complete and match the state root of the head and the history tree
+    /// must match the history root of the head.
+    ///
+    /// Nodes that synced via ZKP don't store the election blocks preceding the one they synced
+    /// to. For them, `election_proof` must contain the block number of a stored election block
+    /// together with a ZK proof that it links to our genesis block.
+    /// Returns the hash of the verified head.
+    pub fn verify_snapshot(
+        &self,
+        txn: &MdbxReadTransaction,
+        election_proof: Option<(u32, NanoProof)>,
+    ) -> Result<Blake2bHash, BlockchainError> {
+        let head_hash = self
+            .chain_store
+            .get_head(Some(txn))
+            .ok_or(BlockchainError::FailedLoadingMainChain)?;
+        let head = self
+            .chain_store
+            .get_chain_info(&head_hash, false, Some(txn))
+            .map_err(|_| BlockchainError::FailedLoadingMainChain)?
+            .head;
+
+        self.verify_snapshot_linkage(&head, election_proof, txn)?;
+
+        if !self.state.accounts.is_complete(Some(txn)) {
+            log::error!("Snapshot accounts tree is incomplete");
+            return Err(BlockchainError::InconsistentState);
+        }
+
+        let accounts_hash = self.state.accounts.get_root_hash(Some(txn));
+        if accounts_hash.as_ref() != Some(head.state_root()) {
+            log::error!(
+                head_state_root = %head.state_root(),
+                accounts_root = ?accounts_hash,
+                "Snapshot accounts tree doesn't match the head's state root"
+            );
+            return Err(BlockchainError::InconsistentState);
+        }
+
+        let history_root = self
+            .history_store
+            .get_history_tree_root(head.block_number(), Some(txn));
+        if history_root.as_ref() != Some(head.history_root()) {
+            log::error!(
+                head_history_root = %head.history_root(),
+                ?history_root,
+                "Snapshot history tree doesn't match the head's history root"
+            );
+            return Err(BlockchainError::InconsistentState);
+        }
+
+        Ok(head_hash)
+    }
+
+    /// Verifies that the chain ending in `head` links to our genesis block:
+    /// - The genesis block in the snapshot must be our genesis block.
+    /// - The chain is anchored at the election block that `election_proof` proves to link to the
+    ///   genesis block. Without a proof, or if the proven block isn't stored, it is anchored at the
+    ///   genesis block.
+    /// - Every election block after the anchor must be a macro successor of the previous one and
+    ///   be justified by the validators it elected.
+    /// - The macro head must be a macro successor of the last election block and be justified by
+    ///   its validators.
+    /// - The micro blocks after the macro head must form a chain up to `head`.
+    ///
+    /// All of these blocks must be on the main chain.
+    fn verify_snapshot_linkage(
+        &self,
+        head: &Block,
+        election_proof: Option<(u32, NanoProof)>,
+        txn: &MdbxReadTransaction,
+    ) -> Result<(), BlockchainError> {
+        let genesis_block = self.get_main_chain_block(Policy::genesis_block_number(), txn)?;
+        if genesis_block.hash() != self.genesis_hash {
+            log::error!(
+                genesis_hash = %self.genesis_hash,
+                snapshot_genesis_hash = %genesis_block.hash(),
+                "Snapshot was taken from a different chain"
+            );
+            return Err(BlockchainError::InconsistentState);
+        }
+
+        let macro_head_number = Policy::last_macro_block(head.block_number());
+        let last_election_number = Policy::last_election_block(macro_head_number);
+
+        let mut election_block = match election_proof {
+            Some((block_number, proof)) => {
+                self.get_proven_election_block(&genesis_block, block_number, proof, txn)?
+            }
+            None => None,
+        }
+        .unwrap_or_else(|| genesis_block.unwrap_macro());
+
+        let mut block_number = election_block.block_number();
+        while block_number < macro_head_number {
+            block_number = if block_number < last_election_number {
+                block_number + Policy::blocks_per_epoch()
+            } else {
+                macro_head_number
+            };
+
+            let block = self.get_main_chain_block(block_number, txn)?;
+            let validators = election_block
+                .get_validators()
+                .expect("Election block must contain validators");
+            if let Err(error) = block
+                .verify_macro_successor(&election_block)
+                .and_then(|_| block.verify_validators(&validators))
+            {
+                log::error!(
+                    %block,
+                    %error,
+                    "Snapshot macro block doesn't link to the previous election block"
+                );
+                return Err(BlockchainError::InconsistentState);
+            }
+
+            if block.is_election() {
+                election_block = block.unwrap_macro();
+            }
+        }
+
+        let mut block = head.clone();
+        while block.block_number() > macro_head_number {
+            let predecessor = self.get_main_chain_block(block.block_number() - 1, txn)?;
+            if let Err(error) = block.verify_immediate_successor(&predecessor) {
+                log::error!(
+                    %block,
+                    %error,
+                    "Snapshot micro block doesn't link to its predecessor"
+                );
+                return Err(BlockchainError::InconsistentState);
+            }
+            block = predecessor;
+        }
+        if !block.is_macro() {
+            return Err(BlockchainError::InconsistentState);
+        }
+
+        Ok(())
+    }
+
+    /// Returns the main chain election block at `block_number` if it is stored and `proof` proves
+    /// that it links to the genesis block. Returns `None` if no such block is stored and an error
+    /// if the proof is invalid.
+    fn get_proven_election_block(
+        &self,
+        genesis_block: &Block,
+        block_number: u32,
+        proof: NanoProof,
+        txn: &MdbxReadTransaction,
+    ) -> Result<Option<MacroBlock>, BlockchainError> {
+        let block = match self
+            .chain_store
+            .get_block_at(block_number, false, Some(txn))
+        {
+            Ok(block) if block.is_election() => block.unwrap_macro(),
+            _ => {
+                log::debug!(
+                    block_number,
+                    "Snapshot doesn't contain the proven election block"
+                );
+                return Ok(None);
+            }
+        };
+
+        let genesis_hash = genesis_block.unwrap_macro_ref().hash_blake2s();
+        if !verify(
+            genesis_hash,
+            block.hash_blake2s(),
+            proof,
+            &ZKP_VERIFYING_DATA,
+        )
+        .unwrap_or(false)
+        {
+            log::error!(block_number, "Snapshot contains an invalid ZK proof");
+            return Err(BlockchainError::InconsistentState);
+        }
+
+        Ok(Some(block))
+    }
+
+    /// Returns the main chain block at the given block number as seen by the given transaction.
+    fn get_main_chain_block(
+        &self,
+        block_number: u32,
+        txn: &MdbxReadTransaction,
+    ) -> Result<Block, BlockchainError> {
+        self.chain_store
+            .get_block_at(block_number, false, Some(txn))
+            .map_err(|error| {
+                log::error!(block_number, %error, "Snapshot is missing a main chain block");
+                BlockchainError::InconsistentState
+            })
+    }
+}
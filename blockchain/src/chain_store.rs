@@ -23,6 +23,7 @@ declare_table!(StoredBlockTable, "StoredBlockTable", Blake2bHash => Block);
 declare_table!(HeightIndex, "HeightIndex", u32 => dup(Blake2bHash));
 declare_table!(RevertTable, "Receipts", u32 => RevertInfo);
 declare_table!(AccountsDiffTable, "AccountsDiff", Blake2bHash => TrieDiff);
+declare_table!(AccountsRevertDiffTable, "AccountsRevertDiff", Blake2bHash => TrieDiff);
 
 /// The non-header content of a block except that transactions are not stored to
 /// optimize blocks storage. This assumes that a block has been pushed and that there
@@ -132,6 +133,9 @@ pub struct ChainStore {
     revert_table: RevertTable,
     /// A database of accounts trie diffs for a block.
     accounts_diff_table: AccountsDiffTable,
+    /// A database of accounts trie diffs that revert the changes of a block, containing the
+    /// previous values of all changed keys. Only stored on history nodes.
+    accounts_revert_diff_table: AccountsRevertDiffTable,
     /// A reference to the history store to recover micro block transactions.
     history_store: Arc<MergedHistoryStoreProxy>,
 }
@@ -147,6 +151,7 @@ impl ChainStore {
             height_idx: HeightIndex,
             revert_table: RevertTable,
             accounts_diff_table: AccountsDiffTable,
+            accounts_revert_diff_table: AccountsRevertDiffTable,
             history_store,
         };
 
@@ -167,6 +172,9 @@ impl ChainStore {
         chain_store
             .db
             .create_regular_table(&chain_store.accounts_diff_table);
+        chain_store
+            .db
+            .create_regular_table(&chain_store.accounts_revert_diff_table);
 
         chain_store
     }
@@ -178,6 +186,7 @@ impl ChainStore {
         txn.clear_table(&self.height_idx);
         txn.clear_table(&self.revert_table);
         txn.clear_table(&self.accounts_diff_table);
+        txn.clear_table(&self.accounts_revert_diff_table);
         txn.clear_table(&self.head_table);
     }
 
@@ -622,6 +631,7 @@ impl ChainStore {
                     txn.remove(&self.chain_table, &hash);
                     txn.remove(&self.pushed_block_table, &hash);
                     txn.remove(&self.accounts_diff_table, &hash);
+                    txn.remove(&self.accounts_revert_diff_table, &hash);
                     txn.remove_item(&self.height_idx, &height, &hash);
                 }
             }
@@ -694,4 +704,26 @@ impl ChainStore {
             }
         }
     }
+
+    /// Puts the diff that reverts the changes of a block to the accounts trie.
+    pub fn put_accounts_revert_diff(
+        &self,
+        txn: &mut MdbxWriteTransaction,
+        hash: &Blake2bHash,
+        diff: &TrieDiff,
+    ) {
+        txn.put_reserve(&self.accounts_revert_diff_table, hash, diff);
+    }
+
+    /// Gets the diff that reverts the changes of a block to the accounts trie, `None` if it
+    /// wasn't stored or has been pruned.
+    pub fn get_accounts_revert_diff(
+        &self,
+        hash: &Blake2bHash,
+        txn_option: Option<&MdbxReadTransaction>,
+    ) -> Option<TrieDiff> {
+        let txn = txn_option.or_new(&self.db);
+
+        txn.get(&self.accounts_revert_diff_table, hash)
+    }
 }
@@ -33,6 +33,19 @@ use crate::{history::HistoryTreeChunk, interface::HistoryIndexInterface, History
 declare_table!(TxHashTable, "LeafIndexByTxHash", RawTransactionHash => EpochBasedIndex);
 // `Address` -> `EpochBasedIndex` -> `Blake2bHash`
 declare_table!(AddressTable, "TxHashesByAddress", Address => EpochBasedIndex => Blake2bHash);
+// `Address` -> `EpochBasedIndex` -> `Blake2bHash`
+declare_table!(InherentTable, "InherentHashesByAddress", Address => EpochBasedIndex => Blake2bHash);
+// Version of the index layout the tables above were built with.
+declare_table!(IndexVersionTable, "HistoryIndexVersion", () => u32);
+
+/// The current version of the index layout. Needs to be increased whenever the layout of the
+/// index tables changes, such that existing indices are rebuilt on startup.
+/// - Version 1: Added the inherent index.
+const INDEX_VERSION: u32 = 1;
+
+/// The maximum number of inherents scanned for a single `get_inherents_by_address` call, such
+/// that a filter matching only few inherents doesn't scan the entire index.
+const MAX_SCANNED_INHERENTS: usize = 10_000;
 
 #[derive(Debug)]
 /// A struct that contains databases to store history indices.
@@ -46,6 +59,11 @@ pub struct HistoryStoreIndex {
     /// A database of all raw transaction (and reward inherent) hashes indexed by their sender and
     /// recipient addresses.
     address_table: AddressTable,
+    /// A database of all reward, penalty and jail inherent hashes indexed by the affected address.
+    /// Rewards are indexed by their reward address, punishments by the validator address.
+    inherent_table: InherentTable,
+    /// A database holding the version of the index layout.
+    index_version_table: IndexVersionTable,
     /// The history store.
     history_store: HistoryStore,
 }
@@ -58,10 +76,14 @@ impl HistoryStoreIndex {
             db,
             tx_hash_table: TxHashTable,
             address_table: AddressTable,
+            inherent_table: InherentTable,
+            index_version_table: IndexVersionTable,
         };
 
         index.db.create_regular_table(&index.tx_hash_table);
         index.db.create_dup_table(&index.address_table);
+        index.db.create_dup_table(&index.inherent_table);
+        index.db.create_regular_table(&index.index_version_table);
 
         index.rebuild_index_if_necessary();
         index
@@ -74,16 +96,28 @@ impl HistoryStoreIndex {
             WriteTransaction::dup_cursor(&txn, &self.history_store.hist_tx_table);
 
         trace!("Check if history index needs to be rebuilt.");
-        // Check if last transaction is part of index.
-        if let Some((_, hist_tx)) = hist_tx_cursor.last() {
-            let raw_tx_hash = hist_tx.value.tx_hash();
-            if txn.get(&self.tx_hash_table, &raw_tx_hash).is_none() {
-                info!("History index out-of-date. Starting to rebuild index (this can take a long time).");
-                self.rebuild_index(&mut txn);
-                debug!("Committing rebuilt index.");
+        // Check if the index was built with the current layout and if the last transaction is
+        // part of it.
+        let version: Option<u32> = txn.get(&self.index_version_table, &());
+        let Some((_, hist_tx)) = hist_tx_cursor.last() else {
+            // There is nothing to index yet.
+            if version != Some(INDEX_VERSION) {
+                txn.put(&self.index_version_table, &(), &INDEX_VERSION);
                 txn.commit();
-                info!("Finished rebuilding history index.")
             }
+            return;
+        };
+
+        let raw_tx_hash = hist_tx.value.tx_hash();
+        // A missing version compares as less than any version.
+        if version < Some(INDEX_VERSION) || txn.get(&self.tx_hash_table, &raw_tx_hash).is_none() {
+            info!(
+                "History index out-of-date. Starting to rebuild index (this can take a long time)."
+            );
+            self.rebuild_index(&mut txn);
+            debug!("Committing rebuilt index.");
+            txn.commit();
+            info!("Finished rebuilding history index.")
         }
     }
 
@@ -116,10 +150,15 @@ impl HistoryStoreIndex {
                 }
                 HistoricTransactionData::Reward(ev) => {
                     txn.remove_item(&self.address_table, &ev.reward_address, &ordered_hash);
+                    txn.remove_item(&self.inherent_table, &ev.reward_address, &ordered_hash);
+                }
+                HistoricTransactionData::Penalize(ev) => {
+                    txn.remove_item(&self.inherent_table, &ev.validator_address, &ordered_hash);
+                }
+                HistoricTransactionData::Jail(ev) => {
+                    txn.remove_item(&self.inherent_table, &ev.validator_address, &ordered_hash);
                 }
-                HistoricTransactionData::Equivocation(_)
-                | HistoricTransactionData::Penalize(_)
-                | HistoricTransactionData::Jail(_) => {}
+                HistoricTransactionData::Equivocation(_) => {}
             }
         }
     }
@@ -130,6 +169,7 @@ impl HistoryStoreIndex {
         &self,
         hashes: &mut BTreeMap<RawTransactionHash, EpochBasedIndex>,
         addresses: &mut BTreeMap<Address, Vec<OrderedHash>>,
+        inherents: &mut BTreeMap<Address, Vec<OrderedHash>>,
         epoch_number: u32,
         leaf_index: u32,
         hist_tx: &HistoricTransaction,
@@ -158,17 +198,31 @@ impl HistoryStoreIndex {
                     .push(ordered_hash);
             }
             HistoricTransactionData::Reward(ev) => {
-                // We only add reward inherents to the address database.
+                // Reward inherents are the only inherents in the address database.
                 addresses
                     .entry(ev.reward_address.clone())
                     .or_default()
+                    .push(ordered_hash.clone());
+                inherents
+                    .entry(ev.reward_address.clone())
+                    .or_default()
+                    .push(ordered_hash);
+            }
+            // Punishments are only added to the inherent database.
+            HistoricTransactionData::Penalize(ev) => {
+                inherents
+                    .entry(ev.validator_address.clone())
+                    .or_default()
+                    .push(ordered_hash);
+            }
+            HistoricTransactionData::Jail(ev) => {
+                inherents
+                    .entry(ev.validator_address.clone())
+                    .or_default()
                     .push(ordered_hash);
             }
-            // Do not index equivocation or punishments events, since I do not see a use case
-            // for this at the time.
-            HistoricTransactionData::Equivocation(_)
-            | HistoricTransactionData::Penalize(_)
-            | HistoricTransactionData::Jail(_) => {}
+            // Do not index equivocation events, since I do not see a use case for this at the time.
+            HistoricTransactionData::Equivocation(_) => {}
         }
     }
 
@@ -195,16 +249,20 @@ impl HistoryStoreIndex {
         // Clear the tables.
         txn.clear_table(&self.tx_hash_table);
         txn.clear_table(&self.address_table);
+        txn.clear_table(&self.inherent_table);
+        txn.put(&self.index_version_table, &(), &INDEX_VERSION);
 
         // Iterate over all epochs and leafs.
         let mut hashes = BTreeMap::new();
         let mut addresses = BTreeMap::new();
+        let mut inherents = BTreeMap::new();
         let cursor = WriteTransaction::dup_cursor(txn, &self.history_store.hist_tx_table);
         debug!("Reading historic transactions.");
         for (epoch_number, hist_tx) in cursor.into_iter_start() {
             self.put_historic_tx(
                 &mut hashes,
                 &mut addresses,
+                &mut inherents,
                 epoch_number,
                 hist_tx.index,
                 &hist_tx.value,
@@ -225,6 +283,14 @@ impl HistoryStoreIndex {
                 addresses_cursor.append(address, ordered_hash);
             }
         }
+
+        debug!("Writing inherent index");
+        let mut inherents_cursor = WriteTransaction::dup_cursor(txn, &self.inherent_table);
+        for (address, ordered_hashes) in inherents.iter() {
+            for ordered_hash in ordered_hashes.iter() {
+                inherents_cursor.append(address, ordered_hash);
+            }
+        }
     }
 
     /// Returns an iterator containing all transaction (and reward inherents) hashes corresponding to the given
@@ -267,6 +333,66 @@ impl HistoryStoreIndex {
 
         TxHashIterator::new(cursor, initial_item)
     }
+
+    /// Implements `get_inherents_by_address`, scanning at most `max_scanned` inherents.
+    fn scan_inherents_by_address(
+        &self,
+        address: &Address,
+        max: u16,
+        start_at: Option<EpochBasedIndex>,
+        filter: &dyn Fn(&HistoricTransaction) -> bool,
+        max_scanned: usize,
+        txn_option: Option<&MdbxReadTransaction>,
+    ) -> (
+        Vec<(EpochBasedIndex, HistoricTransaction)>,
+        Option<EpochBasedIndex>,
+    ) {
+        if max == 0 {
+            return (vec![], start_at);
+        }
+
+        let txn = txn_option.or_new(&self.db);
+        let mut cursor = txn.dup_cursor(&self.inherent_table);
+
+        // Find the first inherent to look at.
+        let mut next = if let Some(start_index) = start_at {
+            if cursor.set_subkey(address, &start_index).is_none() {
+                return (vec![], None);
+            }
+            cursor.prev_duplicate().map(|(_address, hash)| hash)
+        } else {
+            if cursor.set_key(address).is_none() {
+                return (vec![], None);
+            }
+            cursor.last_duplicate()
+        };
+
+        let mut inherents = vec![];
+        let mut num_scanned = 0;
+        let mut last_scanned = None;
+        while let Some(ordered_hash) = next {
+            // There are more inherents to scan, continue after the last one scanned.
+            if inherents.len() == max as usize || num_scanned == max_scanned {
+                return (inherents, last_scanned);
+            }
+
+            let index = ordered_hash.index;
+            if let Some(hist_tx) =
+                self.history_store
+                    .get_historic_tx(index.epoch_number, index.index, Some(&txn))
+            {
+                if filter(&hist_tx) {
+                    inherents.push((index, hist_tx));
+                }
+            }
+            num_scanned += 1;
+            last_scanned = Some(index);
+
+            next = cursor.prev_duplicate().map(|(_address, hash)| hash);
+        }
+
+        (inherents, None)
+    }
 }
 
 impl HistoryInterface for HistoryStoreIndex {
@@ -359,6 +485,7 @@ impl HistoryInterface for HistoryStoreIndex {
         self.history_store.clear(txn);
         txn.clear_table(&self.tx_hash_table);
         txn.clear_table(&self.address_table);
+        txn.clear_table(&self.inherent_table);
     }
 
     fn length_at(
@@ -518,8 +645,16 @@ impl HistoryInterface for HistoryStoreIndex {
             // Sort everything first and then put with a cursor for improved database performance.
             let mut hashes = BTreeMap::new();
             let mut addresses = BTreeMap::new();
+            let mut inherents = BTreeMap::new();
             for (tx, i) in hist_txs.iter().zip(leaf_idx.iter()) {
-                self.put_historic_tx(&mut hashes, &mut addresses, epoch_number, *i, tx);
+                self.put_historic_tx(
+                    &mut hashes,
+                    &mut addresses,
+                    &mut inherents,
+                    epoch_number,
+                    *i,
+                    tx,
+                );
             }
 
             // Put the hashes and addresses into the respective databases.
@@ -533,6 +668,12 @@ impl HistoryInterface for HistoryStoreIndex {
                     address_cursor.put(address, ordered_hash);
                 }
             }
+            let mut inherent_cursor = WriteTransaction::dup_cursor(txn, &self.inherent_table);
+            for (address, keys) in inherents.iter() {
+                for ordered_hash in keys.iter() {
+                    inherent_cursor.put(address, ordered_hash);
+                }
+            }
             return Some((root, size));
         }
         None
@@ -656,6 +797,27 @@ impl HistoryIndexInterface for HistoryStoreIndex {
             .collect()
     }
 
+    fn get_inherents_by_address(
+        &self,
+        address: &Address,
+        max: u16,
+        start_at: Option<EpochBasedIndex>,
+        filter: &dyn Fn(&HistoricTransaction) -> bool,
+        txn_option: Option<&MdbxReadTransaction>,
+    ) -> (
+        Vec<(EpochBasedIndex, HistoricTransaction)>,
+        Option<EpochBasedIndex>,
+    ) {
+        self.scan_inherents_by_address(
+            address,
+            max,
+            start_at,
+            filter,
+            MAX_SCANNED_INHERENTS,
+            txn_option,
+        )
+    }
+
     /// Returns a proof for transactions with the given hashes. The proof also includes the extended
     /// transactions.
     /// The verifier state is used for those cases where the verifier might have an incomplete MMR,
@@ -1305,6 +1467,145 @@ mod tests {
         assert_eq!(query_5[1], *hashes[1]);
     }
 
+    #[test]
+    fn get_inherents_by_address_works() {
+        // Initialize History Store.
+        let env = MdbxDatabase::new_volatile(Default::default()).unwrap();
+        let history_store = HistoryStoreIndex::new(env.clone(), NetworkId::UnitAlbatross);
+
+        // Create historic transactions.
+        let hist_txs = gen_hist_txs();
+
+        // Add historic transactions to History Store.
+        let mut txn = env.write_transaction();
+        history_store.add_to_history(&mut txn, Policy::genesis_block_number(), &hist_txs[..3]);
+        history_store.add_to_history(&mut txn, Policy::genesis_block_number() + 2, &hist_txs[3..]);
+
+        let validator_address =
+            Address::from_user_friendly_address("NQ04 B79B R4FF 4NGU A9H0 2PT9 9ART 5A88 J73T")
+                .unwrap();
+
+        // Verify method works.
+        let (query_1, next) = history_store.get_inherents_by_address(
+            &validator_address,
+            99,
+            None,
+            &|_| true,
+            Some(&txn),
+        );
+
+        assert_eq!(query_1.len(), 5);
+        assert_eq!(query_1[0].1, hist_txs[9]);
+        assert_eq!(query_1[1].1, hist_txs[8]);
+        assert_eq!(query_1[2].1, hist_txs[7]);
+        assert_eq!(query_1[3].1, hist_txs[4]);
+        assert_eq!(query_1[4].1, hist_txs[2]);
+        assert_eq!(next, None);
+
+        let (query_2, next) = history_store.get_inherents_by_address(
+            &validator_address,
+            99,
+            None,
+            &|hist_tx| matches!(hist_tx.data, HistoricTransactionData::Reward(_)),
+            Some(&txn),
+        );
+
+        assert_eq!(query_2.len(), 3);
+        assert_eq!(query_2[0].1, hist_txs[7]);
+        assert_eq!(query_2[1].1, hist_txs[4]);
+        assert_eq!(query_2[2].1, hist_txs[2]);
+        assert_eq!(next, None);
+
+        // Transactions are not part of the inherent index.
+        let (query_3, next) = history_store.get_inherents_by_address(
+            &Address::from_user_friendly_address("NQ09 VF5Y 1PKV MRM4 5LE1 55KV P6R2 GXYJ XYQF")
+                .unwrap(),
+            99,
+            None,
+            &|_| true,
+            Some(&txn),
+        );
+
+        assert!(query_3.is_empty());
+        assert_eq!(next, None);
+
+        // Page through the inherents.
+        let (query_4, next) = history_store.get_inherents_by_address(
+            &validator_address,
+            2,
+            Some(query_1[1].0),
+            &|_| true,
+            Some(&txn),
+        );
+
+        assert_eq!(query_4, query_1[2..4]);
+        assert_eq!(next, Some(query_1[3].0));
+
+        let (query_5, next) = history_store.get_inherents_by_address(
+            &validator_address,
+            2,
+            next,
+            &|_| true,
+            Some(&txn),
+        );
+
+        assert_eq!(query_5, query_1[4..]);
+        assert_eq!(next, None);
+
+        // An unknown start position returns an empty vector.
+        let (query_6, next) = history_store.get_inherents_by_address(
+            &validator_address,
+            2,
+            Some(EpochBasedIndex::new(99, 0)),
+            &|_| true,
+            Some(&txn),
+        );
+
+        assert!(query_6.is_empty());
+        assert_eq!(next, None);
+
+        // The scan stops after the given number of inherents, even if fewer than the maximum
+        // matched. It continues after the last inherent scanned.
+        let is_reward = |hist_tx: &HistoricTransaction| {
+            matches!(hist_tx.data, HistoricTransactionData::Reward(_))
+        };
+        let (query_7, next) = history_store.scan_inherents_by_address(
+            &validator_address,
+            99,
+            None,
+            &is_reward,
+            2,
+            Some(&txn),
+        );
+
+        assert!(query_7.is_empty());
+        assert_eq!(next, Some(query_1[1].0));
+
+        let (query_8, next) = history_store.scan_inherents_by_address(
+            &validator_address,
+            99,
+            next,
+            &is_reward,
+            2,
+            Some(&txn),
+        );
+
+        assert_eq!(query_8, query_2[..2]);
+        assert_eq!(next, Some(query_1[3].0));
+
+        let (query_9, next) = history_store.scan_inherents_by_address(
+            &validator_address,
+            99,
+            next,
+            &is_reward,
+            2,
+            Some(&txn),
+        );
+
+        assert_eq!(query_9, query_2[2..]);
+        assert_eq!(next, None);
+    }
+
     #[test]
     fn prove_works() {
         // Initialize History Store.
@@ -1454,6 +1755,50 @@ mod tests {
         }
     }
 
+    #[test]
+    fn rebuild_index_if_necessary_works() {
+        // Initialize History Store.
+        let env = MdbxDatabase::new_volatile(Default::default()).unwrap();
+        let history_store = HistoryStoreIndex::new(env.clone(), NetworkId::UnitAlbatross);
+
+        // Add historic transactions to History Store.
+        let hist_txs = gen_hist_txs();
+        let mut txn = env.write_transaction();
+        history_store.add_to_history(&mut txn, Policy::genesis_block_number(), &hist_txs[..3]);
+        history_store.add_to_history(&mut txn, Policy::genesis_block_number() + 2, &hist_txs[3..]);
+        assert_eq!(txn.get(&IndexVersionTable, &()), Some(INDEX_VERSION));
+
+        // Simulate an index that was built before the inherent index was introduced.
+        txn.clear_table(&InherentTable);
+        txn.remove(&IndexVersionTable, &());
+        txn.commit();
+
+        let validator_address =
+            Address::from_user_friendly_address("NQ04 B79B R4FF 4NGU A9H0 2PT9 9ART 5A88 J73T")
+                .unwrap();
+        let num_inherents = |history_store: &HistoryStoreIndex| {
+            history_store
+                .get_inherents_by_address(&validator_address, 99, None, &|_| true, None)
+                .0
+                .len()
+        };
+
+        // The index is rebuilt since the version marker is missing.
+        let history_store = HistoryStoreIndex::new(env.clone(), NetworkId::UnitAlbatross);
+        assert_eq!(num_inherents(&history_store), 5);
+        let txn = env.read_transaction();
+        assert_eq!(txn.get(&IndexVersionTable, &()), Some(INDEX_VERSION));
+        drop(txn);
+
+        // An up-to-date index is not rebuilt, even if it doesn't contain any inherents.
+        let mut txn = env.write_transaction();
+        txn.clear_table(&InherentTable);
+        txn.commit();
+
+        let history_store = HistoryStoreIndex::new(env.clone(), NetworkId::UnitAlbatross);
+        assert_eq!(num_inherents(&history_store), 0);
+    }
+
     fn create_reward_inherent(block: u32, value: u64) -> HistoricTransaction {
         let reward_address =
             Address::from_user_friendly_address("NQ04 B79B R4FF 4NGU A9H0 2PT9 9ART 5A88 J73T")
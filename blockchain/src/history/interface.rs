@@ -15,7 +15,7 @@ use nimiq_transaction::{
     EquivocationLocator,
 };
 
-use crate::HistoryTreeChunk;
+use crate::{EpochBasedIndex, HistoryTreeChunk};
 
 /// Defines several methods to interact with a history store.
 pub trait HistoryInterface: Debug {
@@ -207,6 +207,26 @@ pub trait HistoryIndexInterface: HistoryInterface {
         txn_option: Option<&MdbxReadTransaction>,
     ) -> Vec<Blake2bHash>;
 
+    /// Returns the reward, penalty and jail inherents affecting the given address that match the
+    /// given filter, together with their position in the history store. It fetches the inherents
+    /// from most recent to least recent up to the maximum number given. It allows to give the
+    /// position of an inherent to start from (exclusive). If this position is given but not found,
+    /// the function will return an empty vector.
+    /// Only a bounded number of inherents is scanned per call, so fewer inherents than the maximum
+    /// might be returned even if more match. The position to continue the scan from is returned
+    /// as well, it is absent once all inherents have been scanned.
+    fn get_inherents_by_address(
+        &self,
+        address: &Address,
+        max: u16,
+        start_at: Option<EpochBasedIndex>,
+        filter: &dyn Fn(&HistoricTransaction) -> bool,
+        txn_option: Option<&MdbxReadTransaction>,
+    ) -> (
+        Vec<(EpochBasedIndex, HistoricTransaction)>,
+        Option<EpochBasedIndex>,
+    );
+
     /// Returns a proof for transactions with the given hashes. The proof also includes the extended
     /// transactions.
     /// The verifier state is used for those cases where the verifier might have an incomplete MMR,
@@ -16,7 +16,10 @@ use nimiq_transaction::{
     EquivocationLocator,
 };
 
-use super::interface::{HistoryIndexInterface, HistoryInterface};
+use super::{
+    interface::{HistoryIndexInterface, HistoryInterface},
+    utils::EpochBasedIndex,
+};
 
 /// A wrapper around two history stores, one for the pre-genesis epoch and one for the main epoch.
 #[derive(Debug)]
@@ -378,6 +381,56 @@ impl<S: HistoryInterface + HistoryIndexInterface> HistoryIndexInterface for Hist
         tx_hashes
     }
 
+    fn get_inherents_by_address(
+        &self,
+        address: &Address,
+        max: u16,
+        start_at: Option<EpochBasedIndex>,
+        filter: &dyn Fn(&HistoricTransaction) -> bool,
+        txn_option: Option<&MdbxReadTransaction>,
+    ) -> (
+        Vec<(EpochBasedIndex, HistoricTransaction)>,
+        Option<EpochBasedIndex>,
+    ) {
+        // The pre-genesis database only contains epoch 0, so we can skip the main database if we
+        // start in there.
+        let start_in_pre_genesis =
+            self.pre_genesis.is_some() && start_at.is_some_and(|index| index.epoch_number == 0);
+
+        let (mut inherents, next) = if start_in_pre_genesis {
+            (vec![], None)
+        } else {
+            self.main
+                .get_inherents_by_address(address, max, start_at, filter, txn_option)
+        };
+
+        // Only continue in pre-genesis once the main database has been scanned completely.
+        if next.is_some() {
+            return (inherents, next);
+        }
+        let Some(ref pre_genesis) = self.pre_genesis else {
+            return (inherents, None);
+        };
+
+        // If the page is full already, the next page starts in pre-genesis.
+        if inherents.len() == max as usize {
+            let next = inherents.last().map(|(index, _)| *index);
+            return (inherents, next);
+        }
+
+        let pre_genesis_start = if start_in_pre_genesis { start_at } else { None };
+        let (mut pre_genesis_inherents, next) = pre_genesis.get_inherents_by_address(
+            address,
+            max - inherents.len() as u16,
+            pre_genesis_start,
+            filter,
+            None,
+        );
+        inherents.append(&mut pre_genesis_inherents);
+
+        (inherents, next)
+    }
+
     fn prove(
         &self,
         epoch_number: u32,
@@ -450,6 +503,25 @@ mod tests {
         });
     }
 
+    #[test]
+    fn get_inherents_by_address_works() {
+        let address =
+            Address::from_user_friendly_address("NQ04 B79B R4FF 4NGU A9H0 2PT9 9ART 5A88 J73T")
+                .unwrap();
+
+        test_history_fn(|history_store| {
+            history_store.get_inherents_by_address(&address, 99, None, &|_| true, None)
+        });
+        test_history_fn(|history_store| {
+            history_store.get_inherents_by_address(&address, 2, None, &|_| true, None)
+        });
+        test_history_fn(|history_store| {
+            let first_page =
+                history_store.get_inherents_by_address(&address, 3, None, &|_| true, None);
+            history_store.get_inherents_by_address(&address, 99, first_page.1, &|_| true, None)
+        });
+    }
+
     #[test]
     fn get_hist_tx_by_hash_works() {
         let hist_txs = gen_hist_txs();
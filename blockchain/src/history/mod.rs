@@ -1,7 +1,11 @@
 pub use history_store::HistoryStore;
+pub(crate) use history_store::{HistoricTransactionTable, HistoryTreeTable, LastLeafTable};
 pub use history_store_index::HistoryStoreIndex;
+pub(crate) use history_store_index::{AddressTable, IndexVersionTable, InherentTable, TxHashTable};
 pub use history_tree_chunk::{HistoryTreeChunk, CHUNK_SIZE};
 pub use merged_history_store::HistoryStoreMerger;
+pub use utils::EpochBasedIndex;
+pub(crate) use validity_store::{BlockTxnsTable, TxnHashesTable};
 
 mod history_store;
 mod history_store_index;
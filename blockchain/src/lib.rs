@@ -3,6 +3,7 @@ extern crate log;
 
 pub use block_production::{BlockProducer, BlockProducerError};
 pub use blockchain::{
+    accounts::AccountsChunk,
     blockchain::{Blockchain, BlockchainConfig, TransactionVerificationCache},
     PostValidationHook,
 };
@@ -1,10 +1,14 @@
 use std::sync::Arc;
 
+use nimiq_account::Account;
 use nimiq_block::{Block, BlockError};
 use nimiq_blockchain::Blockchain;
-use nimiq_blockchain_interface::{AbstractBlockchain, PushError, PushResult};
+use nimiq_blockchain_interface::{
+    AbstractBlockchain, BlockchainError, PushError, PushResult, StateProof, StateProofError,
+};
 use nimiq_hash::Hash;
-use nimiq_primitives::policy::Policy;
+use nimiq_keys::Address;
+use nimiq_primitives::{key_nibbles::KeyNibbles, policy::Policy};
 use nimiq_tendermint::ProposalMessage;
 use nimiq_test_log::test;
 use nimiq_test_utils::{
@@ -169,3 +173,268 @@ fn can_detect_invalid_punished_set() {
         Err(PushError::InvalidBlock(BlockError::InvalidValidators))
     );
 }
+
+#[test]
+fn state_proofs_verify_against_head() {
+    let temp_producer = TemporaryBlockProducer::new();
+    temp_producer.next_block(vec![], false);
+
+    let blockchain = temp_producer.blockchain.read();
+    let head_hash = blockchain.head_hash();
+    let validator_address = blockchain
+        .get_staking_contract()
+        .active_validators
+        .keys()
+        .next()
+        .unwrap()
+        .clone();
+    let unknown_address = Address::burn_address();
+
+    // Existing values are proven.
+    let proof = blockchain
+        .get_state_proof(vec![&StateProof::validator_key(&validator_address)])
+        .unwrap();
+    assert_eq!(proof.block.hash(), head_hash);
+    let validator = proof
+        .clone()
+        .verify_validator(&head_hash, &validator_address)
+        .unwrap();
+    assert_eq!(validator.unwrap().address, validator_address);
+
+    // The proof must not be accepted for a different block or a different key.
+    assert!(matches!(
+        proof
+            .clone()
+            .verify_validator(blockchain.head().parent_hash(), &validator_address),
+        Err(StateProofError::BlockHashMismatch { .. })
+    ));
+    assert!(proof.verify_staker(&head_hash, &validator_address).is_err());
+
+    // Missing values are proven to be absent.
+    let proof = blockchain
+        .get_state_proof(vec![&StateProof::staker_key(&unknown_address)])
+        .unwrap();
+    assert_eq!(
+        proof.verify_staker(&head_hash, &unknown_address).unwrap(),
+        None
+    );
+
+    let proof = blockchain
+        .get_state_proof(vec![&StateProof::account_key(&unknown_address)])
+        .unwrap();
+    assert_eq!(
+        proof.verify_account(&head_hash, &unknown_address).unwrap(),
+        Account::default()
+    );
+}
+
+#[test]
+fn historical_accounts_are_reverted() {
+    let temp_producer = TemporaryBlockProducer::new();
+    temp_producer.next_block(vec![], false);
+
+    let (reward_address, block_number, balance) = {
+        let blockchain = temp_producer.blockchain.read();
+        let staking_contract = blockchain.get_staking_contract();
+        let txn = blockchain.read_transaction();
+        let reward_address = staking_contract
+            .iter_validators(&blockchain.get_staking_contract_store().read(&txn))
+            .next()
+            .unwrap()
+            .reward_address;
+        let balance = blockchain
+            .get_account_if_complete(&reward_address)
+            .unwrap()
+            .balance();
+        (reward_address, blockchain.block_number(), balance)
+    };
+
+    // The batch rewards are distributed with the next macro block.
+    while !Policy::is_macro_block_at(temp_producer.blockchain.read().block_number()) {
+        temp_producer.next_block(vec![], false);
+    }
+
+    let blockchain = temp_producer.blockchain.read();
+    let current = blockchain.get_account_if_complete(&reward_address).unwrap();
+    assert!(current.balance() > balance);
+
+    assert_eq!(
+        blockchain
+            .get_historical_account(&reward_address, block_number)
+            .unwrap()
+            .balance(),
+        balance
+    );
+    assert_eq!(
+        blockchain
+            .get_historical_account(&reward_address, blockchain.block_number())
+            .unwrap(),
+        current
+    );
+    assert!(matches!(
+        blockchain.get_historical_account(&reward_address, blockchain.block_number() + 1),
+        Err(BlockchainError::BlockNotFound(_))
+    ));
+    drop(blockchain);
+
+    // States further behind the head than configured are not available.
+    let mut blockchain = temp_producer.blockchain.write();
+    blockchain.config.max_historical_state_distance = blockchain.block_number() - block_number - 1;
+    assert_eq!(
+        blockchain.get_historical_account(&reward_address, block_number),
+        Err(BlockchainError::StateNotAvailable(block_number))
+    );
+    assert!(blockchain
+        .get_historical_account(&reward_address, block_number + 1)
+        .is_ok());
+}
+
+#[test]
+fn state_proofs_verify_against_past_blocks() {
+    let temp_producer = TemporaryBlockProducer::new();
+    temp_producer.next_block(vec![], false);
+
+    let (reward_address, block_number, block_hash, balance) = {
+        let blockchain = temp_producer.blockchain.read();
+        let staking_contract = blockchain.get_staking_contract();
+        let txn = blockchain.read_transaction();
+        let reward_address = staking_contract
+            .iter_validators(&blockchain.get_staking_contract_store().read(&txn))
+            .next()
+            .unwrap()
+            .reward_address;
+        let balance = blockchain
+            .get_account_if_complete(&reward_address)
+            .unwrap()
+            .balance();
+        (
+            reward_address,
+            blockchain.block_number(),
+            blockchain.head_hash(),
+            balance,
+        )
+    };
+
+    // The batch rewards are distributed with the next macro block.
+    while !Policy::is_macro_block_at(temp_producer.blockchain.read().block_number()) {
+        temp_producer.next_block(vec![], false);
+    }
+    temp_producer.next_block(vec![], false);
+
+    let blockchain = temp_producer.blockchain.read();
+    let macro_hash = blockchain.macro_head_hash();
+    let current = blockchain.get_account_if_complete(&reward_address).unwrap();
+    assert!(current.balance() > balance);
+
+    // The past state is proven without touching the current one.
+    let proof = blockchain
+        .get_historical_state_proof(vec![&StateProof::account_key(&reward_address)], &block_hash)
+        .unwrap();
+    assert_eq!(
+        proof
+            .verify_account(&block_hash, &reward_address)
+            .unwrap()
+            .balance(),
+        balance
+    );
+    assert_eq!(
+        blockchain.get_account_if_complete(&reward_address).unwrap(),
+        current
+    );
+
+    // The latest macro block can be proven as well.
+    let proof = blockchain
+        .get_historical_state_proof(vec![&StateProof::account_key(&reward_address)], &macro_hash)
+        .unwrap();
+    assert_eq!(
+        proof.verify_account(&macro_hash, &reward_address).unwrap(),
+        current
+    );
+    drop(blockchain);
+
+    // States further behind the head than configured are not available.
+    let mut blockchain = temp_producer.blockchain.write();
+    blockchain.config.max_historical_state_distance = blockchain.block_number() - block_number - 1;
+    assert_eq!(
+        blockchain
+            .get_historical_state_proof(
+                vec![&StateProof::account_key(&reward_address)],
+                &block_hash
+            )
+            .unwrap_err(),
+        BlockchainError::StateNotAvailable(block_number)
+    );
+}
+
+#[test]
+fn accounts_chunks_are_read_against_past_blocks() {
+    let temp_producer = TemporaryBlockProducer::new();
+    temp_producer.next_block(vec![], false);
+
+    let (reward_address, block_number, block_hash, balance) = {
+        let blockchain = temp_producer.blockchain.read();
+        let staking_contract = blockchain.get_staking_contract();
+        let txn = blockchain.read_transaction();
+        let reward_address = staking_contract
+            .iter_validators(&blockchain.get_staking_contract_store().read(&txn))
+            .next()
+            .unwrap()
+            .reward_address;
+        let balance = blockchain
+            .get_account_if_complete(&reward_address)
+            .unwrap()
+            .balance();
+        (
+            reward_address,
+            blockchain.block_number(),
+            blockchain.head_hash(),
+            balance,
+        )
+    };
+
+    // The batch rewards are distributed with the next macro block.
+    while !Policy::is_macro_block_at(temp_producer.blockchain.read().block_number()) {
+        temp_producer.next_block(vec![], false);
+    }
+    temp_producer.next_block(vec![], false);
+
+    let blockchain = temp_producer.blockchain.read();
+    let current = blockchain.get_account_if_complete(&reward_address).unwrap();
+    assert!(current.balance() > balance);
+
+    // Walking the accounts in small pages yields the same accounts as a single large page.
+    let chunk = blockchain
+        .get_historical_accounts_chunk(KeyNibbles::default(), 1000, &block_hash)
+        .unwrap();
+    assert_eq!(chunk.end_key, None);
+    let mut accounts = vec![];
+    let mut start = Some(KeyNibbles::default());
+    while let Some(key) = start {
+        let page = blockchain
+            .get_historical_accounts_chunk(key, 2, &block_hash)
+            .unwrap();
+        accounts.extend(page.accounts);
+        start = page.end_key;
+    }
+    assert_eq!(accounts, chunk.accounts);
+
+    // The accounts are read against the past state without touching the current one.
+    let (_, account) = accounts
+        .iter()
+        .find(|(address, _)| *address == reward_address)
+        .unwrap();
+    assert_eq!(account.balance(), balance);
+    assert_eq!(
+        blockchain.get_account_if_complete(&reward_address).unwrap(),
+        current
+    );
+    drop(blockchain);
+
+    // States further behind the head than configured are not available.
+    let mut blockchain = temp_producer.blockchain.write();
+    blockchain.config.max_historical_state_distance = blockchain.block_number() - block_number - 1;
+    assert!(matches!(
+        blockchain.get_historical_accounts_chunk(KeyNibbles::default(), 1000, &block_hash),
+        Err(BlockchainError::StateNotAvailable(number)) if number == block_number
+    ));
+}
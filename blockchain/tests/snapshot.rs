@@ -0,0 +1,225 @@
+use std::{path::Path, sync::Arc};
+
+use nimiq_block::Block;
+use nimiq_blockchain::{chain_store::ChainTable, BlockProducer, Blockchain, BlockchainConfig};
+use nimiq_blockchain_interface::{AbstractBlockchain, BlockchainError, PushResult};
+use nimiq_database::{mdbx::MdbxDatabase, traits::WriteTransaction};
+use nimiq_genesis::NetworkId;
+use nimiq_primitives::{key_nibbles::KeyNibbles, policy::Policy};
+use nimiq_test_log::test;
+use nimiq_test_utils::{
+    block_production::TemporaryBlockProducer,
+    blockchain::{produce_macro_blocks_with_txns, push_micro_block, signing_key, voting_key},
+    zkp_test_data::{get_base_seed, simulate_merger_wrapper, ZKP_TEST_KEYS_PATH},
+};
+use nimiq_utils::time::OffsetTime;
+use nimiq_zkp::{NanoProof, ZKP_VERIFYING_DATA};
+use parking_lot::RwLock;
+
+fn new_blockchain() -> Blockchain {
+    let env = MdbxDatabase::new_volatile(Default::default()).unwrap();
+    Blockchain::new(
+        env,
+        BlockchainConfig::default(),
+        NetworkId::UnitAlbatross,
+        Arc::new(OffsetTime::new()),
+    )
+    .unwrap()
+}
+
+/// Produces a chain spanning two election blocks and a few micro blocks after the last macro
+/// block and exports a snapshot of it.
+fn export_producer_chain() -> (Arc<RwLock<Blockchain>>, Vec<u8>) {
+    let blockchain = Arc::new(RwLock::new(new_blockchain()));
+    let producer = BlockProducer::new(signing_key(), voting_key());
+
+    produce_macro_blocks_with_txns(
+        &producer,
+        &blockchain,
+        2 * Policy::batches_per_epoch() as usize + 1,
+        1,
+        0,
+    );
+    for _ in 0..3 {
+        push_micro_block(&producer, &blockchain);
+    }
+
+    let mut snapshot = vec![];
+    {
+        let blockchain = blockchain.read();
+        let txn = blockchain.read_transaction();
+        assert_eq!(
+            blockchain.verify_snapshot(&txn, None),
+            Ok(blockchain.head_hash())
+        );
+        txn.export_tables(Blockchain::SNAPSHOT_TABLES, &mut snapshot)
+            .unwrap();
+    }
+
+    (blockchain, snapshot)
+}
+
+#[test]
+fn it_can_export_import_and_verify_a_snapshot() {
+    let (source, snapshot) = export_producer_chain();
+    let source = source.read();
+
+    let target = new_blockchain();
+    let mut txn = target.write_transaction();
+    txn.import_tables(Blockchain::SNAPSHOT_TABLES, &mut &snapshot[..])
+        .unwrap();
+    assert_eq!(target.verify_snapshot(&txn, None), Ok(source.head_hash()));
+    txn.commit();
+
+    // The imported chain is the one we exported.
+    let txn = target.read_transaction();
+    assert_eq!(
+        target.chain_store.get_head(Some(&txn)),
+        Some(source.head_hash())
+    );
+    assert_eq!(
+        target
+            .chain_store
+            .get_block_at(
+                Policy::last_election_block(source.block_number()),
+                false,
+                Some(&txn)
+            )
+            .unwrap(),
+        source
+            .chain_store
+            .get_block_at(
+                Policy::last_election_block(source.block_number()),
+                false,
+                None
+            )
+            .unwrap()
+    );
+}
+
+#[test]
+fn it_rejects_a_snapshot_with_an_unjustified_election_block() {
+    let (source, snapshot) = export_producer_chain();
+    let source = source.read();
+
+    let target = new_blockchain();
+    let mut txn = target.write_transaction();
+    txn.import_tables(Blockchain::SNAPSHOT_TABLES, &mut &snapshot[..])
+        .unwrap();
+
+    // Strip the justification of the last election block, it then no longer links to the chain.
+    let election_block_number = Policy::last_election_block(source.block_number());
+    let mut chain_info = target
+        .chain_store
+        .get_chain_info_at(election_block_number, false, Some(&txn))
+        .unwrap();
+    let hash = chain_info.head.hash();
+    match chain_info.head {
+        Block::Macro(ref mut block) => block.justification = None,
+        Block::Micro(_) => unreachable!(),
+    }
+    txn.put_reserve(&ChainTable, &hash, &chain_info);
+
+    assert_eq!(
+        target.verify_snapshot(&txn, None),
+        Err(BlockchainError::InconsistentState)
+    );
+}
+
+#[test]
+fn it_rejects_a_snapshot_with_a_broken_micro_block_chain() {
+    let (source, snapshot) = export_producer_chain();
+    let source = source.read();
+
+    let target = new_blockchain();
+    let mut txn = target.write_transaction();
+    txn.import_tables(Blockchain::SNAPSHOT_TABLES, &mut &snapshot[..])
+        .unwrap();
+
+    // Replace the block preceding the head, the head then no longer links to the macro head.
+    let mut chain_info = target
+        .chain_store
+        .get_chain_info_at(source.block_number() - 1, false, Some(&txn))
+        .unwrap();
+    let hash = chain_info.head.hash();
+    match chain_info.head {
+        Block::Micro(ref mut block) => block.header.extra_data = b"tampered".to_vec(),
+        Block::Macro(_) => unreachable!(),
+    }
+    txn.put_reserve(&ChainTable, &hash, &chain_info);
+
+    assert_eq!(
+        target.verify_snapshot(&txn, None),
+        Err(BlockchainError::InconsistentState)
+    );
+}
+
+#[test]
+fn it_can_export_import_and_verify_a_snapshot_of_a_zkp_synced_chain() {
+    let producer = TemporaryBlockProducer::new();
+    let zkp_synced = TemporaryBlockProducer::new();
+
+    // Produce two epochs and sync the second blockchain to the last election block via ZKP. It
+    // then doesn't store the first election block.
+    for _ in 0..2 * Policy::blocks_per_epoch() - 1 {
+        producer.next_block(vec![], false);
+    }
+    let election_block = producer.next_block(vec![], false);
+    let block_number = election_block.block_number();
+    let proof = simulate_merger_wrapper(
+        Path::new(ZKP_TEST_KEYS_PATH),
+        &producer.blockchain,
+        &ZKP_VERIFYING_DATA,
+        &mut get_base_seed(),
+    )
+    .proof
+    .unwrap();
+    assert_eq!(
+        Blockchain::push_zkp(
+            zkp_synced.blockchain.upgradable_read(),
+            election_block,
+            proof.clone(),
+            false
+        ),
+        Ok(PushResult::Extended)
+    );
+
+    // Complete the accounts tree with the next block.
+    let (block, diff) = producer.next_block_and_diff_with_txs(vec![], false, vec![]);
+    let chunk = producer.get_chunk(KeyNibbles::ROOT, 1000);
+    let (result, _) = zkp_synced
+        .push_with_chunks(block, diff, vec![chunk])
+        .unwrap();
+    assert_eq!(result, PushResult::Extended);
+
+    let mut snapshot = vec![];
+    {
+        let blockchain = zkp_synced.blockchain.read();
+        let txn = blockchain.read_transaction();
+
+        // The chain can't be verified starting at the genesis block.
+        assert_eq!(
+            blockchain.verify_snapshot(&txn, None),
+            Err(BlockchainError::InconsistentState)
+        );
+        assert_eq!(
+            blockchain.verify_snapshot(&txn, Some((block_number, proof.clone()))),
+            Ok(blockchain.head_hash())
+        );
+        txn.export_tables(Blockchain::SNAPSHOT_TABLES, &mut snapshot)
+            .unwrap();
+    }
+
+    let target = new_blockchain();
+    let mut txn = target.write_transaction();
+    txn.import_tables(Blockchain::SNAPSHOT_TABLES, &mut &snapshot[..])
+        .unwrap();
+    assert_eq!(
+        target.verify_snapshot(&txn, Some((block_number, NanoProof::default()))),
+        Err(BlockchainError::InconsistentState)
+    );
+    assert_eq!(
+        target.verify_snapshot(&txn, Some((block_number, proof))),
+        Ok(producer.blockchain.read().head_hash())
+    );
+}
@@ -1,17 +1,22 @@
-use std::time::Duration;
+use std::{sync::Arc, time::Duration};
 
 use futures::StreamExt as _;
 use log::info;
 use nimiq::prover::prover_main;
 pub use nimiq::{
     client::Client,
-    config::{command_line::CommandLine, config::ClientConfig, config_file::ConfigFile},
+    config::{
+        command_line::{Command, CommandLine, SnapshotCommand},
+        config::ClientConfig,
+        config_file::ConfigFile,
+    },
     error::Error,
     extras::{
         logging::{initialize_logging, log_error_cause_chain},
         metrics_server::NimiqTaskMonitor,
         panic::initialize_panic_reporting,
-        signal_handling::initialize_signal_handler,
+        signal_handling::{initialize_signal_handler, on_shutdown},
+        snapshot::{export_snapshot, import_snapshot},
     },
 };
 use nimiq_time::interval;
@@ -61,6 +66,14 @@ async fn main_inner() -> Result<(), Error> {
     let config = builder.build()?;
     log::debug!("Final configuration: {:#?}", config);
 
+    // Early return in case of a maintenance command.
+    if let Some(Command::Snapshot(command)) = &command_line.command {
+        return match command {
+            SnapshotCommand::Export { path } => export_snapshot(&config, path),
+            SnapshotCommand::Import { path } => import_snapshot(&config, path),
+        };
+    }
+
     // Clone config for RPC and metrics server
     let rpc_config = config.rpc_server.clone();
     let metrics_config = config.metrics_server.clone();
@@ -97,6 +110,12 @@ async fn main_inner() -> Result<(), Error> {
     let consensus = client.consensus_proxy();
     let mempool = client.mempool();
 
+    // Persist the mempool transactions one last time on shutdown.
+    if let Some(mempool) = mempool.as_ref().filter(|mempool| mempool.is_persistent()) {
+        let mempool = Arc::clone(mempool);
+        on_shutdown(move || mempool.persist());
+    }
+
     let zkp_component = client.take_zkp_component().unwrap();
     spawn(zkp_component); //ITODO get metrics on this? ask JD
 
@@ -148,6 +167,9 @@ async fn main_inner() -> Result<(), Error> {
             mempool,
             client.consensus_proxy(),
             client.network(),
+            client
+                .validator_proxy()
+                .map(|validator| validator.network_status),
             &nimiq_task_metric,
         )
     }
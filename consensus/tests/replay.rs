@@ -0,0 +1,216 @@
+use std::sync::Arc;
+
+use futures::{future, StreamExt};
+use nimiq_blockchain::{BlockProducer, Blockchain, BlockchainConfig};
+use nimiq_blockchain_interface::{AbstractBlockchain, BlockchainEvent, Direction};
+use nimiq_blockchain_proxy::BlockchainProxy;
+use nimiq_consensus::{
+    messages::{BlockBodyTopic, BlockHeaderMessage, BlockHeaderTopic, RequestHead, ResponseHead},
+    sync::syncer_proxy::SyncerProxy,
+    BlsCache, Consensus,
+};
+use nimiq_database::mdbx::MdbxDatabase;
+use nimiq_genesis::NetworkId;
+use nimiq_network_interface::{
+    network::{Network, PubsubId, Topic},
+    request::{InboundRequestError, RequestCommon},
+};
+use nimiq_network_libp2p::{CaptureEvent, CaptureRecord, PeerId};
+use nimiq_network_mock::MockHub;
+use nimiq_primitives::policy::Policy;
+use nimiq_serde::Serialize;
+use nimiq_test_log::test;
+use nimiq_test_utils::{
+    blockchain::{produce_macro_blocks, push_micro_block, signing_key, voting_key},
+    replay::TrafficReplay,
+};
+use nimiq_utils::{spawn, time::OffsetTime};
+use nimiq_zkp_component::ZKPComponent;
+use parking_lot::{Mutex, RwLock};
+
+fn new_blockchain() -> Arc<RwLock<Blockchain>> {
+    Arc::new(RwLock::new(
+        Blockchain::new(
+            MdbxDatabase::new_volatile(Default::default()).unwrap(),
+            BlockchainConfig::default(),
+            NetworkId::UnitAlbatross,
+            Arc::new(OffsetTime::new()),
+        )
+        .unwrap(),
+    ))
+}
+
+fn record(timestamp: u64, peer_id: PeerId, event: CaptureEvent) -> CaptureRecord {
+    CaptureRecord {
+        timestamp,
+        peer_id,
+        event,
+    }
+}
+
+#[test(tokio::test)]
+async fn it_replays_gossip_and_requests() {
+    let mut hub = MockHub::default();
+    let local_peer = PeerId::random();
+    let peer1 = PeerId::random();
+    let peer2 = PeerId::random();
+
+    let producer = BlockProducer::new(signing_key(), voting_key());
+    let blockchain = new_blockchain();
+    push_micro_block(&producer, &blockchain);
+    let (header, _) = BlockHeaderMessage::split_block(blockchain.read().head());
+    let response = ResponseHead {
+        block_number: 1,
+        block_hash: blockchain.read().head_hash(),
+        macro_hash: Default::default(),
+        election_hash: Default::default(),
+    };
+
+    let records = vec![
+        record(
+            0,
+            peer1,
+            CaptureEvent::GossipReceived {
+                topic: BlockHeaderTopic::NAME.to_owned(),
+                data: header.serialize_to_vec(),
+            },
+        ),
+        // Messages published by the captured node are not replayed.
+        record(
+            1,
+            local_peer,
+            CaptureEvent::GossipPublished {
+                topic: BlockHeaderTopic::NAME.to_owned(),
+                data: header.serialize_to_vec(),
+            },
+        ),
+        record(
+            2,
+            peer1,
+            CaptureEvent::RequestSent {
+                id: 0,
+                data: RequestHead {}.serialize_request(),
+            },
+        ),
+        record(
+            3,
+            peer1,
+            CaptureEvent::ResponseReceived {
+                id: 0,
+                data: Some(Ok::<_, InboundRequestError>(response.clone()).serialize_to_vec()),
+            },
+        ),
+        record(
+            4,
+            peer2,
+            CaptureEvent::RequestReceived {
+                id: 1,
+                data: RequestHead {}.serialize_request(),
+            },
+        ),
+    ];
+
+    let replay = TrafficReplay::new(records, &mut hub);
+    let network1 = Arc::clone(replay.network(&peer1).unwrap());
+    let network2 = Arc::clone(replay.network(&peer2).unwrap());
+    assert!(replay.network(&local_peer).is_none());
+
+    let target = hub.new_network();
+    replay.connect(&target);
+    let mut headers = target.subscribe::<BlockHeaderTopic>().await.unwrap();
+    let mut requests = target.receive_requests::<RequestHead>();
+
+    replay.run(&target, false).await;
+
+    // The gossipsub message arrives from the mock network of the captured peer.
+    let (received_header, id) = headers.next().await.unwrap();
+    assert_eq!(
+        received_header.serialize_to_vec(),
+        header.serialize_to_vec()
+    );
+    assert_eq!(id.propagation_source(), network1.peer_id());
+
+    // The request received by the captured node is sent to the target.
+    let (_, _, peer_id) = requests.next().await.unwrap();
+    assert_eq!(peer_id, network2.peer_id());
+
+    // Requests of the target are answered with the captured response.
+    let received_response = target
+        .request(RequestHead {}, network1.peer_id())
+        .await
+        .unwrap();
+    assert_eq!(received_response.block_number, response.block_number);
+    assert_eq!(received_response.block_hash, response.block_hash);
+}
+
+#[test(tokio::test)]
+async fn it_feeds_a_consensus_instance() {
+    let mut hub = MockHub::default();
+    let peer_id = PeerId::random();
+
+    // Capture a batch of blocks announced by a peer.
+    let producer = BlockProducer::new(signing_key(), voting_key());
+    let producer_blockchain = new_blockchain();
+    let genesis_hash = producer_blockchain.read().head_hash();
+    produce_macro_blocks(&producer, &producer_blockchain, 1);
+    let blocks = producer_blockchain
+        .read()
+        .get_blocks(
+            &genesis_hash,
+            Policy::blocks_per_batch(),
+            true,
+            Direction::Forward,
+        )
+        .unwrap();
+
+    let records = blocks
+        .into_iter()
+        .flat_map(|block| {
+            let (header, body) = BlockHeaderMessage::split_block(block);
+            [
+                CaptureEvent::GossipReceived {
+                    topic: BlockHeaderTopic::NAME.to_owned(),
+                    data: header.serialize_to_vec(),
+                },
+                CaptureEvent::GossipReceived {
+                    topic: BlockBodyTopic::NAME.to_owned(),
+                    data: body.serialize_to_vec(),
+                },
+            ]
+        })
+        .enumerate()
+        .map(|(i, event)| record(i as u64, peer_id, event))
+        .collect();
+    let replay = TrafficReplay::new(records, &mut hub);
+
+    // Set up the node under test at the genesis block.
+    let blockchain = BlockchainProxy::from(new_blockchain());
+    let network = Arc::new(hub.new_network());
+    let zkp_prover = ZKPComponent::new(blockchain.clone(), Arc::clone(&network), None)
+        .await
+        .proxy();
+    let syncer = SyncerProxy::new_history(
+        blockchain.clone(),
+        Arc::clone(&network),
+        Arc::new(Mutex::new(BlsCache::new_test())),
+        network.subscribe_events(),
+    )
+    .await;
+    let consensus =
+        Consensus::from_network(blockchain.clone(), Arc::clone(&network), syncer, zkp_prover);
+    let mut events = blockchain
+        .read()
+        .notifier_as_stream()
+        .filter(|event| future::ready(matches!(event, BlockchainEvent::Finalized(_))));
+    spawn(consensus);
+
+    replay.connect(&network);
+    replay.run(&network, false).await;
+
+    // The node follows the captured chain up to the macro block.
+    events.next().await.unwrap();
+    assert_eq!(
+        blockchain.read().head_hash(),
+        producer_blockchain.read().head_hash()
+    );
+}
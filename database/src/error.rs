@@ -6,4 +6,10 @@ pub enum Error {
     CreateDirectory(#[from] std::io::Error),
     #[error("Mdbx error: {0}")]
     Mdbx(#[from] libmdbx::Error),
+    #[error("Couldn't read or write snapshot: {0}")]
+    Snapshot(std::io::Error),
+    #[error("Malformed snapshot: {0}")]
+    MalformedSnapshot(&'static str),
+    #[error("Table {0} does not exist")]
+    UnknownTable(String),
 }
@@ -1,5 +1,11 @@
 mod error;
 pub mod mdbx;
+/// A database backend that is kept entirely in memory.
+///
+/// It can be used by code that is generic over the [`traits::Database`] trait, like the wallet
+/// store. The chain and history stores as well as the accounts trie are not generic yet and still
+/// require MDBX, so they can't run on this backend.
+pub mod memory;
 /// Abstraction for methods related to the database.
 pub mod traits;
 pub mod utils;
@@ -34,7 +34,7 @@ pub struct DatabaseConfig {
 impl Default for DatabaseConfig {
     fn default() -> Self {
         DatabaseConfig {
-            max_tables: Some(20),
+            max_tables: Some(24),
             max_readers: None,
             no_rdahead: true,
             // Default max database size: 2TB
@@ -324,4 +324,91 @@ mod tests {
         }
         tempdir.close().unwrap();
     }
+
+    #[test]
+    fn it_exports_and_imports_tables() {
+        let source = MdbxDatabase::new_volatile(Default::default()).unwrap();
+        let target = MdbxDatabase::new_volatile(Default::default()).unwrap();
+        for db in [&source, &target] {
+            db.create_regular_table(&TestTable);
+            db.create_dup_table(&U32DupTable);
+            db.create_regular_table(&U32Table);
+        }
+
+        let mut txw = source.write_transaction();
+        txw.put(&TestTable, &"a".to_string(), &"one".to_string());
+        txw.put(&TestTable, &"b".to_string(), &"two".to_string());
+        txw.put(&U32DupTable, &256, &3);
+        txw.put(&U32DupTable, &256, &2);
+        txw.put(&U32DupTable, &3, &1);
+        txw.commit();
+
+        // Existing entries of the target are replaced, also in tables missing in the snapshot.
+        let mut txw = target.write_transaction();
+        txw.put(&TestTable, &"c".to_string(), &"three".to_string());
+        txw.put(&U32Table, &1, &1);
+        txw.commit();
+
+        let mut snapshot = vec![];
+        let num_entries = source
+            .read_transaction()
+            .export_tables(&["test", "u32_dup", "missing"], &mut snapshot)
+            .unwrap();
+        assert_eq!(num_entries, 5);
+
+        let mut txw = target.write_transaction();
+        let names = txw
+            .import_tables(&["test", "u32_dup", "u32_nodup"], &mut &snapshot[..])
+            .unwrap();
+        assert_eq!(names, vec!["test".to_string(), "u32_dup".to_string()]);
+        txw.commit();
+
+        let tx = target.read_transaction();
+        let entries: Vec<_> = tx.cursor(&TestTable).into_iter_start().collect();
+        assert_eq!(
+            entries,
+            vec![
+                ("a".to_string(), "one".to_string()),
+                ("b".to_string(), "two".to_string())
+            ]
+        );
+        let entries: Vec<_> = tx.dup_cursor(&U32DupTable).into_iter_start().collect();
+        assert_eq!(entries, vec![(3, 1), (256, 2), (256, 3)]);
+        assert_eq!(tx.cursor(&U32Table).into_iter_start().count(), 0);
+        tx.close();
+
+        // Snapshots containing unexpected tables are rejected.
+        let mut txw = target.write_transaction();
+        assert!(matches!(
+            txw.import_tables(&["test"], &mut &snapshot[..]),
+            Err(crate::Error::MalformedSnapshot(_))
+        ));
+        txw.abort();
+
+        // Importing into a database without the tables fails.
+        let empty = MdbxDatabase::new_volatile(Default::default()).unwrap();
+        let mut txw = empty.write_transaction();
+        assert!(matches!(
+            txw.import_tables(&["test", "u32_dup"], &mut &snapshot[..]),
+            Err(crate::Error::UnknownTable(_))
+        ));
+    }
+
+    #[test]
+    fn it_rejects_oversized_snapshot_records() {
+        let db = MdbxDatabase::new_volatile(Default::default()).unwrap();
+        db.create_regular_table(&TestTable);
+
+        // A table name with a length prefix of 4 GiB.
+        let mut snapshot = vec![1u8];
+        snapshot.extend_from_slice(&u32::MAX.to_be_bytes());
+
+        let mut txw = db.write_transaction();
+        match txw.import_tables(&["test"], &mut &snapshot[..]) {
+            Err(crate::Error::Snapshot(error)) => {
+                assert_eq!(error.kind(), std::io::ErrorKind::InvalidData)
+            }
+            result => panic!("Unexpected result: {:?}", result),
+        }
+    }
 }
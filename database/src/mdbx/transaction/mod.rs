@@ -1,4 +1,5 @@
 mod proxy;
+mod snapshot;
 mod wrapper;
 
 use std::borrow::Cow;
@@ -0,0 +1,148 @@
+use std::{
+    borrow::Cow,
+    io::{self, Read, Write},
+};
+
+use libmdbx::{TransactionKind, WriteFlags, RW};
+
+use super::MdbxTransaction;
+use crate::Error;
+
+/// Marks the start of a table in a snapshot. It is followed by the length-prefixed table name.
+const TAG_TABLE: u8 = 1;
+/// Marks an entry of the current table. It is followed by the length-prefixed key and value.
+const TAG_ENTRY: u8 = 2;
+/// Marks the end of a snapshot.
+const TAG_END: u8 = 0;
+
+/// The maximum size of a single key, value or table name in a snapshot. Lengths are read before
+/// the snapshot can be verified, so they must be bounded to not allocate arbitrary amounts of
+/// memory.
+pub(crate) const MAX_RECORD_SIZE: usize = 64 * 1024 * 1024;
+
+type DbKvPair<'a> = (Cow<'a, [u8]>, Cow<'a, [u8]>);
+
+fn write_bytes<W: Write>(writer: &mut W, bytes: &[u8]) -> io::Result<()> {
+    let len = u32::try_from(bytes.len())
+        .ok()
+        .filter(|_| bytes.len() <= MAX_RECORD_SIZE)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "entry too large"))?;
+    writer.write_all(&len.to_be_bytes())?;
+    writer.write_all(bytes)
+}
+
+fn read_u8<R: Read>(reader: &mut R) -> io::Result<u8> {
+    let mut buf = [0u8; 1];
+    reader.read_exact(&mut buf)?;
+    Ok(buf[0])
+}
+
+fn read_bytes<R: Read>(reader: &mut R) -> io::Result<Vec<u8>> {
+    let mut len = [0u8; 4];
+    reader.read_exact(&mut len)?;
+    let len = u32::from_be_bytes(len) as usize;
+    if len > MAX_RECORD_SIZE {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "entry exceeds the maximum size",
+        ));
+    }
+    let mut bytes = vec![0u8; len];
+    reader.read_exact(&mut bytes)?;
+    Ok(bytes)
+}
+
+impl<Kind> MdbxTransaction<'_, Kind>
+where
+    Kind: TransactionKind,
+{
+    /// Writes the raw entries of the given tables to `writer`.
+    /// Tables that don't exist in the database are skipped.
+    /// Returns the number of entries written.
+    pub(crate) fn export_tables<W: Write>(
+        &self,
+        names: &[&str],
+        writer: &mut W,
+    ) -> Result<usize, Error> {
+        let mut num_entries = 0;
+
+        for name in names {
+            let table = match self.txn.open_table(Some(name)) {
+                Ok(table) => table,
+                Err(libmdbx::Error::NotFound) => continue,
+                Err(e) => return Err(e.into()),
+            };
+
+            writer.write_all(&[TAG_TABLE]).map_err(Error::Snapshot)?;
+            write_bytes(writer, name.as_bytes()).map_err(Error::Snapshot)?;
+
+            // Duplicate values are visited one by one, so dup tables need no special treatment.
+            let mut cursor = self.txn.cursor(&table)?;
+            let mut entry: Option<DbKvPair> = cursor.first()?;
+            while let Some((key, value)) = entry {
+                writer.write_all(&[TAG_ENTRY]).map_err(Error::Snapshot)?;
+                write_bytes(writer, &key).map_err(Error::Snapshot)?;
+                write_bytes(writer, &value).map_err(Error::Snapshot)?;
+                num_entries += 1;
+
+                entry = cursor.next()?;
+            }
+        }
+
+        writer.write_all(&[TAG_END]).map_err(Error::Snapshot)?;
+        Ok(num_entries)
+    }
+}
+
+impl MdbxTransaction<'_, RW> {
+    /// Reads a snapshot written by `export_tables` from `reader` and replaces the contents of the
+    /// given tables with it. All of the tables are cleared, even if the snapshot doesn't contain
+    /// them, and the snapshot must not contain any other table. The tables must already exist.
+    /// Returns the names of the imported tables.
+    pub(crate) fn import_tables<R: Read>(
+        &mut self,
+        names: &[&str],
+        reader: &mut R,
+    ) -> Result<Vec<String>, Error> {
+        for name in names {
+            let table = match self.txn.open_table(Some(name)) {
+                Ok(table) => table,
+                Err(libmdbx::Error::NotFound) => return Err(Error::UnknownTable(name.to_string())),
+                Err(e) => return Err(e.into()),
+            };
+            self.txn.clear_table(&table)?;
+        }
+
+        let mut imported_names: Vec<String> = vec![];
+        let mut table = None;
+
+        loop {
+            match read_u8(reader).map_err(Error::Snapshot)? {
+                TAG_TABLE => {
+                    let name = String::from_utf8(read_bytes(reader).map_err(Error::Snapshot)?)
+                        .map_err(|_| Error::MalformedSnapshot("invalid table name"))?;
+                    if !names.contains(&name.as_str()) {
+                        return Err(Error::MalformedSnapshot("unexpected table"));
+                    }
+                    if imported_names.contains(&name) {
+                        return Err(Error::MalformedSnapshot("duplicate table"));
+                    }
+
+                    table = Some(self.txn.open_table(Some(&name))?);
+                    imported_names.push(name);
+                }
+                TAG_ENTRY => {
+                    let table = table
+                        .as_ref()
+                        .ok_or(Error::MalformedSnapshot("entry outside of a table"))?;
+                    let key = read_bytes(reader).map_err(Error::Snapshot)?;
+                    let value = read_bytes(reader).map_err(Error::Snapshot)?;
+
+                    self.txn.put(table, key, value, WriteFlags::empty())?;
+                }
+                TAG_END => return Ok(imported_names),
+                _ => return Err(Error::MalformedSnapshot("unknown tag")),
+            }
+        }
+    }
+}
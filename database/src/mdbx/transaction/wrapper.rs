@@ -1,4 +1,7 @@
-use std::ops::Deref;
+use std::{
+    io::{Read, Write},
+    ops::Deref,
+};
 
 use libmdbx::{NoWriteMap, RO, RW};
 use nimiq_database_value::IntoDatabaseValue;
@@ -7,6 +10,7 @@ use super::MdbxTransaction;
 use crate::{
     mdbx::{CursorProxy, MdbxCursor},
     traits::{DupTable, ReadTransaction, RegularTable, Table, WriteTransaction},
+    Error,
 };
 
 /// A proxy object that can be either a read or a write transaction.
@@ -26,6 +30,16 @@ impl<'db> MdbxReadTransaction<'db> {
     pub(crate) fn new_read(txn: libmdbx::Transaction<'db, RO, NoWriteMap>) -> Self {
         MdbxReadTransaction::Read(MdbxTransaction::new(txn))
     }
+
+    /// Writes a snapshot of the given tables to `writer`. Since all tables are read within this
+    /// transaction, the snapshot is consistent. Tables that don't exist are skipped.
+    /// Returns the number of entries written.
+    pub fn export_tables<W: Write>(&self, names: &[&str], writer: &mut W) -> Result<usize, Error> {
+        match self {
+            MdbxReadTransaction::Read(ref txn) => txn.export_tables(names, writer),
+            MdbxReadTransaction::Write(ref txn) => txn.export_tables(names, writer),
+        }
+    }
 }
 
 impl<'db> ReadTransaction<'db> for MdbxReadTransaction<'db> {
@@ -75,6 +89,21 @@ impl<'db> MdbxWriteTransaction<'db> {
             txn: MdbxReadTransaction::Write(MdbxTransaction::new(txn)),
         }
     }
+
+    /// Imports a snapshot written by `MdbxReadTransaction::export_tables`.
+    /// The contents of all given tables are replaced, tables that are missing in the snapshot are
+    /// left empty. Snapshots containing other tables are rejected. The tables must already exist.
+    /// Returns the names of the imported tables.
+    pub fn import_tables<R: Read>(
+        &mut self,
+        names: &[&str],
+        reader: &mut R,
+    ) -> Result<Vec<String>, Error> {
+        match self.txn {
+            MdbxReadTransaction::Write(ref mut txn) => txn.import_tables(names, reader),
+            _ => unreachable!(),
+        }
+    }
 }
 
 impl<'db> ReadTransaction<'db> for MdbxWriteTransaction<'db> {
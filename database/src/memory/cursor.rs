@@ -0,0 +1,276 @@
+use std::{cmp::Ordering, marker::PhantomData};
+
+use nimiq_database_value::{AsDatabaseBytes, FromDatabaseBytes};
+
+use super::{
+    table::{Entry, MemoryTable, TableView},
+    transaction::TxnTables,
+    IntoIter,
+};
+use crate::traits::{
+    DupReadCursor, DupSubKey, DupTable, DupTableValue, DupWriteCursor, ReadCursor, Row, Table,
+    WriteCursor,
+};
+
+/// A cursor for navigating the entries within a table of the in-memory database.
+///
+/// The cursor remembers the entry it is positioned at, so it stays valid if the table is modified
+/// through the same transaction.
+pub struct MemoryCursor<'txn, T: Table> {
+    tables: &'txn TxnTables,
+    /// The entry the cursor is positioned at.
+    pub(super) position: Option<Entry>,
+    _table: PhantomData<T>,
+}
+/// Instantiation of the `MemoryCursor` for read transactions.
+pub type MemoryReadCursor<'txn, T> = MemoryCursor<'txn, T>;
+/// Instantiation of the `MemoryCursor` for write transactions.
+pub type MemoryWriteCursor<'txn, T> = MemoryCursor<'txn, T>;
+
+impl<'txn, T: Table> MemoryCursor<'txn, T> {
+    pub(super) fn new(tables: &'txn TxnTables) -> Self {
+        MemoryCursor {
+            tables,
+            position: None,
+            _table: PhantomData,
+        }
+    }
+
+    /// Decodes an entry into a row.
+    fn decode(entry: &Entry) -> Row<T> {
+        let (key, value) = entry;
+        (
+            FromDatabaseBytes::from_key_bytes(&MemoryTable::decode_key::<T>(key)),
+            FromDatabaseBytes::from_value_bytes(value),
+        )
+    }
+
+    /// Moves the cursor to the entry returned by `f`.
+    /// The cursor stays at its position if there is no such entry.
+    fn move_to(
+        &mut self,
+        f: impl FnOnce(&TableView, Option<&Entry>) -> Option<Entry>,
+    ) -> Option<Row<T>> {
+        let entry = self
+            .tables
+            .with_table::<T, _>(|table| f(table, self.position.as_ref()))?;
+        let row = Self::decode(&entry);
+        self.position = Some(entry);
+        Some(row)
+    }
+
+    /// Moves the cursor relative to its current position using `f`.
+    /// Returns `None` if the cursor isn't positioned.
+    fn move_from(
+        &mut self,
+        f: impl FnOnce(&TableView, &[u8], &[u8]) -> Option<Entry>,
+    ) -> Option<Row<T>> {
+        self.move_to(|table, position| {
+            let (key, value) = position?;
+            f(table, &key[..], &value[..])
+        })
+    }
+}
+
+impl<T: DupTable> MemoryCursor<'_, T>
+where
+    T::Value: DupTableValue,
+{
+    fn encode_subkey(subkey: &DupSubKey<T>) -> Vec<u8> {
+        let mut enc_key = subkey.as_value_bytes().into_owned();
+        if let Some(new_len) = T::Value::FIXED_SIZE {
+            enc_key.resize(new_len, 0);
+        }
+        enc_key
+    }
+}
+
+impl<'txn, T: Table> ReadCursor<'txn, T> for MemoryCursor<'txn, T> {
+    type IntoIter = IntoIter<'txn, T>;
+
+    fn first(&mut self) -> Option<Row<T>> {
+        self.move_to(|table, _| table.first())
+    }
+
+    fn last(&mut self) -> Option<Row<T>> {
+        self.move_to(|table, _| table.last())
+    }
+
+    fn next(&mut self) -> Option<Row<T>> {
+        self.move_to(|table, position| match position {
+            Some((key, value)) => table.next(key, value),
+            None => table.first(),
+        })
+    }
+
+    fn prev(&mut self) -> Option<Row<T>> {
+        self.move_to(|table, position| match position {
+            Some((key, value)) => table.prev(key, value),
+            None => table.last(),
+        })
+    }
+
+    fn get_current(&mut self) -> Option<Row<T>> {
+        let (key, value) = self.position.as_ref()?;
+        if !self
+            .tables
+            .with_table::<T, _>(|table| table.contains(key, value))
+        {
+            return None;
+        }
+        Some(Self::decode(self.position.as_ref()?))
+    }
+
+    fn set_key(&mut self, key: &T::Key) -> Option<T::Value> {
+        let key = MemoryTable::encode_key::<T>(key);
+        self.move_to(|table, _| table.duplicate(&key, false))
+            .map(|(_, value)| value)
+    }
+
+    fn set_lowerbound_key(&mut self, key: &T::Key) -> Option<Row<T>> {
+        let key = MemoryTable::encode_key::<T>(key);
+        self.move_to(|table, _| table.lower_bound(&key))
+    }
+
+    fn into_iter_start(mut self) -> Self::IntoIter {
+        let first = self.first();
+        IntoIter::new(self, first, None)
+    }
+
+    fn into_iter_from(mut self, key: &T::Key) -> Self::IntoIter {
+        let first = self.set_lowerbound_key(key);
+        IntoIter::new(self, first, None)
+    }
+}
+
+impl<'txn, T: DupTable> DupReadCursor<'txn, T> for MemoryCursor<'txn, T> {
+    fn first_duplicate(&mut self) -> Option<T::Value> {
+        self.move_from(|table, key, _| table.duplicate(key, false))
+            .map(|(_, value)| value)
+    }
+
+    fn last_duplicate(&mut self) -> Option<T::Value> {
+        self.move_from(|table, key, _| table.duplicate(key, true))
+            .map(|(_, value)| value)
+    }
+
+    fn next_duplicate(&mut self) -> Option<Row<T>> {
+        self.move_from(|table, key, value| table.next_duplicate(key, value))
+    }
+
+    fn next_no_duplicate(&mut self) -> Option<Row<T>> {
+        self.move_to(|table, position| match position {
+            Some((key, _)) => table.next_key(key),
+            None => table.first(),
+        })
+    }
+
+    fn prev_duplicate(&mut self) -> Option<Row<T>> {
+        self.move_from(|table, key, value| table.prev_duplicate(key, value))
+    }
+
+    fn prev_no_duplicate(&mut self) -> Option<Row<T>> {
+        self.move_to(|table, position| match position {
+            Some((key, _)) => table.prev_key(key),
+            None => table.last(),
+        })
+    }
+
+    fn set_subkey(&mut self, key: &T::Key, subkey: &DupSubKey<T>) -> Option<T::Value>
+    where
+        T::Value: DupTableValue,
+    {
+        let value = self.set_lowerbound_subkey(key, subkey)?;
+        if value.subkey().cmp(subkey) == Ordering::Equal {
+            Some(value)
+        } else {
+            None
+        }
+    }
+
+    fn set_lowerbound_both(&mut self, key: &T::Key, subkey: &DupSubKey<T>) -> Option<Row<T>>
+    where
+        T::Value: DupTableValue,
+    {
+        let key = MemoryTable::encode_key::<T>(key);
+        let data = Self::encode_subkey(subkey);
+        self.move_to(|table, _| table.lower_bound_both(&key, &data))
+    }
+
+    fn set_lowerbound_subkey(&mut self, key: &T::Key, subkey: &DupSubKey<T>) -> Option<T::Value>
+    where
+        T::Value: DupTableValue,
+    {
+        let key = MemoryTable::encode_key::<T>(key);
+        let data = Self::encode_subkey(subkey);
+        self.move_to(|table, _| table.lower_bound_value(&key, &data))
+            .map(|(_, value)| value)
+    }
+
+    fn count_duplicates(&mut self) -> usize {
+        let Some((key, _)) = &self.position else {
+            return 0;
+        };
+        self.tables
+            .with_table::<T, _>(|table| table.count_duplicates(key))
+    }
+
+    fn into_iter_dup_of(mut self, key: &T::Key) -> Self::IntoIter {
+        let encoded_key = MemoryTable::encode_key::<T>(key);
+        let first = self.move_to(|table, _| table.duplicate(&encoded_key, false));
+        IntoIter::new(self, first, Some(encoded_key))
+    }
+}
+
+impl<T: Table> Clone for MemoryCursor<'_, T> {
+    fn clone(&self) -> Self {
+        Self {
+            tables: self.tables,
+            position: self.position.clone(),
+            _table: PhantomData,
+        }
+    }
+}
+
+impl<'txn, T: Table> WriteCursor<'txn, T> for MemoryWriteCursor<'txn, T> {
+    fn remove(&mut self) {
+        // The cursor keeps its position, such that `next` continues with the following entry.
+        if let Some((key, value)) = &self.position {
+            self.tables
+                .with_table_mut::<T, _>(|table| table.remove_item(key, value));
+        }
+    }
+
+    fn append(&mut self, key: &T::Key, value: &T::Value) {
+        let key = MemoryTable::encode_key::<T>(key);
+        let value = AsDatabaseBytes::as_value_bytes(value).into_owned();
+        self.tables
+            .with_table_mut::<T, _>(|table| table.append(key.clone(), value.clone()));
+        self.position = Some((key, value));
+    }
+
+    fn put(&mut self, key: &T::Key, value: &T::Value) {
+        let key = MemoryTable::encode_key::<T>(key);
+        let value = AsDatabaseBytes::as_value_bytes(value).into_owned();
+        self.tables
+            .with_table_mut::<T, _>(|table| table.put(key.clone(), value.clone()));
+        self.position = Some((key, value));
+    }
+}
+
+impl<'txn, T: DupTable> DupWriteCursor<'txn, T> for MemoryWriteCursor<'txn, T> {
+    fn append_dup(&mut self, key: &T::Key, value: &T::Value) {
+        let key = MemoryTable::encode_key::<T>(key);
+        let value = AsDatabaseBytes::as_value_bytes(value).into_owned();
+        self.tables
+            .with_table_mut::<T, _>(|table| table.append_dup(key.clone(), value.clone()));
+        self.position = Some((key, value));
+    }
+
+    fn remove_all_dup(&mut self) {
+        if let Some((key, _)) = &self.position {
+            self.tables
+                .with_table_mut::<T, _>(|table| table.remove(key));
+        }
+    }
+}
@@ -0,0 +1,71 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex, RwLock},
+};
+
+use log::debug;
+
+use super::{table::MemoryTable, MemoryReadTransaction, MemoryWriteTransaction};
+use crate::traits::{Database, DupTable, RegularTable, Table};
+
+/// The tables of a database, indexed by their name.
+/// Tables are shared with the snapshots of the transactions. Committing changes to a table copies
+/// it only if a snapshot still refers to it.
+pub(super) type Tables = HashMap<&'static str, Arc<MemoryTable>>;
+
+/// A database that is kept entirely in memory.
+/// It does not touch the file system and all data is lost once it is dropped.
+///
+/// Like MDBX, it supports multiple concurrent read transactions, which see a consistent snapshot
+/// of the database, and a single write transaction at a time.
+#[derive(Clone, Debug, Default)]
+pub struct MemoryDatabase {
+    /// The committed state of the tables.
+    pub(super) tables: Arc<RwLock<Tables>>,
+    /// Lock that is held by the write transaction.
+    pub(super) write_lock: Arc<Mutex<()>>,
+}
+
+impl MemoryDatabase {
+    /// Creates a new, empty database.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a table if it doesn't exist yet.
+    fn create_table<T: Table>(&self, _table: &T, dup: bool) {
+        // Acquire the write lock, so that a concurrent write transaction doesn't operate on a
+        // snapshot without the table.
+        let _write_guard = self.write_lock.lock().unwrap();
+        let mut tables = self.tables.write().unwrap();
+        if !tables.contains_key(T::NAME) {
+            debug!("Creating table: {}, dup: {}", T::NAME, dup);
+            tables.insert(T::NAME, Arc::new(MemoryTable::new(dup)));
+        }
+    }
+}
+
+impl Database for MemoryDatabase {
+    type ReadTransaction<'db> = MemoryReadTransaction<'db>;
+
+    type WriteTransaction<'db> = MemoryWriteTransaction<'db>;
+
+    fn create_regular_table<T: RegularTable>(&self, table: &T) {
+        self.create_table(table, false)
+    }
+
+    fn create_dup_table<T: DupTable>(&self, table: &T) {
+        self.create_table(table, true)
+    }
+
+    fn read_transaction(&self) -> Self::ReadTransaction<'_> {
+        MemoryReadTransaction::new(self.tables.read().unwrap().clone())
+    }
+
+    fn write_transaction(&self) -> Self::WriteTransaction<'_> {
+        // Acquire the write lock first, so that the snapshot can't be outdated by another writer.
+        let write_guard = self.write_lock.lock().unwrap();
+        let tables = self.tables.read().unwrap().clone();
+        MemoryWriteTransaction::new(self, tables, write_guard)
+    }
+}
@@ -0,0 +1,52 @@
+use super::MemoryCursor;
+use crate::traits::{ReadCursor, Row, Table};
+
+/// Iterates over database entries (key, value pairs).
+/// Can be instantiated for both read and write transactions.
+pub struct IntoIter<'txn, T: Table> {
+    cursor: MemoryCursor<'txn, T>,
+    /// The entry to be returned first, the cursor is positioned at it.
+    first: Option<Row<T>>,
+    /// If set, only the entries with this (encoded) key are returned.
+    dup_key: Option<Vec<u8>>,
+    exhausted: bool,
+}
+
+impl<'txn, T: Table> IntoIter<'txn, T> {
+    pub(super) fn new(
+        cursor: MemoryCursor<'txn, T>,
+        first: Option<Row<T>>,
+        dup_key: Option<Vec<u8>>,
+    ) -> Self {
+        IntoIter {
+            exhausted: first.is_none(),
+            cursor,
+            first,
+            dup_key,
+        }
+    }
+}
+
+impl<T: Table> Iterator for IntoIter<'_, T> {
+    type Item = Row<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.exhausted {
+            return None;
+        }
+        if let Some(first) = self.first.take() {
+            return Some(first);
+        }
+
+        let row = self.cursor.next().filter(|_| match &self.dup_key {
+            Some(dup_key) => self
+                .cursor
+                .position
+                .as_ref()
+                .is_some_and(|(key, _)| key == dup_key),
+            None => true,
+        });
+        self.exhausted = row.is_none();
+        row
+    }
+}
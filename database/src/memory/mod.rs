@@ -0,0 +1,434 @@
+mod cursor;
+mod database;
+mod iterators;
+mod table;
+mod transaction;
+
+pub use self::{cursor::*, database::*, iterators::*, transaction::*};
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::{mpsc, Arc},
+        thread,
+        time::Duration,
+    };
+
+    use super::*;
+    use crate::{
+        declare_table,
+        traits::{
+            Database, DupReadCursor, DupWriteCursor, ReadCursor, ReadTransaction, Table,
+            WriteCursor, WriteTransaction,
+        },
+        utils::IndexedValue,
+    };
+
+    declare_table!(TestTable, "test", String => String);
+    declare_table!(DupTestTable, "dup_test", String => dup(u32));
+    declare_table!(U32DupTable, "u32_dup", u32 => dup(u32));
+    declare_table!(U32Table, "u32_nodup", u32 => u32);
+    declare_table!(IndexedTable, "indexed", u32 => u32 => String);
+
+    #[test]
+    fn it_can_save_basic_objects() {
+        let db = MemoryDatabase::new();
+        let table = TestTable {};
+        db.create_regular_table(&table);
+
+        // Read non-existent value.
+        {
+            let tx = db.read_transaction();
+            assert!(tx.get(&table, &"test".to_string()).is_none());
+        }
+
+        // Read non-existent value.
+        let mut tx = db.write_transaction();
+        assert!(tx.get(&table, &"test".to_string()).is_none());
+
+        // Write and read value.
+        tx.put(&table, &"test".to_string(), &"one".to_string());
+        assert_eq!(tx.get(&table, &"test".to_string()), Some("one".to_string()));
+        // Overwrite and read value.
+        tx.put(&table, &"test".to_string(), &"two".to_string());
+        assert_eq!(tx.get(&table, &"test".to_string()), Some("two".to_string()));
+        tx.commit();
+
+        // Read value.
+        let tx = db.read_transaction();
+        assert_eq!(tx.get(&table, &"test".to_string()), Some("two".to_string()));
+        tx.close();
+
+        // Remove value.
+        let mut tx = db.write_transaction();
+        tx.remove(&table, &"test".to_string());
+        assert!(tx.get(&table, &"test".to_string()).is_none());
+        tx.commit();
+
+        // Check removal.
+        {
+            let tx = db.read_transaction();
+            assert!(tx.get(&table, &"test".to_string()).is_none());
+        }
+
+        // Write and abort.
+        let mut tx = db.write_transaction();
+        tx.put(&table, &"test".to_string(), &"one".to_string());
+        tx.abort();
+
+        // Check aborted transaction.
+        let tx = db.read_transaction();
+        assert!(tx.get(&table, &"test".to_string()).is_none());
+    }
+
+    #[test]
+    fn isolation_test() {
+        let db = MemoryDatabase::new();
+        let table = TestTable {};
+        db.create_regular_table(&table);
+
+        // Read non-existent value.
+        let tx = db.read_transaction();
+        assert!(tx.get(&table, &"test".to_string()).is_none());
+
+        // WriteTransaction.
+        let mut txw = db.write_transaction();
+        assert!(txw.get(&table, &"test".to_string()).is_none());
+        txw.put(&table, &"test".to_string(), &"one".to_string());
+        assert_eq!(
+            txw.get(&table, &"test".to_string()),
+            Some("one".to_string())
+        );
+
+        // ReadTransaction should still have the old state.
+        assert!(tx.get(&table, &"test".to_string()).is_none());
+
+        // Commit WriteTransaction.
+        txw.commit();
+
+        // ReadTransaction should still have the old state.
+        assert!(tx.get(&table, &"test".to_string()).is_none());
+
+        // Have a new ReadTransaction read the new state.
+        let tx2 = db.read_transaction();
+        assert_eq!(
+            tx2.get(&table, &"test".to_string()),
+            Some("one".to_string())
+        );
+    }
+
+    #[test]
+    fn duplicates_test() {
+        let db = MemoryDatabase::new();
+        let table = DupTestTable {};
+        db.create_dup_table(&table);
+
+        // Write one value.
+        let mut txw = db.write_transaction();
+        assert!(txw.get(&table, &"test".to_string()).is_none());
+        txw.put(&table, &"test".to_string(), &125);
+        assert_eq!(txw.get(&table, &"test".to_string()), Some(125));
+        txw.commit();
+
+        // Write a second smaller value.
+        let mut txw = db.write_transaction();
+        txw.put(&table, &"test".to_string(), &12);
+        assert_eq!(txw.get(&table, &"test".to_string()), Some(12));
+        txw.commit();
+
+        // Remove smaller value and write larger value.
+        let mut txw = db.write_transaction();
+        txw.remove_item(&table, &"test".to_string(), &12);
+        txw.put(&table, &"test".to_string(), &5783);
+        assert_eq!(txw.get(&table, &"test".to_string()), Some(125));
+        txw.commit();
+
+        {
+            let tx = db.read_transaction();
+            assert_eq!(tx.get(&table, &"test".to_string()), Some(125));
+        }
+
+        // Remove everything.
+        let mut txw = db.write_transaction();
+        txw.remove(&table, &"test".to_string());
+        assert!(txw.get(&table, &"test".to_string()).is_none());
+        txw.commit();
+
+        {
+            let tx = db.read_transaction();
+            assert!(tx.get(&table, &"test".to_string()).is_none());
+        }
+    }
+
+    #[test]
+    fn cursor_test() {
+        let db = MemoryDatabase::new();
+        let table = DupTestTable {};
+        db.create_dup_table(&table);
+
+        let test1: String = "test1".to_string();
+        let test2: String = "test2".to_string();
+
+        // Write some values.
+        let mut txw = db.write_transaction();
+        txw.put(&table, &"test1".to_string(), &125);
+        txw.put(&table, &"test1".to_string(), &12);
+        txw.put(&table, &"test1".to_string(), &5783);
+        txw.put(&table, &"test2".to_string(), &5783);
+        txw.commit();
+
+        let tx = db.read_transaction();
+        let mut cursor = tx.dup_cursor(&table);
+        assert_eq!(cursor.first(), Some((test1.clone(), 12)));
+        assert_eq!(cursor.last(), Some((test2.clone(), 5783)));
+        assert_eq!(cursor.prev(), Some((test1.clone(), 5783)));
+        assert_eq!(cursor.first_duplicate(), Some(12));
+        assert_eq!(cursor.next_duplicate(), Some((test1.clone(), 125)));
+        assert_eq!(cursor.prev_duplicate(), Some((test1.clone(), 12)));
+        assert_eq!(cursor.next_no_duplicate(), Some((test2.clone(), 5783)));
+        assert!(cursor.set_key(&"test".to_string()).is_none());
+        assert_eq!(cursor.set_key(&"test1".to_string()), Some(12));
+        assert_eq!(cursor.count_duplicates(), 3);
+        assert_eq!(cursor.last_duplicate(), Some(5783));
+
+        assert_eq!(cursor.get_current(), Some((test1.clone(), 5783)));
+        assert!(cursor.prev_no_duplicate().is_none());
+        assert_eq!(cursor.next(), Some((test2.clone(), 5783)));
+
+        let cursor = tx.dup_cursor(&table);
+        assert_eq!(
+            cursor.into_iter_dup_of(&test1).collect::<Vec<_>>(),
+            vec![(test1.clone(), 12), (test1.clone(), 125), (test1, 5783)]
+        );
+        let cursor = tx.dup_cursor(&table);
+        assert_eq!(
+            cursor
+                .into_iter_from(&"test2".to_string())
+                .collect::<Vec<_>>(),
+            vec![(test2, 5783)]
+        );
+        let cursor = tx.dup_cursor(&table);
+        assert_eq!(cursor.into_iter_from(&"test3".to_string()).count(), 0);
+    }
+
+    #[test]
+    fn write_cursor_test() {
+        let db = MemoryDatabase::new();
+        let table = U32DupTable {};
+        db.create_dup_table(&table);
+
+        let txw = db.write_transaction();
+        {
+            let mut cursor = WriteTransaction::dup_cursor(&txw, &table);
+            cursor.append(&1, &1);
+            cursor.append_dup(&1, &2);
+            cursor.append(&2, &1);
+            cursor.put(&3, &1);
+
+            // Remove an entry while iterating.
+            assert_eq!(cursor.first(), Some((1, 1)));
+            cursor.remove();
+            assert_eq!(cursor.next(), Some((1, 2)));
+            assert_eq!(cursor.next(), Some((2, 1)));
+            cursor.remove_all_dup();
+            assert_eq!(cursor.next(), Some((3, 1)));
+        }
+        txw.commit();
+
+        let tx = db.read_transaction();
+        let cursor = tx.dup_cursor(&table);
+        assert_eq!(
+            cursor.into_iter_start().collect::<Vec<_>>(),
+            vec![(1, 2), (3, 1)]
+        );
+    }
+
+    #[test]
+    fn it_correctly_orders_u32() {
+        let db = MemoryDatabase::new();
+        let dup_table = U32DupTable {};
+        let table = U32Table {};
+        db.create_dup_table(&dup_table);
+        db.create_regular_table(&table);
+
+        // Write some values.
+        let mut txw = db.write_transaction();
+
+        txw.put(&table, &256, &2);
+        txw.put(&table, &3, &2);
+
+        txw.put(&dup_table, &256, &3);
+        txw.put(&dup_table, &3, &3);
+        txw.put(&dup_table, &256, &2);
+        txw.put(&dup_table, &3, &2);
+        txw.commit();
+
+        // Have a new ReadTransaction read the new state.
+        let tx = db.read_transaction();
+
+        let mut cursor = tx.cursor(&table);
+        assert_eq!(cursor.first(), Some((3, 2)));
+        assert_eq!(cursor.last(), Some((256, 2)));
+
+        let mut cursor = tx.dup_cursor(&dup_table);
+        assert_eq!(cursor.first(), Some((3, 2)));
+        assert_eq!(cursor.last(), Some((256, 3)));
+        assert_eq!(cursor.prev(), Some((256, 2)));
+        assert_eq!(cursor.prev(), Some((3, 3)));
+        assert_eq!(cursor.first_duplicate(), Some(2));
+        assert_eq!(cursor.last_duplicate(), Some(3));
+        assert_eq!(cursor.next_duplicate(), None);
+        assert_eq!(cursor.next_no_duplicate(), Some((256, 2)));
+    }
+
+    #[test]
+    fn it_finds_subkeys() {
+        let db = MemoryDatabase::new();
+        let table = IndexedTable {};
+        db.create_dup_table(&table);
+
+        let mut txw = db.write_transaction();
+        txw.put(&table, &1, &IndexedValue::new(5, "five".to_string()));
+        txw.put(
+            &table,
+            &1,
+            &IndexedValue::new(300, "three hundred".to_string()),
+        );
+        txw.put(&table, &2, &IndexedValue::new(1, "one".to_string()));
+        txw.commit();
+
+        let tx = db.read_transaction();
+        let mut cursor = tx.dup_cursor(&table);
+        assert_eq!(
+            cursor.set_subkey(&1, &300),
+            Some(IndexedValue::new(300, "three hundred".to_string()))
+        );
+        assert!(cursor.set_subkey(&1, &6).is_none());
+        assert_eq!(
+            cursor.set_lowerbound_subkey(&1, &6),
+            Some(IndexedValue::new(300, "three hundred".to_string()))
+        );
+        assert_eq!(
+            cursor.set_lowerbound_both(&1, &301),
+            Some((2, IndexedValue::new(1, "one".to_string())))
+        );
+    }
+
+    #[test]
+    fn it_merges_uncommitted_changes() {
+        let db = MemoryDatabase::new();
+        let table = U32DupTable {};
+        db.create_dup_table(&table);
+
+        let mut txw = db.write_transaction();
+        txw.put(&table, &1, &1);
+        txw.put(&table, &3, &1);
+        txw.put(&table, &3, &2);
+        txw.put(&table, &5, &1);
+        txw.commit();
+
+        let tx = db.read_transaction();
+
+        let mut txw = db.write_transaction();
+        txw.remove(&table, &3);
+        txw.put(&table, &2, &1);
+        txw.put(&table, &4, &1);
+        txw.remove_item(&table, &5, &1);
+        txw.put(&table, &6, &1);
+        txw.put(&table, &6, &2);
+
+        let expected = vec![(1, 1), (2, 1), (4, 1), (6, 1), (6, 2)];
+        {
+            let cursor = WriteTransaction::dup_cursor(&txw, &table);
+            assert_eq!(cursor.into_iter_start().collect::<Vec<_>>(), expected);
+
+            let mut cursor = WriteTransaction::dup_cursor(&txw, &table);
+            assert_eq!(cursor.last(), Some((6, 2)));
+            assert_eq!(cursor.prev(), Some((6, 1)));
+            assert_eq!(cursor.prev(), Some((4, 1)));
+            assert_eq!(cursor.prev_no_duplicate(), Some((2, 1)));
+            assert_eq!(cursor.prev(), Some((1, 1)));
+            assert_eq!(cursor.prev(), None);
+            assert_eq!(cursor.set_lowerbound_key(&3), Some((4, 1)));
+            assert_eq!(cursor.set_key(&5), None);
+        }
+
+        // Uncommitted changes are not visible to other transactions.
+        let cursor = tx.dup_cursor(&table);
+        assert_eq!(
+            cursor.into_iter_start().collect::<Vec<_>>(),
+            vec![(1, 1), (3, 1), (3, 2), (5, 1)]
+        );
+
+        txw.commit();
+
+        let cursor = db.read_transaction().dup_cursor(&table);
+        assert_eq!(cursor.into_iter_start().collect::<Vec<_>>(), expected);
+
+        // Entries that are written after clearing the table are kept.
+        let mut txw = db.write_transaction();
+        txw.clear_table(&table);
+        txw.put(&table, &4, &2);
+        assert_eq!(txw.get(&table, &1), None);
+        txw.commit();
+
+        let cursor = db.read_transaction().dup_cursor(&table);
+        assert_eq!(cursor.into_iter_start().collect::<Vec<_>>(), vec![(4, 2)]);
+    }
+
+    #[test]
+    fn commit_only_updates_modified_tables() {
+        let db = MemoryDatabase::new();
+        let table = U32Table {};
+        let other_table = TestTable {};
+        db.create_regular_table(&table);
+        db.create_regular_table(&other_table);
+
+        let table_ptr = |name: &str| Arc::as_ptr(&db.tables.read().unwrap()[name]);
+        let committed = table_ptr(U32Table::NAME);
+        let other_committed = table_ptr(TestTable::NAME);
+
+        // Without concurrent readers, the table is updated in place.
+        let mut txw = db.write_transaction();
+        txw.put(&table, &1, &1);
+        txw.commit();
+        assert_eq!(table_ptr(U32Table::NAME), committed);
+
+        // A concurrent reader keeps its snapshot, so the table is copied.
+        let tx = db.read_transaction();
+        let mut txw = db.write_transaction();
+        txw.put(&table, &2, &2);
+        txw.commit();
+        assert_ne!(table_ptr(U32Table::NAME), committed);
+        assert_eq!(tx.get(&table, &2), None);
+
+        // Unmodified tables are never touched.
+        assert_eq!(table_ptr(TestTable::NAME), other_committed);
+    }
+
+    #[test]
+    fn create_table_waits_for_write_transaction() {
+        let db = MemoryDatabase::new();
+        let table = TestTable {};
+        db.create_regular_table(&table);
+
+        let txw = db.write_transaction();
+
+        let (sender, receiver) = mpsc::channel();
+        let thread_db = db.clone();
+        let handle = thread::spawn(move || {
+            thread_db.create_regular_table(&U32Table {});
+            sender.send(()).unwrap();
+        });
+
+        assert!(receiver.recv_timeout(Duration::from_millis(100)).is_err());
+        txw.commit();
+        receiver.recv().unwrap();
+        handle.join().unwrap();
+
+        let mut txw = db.write_transaction();
+        txw.put(&U32Table {}, &1, &1);
+        txw.commit();
+        assert_eq!(db.read_transaction().get(&U32Table {}, &1), Some(1));
+    }
+}
@@ -0,0 +1,330 @@
+use std::{
+    any::TypeId,
+    collections::{BTreeMap, BTreeSet},
+    ops::Bound,
+};
+
+use nimiq_database_value::AsDatabaseBytes;
+
+use crate::traits::Table;
+
+/// An entry of a table in its byte representation.
+pub(super) type Entry = (Vec<u8>, Vec<u8>);
+
+/// The values of a key, sorted lexicographically.
+type Values = BTreeSet<Vec<u8>>;
+
+/// A table stored in memory, holding its committed entries.
+///
+/// Entries are kept in the same order as in MDBX: keys are sorted lexicographically by their
+/// byte representation (except for integer keys, which are sorted numerically), and the values
+/// of duplicate keys are sorted lexicographically.
+#[derive(Clone, Debug, Default)]
+pub(super) struct MemoryTable {
+    /// Whether the table allows duplicate keys.
+    dup: bool,
+    entries: BTreeMap<Vec<u8>, Values>,
+}
+
+/// The uncommitted changes of a write transaction to a table.
+///
+/// Changes are tracked per key: a changed key maps to all of its values, or to `None` if it was
+/// removed. Thus, a write only copies the values of the key it touches instead of the whole table.
+#[derive(Debug, Default)]
+pub(super) struct TableChanges {
+    /// Whether the table was cleared. If so, the committed entries are ignored.
+    cleared: bool,
+    entries: BTreeMap<Vec<u8>, Option<Values>>,
+}
+
+/// A view on a table with the changes of a transaction applied on top.
+pub(super) struct TableView<'a> {
+    table: &'a MemoryTable,
+    changes: Option<&'a TableChanges>,
+}
+
+/// A view on a table that records modifications in the changes of a transaction.
+pub(super) struct TableViewMut<'a> {
+    table: &'a MemoryTable,
+    changes: &'a mut TableChanges,
+}
+
+impl MemoryTable {
+    pub(super) fn new(dup: bool) -> Self {
+        MemoryTable {
+            dup,
+            entries: BTreeMap::new(),
+        }
+    }
+
+    /// Applies the changes of a committed transaction.
+    pub(super) fn apply(&mut self, changes: TableChanges) {
+        if changes.cleared {
+            self.entries.clear();
+        }
+        for (key, values) in changes.entries {
+            match values {
+                Some(values) => self.entries.insert(key, values),
+                None => self.entries.remove(&key),
+            };
+        }
+    }
+
+    /// Returns whether the key of the given table is stored as an integer key.
+    /// This mirrors the `INTEGER_KEY` flag that is set for these tables in MDBX.
+    fn is_integer_key<T: Table>() -> bool {
+        let key_type = TypeId::of::<T::Key>();
+        key_type == TypeId::of::<u32>() || key_type == TypeId::of::<u64>()
+    }
+
+    /// Encodes a key such that the lexicographic order of the bytes matches the table order.
+    /// Integer keys are stored in native endianness, so they are converted to big endian.
+    pub(super) fn encode_key<T: Table>(key: &T::Key) -> Vec<u8> {
+        let mut bytes = AsDatabaseBytes::as_key_bytes(key).into_owned();
+        if Self::is_integer_key::<T>() && cfg!(target_endian = "little") {
+            bytes.reverse();
+        }
+        bytes
+    }
+
+    /// Reverts the encoding of `encode_key`, returning the original key bytes.
+    pub(super) fn decode_key<T: Table>(bytes: &[u8]) -> Vec<u8> {
+        let mut bytes = bytes.to_vec();
+        if Self::is_integer_key::<T>() && cfg!(target_endian = "little") {
+            bytes.reverse();
+        }
+        bytes
+    }
+}
+
+impl<'a> TableView<'a> {
+    pub(super) fn new(table: &'a MemoryTable, changes: Option<&'a TableChanges>) -> Self {
+        TableView { table, changes }
+    }
+
+    /// Returns the values of a key, taking the changes into account.
+    fn values(&self, key: &[u8]) -> Option<&'a Values> {
+        match self.changes {
+            Some(changes) => match changes.entries.get(key) {
+                Some(values) => values.as_ref(),
+                None if changes.cleared => None,
+                None => self.table.entries.get(key),
+            },
+            None => self.table.entries.get(key),
+        }
+    }
+
+    /// Returns the first (or, if `back` is set, the last) key within the given bounds along with
+    /// its values, merging the committed entries with the changes.
+    fn find_key(
+        &self,
+        lower: Bound<&[u8]>,
+        upper: Bound<&[u8]>,
+        back: bool,
+    ) -> Option<(&'a Vec<u8>, &'a Values)> {
+        let cleared = self.changes.is_some_and(|changes| changes.cleared);
+        // Changed keys are taken from the changes only.
+        let mut committed = (!cleared)
+            .then(|| self.table.entries.range::<[u8], _>((lower, upper)))
+            .into_iter()
+            .flatten()
+            .filter(|(key, _)| {
+                !self
+                    .changes
+                    .is_some_and(|changes| changes.entries.contains_key(*key))
+            });
+        let mut changed = self
+            .changes
+            .into_iter()
+            .flat_map(|changes| changes.entries.range::<[u8], _>((lower, upper)))
+            .filter_map(|(key, values)| Some((key, values.as_ref()?)));
+
+        let (committed, changed) = if back {
+            (committed.next_back(), changed.next_back())
+        } else {
+            (committed.next(), changed.next())
+        };
+        match (committed, changed) {
+            (Some(committed), Some(changed)) => {
+                if (committed.0 < changed.0) != back {
+                    Some(committed)
+                } else {
+                    Some(changed)
+                }
+            }
+            (entry, None) | (None, entry) => entry,
+        }
+    }
+
+    pub(super) fn get(&self, key: &[u8]) -> Option<&'a Vec<u8>> {
+        self.values(key)?.first()
+    }
+
+    pub(super) fn first(&self) -> Option<Entry> {
+        let (key, values) = self.find_key(Bound::Unbounded, Bound::Unbounded, false)?;
+        Some((key.clone(), values.first()?.clone()))
+    }
+
+    pub(super) fn last(&self) -> Option<Entry> {
+        let (key, values) = self.find_key(Bound::Unbounded, Bound::Unbounded, true)?;
+        Some((key.clone(), values.last()?.clone()))
+    }
+
+    /// Returns the entry following the given entry.
+    pub(super) fn next(&self, key: &[u8], value: &[u8]) -> Option<Entry> {
+        self.next_duplicate(key, value)
+            .or_else(|| self.next_key(key))
+    }
+
+    /// Returns the entry preceding the given entry.
+    pub(super) fn prev(&self, key: &[u8], value: &[u8]) -> Option<Entry> {
+        self.prev_duplicate(key, value)
+            .or_else(|| self.prev_key(key))
+    }
+
+    /// Returns the next value of the same key.
+    pub(super) fn next_duplicate(&self, key: &[u8], value: &[u8]) -> Option<Entry> {
+        let next = self
+            .values(key)?
+            .range::<[u8], _>((Bound::Excluded(value), Bound::Unbounded))
+            .next()?;
+        Some((key.to_vec(), next.clone()))
+    }
+
+    /// Returns the previous value of the same key.
+    pub(super) fn prev_duplicate(&self, key: &[u8], value: &[u8]) -> Option<Entry> {
+        let prev = self
+            .values(key)?
+            .range::<[u8], _>((Bound::Unbounded, Bound::Excluded(value)))
+            .next_back()?;
+        Some((key.to_vec(), prev.clone()))
+    }
+
+    /// Returns the first value of the next key.
+    pub(super) fn next_key(&self, key: &[u8]) -> Option<Entry> {
+        let (key, values) = self.find_key(Bound::Excluded(key), Bound::Unbounded, false)?;
+        Some((key.clone(), values.first()?.clone()))
+    }
+
+    /// Returns the last value of the previous key.
+    pub(super) fn prev_key(&self, key: &[u8]) -> Option<Entry> {
+        let (key, values) = self.find_key(Bound::Unbounded, Bound::Excluded(key), true)?;
+        Some((key.clone(), values.last()?.clone()))
+    }
+
+    /// Returns the first or last value of the given key.
+    pub(super) fn duplicate(&self, key: &[u8], last: bool) -> Option<Entry> {
+        let values = self.values(key)?;
+        let value = if last { values.last() } else { values.first() };
+        Some((key.to_vec(), value?.clone()))
+    }
+
+    /// Returns the first entry with a key >= `key`.
+    pub(super) fn lower_bound(&self, key: &[u8]) -> Option<Entry> {
+        let (key, values) = self.find_key(Bound::Included(key), Bound::Unbounded, false)?;
+        Some((key.clone(), values.first()?.clone()))
+    }
+
+    /// Returns the first entry with `key` and a value >= `value`.
+    pub(super) fn lower_bound_value(&self, key: &[u8], value: &[u8]) -> Option<Entry> {
+        let value = self
+            .values(key)?
+            .range::<[u8], _>((Bound::Included(value), Bound::Unbounded))
+            .next()?;
+        Some((key.to_vec(), value.clone()))
+    }
+
+    /// Returns the first entry that is >= (`key`, `value`).
+    pub(super) fn lower_bound_both(&self, key: &[u8], value: &[u8]) -> Option<Entry> {
+        self.lower_bound_value(key, value)
+            .or_else(|| self.next_key(key))
+    }
+
+    /// Returns the number of values stored for the given key.
+    pub(super) fn count_duplicates(&self, key: &[u8]) -> usize {
+        self.values(key).map_or(0, BTreeSet::len)
+    }
+
+    pub(super) fn contains(&self, key: &[u8], value: &[u8]) -> bool {
+        self.values(key)
+            .is_some_and(|values| values.contains(value))
+    }
+}
+
+impl<'a> TableViewMut<'a> {
+    pub(super) fn new(table: &'a MemoryTable, changes: &'a mut TableChanges) -> Self {
+        TableViewMut { table, changes }
+    }
+
+    pub(super) fn view(&self) -> TableView<'_> {
+        TableView::new(self.table, Some(&*self.changes))
+    }
+
+    /// Modifies the values of a key. The values are copied into the changes the first time the
+    /// key is modified. A key without values is removed.
+    fn modify(&mut self, key: &[u8], f: impl FnOnce(&mut Values)) {
+        let mut values = match self.changes.entries.remove(key) {
+            Some(values) => values.unwrap_or_default(),
+            None if self.changes.cleared => Values::new(),
+            None => self.table.entries.get(key).cloned().unwrap_or_default(),
+        };
+        f(&mut values);
+        let values = (!values.is_empty()).then_some(values);
+        self.changes.entries.insert(key.to_vec(), values);
+    }
+
+    /// Puts an entry into the table. In a regular table, this replaces the existing value.
+    /// In a dup table, the value is added to the existing values.
+    pub(super) fn put(&mut self, key: Vec<u8>, value: Vec<u8>) {
+        let dup = self.table.dup;
+        self.modify(&key, |values| {
+            if !dup {
+                values.clear();
+            }
+            values.insert(value);
+        });
+    }
+
+    /// Appends an entry to the table.
+    /// Panics if the entry would not be the last entry of the table.
+    pub(super) fn append(&mut self, key: Vec<u8>, value: Vec<u8>) {
+        if let Some((last_key, _)) = self.view().last() {
+            assert!(
+                key > last_key || (self.table.dup && key == last_key),
+                "Appended key must be greater than the last key"
+            );
+        }
+        self.put(key, value);
+    }
+
+    /// Appends a value to the values of a key.
+    /// Panics if the value would not be the last value of the key.
+    pub(super) fn append_dup(&mut self, key: Vec<u8>, value: Vec<u8>) {
+        if let Some((_, last_value)) = self.view().duplicate(&key, true) {
+            assert!(
+                value > last_value,
+                "Appended value must be greater than the last value"
+            );
+        }
+        self.append(key, value);
+    }
+
+    /// Removes all values of the given key.
+    pub(super) fn remove(&mut self, key: &[u8]) {
+        self.changes.entries.insert(key.to_vec(), None);
+    }
+
+    /// Removes a single entry.
+    pub(super) fn remove_item(&mut self, key: &[u8], value: &[u8]) {
+        if self.view().contains(key, value) {
+            self.modify(key, |values| {
+                values.remove(value);
+            });
+        }
+    }
+
+    pub(super) fn clear(&mut self) {
+        self.changes.cleared = true;
+        self.changes.entries.clear();
+    }
+}
@@ -0,0 +1,252 @@
+use std::{
+    collections::HashMap,
+    marker::PhantomData,
+    ops::Deref,
+    sync::{Arc, MutexGuard, RwLock},
+};
+
+use nimiq_database_value::{AsDatabaseBytes, FromDatabaseBytes, IntoDatabaseValue};
+
+use super::{
+    database::Tables,
+    table::{MemoryTable, TableChanges, TableView, TableViewMut},
+    MemoryCursor, MemoryDatabase, MemoryWriteCursor,
+};
+use crate::traits::{DupTable, ReadTransaction, RegularTable, Table, WriteTransaction};
+
+/// The tables as seen by a transaction: a snapshot of the committed tables taken when the
+/// transaction was created, along with the uncommitted changes of the transaction.
+#[derive(Debug)]
+pub(super) struct TxnTables {
+    snapshot: Tables,
+    changes: RwLock<HashMap<&'static str, TableChanges>>,
+}
+
+impl TxnTables {
+    fn new(snapshot: Tables) -> Self {
+        TxnTables {
+            snapshot,
+            changes: Default::default(),
+        }
+    }
+
+    /// Returns the committed state of the given table. Panics if the table doesn't exist.
+    fn table<T: Table>(&self) -> &MemoryTable {
+        self.snapshot
+            .get(T::NAME)
+            .unwrap_or_else(|| panic!("Table {} does not exist", T::NAME))
+    }
+
+    /// Runs `f` on the given table. Panics if the table doesn't exist.
+    pub(super) fn with_table<T: Table, R>(&self, f: impl FnOnce(&TableView) -> R) -> R {
+        let changes = self.changes.read().unwrap();
+        f(&TableView::new(self.table::<T>(), changes.get(T::NAME)))
+    }
+
+    /// Runs `f` on a mutable view of the given table, which records the modifications in the
+    /// changes of the transaction. Panics if the table doesn't exist.
+    pub(super) fn with_table_mut<T: Table, R>(&self, f: impl FnOnce(&mut TableViewMut) -> R) -> R {
+        let table = self.table::<T>();
+        let mut changes = self.changes.write().unwrap();
+        f(&mut TableViewMut::new(
+            table,
+            changes.entry(T::NAME).or_default(),
+        ))
+    }
+}
+
+/// A read transaction on the in-memory database.
+/// It operates on a snapshot of the database taken when the transaction was created.
+#[derive(Debug)]
+pub struct MemoryReadTransaction<'db> {
+    pub(super) tables: TxnTables,
+    _db: PhantomData<&'db MemoryDatabase>,
+}
+
+impl<'db> MemoryReadTransaction<'db> {
+    pub(super) fn new(tables: Tables) -> Self {
+        MemoryReadTransaction {
+            tables: TxnTables::new(tables),
+            _db: PhantomData,
+        }
+    }
+}
+
+impl<'db> AsRef<MemoryReadTransaction<'db>> for MemoryReadTransaction<'db> {
+    fn as_ref(&self) -> &MemoryReadTransaction<'db> {
+        self
+    }
+}
+
+impl<'db> ReadTransaction<'db> for MemoryReadTransaction<'db> {
+    type Cursor<'txn, T: Table>
+        = MemoryCursor<'txn, T>
+    where
+        Self: 'txn;
+
+    type DupCursor<'txn, T: DupTable>
+        = MemoryCursor<'txn, T>
+    where
+        Self: 'txn;
+
+    fn get<T: Table>(&self, _table: &T, key: &T::Key) -> Option<T::Value> {
+        let key = MemoryTable::encode_key::<T>(key);
+        self.tables
+            .with_table::<T, _>(|table| Some(FromDatabaseBytes::from_value_bytes(table.get(&key)?)))
+    }
+
+    fn cursor<'txn, T: RegularTable>(&'txn self, _table: &T) -> Self::Cursor<'txn, T> {
+        MemoryCursor::new(&self.tables)
+    }
+
+    fn dup_cursor<'txn, T: DupTable>(&'txn self, _table: &T) -> Self::DupCursor<'txn, T> {
+        MemoryCursor::new(&self.tables)
+    }
+}
+
+/// A write transaction on the in-memory database.
+/// Changes only become visible to other transactions once the transaction is committed.
+/// There can only be one write transaction at a time.
+pub struct MemoryWriteTransaction<'db> {
+    txn: MemoryReadTransaction<'db>,
+    db: &'db MemoryDatabase,
+    _write_guard: MutexGuard<'db, ()>,
+}
+
+impl<'db> MemoryWriteTransaction<'db> {
+    pub(super) fn new(
+        db: &'db MemoryDatabase,
+        tables: Tables,
+        write_guard: MutexGuard<'db, ()>,
+    ) -> Self {
+        MemoryWriteTransaction {
+            txn: MemoryReadTransaction::new(tables),
+            db,
+            _write_guard: write_guard,
+        }
+    }
+}
+
+impl<'db> ReadTransaction<'db> for MemoryWriteTransaction<'db> {
+    type Cursor<'txn, T: Table>
+        = MemoryCursor<'txn, T>
+    where
+        Self: 'txn;
+
+    type DupCursor<'txn, T: DupTable>
+        = MemoryCursor<'txn, T>
+    where
+        Self: 'txn;
+
+    fn get<T: Table>(&self, table: &T, key: &T::Key) -> Option<T::Value> {
+        self.txn.get(table, key)
+    }
+
+    fn cursor<'txn, T: RegularTable>(&'txn self, table: &T) -> Self::Cursor<'txn, T> {
+        self.txn.cursor(table)
+    }
+
+    fn dup_cursor<'txn, T: DupTable>(&'txn self, table: &T) -> Self::DupCursor<'txn, T> {
+        self.txn.dup_cursor(table)
+    }
+}
+
+impl<'db> WriteTransaction<'db> for MemoryWriteTransaction<'db> {
+    type WriteCursor<'txn, T: Table>
+        = MemoryWriteCursor<'txn, T>
+    where
+        Self: 'txn;
+
+    type DupWriteCursor<'txn, T: DupTable>
+        = MemoryWriteCursor<'txn, T>
+    where
+        Self: 'txn;
+
+    fn put_reserve<T: RegularTable>(&mut self, _table: &T, key: &T::Key, value: &T::Value)
+    where
+        T::Value: IntoDatabaseValue,
+    {
+        let key = MemoryTable::encode_key::<T>(key);
+        let mut bytes = vec![0; IntoDatabaseValue::database_byte_size(value)];
+        IntoDatabaseValue::copy_into_database(value, &mut bytes);
+
+        self.txn
+            .tables
+            .with_table_mut::<T, _>(|table| table.put(key, bytes));
+    }
+
+    fn put<T: Table>(&mut self, _table: &T, key: &T::Key, value: &T::Value) {
+        let key = MemoryTable::encode_key::<T>(key);
+        let value = AsDatabaseBytes::as_value_bytes(value).into_owned();
+
+        self.txn
+            .tables
+            .with_table_mut::<T, _>(|table| table.put(key, value));
+    }
+
+    fn append<T: Table>(&mut self, _table: &T, key: &T::Key, value: &T::Value) {
+        let key = MemoryTable::encode_key::<T>(key);
+        let value = AsDatabaseBytes::as_value_bytes(value).into_owned();
+
+        self.txn
+            .tables
+            .with_table_mut::<T, _>(|table| table.append(key, value));
+    }
+
+    fn remove<T: Table>(&mut self, _table: &T, key: &T::Key) {
+        let key = MemoryTable::encode_key::<T>(key);
+
+        self.txn
+            .tables
+            .with_table_mut::<T, _>(|table| table.remove(&key));
+    }
+
+    fn remove_item<T: Table>(&mut self, _table: &T, key: &T::Key, value: &T::Value) {
+        let key = MemoryTable::encode_key::<T>(key);
+        let value = AsDatabaseBytes::as_value_bytes(value);
+
+        self.txn
+            .tables
+            .with_table_mut::<T, _>(|table| table.remove_item(&key, &value));
+    }
+
+    fn commit(self) {
+        let TxnTables { snapshot, changes } = self.txn.tables;
+        // Release the snapshot first, such that the committed tables are only copied if a read
+        // transaction still uses them.
+        drop(snapshot);
+
+        // Only the tables that were modified by this transaction are updated.
+        let mut tables = self.db.tables.write().unwrap();
+        for (name, changes) in changes.into_inner().unwrap() {
+            let table = tables.get_mut(name).expect("Table must exist");
+            Arc::make_mut(table).apply(changes);
+        }
+    }
+
+    fn cursor<'txn, T: RegularTable>(&'txn self, _table: &T) -> Self::WriteCursor<'txn, T> {
+        MemoryCursor::new(&self.txn.tables)
+    }
+
+    fn dup_cursor<'txn, T: DupTable>(&'txn self, _table: &T) -> Self::DupWriteCursor<'txn, T> {
+        MemoryCursor::new(&self.txn.tables)
+    }
+
+    fn clear_table<T: Table>(&mut self, _table: &T) {
+        self.txn.tables.with_table_mut::<T, _>(TableViewMut::clear);
+    }
+}
+
+impl<'db> Deref for MemoryWriteTransaction<'db> {
+    type Target = MemoryReadTransaction<'db>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.txn
+    }
+}
+
+impl<'db> AsRef<MemoryReadTransaction<'db>> for MemoryWriteTransaction<'db> {
+    fn as_ref(&self) -> &MemoryReadTransaction<'db> {
+        &self.txn
+    }
+}
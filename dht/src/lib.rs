@@ -24,9 +24,12 @@ impl Verifier {
             TaggedSigned::<ValidatorRecord<PeerId>, KeyPair>::deserialize_from_vec(&record.value)
                 .map_err(DhtVerifierError::MalformedValue)?;
 
-        // Make sure the peer who signed the record is also the one presented in the record.
+        // Make sure the peer who published the record is either the one presented in the record
+        // or, for validators in private mode, one of its relays.
         if let Some(publisher) = record.publisher {
-            if validator_record.record.peer_id != publisher {
+            if validator_record.record.peer_id != publisher
+                && !validator_record.record.relays.contains(&publisher)
+            {
                 return Err(DhtVerifierError::PublisherMismatch(
                     publisher,
                     validator_record.record.peer_id,
@@ -60,6 +60,16 @@ impl NetworkInfo {
         })
     }
 
+    /// Block number from which on vesting contracts can be created with a release schedule. Release
+    /// schedules are available from genesis on the test networks and not activated on mainnet yet.
+    #[inline]
+    pub fn vesting_schedule_activation_block(&self) -> u32 {
+        match self.network_id {
+            NetworkId::MainAlbatross => u32::MAX,
+            _ => self.genesis_block().block_number(),
+        }
+    }
+
     pub fn from_network_id(network_id: NetworkId) -> &'static Self {
         network(network_id).unwrap_or_else(|| panic!("No such network ID: {network_id}"))
     }
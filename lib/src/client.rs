@@ -48,7 +48,7 @@ use nimiq_zkp::ZKP_VERIFYING_DATA;
 #[cfg(feature = "zkp-prover")]
 use nimiq_zkp_circuits::setup::{all_files_created, load_verifying_data, setup, DEVELOPMENT_SEED};
 #[cfg(feature = "database-storage")]
-use nimiq_zkp_component::proof_store::{DBProofStore, ProofStore};
+use nimiq_zkp_component::proof_store::{DBProofArchive, DBProofStore, ProofStore};
 use nimiq_zkp_component::zkp_component::{
     ZKPComponent as AbstractZKPComponent, ZKPComponentProxy as AbstractZKPComponentProxy,
 };
@@ -161,6 +161,7 @@ impl ClientInner {
 
         let policy_config = Policy {
             genesis_block_number: network_info.genesis_block().block_number(),
+            vesting_schedule_activation_block: network_info.vesting_schedule_activation_block(),
             ..Default::default()
         };
 
@@ -327,7 +328,7 @@ impl ClientInner {
         };
 
         // Setup libp2p network
-        let network_config = NetworkConfig::new(
+        let mut network_config = NetworkConfig::new(
             identity_keypair,
             peer_contact,
             seeds,
@@ -345,7 +346,16 @@ impl ClientInner {
                 .network
                 .dht_quorum
                 .unwrap_or(NonZeroU8::new(3).unwrap()),
+            config.storage.peer_contacts_path(),
         );
+        network_config.quic = config.network.quic;
+        network_config.ban_list_path = config.storage.ban_list_path();
+        network_config.deny_list = config.network.deny_list.clone();
+        network_config.allow_list = config.network.allow_list.clone();
+        network_config.sentry_for = config.network.sentry_for.clone();
+        network_config.rate_limits = config.network.rate_limits.clone();
+        network_config.reputation = config.network.reputation.clone();
+        network_config.capture_path = config.network.capture_path.clone();
 
         log::debug!(
             addresses = ?config.network.listen_addresses,
@@ -383,10 +393,20 @@ impl ClientInner {
             max_epochs_stored: config.consensus.max_epochs_stored,
             ..Default::default()
         };
+        #[cfg(feature = "full-consensus")]
+        if let Some(distance) = config.consensus.max_historical_state_distance {
+            blockchain_config.max_historical_state_distance = distance;
+        }
 
         #[cfg(feature = "database-storage")]
-        let zkp_storage: Option<Box<dyn ProofStore>> =
-            Some(Box::new(DBProofStore::new(environment.clone())));
+        let zkp_storage: Option<Box<dyn ProofStore>> = if config.consensus.zkp_archive {
+            Some(Box::new(DBProofArchive::new(
+                environment.clone(),
+                config.consensus.zkp_archive_max_proofs,
+            )))
+        } else {
+            Some(Box::new(DBProofStore::new(environment.clone())))
+        };
         #[cfg(not(feature = "database-storage"))]
         let zkp_storage = None;
 
@@ -637,6 +657,7 @@ impl ClientInner {
                 validator_or_mempool = Some(ValidatorOrMempool::Mempool(MempoolTask::new(
                     &consensus,
                     Arc::clone(blockchain),
+                    environment.clone(),
                     config.mempool,
                 )));
             }
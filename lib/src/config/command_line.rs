@@ -1,6 +1,6 @@
 use std::path::PathBuf;
 
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use log::level_filters::{LevelFilter, ParseLevelFilterError};
 use nimiq_primitives::networks::NetworkId;
 use thiserror::Error;
@@ -67,6 +67,42 @@ pub struct CommandLine {
     /// Internally used flag to start a zero-knowledge prover process.
     #[clap(long, action)]
     pub prove: bool,
+
+    /// Run a maintenance command instead of starting the client.
+    #[clap(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Export or import a snapshot of the consensus database.
+    ///
+    /// Snapshots allow to bootstrap a node without syncing the chain from the network.
+    /// They contain the chain state only, the wallet and validator state are never included.
+    #[clap(subcommand)]
+    Snapshot(SnapshotCommand),
+}
+
+#[derive(Debug, Subcommand)]
+pub enum SnapshotCommand {
+    /// Write a snapshot of the consensus database to a file.
+    ///
+    /// # Examples
+    ///
+    /// * `nimiq-client snapshot export ~/snapshot.bin`
+    ///
+    Export { path: PathBuf },
+
+    /// Import a snapshot into an empty consensus database.
+    ///
+    /// The snapshot must have been taken from a node with the same network, sync mode and
+    /// history index settings. It is verified before it is committed.
+    ///
+    /// # Examples
+    ///
+    /// * `nimiq-client snapshot import ~/snapshot.bin`
+    ///
+    Import { path: PathBuf },
 }
 
 impl CommandLine {
@@ -7,6 +7,7 @@ use std::{
     num::NonZeroU8,
     path::{Path, PathBuf},
     string::ToString,
+    time::Duration,
 };
 
 use derive_builder::Builder;
@@ -19,8 +20,11 @@ use nimiq_hash::{Blake2bHash, Hash};
 use nimiq_keys::{Address, KeyPair, PrivateKey};
 #[cfg(feature = "nimiq-mempool")]
 use nimiq_mempool::{config::MempoolConfig, filter::MempoolRules};
-use nimiq_network_interface::Multiaddr;
-use nimiq_network_libp2p::{Keypair as IdentityKeypair, Libp2pKeyPair};
+use nimiq_network_interface::{Multiaddr, Protocol};
+use nimiq_network_libp2p::{
+    BanTarget, Keypair as IdentityKeypair, Libp2pKeyPair, PeerId, RateLimitConfig,
+    RateLimitOverrides, ReputationConfig,
+};
 use nimiq_primitives::{networks::NetworkId, policy::Policy};
 use nimiq_serde::Deserialize;
 #[cfg(feature = "validator")]
@@ -38,7 +42,7 @@ use crate::config::consts::default_bind;
 use crate::{
     config::{
         command_line::CommandLine,
-        config_file::{ConfigFile, Seed, TlsSettings},
+        config_file::{ConfigFile, RateLimitSettings, ReputationSettings, Seed, TlsSettings},
         paths,
         user_agent::UserAgent,
     },
@@ -92,6 +96,16 @@ pub struct ConsensusConfig {
     #[builder(setter(custom))]
     /// History indices enabled. Defaults to `true` for history nodes and `false` to full/light nodes.
     pub index_history: bool,
+    #[builder(default)]
+    /// Whether the zk proofs of past election blocks are kept in addition to the latest one
+    pub zkp_archive: bool,
+    #[builder(default)]
+    /// Maximum number of zk proofs that are kept if `zkp_archive` is enabled. Unlimited if `None`.
+    pub zkp_archive_max_proofs: Option<usize>,
+    #[builder(default)]
+    /// Maximum number of blocks behind the head for which historical account states are served.
+    /// Defaults to one epoch if `None`.
+    pub max_historical_state_distance: Option<u32>,
 }
 
 impl ConsensusConfigBuilder {
@@ -118,6 +132,9 @@ impl Default for ConsensusConfig {
             max_epochs_stored: Policy::MIN_EPOCHS_STORED,
             full_sync_threshold: 10800,
             index_history: true,
+            zkp_archive: false,
+            zkp_archive_max_proofs: None,
+            max_historical_state_distance: None,
         }
     }
 }
@@ -176,9 +193,123 @@ pub struct NetworkConfig {
     #[builder(default)]
     pub allow_loopback_addresses: bool,
 
+    /// Optional, bool to enable the QUIC transport. Requires the `quic` feature.
+    #[builder(default)]
+    pub quic: bool,
+
     /// Optional, quorum value for the network DHT.
     #[builder(default)]
     pub dht_quorum: Option<NonZeroU8>,
+
+    /// Optional, peer IDs, IP addresses and subnets that are permanently banned.
+    #[builder(default)]
+    pub deny_list: Vec<BanTarget>,
+
+    /// Optional, the only peers to dial or accept. If not empty, the node runs in private mode
+    /// and doesn't advertise itself. Entries must end in `/p2p/<peer ID>`.
+    #[builder(default)]
+    pub allow_list: Vec<Multiaddr>,
+
+    /// Optional, the peers in private mode this node is a sentry for.
+    #[builder(default)]
+    pub sentry_for: Vec<PeerId>,
+
+    /// Optional, rate limits replacing the ones defined by the request and topic types.
+    #[builder(default)]
+    pub rate_limits: RateLimitOverrides,
+
+    /// Optional, penalties and thresholds of the peer reputation.
+    #[builder(default)]
+    pub reputation: ReputationConfig,
+
+    /// Optional, file to record the gossipsub and request-response traffic to.
+    #[builder(default)]
+    pub capture_path: Option<PathBuf>,
+}
+
+impl NetworkConfig {
+    /// Parses the allow list from the config file. Plain peer IDs are turned into `/p2p/<peer ID>`
+    /// multiaddresses.
+    fn allow_list(entries: &[String]) -> Result<Vec<Multiaddr>, Error> {
+        entries
+            .iter()
+            .map(|entry| {
+                if let Ok(peer_id) = entry.parse::<PeerId>() {
+                    return Ok(Multiaddr::empty().with(Protocol::P2p(peer_id)));
+                }
+                let address = entry.parse::<Multiaddr>().map_err(|error| {
+                    Error::config_error(format!("Invalid allow list entry {entry}: {error}"))
+                })?;
+                match address.iter().last() {
+                    Some(Protocol::P2p(_)) => Ok(address),
+                    _ => Err(Error::config_error(format!(
+                        "Allow list entry {entry} doesn't end in a peer ID"
+                    ))),
+                }
+            })
+            .collect()
+    }
+
+    /// Collects the rate limit overrides from the config file. Each of them must either name a
+    /// request type or a gossipsub topic.
+    fn rate_limit_overrides(settings: &[RateLimitSettings]) -> Result<RateLimitOverrides, Error> {
+        let mut overrides = RateLimitOverrides::default();
+        for setting in settings {
+            let rate_limit = RateLimitConfig {
+                max_requests: setting.max_requests,
+                time_window: Duration::from_secs(setting.time_window),
+            };
+            let result = match (setting.request, &setting.topic) {
+                (Some(request), None) => overrides.set_request_limit(request, rate_limit),
+                (None, Some(topic)) => overrides.set_topic_limit(topic.clone(), rate_limit),
+                _ => {
+                    return Err(Error::config_error(
+                        "A rate limit must specify either a request or a topic",
+                    ))
+                }
+            };
+            result.map_err(|error| Error::config_error(format!("Invalid rate limit: {error}")))?;
+        }
+        Ok(overrides)
+    }
+
+    /// Applies the peer reputation settings from the config file to the defaults. Penalties and
+    /// the recovery must not be negative and peers must be disconnected before they get banned.
+    fn reputation(settings: &ReputationSettings) -> Result<ReputationConfig, Error> {
+        let default = ReputationConfig::default();
+        let reputation = ReputationConfig {
+            rate_limit_penalty: settings
+                .rate_limit_penalty
+                .unwrap_or(default.rate_limit_penalty),
+            invalid_response_penalty: settings
+                .invalid_response_penalty
+                .unwrap_or(default.invalid_response_penalty),
+            recovery_per_second: settings
+                .recovery_per_second
+                .unwrap_or(default.recovery_per_second),
+            disconnect_threshold: settings
+                .disconnect_threshold
+                .unwrap_or(default.disconnect_threshold),
+            ban_threshold: settings.ban_threshold.unwrap_or(default.ban_threshold),
+        };
+
+        if reputation.rate_limit_penalty < 0.0
+            || reputation.invalid_response_penalty < 0.0
+            || reputation.recovery_per_second < 0.0
+        {
+            return Err(Error::config_error(
+                "Reputation penalties and recovery must not be negative",
+            ));
+        }
+        if reputation.disconnect_threshold >= 0.0
+            || reputation.ban_threshold > reputation.disconnect_threshold
+        {
+            return Err(Error::config_error(
+                "The reputation disconnect threshold must be negative and above the ban threshold",
+            ));
+        }
+        Ok(reputation)
+    }
 }
 
 /// Configuration for setting TLS for secure WebSocket
@@ -213,6 +344,12 @@ pub struct FileStorageConfig {
     /// The key used for the peer key, if the file is not present.
     pub peer_key: Option<Sensitive<String>>,
 
+    /// Path to the file the known peer contacts are persisted to.
+    pub peer_contacts_path: PathBuf,
+
+    /// Path to the file the ban list is persisted to.
+    pub ban_list_path: PathBuf,
+
     /// Path to voting key.
     #[cfg(feature = "validator")]
     pub voting_key_path: Option<PathBuf>,
@@ -251,6 +388,8 @@ impl FileStorageConfig {
             database_parent: path.to_path_buf(),
             peer_key_path: path.join("peer_key.dat"),
             peer_key: None,
+            peer_contacts_path: path.join("peer_contacts.dat"),
+            ban_list_path: path.join("ban_list.dat"),
             #[cfg(feature = "validator")]
             voting_key_paths: None,
             #[cfg(feature = "validator")]
@@ -295,8 +434,8 @@ pub struct DatabaseConfig {
     #[builder(default = "1024 * 1024 * 1024 * 1024")]
     size: usize,
 
-    /// Max number of DBs. Recommended: 20
-    #[builder(default = "20")]
+    /// Max number of DBs. Recommended: 24
+    #[builder(default = "24")]
     max_dbs: u32,
 
     /// Max number of threads that can open read transactions.
@@ -312,7 +451,7 @@ impl Default for DatabaseConfig {
         Self {
             // 1 TB
             size: 1024 * 1024 * 1024 * 1024,
-            max_dbs: 20,
+            max_dbs: 24,
             max_readers: 600,
         }
     }
@@ -566,6 +705,26 @@ impl StorageConfig {
         })
     }
 
+    /// Returns the path of the file the known peer contacts are persisted to. Peer contacts are not
+    /// persisted with volatile storage.
+    pub(crate) fn peer_contacts_path(&self) -> Option<PathBuf> {
+        match self {
+            StorageConfig::Volatile => None,
+            StorageConfig::Filesystem(file_storage) => {
+                Some(file_storage.peer_contacts_path.clone())
+            }
+        }
+    }
+
+    /// Returns the path of the file the ban list is persisted to. The ban list is not persisted
+    /// with volatile storage.
+    pub(crate) fn ban_list_path(&self) -> Option<PathBuf> {
+        match self {
+            StorageConfig::Volatile => None,
+            StorageConfig::Filesystem(file_storage) => Some(file_storage.ban_list_path.clone()),
+        }
+    }
+
     pub(crate) fn identity_keypair(&self) -> Result<IdentityKeypair, Error> {
         match self {
             StorageConfig::Volatile => Ok(IdentityKeypair::generate_ed25519()),
@@ -813,6 +972,7 @@ impl ClientConfigBuilder {
             control_size_limit,
             filter_rules,
             filter_limit,
+            ..Default::default()
         });
         self
     }
@@ -859,7 +1019,28 @@ impl ClientConfigBuilder {
             tls: config_file.network.tls.as_ref().map(|s| s.clone().into()),
             only_secure_ws_connections: false,
             allow_loopback_addresses: config_file.network.allow_loopback_addresses,
+            quic: config_file.network.quic,
             dht_quorum: config_file.network.dht_quorum,
+            deny_list: config_file
+                .network
+                .deny_list
+                .iter()
+                .map(|target| target.parse())
+                .collect::<Result<Vec<BanTarget>, _>>()?,
+            allow_list: NetworkConfig::allow_list(&config_file.network.allow_list)?,
+            sentry_for: config_file
+                .network
+                .sentry_for
+                .iter()
+                .map(|peer_id| {
+                    peer_id.parse().map_err(|error| {
+                        Error::config_error(format!("Invalid sentry_for entry {peer_id}: {error}"))
+                    })
+                })
+                .collect::<Result<Vec<PeerId>, _>>()?,
+            rate_limits: NetworkConfig::rate_limit_overrides(&config_file.network.rate_limits)?,
+            reputation: NetworkConfig::reputation(&config_file.network.reputation)?,
+            capture_path: config_file.network.capture_file.as_ref().map(PathBuf::from),
         });
 
         // Configure consensus
@@ -878,6 +1059,12 @@ impl ClientConfigBuilder {
         if let Some(full_sync_threshold) = config_file.consensus.full_sync_threshold {
             consensus.full_sync_threshold = full_sync_threshold;
         }
+        if let Some(zkp_archive) = config_file.consensus.zkp_archive {
+            consensus.zkp_archive = zkp_archive;
+        }
+        consensus.zkp_archive_max_proofs = config_file.consensus.zkp_archive_max_proofs;
+        consensus.max_historical_state_distance =
+            config_file.consensus.max_historical_state_distance;
         self.consensus(consensus);
 
         // Configure network
@@ -898,6 +1085,12 @@ impl ClientConfigBuilder {
         if let Some(key) = config_file.network.peer_key.as_ref() {
             file_storage.peer_key = Some(key.to_owned());
         }
+        if let Some(contacts_path) = config_file.network.peer_contacts_file.as_ref() {
+            file_storage.peer_contacts_path = PathBuf::from(contacts_path);
+        }
+        if let Some(ban_list_path) = config_file.network.ban_list_file.as_ref() {
+            file_storage.ban_list_path = PathBuf::from(ban_list_path);
+        }
         #[cfg(feature = "validator")]
         if let Some(validator_config) = config_file.validator.as_ref() {
             self.validator(ValidatorConfig {
@@ -1,6 +1,6 @@
 use std::{
     collections::HashMap, fmt::Debug, fs, io, io::Write as _, num::NonZeroU8, path::Path,
-    str::FromStr,
+    str::FromStr, time::Duration,
 };
 
 use log::level_filters::LevelFilter;
@@ -160,6 +160,23 @@ impl FromStr for ConfigFile {
 pub struct NetworkSettings {
     pub peer_key_file: Option<String>,
     pub peer_key: Option<Sensitive<String>>,
+    pub peer_contacts_file: Option<String>,
+    pub ban_list_file: Option<String>,
+    /// File to record the gossipsub and request-response traffic to, for debugging.
+    pub capture_file: Option<String>,
+
+    /// Peer IDs, IP addresses and subnets that are never allowed to connect.
+    #[serde(default)]
+    pub deny_list: Vec<String>,
+
+    /// If not empty, the only peers this node connects to (private mode). Entries are either
+    /// multiaddresses ending in `/p2p/<peer ID>` or plain peer IDs, which are only accepted.
+    #[serde(default)]
+    pub allow_list: Vec<String>,
+
+    /// Peer IDs of the nodes in private mode this node relays traffic for.
+    #[serde(default)]
+    pub sentry_for: Vec<String>,
 
     #[serde(default)]
     pub listen_addresses: Vec<String>,
@@ -188,8 +205,18 @@ pub struct NetworkSettings {
     pub desired_peer_count: usize,
     #[serde(default)]
     pub allow_loopback_addresses: bool,
+    /// Whether to dial and accept QUIC connections on `/udp/<port>/quic-v1` addresses.
+    #[serde(default)]
+    pub quic: bool,
     #[serde(default)]
     pub dht_quorum: Option<NonZeroU8>,
+
+    /// Overrides of the request and gossipsub message rate limits.
+    #[serde(default)]
+    pub rate_limits: Vec<RateLimitSettings>,
+    /// Overrides of the peer reputation penalties and thresholds.
+    #[serde(default)]
+    pub reputation: ReputationSettings,
 }
 
 impl NetworkSettings {
@@ -215,6 +242,36 @@ pub struct Seed {
     pub address: Multiaddr,
 }
 
+/// Overrides the rate limit of either a request type or a gossipsub topic.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RateLimitSettings {
+    /// The type ID of the request.
+    pub request: Option<u16>,
+    /// The name of the gossipsub topic.
+    pub topic: Option<String>,
+    /// Max number of requests or messages a peer may send within the time window.
+    pub max_requests: u32,
+    /// The length of the time window in seconds.
+    pub time_window: u64,
+}
+
+/// Overrides the penalties and thresholds of the peer reputation. Unset values keep their default.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ReputationSettings {
+    /// Score deducted whenever a peer exceeds a rate limit.
+    pub rate_limit_penalty: Option<f64>,
+    /// Score deducted whenever a peer sends a response that can't be deserialized.
+    pub invalid_response_penalty: Option<f64>,
+    /// Score regained per second.
+    pub recovery_per_second: Option<f64>,
+    /// Peers are disconnected once their score drops to this value.
+    pub disconnect_threshold: Option<f64>,
+    /// Peers are banned once their score drops to this value.
+    pub ban_threshold: Option<f64>,
+}
+
 /// Settings for configuring TLS for secure WebSocket
 #[derive(Clone, Debug, Deserialize)]
 #[serde(deny_unknown_fields)]
@@ -245,6 +302,13 @@ pub struct ConsensusSettings {
     /// History indices enabled. Only effective for history and full nodes.
     #[serde(default)]
     pub index_history: Option<bool>,
+    /// Keep the zk proofs of past election blocks in addition to the latest one.
+    #[serde(default)]
+    pub zkp_archive: Option<bool>,
+    /// Maximum number of zk proofs that are kept if `zkp_archive` is enabled
+    pub zkp_archive_max_proofs: Option<usize>,
+    /// Maximum number of blocks behind the head for which historical account states are served
+    pub max_historical_state_distance: Option<u32>,
 }
 
 impl Default for ConsensusSettings {
@@ -256,6 +320,9 @@ impl Default for ConsensusSettings {
             min_peers: None,
             full_sync_threshold: None,
             index_history: None,
+            zkp_archive: None,
+            zkp_archive_max_proofs: None,
+            max_historical_state_distance: None,
         }
     }
 }
@@ -399,6 +466,12 @@ pub struct MempoolSettings {
     pub size_limit: Option<usize>,
     pub control_size_limit: Option<usize>,
     pub blacklist_limit: Option<usize>,
+    #[serde(default, deserialize_with = "deserialize_fee_bump_ratio")]
+    pub replacement_fee_bump_ratio: Option<f64>,
+    pub sender_limit: Option<usize>,
+    pub sender_size_limit: Option<usize>,
+    pub persist: Option<bool>,
+    pub persist_interval: Option<u64>,
 }
 
 #[derive(Clone, Debug, Deserialize)]
@@ -463,6 +536,20 @@ impl From<MempoolSettings> for MempoolConfig {
                 .blacklist_limit
                 .unwrap_or(MempoolFilter::DEFAULT_BLACKLIST_SIZE),
             filter_rules: mempool.filter.map(MempoolRules::from).unwrap_or_default(),
+            replacement_fee_bump_ratio: mempool
+                .replacement_fee_bump_ratio
+                .unwrap_or(Mempool::DEFAULT_REPLACEMENT_FEE_BUMP_RATIO),
+            sender_limit: mempool
+                .sender_limit
+                .unwrap_or(Mempool::DEFAULT_SENDER_LIMIT),
+            sender_size_limit: mempool
+                .sender_size_limit
+                .unwrap_or(Mempool::DEFAULT_SENDER_SIZE_LIMIT),
+            persist: mempool.persist.unwrap_or(false),
+            persist_interval: mempool
+                .persist_interval
+                .map(Duration::from_secs)
+                .unwrap_or(Mempool::DEFAULT_PERSIST_INTERVAL),
         }
     }
 }
@@ -11,6 +11,19 @@ where
     Coin::try_from(value).map_err(Error::custom)
 }
 
+pub(crate) fn deserialize_fee_bump_ratio<'de, D>(deserializer: D) -> Result<Option<f64>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value = Option::<f64>::deserialize(deserializer)?;
+    match value {
+        Some(ratio) if !ratio.is_finite() || ratio < 1.0 => Err(Error::custom(format!(
+            "fee bump ratio must be a finite number of at least 1.0, got {ratio}"
+        ))),
+        value => Ok(value),
+    }
+}
+
 #[allow(dead_code)]
 pub(crate) fn deserialize_string<'de, D, T>(deserializer: D) -> Result<T, D::Error>
 where
@@ -44,6 +44,9 @@ pub enum Error {
     #[error("Failed to parse multiaddr: {0}")]
     Multiaddr(#[from] nimiq_network_libp2p::libp2p::core::multiaddr::Error),
 
+    #[error("Failed to parse ban target: {0}")]
+    BanTarget(#[from] nimiq_network_libp2p::ParseBanTargetError),
+
     #[error("Failed to parse Address: {0}")]
     Address(#[from] nimiq_keys::AddressParseError),
 
@@ -52,6 +55,10 @@ pub enum Error {
 
     #[error("Nano ZKP Error: {0}")]
     NanoZKP(#[from] nimiq_zkp_primitives::NanoZKPError),
+
+    #[cfg(feature = "snapshot")]
+    #[error("Snapshot error: {0}")]
+    Snapshot(String),
 }
 
 impl Error {
@@ -64,4 +71,15 @@ impl Error {
     pub fn config_error<S: AsRef<str>>(msg: S) -> Self {
         Self::Config(msg.as_ref().to_string())
     }
+
+    /// Constructs a snapshot error from an error message.
+    ///
+    /// # Arguments
+    ///
+    /// * msg - The error message
+    ///
+    #[cfg(feature = "snapshot")]
+    pub fn snapshot_error<S: AsRef<str>>(msg: S) -> Self {
+        Self::Snapshot(msg.as_ref().to_string())
+    }
 }
@@ -6,6 +6,8 @@ use nimiq_consensus::ConsensusProxy;
 use nimiq_mempool::mempool::Mempool;
 pub use nimiq_metrics_server::NimiqTaskMonitor;
 use nimiq_network_interface::network::Network;
+#[cfg(feature = "nimiq-validator-network")]
+use nimiq_validator_network::ValidatorNetworkStatus;
 
 pub fn start_metrics_server<TNetwork: Network>(
     addr: SocketAddr,
@@ -13,16 +15,22 @@ pub fn start_metrics_server<TNetwork: Network>(
     #[cfg(feature = "nimiq-mempool")] mempool: Option<Arc<Mempool>>,
     consensus_proxy: ConsensusProxy<TNetwork>,
     network: Arc<nimiq_network_libp2p::Network>,
+    #[cfg(feature = "nimiq-validator-network")] validator_network: Option<
+        Arc<dyn ValidatorNetworkStatus>,
+    >,
     task_monitors: &[NimiqTaskMonitor],
 ) {
     #[cfg(not(feature = "nimiq-mempool"))]
     let mempool = None;
+    #[cfg(not(feature = "nimiq-validator-network"))]
+    let validator_network = None;
     nimiq_metrics_server::start_metrics_server(
         addr,
         blockchain_proxy,
         mempool,
         consensus_proxy,
         network,
+        validator_network,
         task_monitors,
     );
 }
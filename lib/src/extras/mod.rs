@@ -10,6 +10,8 @@ pub mod metrics_server;
 pub mod panic;
 #[cfg(feature = "rpc-server")]
 pub mod rpc_server;
+#[cfg(feature = "snapshot")]
+pub mod snapshot;
 #[cfg(feature = "signal-handling")]
 pub mod signal_handling;
 #[cfg(feature = "web-logging")]
@@ -2,21 +2,55 @@ use std::time::Duration;
 
 use nimiq_time::sleep;
 use nimiq_utils::spawn;
-use signal_hook::{consts::SIGINT, iterator::Signals};
+use parking_lot::{const_mutex, Mutex};
+use signal_hook::{
+    consts::{SIGINT, SIGTERM},
+    iterator::Signals,
+    low_level::signal_name,
+};
+use tokio::task::spawn_blocking;
+
+/// Hooks that are run before the client exits on SIGINT or SIGTERM.
+static SHUTDOWN_HOOKS: Mutex<Vec<Box<dyn FnOnce() + Send>>> = const_mutex(Vec::new());
+
+/// Registers a hook that is run on graceful shutdown, i.e. when SIGINT (Ctrl+C) or SIGTERM is
+/// received. Hooks are run in the order they were registered, on a thread where blocking is
+/// allowed.
+pub fn on_shutdown(hook: impl FnOnce() + Send + 'static) {
+    SHUTDOWN_HOOKS.lock().push(Box::new(hook));
+}
 
 pub fn initialize_signal_handler() {
-    let signals = Signals::new([SIGINT]);
+    let signals = Signals::new([SIGINT, SIGTERM]);
 
     if let Ok(mut signals) = signals {
         spawn(async move {
-            if signals.forever().next().is_some() {
-                log::warn!("Received Ctrl+C. Closing client");
+            // Waiting for a signal blocks the thread.
+            let signal = spawn_blocking(move || signals.forever().next())
+                .await
+                .ok()
+                .flatten();
+            if let Some(signal) = signal {
+                log::warn!(
+                    signal = signal_name(signal).unwrap_or("unknown"),
+                    "Received signal. Closing client"
+                );
+                let hooks = std::mem::take(&mut *SHUTDOWN_HOOKS.lock());
+                let result = spawn_blocking(move || {
+                    for hook in hooks {
+                        hook();
+                    }
+                })
+                .await;
+                if let Err(error) = result {
+                    log::error!(%error, "Shutdown hook failed");
+                }
                 // Add some delay for the log message to propagate into loki
                 sleep(Duration::from_millis(200)).await;
                 std::process::exit(0);
             }
         });
     } else {
-        log::error!("Could not obtain SIGINT signal");
+        log::error!("Could not obtain SIGINT and SIGTERM signals");
     }
 }
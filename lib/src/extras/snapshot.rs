@@ -0,0 +1,277 @@
+use std::{
+    fs::File,
+    io::{self, BufReader, BufWriter, Read, Write},
+    path::Path,
+    sync::Arc,
+};
+
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+use nimiq_blockchain::{Blockchain, BlockchainConfig};
+use nimiq_consensus::Error::BlockchainError;
+use nimiq_database::{
+    mdbx::{MdbxDatabase, MdbxReadTransaction},
+    traits::{Database, ReadTransaction, Table, WriteTransaction},
+};
+use nimiq_genesis::NetworkInfo;
+use nimiq_hash::{Blake2bHash, Blake2bHasher, Hasher};
+use nimiq_utils::time::OffsetTime;
+use nimiq_zkp::NanoProof;
+use nimiq_zkp_component::proof_store::ZKProofTable;
+
+use crate::{
+    config::{config::ClientConfig, config_file::SyncMode},
+    error::Error,
+};
+
+/// Identifies a snapshot file.
+const MAGIC: &[u8; 8] = b"NIMQSNAP";
+/// The version of the snapshot format.
+const VERSION: u8 = 1;
+
+/// The uncompressed header of a snapshot. It is followed by the gzip compressed tables and the
+/// Blake2b checksum of the uncompressed tables.
+struct SnapshotHeader {
+    /// The genesis hash of the network the snapshot was taken from.
+    genesis_hash: Blake2bHash,
+    /// Whether the snapshot contains the full history (history node) or not (full node).
+    keep_history: bool,
+    /// Whether the snapshot contains the history index tables.
+    index_history: bool,
+}
+
+impl SnapshotHeader {
+    fn from_config(config: &ClientConfig) -> Result<Self, Error> {
+        if config.consensus.sync_mode == SyncMode::Light {
+            return Err(Error::snapshot_error(
+                "Snapshots are not supported for light nodes",
+            ));
+        }
+
+        Ok(SnapshotHeader {
+            genesis_hash: NetworkInfo::from_network_id(config.network_id)
+                .genesis_hash()
+                .clone(),
+            keep_history: config.consensus.sync_mode == SyncMode::History,
+            index_history: config.consensus.index_history,
+        })
+    }
+
+    fn write<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        writer.write_all(MAGIC)?;
+        writer.write_all(&[VERSION, self.keep_history as u8, self.index_history as u8])?;
+        writer.write_all(&self.genesis_hash.0)
+    }
+
+    fn read<R: Read>(reader: &mut R) -> Result<Self, Error> {
+        let mut magic = [0u8; 8];
+        reader.read_exact(&mut magic)?;
+        if &magic != MAGIC {
+            return Err(Error::snapshot_error("Not a snapshot file"));
+        }
+
+        let mut flags = [0u8; 3];
+        reader.read_exact(&mut flags)?;
+        if flags[0] != VERSION {
+            return Err(Error::snapshot_error(format!(
+                "Unsupported snapshot version: {}",
+                flags[0]
+            )));
+        }
+
+        let mut genesis_hash = [0u8; 32];
+        reader.read_exact(&mut genesis_hash)?;
+
+        Ok(SnapshotHeader {
+            genesis_hash: genesis_hash.into(),
+            keep_history: flags[1] != 0,
+            index_history: flags[2] != 0,
+        })
+    }
+}
+
+/// Wraps a reader or writer and computes the checksum of all data passing through it.
+struct Checksummed<T> {
+    inner: T,
+    hasher: Blake2bHasher,
+}
+
+impl<T> Checksummed<T> {
+    fn new(inner: T) -> Self {
+        Checksummed {
+            inner,
+            hasher: Blake2bHasher::new(),
+        }
+    }
+
+    fn finish(self) -> (T, Blake2bHash) {
+        (self.inner, self.hasher.finish())
+    }
+}
+
+impl<W: Write> Write for Checksummed<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.inner.write(buf)?;
+        self.hasher.write_all(&buf[..written])?;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+impl<R: Read> Read for Checksummed<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        self.hasher.write_all(&buf[..read])?;
+        Ok(read)
+    }
+}
+
+/// Returns the tables included in a snapshot: the chain state and the latest ZK proof, which
+/// anchors the chain of nodes that synced via ZKP.
+fn snapshot_tables() -> Vec<&'static str> {
+    Blockchain::SNAPSHOT_TABLES
+        .iter()
+        .copied()
+        .chain([ZKProofTable::NAME])
+        .collect()
+}
+
+/// Returns the latest ZK proof stored in the database together with the number of the election
+/// block it proves, if there is any.
+fn election_proof(txn: &MdbxReadTransaction) -> Option<(u32, NanoProof)> {
+    let zk_proof = txn.get(&ZKProofTable, &())?;
+    Some((zk_proof.block_number, zk_proof.proof?))
+}
+
+/// Opens the consensus database and the blockchain for the given configuration, just like the
+/// client does.
+fn open_blockchain(config: &ClientConfig) -> Result<(MdbxDatabase, Blockchain), Error> {
+    let pre_genesis_environment = if config.storage.has_pre_genesis_database(config.network_id) {
+        Some(
+            config
+                .storage
+                .pre_genesis_database(config.network_id, config.database.clone())?,
+        )
+    } else {
+        None
+    };
+
+    let environment = config.storage.database(
+        config.network_id,
+        config.consensus.sync_mode,
+        config.database.clone(),
+    )?;
+
+    let blockchain_config = BlockchainConfig {
+        keep_history: config.consensus.sync_mode == SyncMode::History,
+        max_epochs_stored: config.consensus.max_epochs_stored,
+        index_history: config.consensus.index_history,
+        ..Default::default()
+    };
+
+    let blockchain = Blockchain::new_merged(
+        environment.clone(),
+        pre_genesis_environment,
+        blockchain_config,
+        config.network_id,
+        Arc::new(OffsetTime::new()),
+    )
+    .map_err(|e| Error::Consensus(BlockchainError(e)))?;
+
+    Ok((environment, blockchain))
+}
+
+/// Writes a snapshot of the consensus database to the file at `path`.
+///
+/// All tables are read within a single read transaction, so this can safely be done while a client
+/// is running on the same database. The state is verified before it is exported.
+pub fn export_snapshot(config: &ClientConfig, path: &Path) -> Result<(), Error> {
+    let header = SnapshotHeader::from_config(config)?;
+    let (_environment, blockchain) = open_blockchain(config)?;
+
+    let txn = blockchain.read_transaction();
+    let head_hash = blockchain
+        .verify_snapshot(&txn, election_proof(&txn))
+        .map_err(|e| Error::Consensus(BlockchainError(e)))?;
+
+    let mut file = BufWriter::new(File::create(path)?);
+    header.write(&mut file)?;
+
+    let mut writer = Checksummed::new(GzEncoder::new(file, Compression::default()));
+    let num_entries = txn.export_tables(&snapshot_tables(), &mut writer)?;
+    let (mut encoder, checksum) = writer.finish();
+    encoder.write_all(&checksum.0)?;
+    encoder.finish()?.flush()?;
+
+    log::info!(
+        %head_hash,
+        num_entries,
+        %checksum,
+        path = %path.display(),
+        "Exported snapshot"
+    );
+    Ok(())
+}
+
+/// Imports the snapshot at `path` into the consensus database.
+///
+/// The database must not contain anything but the genesis block, and the snapshot must have been
+/// taken from a node with the same network, sync mode and history index settings.
+/// The import is only committed if the checksum matches, the imported chain links to our genesis
+/// block through justified election blocks, starting at the election block proven by the imported
+/// ZK proof if there is one, and the imported accounts and history trees match the state and
+/// history roots of the imported head.
+pub fn import_snapshot(config: &ClientConfig, path: &Path) -> Result<(), Error> {
+    let expected_header = SnapshotHeader::from_config(config)?;
+
+    let mut file = BufReader::new(File::open(path)?);
+    let header = SnapshotHeader::read(&mut file)?;
+    if header.genesis_hash != expected_header.genesis_hash {
+        return Err(Error::snapshot_error(
+            "Snapshot was taken from a different network",
+        ));
+    }
+    if header.keep_history != expected_header.keep_history
+        || header.index_history != expected_header.index_history
+    {
+        return Err(Error::snapshot_error(
+            "Snapshot was taken with a different sync mode or history index setting",
+        ));
+    }
+
+    // Opening the blockchain creates all tables. Refuse to overwrite an existing chain.
+    let (environment, blockchain) = open_blockchain(config)?;
+    if blockchain.state.head_hash != expected_header.genesis_hash {
+        return Err(Error::snapshot_error(
+            "The consensus database is not empty, remove it before importing a snapshot",
+        ));
+    }
+
+    environment.create_regular_table(&ZKProofTable);
+
+    let mut txn = environment.write_transaction();
+    let mut reader = Checksummed::new(GzDecoder::new(file));
+    let tables = txn.import_tables(&snapshot_tables(), &mut reader)?;
+    let (mut decoder, checksum) = reader.finish();
+
+    let mut expected_checksum = [0u8; 32];
+    decoder.read_exact(&mut expected_checksum)?;
+    if checksum != expected_checksum.into() {
+        return Err(Error::snapshot_error("Snapshot checksum mismatch"));
+    }
+
+    let head_hash = blockchain
+        .verify_snapshot(&txn, election_proof(&txn))
+        .map_err(|e| Error::Consensus(BlockchainError(e)))?;
+    txn.commit();
+
+    log::info!(
+        %head_hash,
+        num_tables = tables.len(),
+        path = %path.display(),
+        "Imported snapshot"
+    );
+    Ok(())
+}
@@ -113,3 +113,55 @@ fn config_file_partial_db_entry() {
 
     assert_eq!(config.storage, db_config.into());
 }
+
+#[cfg(feature = "nimiq-mempool")]
+#[test]
+fn config_file_replacement_fee_bump_ratio() {
+    let config_file: ConfigFile = toml::from_str(
+        r#"
+    [mempool]
+    replacement_fee_bump_ratio = 1.25
+    "#,
+    )
+    .unwrap();
+
+    let mut config_builder = ClientConfigBuilder::default();
+    config_builder.config_file(&config_file).unwrap();
+    let config = config_builder.build().unwrap();
+
+    assert_eq!(config.mempool.replacement_fee_bump_ratio, 1.25);
+
+    // A ratio of exactly 1.0 only requires a strictly higher fee per byte
+    assert!(toml::from_str::<ConfigFile>(
+        r#"
+    [mempool]
+    replacement_fee_bump_ratio = 1.0
+    "#,
+    )
+    .is_ok());
+
+    // Ratios below 1.0 would allow replacing a transaction with a cheaper one
+    assert!(toml::from_str::<ConfigFile>(
+        r#"
+    [mempool]
+    replacement_fee_bump_ratio = 0.9
+    "#,
+    )
+    .is_err());
+
+    assert!(toml::from_str::<ConfigFile>(
+        r#"
+    [mempool]
+    replacement_fee_bump_ratio = nan
+    "#,
+    )
+    .is_err());
+
+    assert!(toml::from_str::<ConfigFile>(
+        r#"
+    [mempool]
+    replacement_fee_bump_ratio = inf
+    "#,
+    )
+    .is_err());
+}
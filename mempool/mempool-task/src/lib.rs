@@ -9,10 +9,13 @@ use log::{debug, warn};
 use nimiq_blockchain::Blockchain;
 use nimiq_blockchain_interface::{AbstractBlockchain, BlockchainEvent};
 use nimiq_consensus::{Consensus, ConsensusEvent, ConsensusProxy};
-use nimiq_mempool::{config::MempoolConfig, mempool::Mempool};
+use nimiq_database::mdbx::MdbxDatabase;
+use nimiq_mempool::{config::MempoolConfig, mempool::Mempool, mempool_store::MempoolStore};
 use nimiq_network_interface::network::Network;
+use nimiq_time::{interval, Interval};
 use nimiq_utils::spawn;
 use parking_lot::RwLock;
+use tokio::task::{spawn_blocking, JoinHandle};
 #[cfg(feature = "metrics")]
 use tokio_metrics::TaskMonitor;
 use tokio_stream::wrappers::{errors::BroadcastStreamRecvError, BroadcastStream};
@@ -37,6 +40,10 @@ pub struct MempoolTask<N: Network> {
 
     pub mempool: Arc<Mempool>,
     mempool_active: bool,
+    /// Interval in which the mempool transactions are persisted, if persistence is enabled.
+    persist_interval: Option<Interval>,
+    /// The currently running persist, if any.
+    persist_handle: Option<JoinHandle<()>>,
     #[cfg(feature = "metrics")]
     mempool_monitor: TaskMonitor,
     #[cfg(feature = "metrics")]
@@ -47,11 +54,23 @@ impl<N: Network> MempoolTask<N> {
     pub fn new(
         consensus: &Consensus<N>,
         blockchain: Arc<RwLock<Blockchain>>,
+        env: MdbxDatabase,
         mempool_config: MempoolConfig,
     ) -> Self {
         let consensus_event_rx = consensus.subscribe_events();
 
-        let mempool = Arc::new(Mempool::new(Arc::clone(&blockchain), mempool_config));
+        let (mempool, persist_interval) = if mempool_config.persist {
+            let persist_interval = interval(mempool_config.persist_interval);
+            let mempool = Mempool::new_persistent(
+                Arc::clone(&blockchain),
+                mempool_config,
+                MempoolStore::new(env),
+            );
+            (mempool, Some(persist_interval))
+        } else {
+            (Mempool::new(Arc::clone(&blockchain), mempool_config), None)
+        };
+        let mempool = Arc::new(mempool);
         let mempool_active = false;
 
         let blockchain_event_rx = blockchain.read().notifier_as_stream();
@@ -64,6 +83,8 @@ impl<N: Network> MempoolTask<N> {
 
             mempool: Arc::clone(&mempool),
             mempool_active,
+            persist_interval,
+            persist_handle: None,
             #[cfg(feature = "metrics")]
             mempool_monitor: TaskMonitor::new(),
             #[cfg(feature = "metrics")]
@@ -95,6 +116,8 @@ impl<N: Network> MempoolTask<N> {
         #[cfg(not(feature = "metrics"))]
         spawn({
             async move {
+                // Re-add the transactions persisted by a previous run, if any.
+                mempool.restore();
                 // The mempool is not updated while consensus is lost.
                 // Thus, we need to check all transactions if they are still valid.
                 mempool.cleanup();
@@ -106,6 +129,8 @@ impl<N: Network> MempoolTask<N> {
             let mempool_monitor = self.mempool_monitor.clone();
             let ctrl_mempool_monitor = self.control_mempool_monitor.clone();
             async move {
+                // Re-add the transactions persisted by a previous run, if any.
+                mempool.restore();
                 // The mempool is not updated while consensus is lost.
                 // Thus, we need to check all transactions if they are still valid.
                 mempool.cleanup();
@@ -133,6 +158,22 @@ impl<N: Network> MempoolTask<N> {
         self.mempool_active = false;
     }
 
+    /// Persists the mempool transactions on the blocking thread pool, such that the database write
+    /// doesn't block the executor. A persist is skipped if the previous one is still running.
+    fn persist(&mut self) {
+        if self
+            .persist_handle
+            .as_ref()
+            .is_some_and(|handle| !handle.is_finished())
+        {
+            debug!("Skipping mempool persist, the previous one is still running");
+            return;
+        }
+
+        let mempool = Arc::clone(&self.mempool);
+        self.persist_handle = Some(spawn_blocking(move || mempool.persist()));
+    }
+
     fn on_blockchain_event(&mut self, event: &BlockchainEvent) {
         match event {
             BlockchainEvent::HistoryAdopted(_) => {
@@ -191,6 +232,17 @@ impl<N: Network> Stream for MempoolTask<N> {
             }
         }
 
+        // Periodically persist the mempool transactions.
+        if let Some(persist_interval) = self.persist_interval.as_mut() {
+            let mut persist = false;
+            while persist_interval.poll_next_unpin(cx).is_ready() {
+                persist = true;
+            }
+            if persist {
+                self.persist();
+            }
+        }
+
         // Process blockchain updates.
         if let Poll::Ready(Some(event)) = self.blockchain_event_rx.poll_next_unpin(cx) {
             self.on_blockchain_event(&event);
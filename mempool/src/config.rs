@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use crate::{
     filter::{MempoolFilter, MempoolRules},
     mempool::Mempool,
@@ -14,6 +16,17 @@ pub struct MempoolConfig {
     pub filter_rules: MempoolRules,
     /// Mempool filter limit or size
     pub filter_limit: usize,
+    /// Ratio by which the fee per byte of a transaction must exceed the fee per byte of the
+    /// pending transaction it replaces
+    pub replacement_fee_bump_ratio: f64,
+    /// Maximum number of transactions per sender
+    pub sender_limit: usize,
+    /// Total size limit of transactions per sender (bytes)
+    pub sender_size_limit: usize,
+    /// Whether the mempool transactions are persisted across restarts
+    pub persist: bool,
+    /// Interval in which the mempool transactions are persisted
+    pub persist_interval: Duration,
 }
 
 impl Default for MempoolConfig {
@@ -23,6 +36,11 @@ impl Default for MempoolConfig {
             control_size_limit: Mempool::DEFAULT_CONTROL_SIZE_LIMIT,
             filter_rules: MempoolRules::default(),
             filter_limit: MempoolFilter::DEFAULT_BLACKLIST_SIZE,
+            replacement_fee_bump_ratio: Mempool::DEFAULT_REPLACEMENT_FEE_BUMP_RATIO,
+            sender_limit: Mempool::DEFAULT_SENDER_LIMIT,
+            sender_size_limit: Mempool::DEFAULT_SENDER_SIZE_LIMIT,
+            persist: false,
+            persist_interval: Mempool::DEFAULT_PERSIST_INTERVAL,
         }
     }
 }
@@ -0,0 +1,324 @@
+use std::collections::VecDeque;
+
+use nimiq_block::Block;
+use nimiq_hash::Blake2bHash;
+use nimiq_primitives::policy::Policy;
+use nimiq_serde::Serialize;
+
+use crate::mempool_state::MempoolState;
+
+/// Fee statistics of a single micro block.
+struct BlockFeeStats {
+    /// The hash of the block, used to forget the block again if it is reverted.
+    block_hash: Blake2bHash,
+    /// The lowest fee per byte a transaction needed to pay in order to be included in the block.
+    /// This is zero if the block wasn't full, i.e. there was space left for any transaction.
+    min_fee_per_byte: f64,
+}
+
+/// Estimates the fee per byte that a transaction needs to pay to be included within a given number
+/// of blocks with a given probability.
+///
+/// The estimation is based on two sources:
+/// - The fees of the transactions that were actually included in the most recent micro blocks.
+/// - The fee distribution of the transactions that are currently waiting in the mempool.
+pub struct FeeEstimator {
+    /// Fee statistics of the most recent micro blocks (oldest to newest).
+    blocks: VecDeque<BlockFeeStats>,
+
+    /// Maximum number of blocks the statistics are kept for.
+    max_blocks: usize,
+}
+
+impl FeeEstimator {
+    /// Default number of recent blocks the estimation is based on
+    pub const DEFAULT_HISTORY_BLOCKS: usize = 120;
+
+    /// A block is considered full if this fraction of the micro body size is used.
+    const FULL_BLOCK_RATIO: f64 = 0.9;
+
+    /// The fee per byte (in Luna) a transaction pays on top of the transactions it needs to outbid.
+    /// Transactions paying the same fee per byte would compete for the same spot.
+    const OUTBID_FEE_PER_BYTE: f64 = 1.0;
+
+    /// Creates a new fee estimator that keeps the statistics of the given number of blocks.
+    pub fn new(max_blocks: usize) -> Self {
+        Self {
+            blocks: VecDeque::with_capacity(max_blocks),
+            max_blocks,
+        }
+    }
+
+    /// Records the fees of the transactions included in an adopted block.
+    /// Macro blocks don't contain transactions and are ignored.
+    pub fn push_block(&mut self, block_hash: &Blake2bHash, block: &Block) {
+        let Some(transactions) = block.transactions() else {
+            return;
+        };
+
+        let mut body_size = 0;
+        let mut min_fee_per_byte = f64::MAX;
+        for tx in transactions {
+            let tx = tx.get_raw_transaction();
+            // We need to account for one extra byte per transaction to encode its final execution status
+            body_size += 1 + tx.serialized_size();
+            min_fee_per_byte = min_fee_per_byte.min(tx.fee_per_byte());
+        }
+
+        let is_full =
+            body_size as f64 >= Policy::MAX_SIZE_MICRO_BODY as f64 * Self::FULL_BLOCK_RATIO;
+        if !is_full {
+            min_fee_per_byte = 0.0;
+        }
+
+        if self.blocks.len() == self.max_blocks {
+            self.blocks.pop_front();
+        }
+        self.blocks.push_back(BlockFeeStats {
+            block_hash: block_hash.clone(),
+            min_fee_per_byte,
+        });
+    }
+
+    /// Forgets the statistics of a reverted block.
+    pub fn revert_block(&mut self, block_hash: &Blake2bHash) {
+        self.blocks.retain(|stats| &stats.block_hash != block_hash);
+    }
+
+    /// Estimates the fee per byte needed to be included within `target_blocks` blocks with the
+    /// given `probability` (between 0 and 1), taking into account the transactions that are
+    /// currently waiting in the mempool.
+    pub(crate) fn estimate(
+        &self,
+        target_blocks: u32,
+        probability: f64,
+        mempool_state: &MempoolState,
+    ) -> f64 {
+        let target_blocks = target_blocks.max(1);
+        let probability = probability.clamp(0.0, 1.0);
+
+        f64::max(
+            self.estimate_from_history(target_blocks, probability),
+            Self::estimate_from_mempool(target_blocks, mempool_state),
+        )
+    }
+
+    /// Looks at the minimum fees needed to get into the recent blocks. If a fee per byte gets into
+    /// a fraction `q` of the blocks, the probability to be included within `n` blocks is
+    /// `1 - (1 - q)^n`. Thus, we search for the lowest fee per byte that gets into the required
+    /// fraction of blocks.
+    fn estimate_from_history(&self, target_blocks: u32, probability: f64) -> f64 {
+        if self.blocks.is_empty() {
+            return 0.0;
+        }
+
+        let required_fraction = 1.0 - (1.0 - probability).powf(1.0 / target_blocks as f64);
+
+        let mut min_fees: Vec<f64> = self
+            .blocks
+            .iter()
+            .map(|stats| stats.min_fee_per_byte)
+            .collect();
+        min_fees.sort_by(|a, b| a.partial_cmp(b).expect("fees can't be NaN"));
+
+        let index = (required_fraction * min_fees.len() as f64).ceil() as usize;
+        min_fees[index.clamp(1, min_fees.len()) - 1]
+    }
+
+    /// Looks at the transactions that would be included before a new transaction. A new transaction
+    /// needs to outbid all transactions that don't fit into the next `target_blocks` blocks, so the
+    /// estimate is strictly above the fee per byte of the first one of them.
+    /// Control transactions are always included first, so they reduce the available space.
+    fn estimate_from_mempool(target_blocks: u32, mempool_state: &MempoolState) -> f64 {
+        let mut pending: Vec<(f64, usize)> = mempool_state
+            .regular_transactions
+            .transactions
+            .values()
+            .map(|tx| (tx.fee_per_byte(), 1 + tx.serialized_size()))
+            .collect();
+        pending.sort_by(|(a, _), (b, _)| b.partial_cmp(a).expect("fees can't be NaN"));
+
+        let capacity = target_blocks as usize * Policy::MAX_SIZE_MICRO_BODY;
+        let mut size = mempool_state.control_transactions.total_size;
+        for (fee_per_byte, tx_size) in pending {
+            size += tx_size;
+            if size > capacity {
+                return fee_per_byte + Self::OUTBID_FEE_PER_BYTE;
+            }
+        }
+
+        0.0
+    }
+}
+
+impl Default for FeeEstimator {
+    fn default() -> Self {
+        FeeEstimator::new(Self::DEFAULT_HISTORY_BLOCKS)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nimiq_keys::Address;
+    use nimiq_primitives::{coin::Coin, networks::NetworkId};
+    use nimiq_test_log::test;
+    use nimiq_transaction::Transaction;
+
+    use super::*;
+    use crate::{config::MempoolConfig, mempool_transactions::TxPriority};
+
+    fn estimator_with_history(min_fees_per_byte: &[f64]) -> FeeEstimator {
+        let mut estimator = FeeEstimator::new(min_fees_per_byte.len());
+        for (i, &min_fee_per_byte) in min_fees_per_byte.iter().enumerate() {
+            estimator.blocks.push_back(BlockFeeStats {
+                block_hash: Blake2bHash::from([i as u8; 32]),
+                min_fee_per_byte,
+            });
+        }
+        estimator
+    }
+
+    fn create_transaction(recipient: u32, fee: u64) -> Transaction {
+        let mut recipient_bytes = [0u8; 20];
+        recipient_bytes[..4].copy_from_slice(&recipient.to_be_bytes());
+        Transaction::new_basic(
+            Address::from([1u8; 20]),
+            Address::from(recipient_bytes),
+            Coin::from_u64_unchecked(1),
+            Coin::from_u64_unchecked(fee),
+            1,
+            NetworkId::UnitAlbatross,
+        )
+    }
+
+    #[test]
+    fn it_estimates_from_history_percentiles() {
+        let estimator = FeeEstimator::default();
+        assert_eq!(estimator.estimate_from_history(1, 0.9), 0.0);
+
+        // The blocks are given out of order to make sure that they are sorted by fee
+        let estimator = estimator_with_history(&[9.0, 0.0, 8.0, 1.0, 7.0, 2.0, 6.0, 3.0, 5.0, 4.0]);
+
+        // Being included in the next block with probability p requires the p-th percentile
+        assert_eq!(estimator.estimate_from_history(1, 0.45), 4.0);
+        assert_eq!(estimator.estimate_from_history(1, 0.85), 8.0);
+        assert_eq!(estimator.estimate_from_history(1, 1.0), 9.0);
+
+        // Even the lowest fee gets into some blocks
+        assert_eq!(estimator.estimate_from_history(1, 0.0), 0.0);
+        assert_eq!(estimator.estimate_from_history(1, 0.05), 0.0);
+
+        // More target blocks only require a smaller fraction of the blocks:
+        // 1 - (1 - 0.9)^(1/2) = 0.68
+        assert_eq!(estimator.estimate_from_history(2, 0.9), 6.0);
+        // 1 - (1 - 0.9)^(1/10) = 0.21
+        assert_eq!(estimator.estimate_from_history(10, 0.9), 2.0);
+    }
+
+    #[test]
+    fn it_forgets_old_and_reverted_blocks() {
+        let mut estimator = estimator_with_history(&[1.0, 2.0, 3.0]);
+        assert_eq!(estimator.estimate_from_history(1, 1.0), 3.0);
+
+        estimator.revert_block(&Blake2bHash::from([2u8; 32]));
+        assert_eq!(estimator.blocks.len(), 2);
+        assert_eq!(estimator.estimate_from_history(1, 1.0), 2.0);
+    }
+
+    #[test]
+    fn it_estimates_from_mempool() {
+        let estimator = FeeEstimator::default();
+        let mut mempool_state = MempoolState::new(&MempoolConfig::default());
+        assert_eq!(FeeEstimator::estimate_from_mempool(1, &mempool_state), 0.0);
+
+        // Fill exactly one block with well paying transactions, followed by as many transactions
+        // paying less and a single transaction paying even less.
+        let tx_size = 1 + create_transaction(0, 1000).serialized_size();
+        let txns_per_block = Policy::MAX_SIZE_MICRO_BODY / tx_size;
+        let mut recipient = 0;
+        let mut fees_per_byte = vec![];
+        for (fee, num_txns) in [(1000, txns_per_block), (500, txns_per_block), (200, 1)] {
+            for _ in 0..num_txns {
+                let tx = create_transaction(recipient, fee);
+                assert_eq!(1 + tx.serialized_size(), tx_size);
+                mempool_state
+                    .regular_transactions
+                    .insert(tx, TxPriority::Medium);
+                recipient += 1;
+            }
+            fees_per_byte.push(create_transaction(0, fee).fee_per_byte());
+        }
+
+        // A transaction needs to outbid the transactions that don't fit into the next block
+        assert_eq!(
+            FeeEstimator::estimate_from_mempool(1, &mempool_state),
+            fees_per_byte[1] + FeeEstimator::OUTBID_FEE_PER_BYTE
+        );
+        // All pending transactions fit into the next three blocks
+        assert_eq!(FeeEstimator::estimate_from_mempool(3, &mempool_state), 0.0);
+
+        // Control transactions take away space from the regular transactions
+        for i in 0..2 {
+            mempool_state
+                .control_transactions
+                .insert(create_transaction(recipient + i, 1), TxPriority::Medium);
+        }
+        assert_eq!(
+            FeeEstimator::estimate_from_mempool(1, &mempool_state),
+            fees_per_byte[0] + FeeEstimator::OUTBID_FEE_PER_BYTE
+        );
+
+        // The overall estimate is the higher one of both estimates
+        let estimator = estimator_with_history(&[2.0 * fees_per_byte[0]; 10]);
+        assert_eq!(
+            estimator.estimate(1, 0.5, &mempool_state),
+            2.0 * fees_per_byte[0]
+        );
+        let estimator = estimator_with_history(&[0.0; 10]);
+        assert_eq!(
+            estimator.estimate(1, 0.5, &mempool_state),
+            fees_per_byte[0] + FeeEstimator::OUTBID_FEE_PER_BYTE
+        );
+    }
+
+    #[test]
+    fn it_outbids_the_first_transaction_that_does_not_fit() {
+        let mut mempool_state = MempoolState::new(&MempoolConfig::default());
+
+        // Fill one block such that only one of two transactions paying less fits into it.
+        let tx_size = 1 + create_transaction(0, 1000).serialized_size();
+        let txns_per_block = Policy::MAX_SIZE_MICRO_BODY / tx_size;
+        let mut recipient = 0;
+        let mut fees = vec![1000; txns_per_block - 1];
+        fees.extend([500, 500]);
+        for fee in fees {
+            mempool_state
+                .regular_transactions
+                .insert(create_transaction(recipient, fee), TxPriority::Medium);
+            recipient += 1;
+        }
+        // Pays the same fee per byte as the transaction that doesn't fit.
+        let first_not_fitting = create_transaction(recipient, 500);
+
+        // Paying the same fee per byte as the transaction that doesn't fit is not enough.
+        let estimate = FeeEstimator::estimate_from_mempool(1, &mempool_state);
+        assert!(estimate > first_not_fitting.fee_per_byte());
+        assert_eq!(
+            estimate,
+            first_not_fitting.fee_per_byte() + FeeEstimator::OUTBID_FEE_PER_BYTE
+        );
+
+        // A transaction paying the estimate takes the last spot in the next block, such that both
+        // transactions paying less don't fit anymore.
+        let fee = (estimate * first_not_fitting.serialized_size() as f64).ceil() as u64;
+        let outbidding = create_transaction(recipient, fee);
+        assert!(outbidding.fee_per_byte() > first_not_fitting.fee_per_byte());
+        mempool_state
+            .regular_transactions
+            .insert(outbidding, TxPriority::Medium);
+        assert_eq!(
+            FeeEstimator::estimate_from_mempool(1, &mempool_state),
+            estimate
+        );
+    }
+}
@@ -14,6 +14,8 @@ mod mempool_state;
 pub mod config;
 /// Mempool executor module
 pub mod executor;
+/// Fee estimator module
+pub mod fee_estimator;
 
 /// Mempool filter module
 pub mod filter;
@@ -22,6 +24,8 @@ pub mod mempool;
 /// Mempool metrics
 #[cfg(feature = "metrics")]
 mod mempool_metrics;
+/// Mempool persistence module
+pub mod mempool_store;
 /// Mempool transaction module
 pub mod mempool_transactions;
 /// Verify transaction module
@@ -1,6 +1,7 @@
 use std::{
     collections::HashSet,
     sync::{atomic::AtomicU32, Arc},
+    time::Duration,
 };
 
 use futures::{
@@ -15,6 +16,7 @@ use nimiq_blockchain_interface::AbstractBlockchain;
 use nimiq_hash::{Blake2bHash, Hash};
 use nimiq_keys::Address;
 use nimiq_network_interface::network::{Network, Topic};
+use nimiq_primitives::account::FailReason;
 use nimiq_serde::Serialize;
 use nimiq_transaction::{
     historic_transaction::RawTransactionHash, ControlTransactionTopic, Transaction,
@@ -29,12 +31,27 @@ use crate::mempool_metrics::MempoolMetrics;
 use crate::{
     config::MempoolConfig,
     executor::MempoolExecutor,
+    fee_estimator::FeeEstimator,
     filter::{MempoolFilter, MempoolRules},
     mempool_state::{EvictionReason, MempoolState},
+    mempool_store::{MempoolStore, PersistedTransaction},
     mempool_transactions::{MempoolTransactions, TxPriority},
     verify::{verify_tx, VerifyErr},
 };
 
+/// The reason a transaction was dropped from the mempool without being included in a block.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DropReason {
+    /// The transaction is not valid anymore at the current block height.
+    Expired,
+    /// The transaction became invalid, e.g. because the sender can't pay for it anymore.
+    Invalid(FailReason),
+    /// The transaction was replaced by a transaction with a higher fee.
+    Replaced,
+    /// The transaction was evicted because the mempool was full.
+    TooFull,
+}
+
 /// Struct defining the Mempool
 pub struct Mempool {
     /// Blockchain reference
@@ -54,6 +71,12 @@ pub struct Mempool {
 
     /// Total number of ongoing verification tasks
     verification_tasks: Arc<AtomicU32>,
+
+    /// Fee estimator fed with the fees of adopted blocks
+    fee_estimator: RwLock<FeeEstimator>,
+
+    /// Store the transactions are persisted to, if persistence is enabled
+    store: Option<MempoolStore>,
 }
 
 impl Mempool {
@@ -63,12 +86,22 @@ impl Mempool {
     /// Default total size limit of control transactions in the mempool (bytes)
     pub const DEFAULT_CONTROL_SIZE_LIMIT: usize = 6_000_000;
 
+    /// Default ratio by which the fee per byte of a replacement transaction must exceed the fee
+    /// per byte of the transaction it replaces
+    pub const DEFAULT_REPLACEMENT_FEE_BUMP_RATIO: f64 = 1.1;
+
+    /// Default maximum number of transactions per sender in the mempool
+    pub const DEFAULT_SENDER_LIMIT: usize = 1_000;
+
+    /// Default total size limit of transactions per sender in the mempool (bytes)
+    pub const DEFAULT_SENDER_SIZE_LIMIT: usize = 150_000;
+
+    /// Default interval in which the mempool transactions are persisted
+    pub const DEFAULT_PERSIST_INTERVAL: Duration = Duration::from_secs(60);
+
     /// Creates a new mempool
     pub fn new(blockchain: Arc<RwLock<Blockchain>>, config: MempoolConfig) -> Self {
-        let state = Arc::new(RwLock::new(MempoolState::new(
-            config.size_limit,
-            config.control_size_limit,
-        )));
+        let state = Arc::new(RwLock::new(MempoolState::new(&config)));
 
         Self {
             blockchain,
@@ -80,6 +113,23 @@ impl Mempool {
             executor_handle: Mutex::new(None),
             control_executor_handle: Mutex::new(None),
             verification_tasks: Arc::new(AtomicU32::new(0)),
+            fee_estimator: RwLock::new(FeeEstimator::default()),
+            store: None,
+        }
+    }
+
+    /// Creates a new mempool that persists its transactions to the given store.
+    ///
+    /// Previously persisted transactions are not added automatically, `restore` needs to be
+    /// called once the blockchain is ready to verify them.
+    pub fn new_persistent(
+        blockchain: Arc<RwLock<Blockchain>>,
+        config: MempoolConfig,
+        store: MempoolStore,
+    ) -> Self {
+        Self {
+            store: Some(store),
+            ..Self::new(blockchain, config)
         }
     }
 
@@ -302,6 +352,17 @@ impl Mempool {
         // First remove the transactions that are no longer valid due to age.
         self.prune_expired_transactions(&blockchain, &mut mempool_state);
 
+        // Keep the fee statistics in line with the current chain.
+        {
+            let mut fee_estimator = self.fee_estimator.write();
+            for (hash, _) in reverted_blocks {
+                fee_estimator.revert_block(hash);
+            }
+            for (hash, block) in adopted_blocks {
+                fee_estimator.push_block(hash, block);
+            }
+        }
+
         // Now iterate over the transactions in the adopted blocks:
         //  if transaction was known:
         //    remove it from the mempool
@@ -463,18 +524,30 @@ impl Mempool {
             // TODO We should have per sender transactions ordered by fee to try to
             //  keep the ones with higher fee
 
+            sender_state.total_size = 0;
             sender_state.txns.retain(|tx_hash| {
                 let tx = match mempool_state.get(tx_hash) {
                     Some(transaction) => transaction,
                     None => return false,
                 };
-                let still_valid = blockchain
-                    .reserve_balance(&sender_account, tx, &mut sender_state.reserved_balance)
-                    .is_ok();
-                if !still_valid {
-                    mempool_state.remove(blockchain, tx_hash, EvictionReason::Invalid);
+                match blockchain.reserve_balance(
+                    &sender_account,
+                    tx,
+                    &mut sender_state.reserved_balance,
+                ) {
+                    Ok(()) => {
+                        sender_state.total_size += tx.serialized_size();
+                        true
+                    }
+                    Err(error) => {
+                        mempool_state.remove(
+                            blockchain,
+                            tx_hash,
+                            EvictionReason::Invalid(error.into()),
+                        );
+                        false
+                    }
                 }
-                still_valid
             });
 
             if !sender_state.txns.is_empty() {
@@ -613,11 +686,14 @@ impl Mempool {
     }
 
     /// Adds a transaction to the local mempool without broadcasting it over the network.
+    ///
+    /// If the transaction replaces a pending transaction with a lower fee, the replaced transaction
+    /// is evicted from the mempool and its hash is returned.
     pub fn add_transaction(
         &self,
         transaction: Transaction,
         tx_priority: Option<TxPriority>,
-    ) -> Result<(), VerifyErr> {
+    ) -> Result<Option<Blake2bHash>, VerifyErr> {
         let blockchain = Arc::clone(&self.blockchain);
         let mempool_state = Arc::clone(&self.state);
         let filter = Arc::clone(&self.filter);
@@ -632,11 +708,106 @@ impl Mempool {
         )
     }
 
+    /// Re-adds the transactions that were persisted by a previous run of the node.
+    ///
+    /// Every transaction is verified again against the current blockchain state. Transactions that
+    /// expired in the meantime or became invalid otherwise are dropped.
+    /// Returns the number of restored transactions.
+    pub fn restore(&self) -> usize {
+        let Some(store) = &self.store else {
+            return 0;
+        };
+
+        let mut persisted = store.load();
+        let num_persisted = persisted.len();
+
+        // Add the best transactions first, such that those are kept if a sender can't afford all
+        // of their transactions anymore.
+        persisted.sort_by(|a, b| {
+            (b.priority as u8).cmp(&(a.priority as u8)).then(
+                b.transaction
+                    .fee_per_byte()
+                    .partial_cmp(&a.transaction.fee_per_byte())
+                    .expect("fees can't be NaN"),
+            )
+        });
+
+        let mut num_restored = 0;
+        for tx in persisted {
+            let tx_hash: Blake2bHash = tx.transaction.hash();
+            match self.add_transaction(tx.transaction, Some(tx.priority)) {
+                Ok(_) => num_restored += 1,
+                Err(error) => {
+                    debug!(%tx_hash, %error, "Dropped persisted transaction");
+                }
+            }
+        }
+
+        info!(
+            num_restored,
+            num_dropped = num_persisted - num_restored,
+            "Restored persisted mempool transactions"
+        );
+
+        num_restored
+    }
+
+    /// Persists all transactions currently in the mempool, replacing the previously persisted
+    /// ones. This does nothing if persistence is disabled or `restore` hasn't been called yet.
+    ///
+    /// This writes to the database and blocks until the write finished, so it should not be
+    /// called from an async context, see `tokio::task::spawn_blocking`.
+    pub fn persist(&self) {
+        let Some(store) = &self.store else {
+            return;
+        };
+
+        store.store(|| {
+            // Only hold the state lock while copying the transactions, not while writing them.
+            let state = self.state.read();
+            [&state.regular_transactions, &state.control_transactions]
+                .into_iter()
+                .flat_map(|transactions| {
+                    transactions.transactions.iter().map(|(hash, tx)| {
+                        let priority = transactions.priority(hash).unwrap_or(TxPriority::Medium);
+                        (
+                            hash.clone(),
+                            PersistedTransaction {
+                                transaction: tx.clone(),
+                                priority,
+                            },
+                        )
+                    })
+                })
+                .collect::<Vec<_>>()
+        });
+    }
+
+    /// Returns whether the mempool persists its transactions.
+    pub fn is_persistent(&self) -> bool {
+        self.store.is_some()
+    }
+
     /// Checks whether a transaction has been filtered
     pub fn is_filtered(&self, hash: &Blake2bHash) -> bool {
         self.filter.read().blacklisted(hash)
     }
 
+    /// Estimates the fee per byte a transaction needs to pay to be included within `target_blocks`
+    /// blocks with the given `probability` (between 0 and 1).
+    ///
+    /// The estimate is based on the fees of recently adopted blocks as well as on the transactions
+    /// currently waiting in the mempool, and is never lower than the minimum fee per byte of the
+    /// mempool rules.
+    pub fn estimate_fee(&self, target_blocks: u32, probability: f64) -> f64 {
+        let state = self.state.read();
+        let estimate = self
+            .fee_estimator
+            .read()
+            .estimate(target_blocks, probability, &state);
+        estimate.max(self.filter.read().rules.tx_fee_per_byte)
+    }
+
     /// Returns the rules for the mempool.
     pub fn get_rules(&self) -> MempoolRules {
         self.filter.read().rules.clone()
@@ -652,6 +823,12 @@ impl Mempool {
         self.state.read().get(hash).cloned()
     }
 
+    /// Returns why the transaction with the given hash was dropped from the mempool, if it was
+    /// dropped recently without being included in a block.
+    pub fn get_drop_reason(&self, hash: &Blake2bHash) -> Option<DropReason> {
+        self.state.read().dropped.get(hash)
+    }
+
     /// Gets all transaction hashes in the mempool.
     pub fn get_transaction_hashes(&self) -> Vec<Blake2bHash> {
         let state = self.state.read();
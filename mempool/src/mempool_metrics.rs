@@ -4,11 +4,12 @@ use prometheus_client::{
     registry::Registry,
 };
 
-use crate::mempool_state::EvictionReason;
+use crate::{mempool_state::EvictionReason, verify::VerifyErr};
 
 #[derive(Default, Clone)]
 pub struct MempoolMetrics {
     evicted_tx: Family<RemovedReasonLabel, Counter>,
+    rejected_tx: Family<RejectedReasonLabel, Counter>,
 }
 
 #[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
@@ -21,9 +22,30 @@ enum TxRemovedReason {
     Expired,
     AlreadyIncludedTx,
     Invalid,
+    Replaced,
     TooFull,
 }
 
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
+struct RejectedReasonLabel {
+    reason: TxRejectedReason,
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelValue)]
+enum TxRejectedReason {
+    InvalidTransaction,
+    AlreadyIncluded,
+    InvalidBlockNumber,
+    InvalidAccount,
+    Known,
+    Filtered,
+    NoConsensus,
+    ReplacementFeeTooLow,
+    ReplacementEvicted,
+    SenderLimitExceeded,
+    SenderSizeLimitExceeded,
+}
+
 impl MempoolMetrics {
     pub fn register(&self, registry: &mut Registry) {
         registry.register(
@@ -31,13 +53,19 @@ impl MempoolMetrics {
             "Number of transactions removed from mempool",
             self.evicted_tx.clone(),
         );
+        registry.register(
+            "rejected_tx_count",
+            "Number of transactions rejected by mempool",
+            self.rejected_tx.clone(),
+        );
     }
 
     pub(crate) fn note_evicted(&self, reason: EvictionReason) {
         let reason = match reason {
             EvictionReason::Expired => TxRemovedReason::Expired,
             EvictionReason::AlreadyIncluded => TxRemovedReason::AlreadyIncludedTx,
-            EvictionReason::Invalid => TxRemovedReason::Invalid,
+            EvictionReason::Invalid(_) => TxRemovedReason::Invalid,
+            EvictionReason::Replaced => TxRemovedReason::Replaced,
             EvictionReason::TooFull => TxRemovedReason::TooFull,
             _ => return,
         };
@@ -45,4 +73,23 @@ impl MempoolMetrics {
             .get_or_create(&RemovedReasonLabel { reason })
             .inc();
     }
+
+    pub(crate) fn note_rejected(&self, error: &VerifyErr) {
+        let reason = match error {
+            VerifyErr::InvalidTransaction(_) => TxRejectedReason::InvalidTransaction,
+            VerifyErr::AlreadyIncluded => TxRejectedReason::AlreadyIncluded,
+            VerifyErr::InvalidBlockNumber => TxRejectedReason::InvalidBlockNumber,
+            VerifyErr::InvalidAccount(_) => TxRejectedReason::InvalidAccount,
+            VerifyErr::Known => TxRejectedReason::Known,
+            VerifyErr::Filtered => TxRejectedReason::Filtered,
+            VerifyErr::NoConsensus => TxRejectedReason::NoConsensus,
+            VerifyErr::ReplacementFeeTooLow => TxRejectedReason::ReplacementFeeTooLow,
+            VerifyErr::ReplacementEvicted => TxRejectedReason::ReplacementEvicted,
+            VerifyErr::SenderLimitExceeded => TxRejectedReason::SenderLimitExceeded,
+            VerifyErr::SenderSizeLimitExceeded => TxRejectedReason::SenderSizeLimitExceeded,
+        };
+        self.rejected_tx
+            .get_or_create(&RejectedReasonLabel { reason })
+            .inc();
+    }
 }
@@ -1,4 +1,4 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::{HashMap, HashSet, VecDeque};
 #[cfg(feature = "metrics")]
 use std::sync::Arc;
 
@@ -6,13 +6,15 @@ use nimiq_account::ReservedBalance;
 use nimiq_blockchain::Blockchain;
 use nimiq_hash::{Blake2bHash, Hash};
 use nimiq_keys::Address;
-use nimiq_primitives::account::AccountType;
+use nimiq_primitives::account::{AccountType, FailReason};
 use nimiq_transaction::Transaction;
 
 #[cfg(feature = "metrics")]
 use crate::mempool_metrics::MempoolMetrics;
 use crate::{
-    mempool_transactions::{MempoolTransactions, TxPriority},
+    config::MempoolConfig,
+    mempool::DropReason,
+    mempool_transactions::{MempoolTransactions, TxPriority, WorstTxOrder},
     verify::VerifyErr,
 };
 
@@ -26,16 +28,33 @@ pub(crate) struct MempoolState {
     // The pending balance per sender.
     pub(crate) state_by_sender: HashMap<Address, SenderPendingState>,
 
+    // The ratio by which the fee per byte of a replacement transaction must exceed the fee per
+    // byte of the transaction it replaces.
+    pub(crate) replacement_fee_bump_ratio: f64,
+
+    // Maximum number of transactions per sender.
+    pub(crate) sender_limit: usize,
+
+    // Maximum total size (in bytes) of the transactions per sender.
+    pub(crate) sender_size_limit: usize,
+
+    // The recently dropped transactions.
+    pub(crate) dropped: DroppedTransactions,
+
     #[cfg(feature = "metrics")]
     pub(crate) metrics: Arc<MempoolMetrics>,
 }
 
 impl MempoolState {
-    pub fn new(regular_txns_limit: usize, control_txns_limit: usize) -> Self {
+    pub fn new(config: &MempoolConfig) -> Self {
         MempoolState {
-            regular_transactions: MempoolTransactions::new(regular_txns_limit),
-            control_transactions: MempoolTransactions::new(control_txns_limit),
+            regular_transactions: MempoolTransactions::new(config.size_limit),
+            control_transactions: MempoolTransactions::new(config.control_size_limit),
             state_by_sender: HashMap::new(),
+            replacement_fee_bump_ratio: config.replacement_fee_bump_ratio,
+            sender_limit: config.sender_limit,
+            sender_size_limit: config.sender_size_limit,
+            dropped: DroppedTransactions::default(),
             #[cfg(feature = "metrics")]
             metrics: Default::default(),
         }
@@ -55,12 +74,17 @@ impl MempoolState {
         }
     }
 
+    /// Adds a transaction to the mempool.
+    ///
+    /// If the transaction replaces a pending transaction of the same sender (see
+    /// [`MempoolState::find_replaceable`]), the replaced transaction is removed and its hash is
+    /// returned.
     pub(crate) fn put(
         &mut self,
         blockchain: &Blockchain,
         tx: Transaction,
         priority: TxPriority,
-    ) -> Result<(), VerifyErr> {
+    ) -> Result<Option<Blake2bHash>, VerifyErr> {
         // Don't add the same transaction twice.
         let tx_hash = tx.hash();
         if self.contains(&tx_hash) {
@@ -72,10 +96,58 @@ impl MempoolState {
             .get_account_if_complete(&tx.sender)
             .ok_or(VerifyErr::NoConsensus)?;
 
+        // Check if this transaction replaces a pending transaction of the same sender.
+        let replaced_tx_hash = self.find_replaceable(&tx);
+        if let Some(replaced_tx_hash) = &replaced_tx_hash {
+            let replaced_tx = self.get(replaced_tx_hash).expect("Transaction must exist");
+            if tx.fee_per_byte() <= replaced_tx.fee_per_byte() * self.replacement_fee_bump_ratio {
+                return Err(VerifyErr::ReplacementFeeTooLow);
+            }
+
+            // Don't give up the replaced transaction for one that would be evicted right away.
+            if self.would_evict_replacement(&tx, priority, replaced_tx_hash) {
+                return Err(VerifyErr::ReplacementEvicted);
+            }
+        }
+        let replaced_tx = replaced_tx_hash
+            .as_ref()
+            .map(|tx_hash| self.get(tx_hash).expect("Transaction must exist"));
+
+        // Enforce the per sender limits. A replaced transaction doesn't count towards them.
+        let tx_size = tx.serialized_size();
+        if let Some(sender_state) = self.state_by_sender.get(&tx.sender) {
+            let (num_txns, total_size) = match replaced_tx {
+                Some(replaced_tx) => (
+                    sender_state.txns.len() - 1,
+                    sender_state.total_size - replaced_tx.serialized_size(),
+                ),
+                None => (sender_state.txns.len(), sender_state.total_size),
+            };
+            if num_txns >= self.sender_limit {
+                return Err(VerifyErr::SenderLimitExceeded);
+            }
+            if total_size + tx_size > self.sender_size_limit {
+                return Err(VerifyErr::SenderSizeLimitExceeded);
+            }
+        } else if tx_size > self.sender_size_limit {
+            return Err(VerifyErr::SenderSizeLimitExceeded);
+        }
+
+        if let (Some(replaced_tx_hash), Some(replaced_tx)) = (&replaced_tx_hash, replaced_tx) {
+            // Make sure the sender can afford the replacement before evicting the replaced transaction.
+            let sender_state = &self.state_by_sender[&tx.sender];
+            let mut reserved_balance = sender_state.reserved_balance.clone();
+            blockchain.release_balance(&sender_account, replaced_tx, &mut reserved_balance)?;
+            blockchain.reserve_balance(&sender_account, &tx, &mut reserved_balance)?;
+
+            self.remove(blockchain, replaced_tx_hash, EvictionReason::Replaced);
+        }
+
         if let Some(sender_state) = self.state_by_sender.get_mut(&tx.sender) {
             let reserved_balance = &mut sender_state.reserved_balance;
             blockchain.reserve_balance(&sender_account, &tx, reserved_balance)?;
             sender_state.txns.insert(tx.hash());
+            sender_state.total_size += tx_size;
         } else {
             let mut reserved_balance = ReservedBalance::new(tx.sender.clone());
             blockchain.reserve_balance(&sender_account, &tx, &mut reserved_balance)?;
@@ -83,6 +155,7 @@ impl MempoolState {
             let sender_state = SenderPendingState {
                 reserved_balance,
                 txns: HashSet::from([tx.hash()]),
+                total_size: tx_size,
             };
             self.state_by_sender.insert(tx.sender.clone(), sender_state);
         }
@@ -95,31 +168,165 @@ impl MempoolState {
             self.regular_transactions.insert(tx, priority);
         }
 
+        // The transaction might have been dropped before, e.g. if it was included in a reverted
+        // block.
+        self.dropped.remove(&tx_hash);
+
         // After inserting the new txn, check if we need to remove txns
         while self.regular_transactions.total_size > self.regular_transactions.total_size_limit {
-            let (tx_hash, _) = self.regular_transactions.worst_transactions.pop().unwrap();
+            let tx_hash = self.eviction_candidate(false);
             self.remove(blockchain, &tx_hash, EvictionReason::TooFull);
         }
 
         while self.control_transactions.total_size > self.control_transactions.total_size_limit {
-            let (tx_hash, _) = self.control_transactions.worst_transactions.pop().unwrap();
+            let tx_hash = self.eviction_candidate(true);
             self.remove(blockchain, &tx_hash, EvictionReason::TooFull);
         }
 
-        Ok(())
+        Ok(replaced_tx_hash)
+    }
+
+    /// Selects the transaction to evict from the regular or control transactions container once it
+    /// is full.
+    ///
+    /// To prevent a single sender from crowding out everybody else, the sender that occupies the
+    /// most space in the container is determined. If it exceeds its fair share of the container
+    /// (i.e. the size limit divided by the number of senders in it) with more than one
+    /// transaction, its worst transaction is evicted. Otherwise, the worst transaction of the whole
+    /// container is evicted.
+    fn eviction_candidate(&self, control: bool) -> Blake2bHash {
+        let transactions = if control {
+            &self.control_transactions
+        } else {
+            &self.regular_transactions
+        };
+
+        let fair_share = transactions.total_size_limit / transactions.sender_sizes.len().max(1);
+        let largest_sender = transactions
+            .sender_sizes
+            .peek()
+            .filter(|(_, size)| **size > fair_share)
+            .and_then(|(sender, _)| self.state_by_sender.get(sender));
+
+        if let Some(sender_state) = largest_sender {
+            let sender_txns: Vec<_> = Self::sender_txns(transactions, sender_state).collect();
+            if sender_txns.len() > 1 {
+                let (tx_hash, _) = sender_txns
+                    .into_iter()
+                    .max_by(|(_, a), (_, b)| a.cmp(b))
+                    .expect("Sender has transactions");
+                return tx_hash.clone();
+            }
+        }
+
+        let (tx_hash, _) = transactions
+            .worst_transactions
+            .peek()
+            .expect("A full container can't be empty");
+        tx_hash.clone()
+    }
+
+    /// Returns the transactions of a sender in the given container, along with their eviction
+    /// order.
+    fn sender_txns<'a>(
+        transactions: &'a MempoolTransactions,
+        sender_state: &'a SenderPendingState,
+    ) -> impl Iterator<Item = (&'a Blake2bHash, &'a WorstTxOrder)> {
+        sender_state.txns.iter().filter_map(|tx_hash| {
+            transactions
+                .worst_transactions
+                .get_priority(tx_hash)
+                .map(|order| (tx_hash, order))
+        })
+    }
+
+    /// Returns whether `tx` would be the first transaction to be evicted by
+    /// [`MempoolState::eviction_candidate`] if it replaced the pending transaction with hash
+    /// `replaced_tx_hash`.
+    fn would_evict_replacement(
+        &self,
+        tx: &Transaction,
+        priority: TxPriority,
+        replaced_tx_hash: &Blake2bHash,
+    ) -> bool {
+        let transactions = &self.regular_transactions;
+        let replaced_size = transactions
+            .get(replaced_tx_hash)
+            .expect("Transaction must exist")
+            .serialized_size();
+        let tx_size = tx.serialized_size();
+        if transactions.total_size - replaced_size + tx_size <= transactions.total_size_limit {
+            return false;
+        }
+
+        let order = transactions.worst_order(tx, priority);
+
+        // If the sender becomes the largest one in the container and exceeds its fair share with
+        // more than one transaction, its worst transaction is evicted. The replacement is larger
+        // than the replaced transaction here, so the sender remains the largest one if it already
+        // was.
+        let sender_size = transactions.sender_size(&tx.sender) - replaced_size + tx_size;
+        let fair_share = transactions.total_size_limit / transactions.sender_sizes.len().max(1);
+        let is_largest_sender = match transactions.sender_sizes.peek() {
+            Some((sender, size)) => sender == &tx.sender || *size <= sender_size,
+            None => true,
+        };
+        if sender_size > fair_share && is_largest_sender {
+            let sender_state = &self.state_by_sender[&tx.sender];
+            let worst_sender_order = Self::sender_txns(transactions, sender_state)
+                .filter(|(tx_hash, _)| *tx_hash != replaced_tx_hash)
+                .map(|(_, order)| order)
+                .max();
+            if let Some(worst_sender_order) = worst_sender_order {
+                return order > *worst_sender_order;
+            }
+        }
+
+        // Otherwise, the worst transaction of the whole container is evicted.
+        self.regular_transactions.is_worst(&order, replaced_tx_hash)
+    }
+
+    /// Returns the hash of the pending transaction that `tx` replaces, if any.
+    ///
+    /// A transaction replaces a pending transaction if both are sent by the same sender to the same
+    /// recipient with the same value and validity start height. Control transactions are never
+    /// replaced, since different staking operations can share all of these fields.
+    fn find_replaceable(&self, tx: &Transaction) -> Option<Blake2bHash> {
+        if tx.sender_type == AccountType::Staking || tx.recipient_type == AccountType::Staking {
+            return None;
+        }
+
+        let sender_state = self.state_by_sender.get(&tx.sender)?;
+        sender_state
+            .txns
+            .iter()
+            .find(|tx_hash| {
+                self.regular_transactions
+                    .get(tx_hash)
+                    .is_some_and(|pending_tx| {
+                        pending_tx.recipient == tx.recipient
+                            && pending_tx.value == tx.value
+                            && pending_tx.validity_start_height == tx.validity_start_height
+                    })
+            })
+            .cloned()
     }
 
     pub(crate) fn remove(
         &mut self,
         blockchain: &Blockchain,
         tx_hash: &Blake2bHash,
-        #[allow(unused_variables)] reason: EvictionReason,
+        reason: EvictionReason,
     ) -> Option<Transaction> {
         let tx = self
             .regular_transactions
             .delete(tx_hash)
             .or_else(|| self.control_transactions.delete(tx_hash))?;
 
+        if let Some(drop_reason) = reason.drop_reason() {
+            self.dropped.insert(tx_hash.clone(), drop_reason);
+        }
+
         let sender_state = match self.state_by_sender.get_mut(&tx.sender) {
             Some(state) => state,
             None => return Some(tx),
@@ -143,6 +350,7 @@ impl MempoolState {
         if !sender_state.txns.remove(tx_hash) {
             return Some(tx);
         }
+        sender_state.total_size -= tx.serialized_size();
 
         blockchain
             .release_balance(&sender_account, &tx, &mut sender_state.reserved_balance)
@@ -166,19 +374,71 @@ impl MempoolState {
     }
 }
 
-#[derive(Clone)]
+#[derive(Clone, Copy)]
 pub(crate) enum EvictionReason {
     BlockBuilding,
     Expired,
     AlreadyIncluded,
-    Invalid,
+    Invalid(FailReason),
+    Replaced,
     TooFull,
 }
 
+impl EvictionReason {
+    /// Returns why the transaction was dropped, `None` if it was removed to be included in a
+    /// block.
+    fn drop_reason(self) -> Option<DropReason> {
+        match self {
+            EvictionReason::BlockBuilding | EvictionReason::AlreadyIncluded => None,
+            EvictionReason::Expired => Some(DropReason::Expired),
+            EvictionReason::Invalid(reason) => Some(DropReason::Invalid(reason)),
+            EvictionReason::Replaced => Some(DropReason::Replaced),
+            EvictionReason::TooFull => Some(DropReason::TooFull),
+        }
+    }
+}
+
+/// The maximum number of dropped transactions whose drop reason is remembered.
+const MAX_DROPPED_TRANSACTIONS: usize = 10_000;
+
+/// Remembers why the most recently dropped transactions were removed from the mempool.
+#[derive(Default)]
+pub(crate) struct DroppedTransactions {
+    reasons: HashMap<Blake2bHash, DropReason>,
+    // The hashes in the order they were dropped, oldest first.
+    order: VecDeque<Blake2bHash>,
+}
+
+impl DroppedTransactions {
+    pub(crate) fn insert(&mut self, tx_hash: Blake2bHash, reason: DropReason) {
+        if self.reasons.insert(tx_hash.clone(), reason).is_none() {
+            self.order.push_back(tx_hash);
+        }
+        if self.order.len() > MAX_DROPPED_TRANSACTIONS {
+            if let Some(oldest) = self.order.pop_front() {
+                self.reasons.remove(&oldest);
+            }
+        }
+    }
+
+    pub(crate) fn remove(&mut self, tx_hash: &Blake2bHash) {
+        if self.reasons.remove(tx_hash).is_some() {
+            self.order.retain(|hash| hash != tx_hash);
+        }
+    }
+
+    pub(crate) fn get(&self, tx_hash: &Blake2bHash) -> Option<DropReason> {
+        self.reasons.get(tx_hash).copied()
+    }
+}
+
 pub(crate) struct SenderPendingState {
     // The balance reserved by transactions that are currently stored in the mempool for this sender.
     pub(crate) reserved_balance: ReservedBalance,
 
     // Transaction hashes for this sender.
     pub(crate) txns: HashSet<Blake2bHash>,
+
+    // Total size (in bytes) of the transactions of this sender.
+    pub(crate) total_size: usize,
 }
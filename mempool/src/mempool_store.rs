@@ -0,0 +1,89 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use nimiq_database::{
+    declare_table,
+    mdbx::MdbxDatabase,
+    traits::{Database, ReadCursor, ReadTransaction, WriteTransaction},
+};
+use nimiq_database_value_derive::DbSerializable;
+use nimiq_hash::Blake2bHash;
+use nimiq_serde::{Deserialize, Serialize};
+use nimiq_transaction::Transaction;
+use parking_lot::Mutex;
+
+use crate::mempool_transactions::TxPriority;
+
+/// A mempool transaction as it is persisted in the database.
+#[derive(Clone, Debug, Serialize, Deserialize, DbSerializable)]
+pub struct PersistedTransaction {
+    /// The transaction itself.
+    pub transaction: Transaction,
+    /// The priority with which the transaction was added to the mempool.
+    pub priority: TxPriority,
+}
+
+declare_table!(MempoolTable, "Mempool", Blake2bHash => PersistedTransaction);
+
+/// Database store used to persist the mempool transactions across restarts.
+#[derive(Debug)]
+pub struct MempoolStore {
+    /// Environment for the DB creation and transaction handling.
+    env: MdbxDatabase,
+
+    /// Whether the persisted transactions have been loaded already. Until then, the stored
+    /// transactions must not be overwritten by the (still empty) mempool.
+    loaded: AtomicBool,
+
+    /// Serializes the writes, such that an older snapshot never overwrites a newer one.
+    write_lock: Mutex<()>,
+}
+
+impl MempoolStore {
+    /// Creates a new mempool store in the given environment.
+    pub fn new(env: MdbxDatabase) -> Self {
+        env.create_regular_table(&MempoolTable);
+
+        Self {
+            env,
+            loaded: AtomicBool::new(false),
+            write_lock: Mutex::new(()),
+        }
+    }
+
+    /// Replaces the persisted transactions with the ones returned by `snapshot`.
+    /// The snapshot is taken only after the previous write finished, so concurrent calls persist
+    /// the most recent transactions last.
+    /// This does nothing if the previously persisted transactions haven't been loaded yet.
+    pub(crate) fn store<I>(&self, snapshot: impl FnOnce() -> I)
+    where
+        I: IntoIterator<Item = (Blake2bHash, PersistedTransaction)>,
+    {
+        if !self.loaded.load(Ordering::Acquire) {
+            return;
+        }
+
+        let _write_guard = self.write_lock.lock();
+        let transactions = snapshot();
+
+        let mut txn = self.env.write_transaction();
+        txn.clear_table(&MempoolTable);
+        for (tx_hash, persisted) in transactions {
+            txn.put_reserve(&MempoolTable, &tx_hash, &persisted);
+        }
+        txn.commit();
+    }
+
+    /// Returns the persisted transactions. Once they have been loaded, subsequent calls return no
+    /// transactions and `store` starts to overwrite them.
+    pub(crate) fn load(&self) -> Vec<PersistedTransaction> {
+        if self.loaded.swap(true, Ordering::AcqRel) {
+            return vec![];
+        }
+
+        let txn = self.env.read_transaction();
+        txn.cursor(&MempoolTable)
+            .into_iter_start()
+            .map(|(_, persisted)| persisted)
+            .collect()
+    }
+}
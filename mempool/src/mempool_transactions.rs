@@ -5,12 +5,13 @@ use std::{
 
 use keyed_priority_queue::KeyedPriorityQueue;
 use nimiq_hash::{Blake2bHash, Hash};
-use nimiq_serde::Serialize;
+use nimiq_keys::Address;
+use nimiq_serde::{Deserialize, Serialize};
 use nimiq_transaction::Transaction;
 
 /// TxPriority that is used when adding transactions into the mempool
 /// Higher Priority transactions are returned first from the mempool
-#[derive(Copy, Clone, Eq, PartialEq)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub enum TxPriority {
     /// Low Priority transactions
     Low = 1,
@@ -108,6 +109,10 @@ pub(crate) struct MempoolTransactions {
     // This ordering is used to evict expired transactions from the mempool.
     pub(crate) oldest_transactions: KeyedPriorityQueue<Blake2bHash, Reverse<u32>>,
 
+    // Total size (in bytes) of the transactions of each sender in this container (largest first).
+    // This ordering is used to evict the transactions of senders exceeding their fair share.
+    pub(crate) sender_sizes: KeyedPriorityQueue<Address, usize>,
+
     // Maximum allowed total size (in bytes) of all transactions in the mempool.
     pub(crate) total_size_limit: usize,
 
@@ -125,6 +130,7 @@ impl MempoolTransactions {
             best_transactions: KeyedPriorityQueue::new(),
             worst_transactions: KeyedPriorityQueue::new(),
             oldest_transactions: KeyedPriorityQueue::new(),
+            sender_sizes: KeyedPriorityQueue::new(),
             total_size_limit: size_limit,
             total_size: 0,
             tx_counter: 0,
@@ -169,6 +175,47 @@ impl MempoolTransactions {
         self.transactions.get(hash)
     }
 
+    // Returns the priority with which the transaction was inserted.
+    pub(crate) fn priority(&self, hash: &Blake2bHash) -> Option<TxPriority> {
+        self.best_transactions
+            .get_priority(hash)
+            .map(|order| order.priority)
+    }
+
+    // Returns the total size (in bytes) of the transactions of `sender` in this container.
+    pub(crate) fn sender_size(&self, sender: &Address) -> usize {
+        self.sender_sizes
+            .get_priority(sender)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    // Returns the eviction order of `tx` if it was inserted next with the given priority.
+    pub(crate) fn worst_order(&self, tx: &Transaction, priority: TxPriority) -> WorstTxOrder {
+        WorstTxOrder {
+            priority,
+            fee_per_byte: tx.fee_per_byte(),
+            insertion_order: self.tx_counter,
+        }
+    }
+
+    // Returns whether a transaction with eviction order `order` would be evicted before all
+    // transactions in the container, except the one with hash `excluded`.
+    pub(crate) fn is_worst(&self, order: &WorstTxOrder, excluded: &Blake2bHash) -> bool {
+        let worst_order = match self.worst_transactions.peek() {
+            // The excluded transaction is the worst one, so find the worst of the remaining ones.
+            Some((tx_hash, _)) if tx_hash == excluded => self
+                .transactions
+                .keys()
+                .filter(|tx_hash| *tx_hash != excluded)
+                .filter_map(|tx_hash| self.worst_transactions.get_priority(tx_hash))
+                .max(),
+            Some((_, worst_order)) => Some(worst_order),
+            None => None,
+        };
+        worst_order.map_or(true, |worst_order| order > worst_order)
+    }
+
     pub(crate) fn insert(&mut self, tx: Transaction, priority: TxPriority) -> bool {
         let tx_hash = tx.hash();
 
@@ -199,7 +246,11 @@ impl MempoolTransactions {
             .push(tx_hash.clone(), Reverse(tx.validity_start_height));
 
         // Update total tx size
-        self.total_size += tx.serialized_size();
+        let tx_size = tx.serialized_size();
+        self.total_size += tx_size;
+
+        let sender_size = self.sender_size(&tx.sender) + tx_size;
+        self.sender_sizes.push(tx.sender.clone(), sender_size);
 
         self.transactions.insert(tx_hash, tx);
 
@@ -213,7 +264,15 @@ impl MempoolTransactions {
         self.worst_transactions.remove(tx_hash);
         self.oldest_transactions.remove(tx_hash);
 
-        self.total_size -= tx.serialized_size();
+        let tx_size = tx.serialized_size();
+        self.total_size -= tx_size;
+
+        let sender_size = self.sender_size(&tx.sender) - tx_size;
+        if sender_size == 0 {
+            self.sender_sizes.remove(&tx.sender);
+        } else {
+            self.sender_sizes.push(tx.sender.clone(), sender_size);
+        }
 
         Some(tx)
     }
@@ -28,17 +28,51 @@ pub enum VerifyErr {
     Filtered,
     #[error("Can't verify transaction without consensus")]
     NoConsensus,
+    #[error("Transaction fee is too low to replace the pending transaction")]
+    ReplacementFeeTooLow,
+    #[error("Replacement transaction would be evicted from the full mempool")]
+    ReplacementEvicted,
+    #[error("Sender exceeds the maximum number of transactions in mempool")]
+    SenderLimitExceeded,
+    #[error("Sender exceeds the maximum size of transactions in mempool")]
+    SenderSizeLimitExceeded,
 }
 
 /// Verifies a transaction and adds it to the mempool.
+/// Returns the hash of the pending transaction that was replaced by it, if any.
 pub(crate) fn verify_tx(
+    transaction: Transaction,
+    blockchain: Arc<RwLock<Blockchain>>,
+    network_id: NetworkId,
+    mempool_state: &Arc<RwLock<MempoolState>>,
+    filter: Arc<RwLock<MempoolFilter>>,
+    priority: TxPriority,
+) -> Result<Option<Blake2bHash>, VerifyErr> {
+    let result = verify_and_put_tx(
+        transaction,
+        blockchain,
+        network_id,
+        mempool_state,
+        filter,
+        priority,
+    );
+
+    #[cfg(feature = "metrics")]
+    if let Err(error) = &result {
+        mempool_state.read().metrics.note_rejected(error);
+    }
+
+    result
+}
+
+fn verify_and_put_tx(
     mut transaction: Transaction,
     blockchain: Arc<RwLock<Blockchain>>,
     network_id: NetworkId,
     mempool_state: &Arc<RwLock<MempoolState>>,
     filter: Arc<RwLock<MempoolFilter>>,
     priority: TxPriority,
-) -> Result<(), VerifyErr> {
+) -> Result<Option<Blake2bHash>, VerifyErr> {
     // 1. Verify transaction signature (and other stuff)
     transaction.verify_mut(network_id)?;
 
@@ -79,7 +113,12 @@ pub(crate) fn verify_tx(
     }
 
     // 7. Add transaction to the mempool. Balance checks are performed within put().
-    mempool_state.put(&blockchain, transaction, priority)?;
+    let replaced_tx_hash = mempool_state.put(&blockchain, transaction, priority)?;
+
+    // 8. Blacklist the replaced transaction so that it is not accepted again.
+    if let Some(replaced_tx_hash) = &replaced_tx_hash {
+        filter.write().blacklist(replaced_tx_hash.clone());
+    }
 
-    Ok(())
+    Ok(replaced_tx_hash)
 }
@@ -11,9 +11,16 @@ use nimiq_keys::{
     Address, Ed25519PublicKey as SchnorrPublicKey, KeyPair as SchnorrKeyPair,
     PrivateKey as SchnorrPrivateKey, SecureGenerate,
 };
-use nimiq_mempool::{config::MempoolConfig, mempool::Mempool, mempool_transactions::TxPriority};
+use nimiq_mempool::{
+    config::MempoolConfig,
+    filter::MempoolRules,
+    mempool::{DropReason, Mempool},
+    mempool_store::MempoolStore,
+    mempool_transactions::TxPriority,
+    verify::VerifyErr,
+};
 use nimiq_network_mock::{MockHub, MockId, MockNetwork, MockPeerId};
-use nimiq_primitives::{coin::Coin, networks::NetworkId, policy::Policy};
+use nimiq_primitives::{account::AccountType, coin::Coin, networks::NetworkId, policy::Policy};
 use nimiq_serde::{Deserialize, Serialize};
 use nimiq_test_log::test;
 use nimiq_test_utils::{
@@ -22,7 +29,7 @@ use nimiq_test_utils::{
     test_rng::test_rng,
     test_transaction::{generate_accounts, generate_transactions, TestTransaction},
 };
-use nimiq_transaction::{ExecutedTransaction, Transaction};
+use nimiq_transaction::{ExecutedTransaction, SignatureProof, Transaction};
 use nimiq_transaction_builder::TransactionBuilder;
 use nimiq_utils::time::OffsetTime;
 use nimiq_vrf::VrfSeed;
@@ -2205,3 +2212,569 @@ async fn it_can_reject_invalid_vesting_contract_transaction() {
         "Number of txns in the mempools is not what is expected"
     );
 }
+
+#[test(tokio::test)]
+async fn replaces_tx_with_higher_fee() {
+    let env = MdbxDatabase::new_volatile(Default::default()).unwrap();
+    let mut genesis_builder = GenesisBuilder::default();
+    genesis_builder.with_network(NetworkId::UnitAlbatross);
+
+    let mut rng = test_rng(false);
+    let recipient_accounts = generate_accounts(vec![0; 1], &mut genesis_builder, false, &mut rng);
+    let sender_accounts = generate_accounts(vec![10000; 1], &mut genesis_builder, true, &mut rng);
+
+    // Generate the same payment with increasing fees
+    let mempool_transactions = [0, 100, 105, 200]
+        .into_iter()
+        .map(|fee| TestTransaction {
+            fee,
+            value: 10,
+            recipient: recipient_accounts[0].clone(),
+            sender: sender_accounts[0].clone(),
+        })
+        .collect();
+    let (txns, _) = generate_transactions(mempool_transactions, true);
+
+    let mut rng = test_rng(true);
+    genesis_builder.with_genesis_validator(
+        Address::from(&SchnorrKeyPair::generate(&mut rng)),
+        SchnorrPublicKey::from([0u8; 32]),
+        BlsKeyPair::generate(&mut rng).public_key,
+        Address::default(),
+        None,
+        None,
+        false,
+    );
+
+    let genesis_info = genesis_builder.generate(env.clone()).unwrap();
+
+    // The genesis block number must match the specs we are setting in Policy
+    let genesis_block = genesis_info.block;
+    let genesis_block = match genesis_block {
+        Block::Macro(mut block) => {
+            block.header.block_number = Policy::genesis_block_number();
+            Block::Macro(block)
+        }
+        Block::Micro(_) => panic!(),
+    };
+
+    let blockchain = Arc::new(RwLock::new(
+        Blockchain::with_genesis(
+            env.clone(),
+            BlockchainConfig::default(),
+            Arc::new(OffsetTime::new()),
+            NetworkId::UnitAlbatross,
+            genesis_block,
+            genesis_info.accounts,
+        )
+        .unwrap(),
+    ));
+
+    let mempool = Mempool::new(blockchain, MempoolConfig::default());
+
+    // The first transaction doesn't replace anything
+    assert_eq!(mempool.add_transaction(txns[0].clone(), None), Ok(None));
+
+    // A transaction with a sufficiently higher fee replaces the pending one
+    assert_eq!(
+        mempool.add_transaction(txns[1].clone(), None),
+        Ok(Some(txns[0].hash()))
+    );
+    assert_eq!(mempool.num_transactions(), 1);
+    assert!(mempool.contains_transaction_by_hash(&txns[1].hash()));
+    assert_eq!(
+        mempool.get_drop_reason(&txns[0].hash()),
+        Some(DropReason::Replaced)
+    );
+    assert_eq!(mempool.get_drop_reason(&txns[1].hash()), None);
+
+    // The replaced transaction is blacklisted
+    assert!(mempool.is_filtered(&txns[0].hash()));
+    assert_eq!(
+        mempool.add_transaction(txns[0].clone(), None),
+        Err(VerifyErr::Filtered)
+    );
+
+    // The fee bump must exceed the configured ratio
+    assert_eq!(
+        mempool.add_transaction(txns[2].clone(), None),
+        Err(VerifyErr::ReplacementFeeTooLow)
+    );
+    assert_eq!(
+        mempool.add_transaction(txns[3].clone(), None),
+        Ok(Some(txns[1].hash()))
+    );
+    assert_eq!(mempool.get_transactions(), vec![txns[3].clone()]);
+}
+
+#[test(tokio::test)]
+async fn rejects_replacement_with_insufficient_fee_bump() {
+    let env = MdbxDatabase::new_volatile(Default::default()).unwrap();
+    let mut genesis_builder = GenesisBuilder::default();
+    genesis_builder.with_network(NetworkId::UnitAlbatross);
+
+    let mut rng = test_rng(false);
+    let recipient_accounts = generate_accounts(vec![0; 1], &mut genesis_builder, false, &mut rng);
+    let sender_accounts = generate_accounts(vec![10000; 1], &mut genesis_builder, true, &mut rng);
+
+    // Generate the same payment with fees below the required bump
+    let mempool_transactions = [100, 105, 109]
+        .into_iter()
+        .map(|fee| TestTransaction {
+            fee,
+            value: 10,
+            recipient: recipient_accounts[0].clone(),
+            sender: sender_accounts[0].clone(),
+        })
+        .collect();
+    let (txns, _) = generate_transactions(mempool_transactions, true);
+
+    let mut rng = test_rng(true);
+    genesis_builder.with_genesis_validator(
+        Address::from(&SchnorrKeyPair::generate(&mut rng)),
+        SchnorrPublicKey::from([0u8; 32]),
+        BlsKeyPair::generate(&mut rng).public_key,
+        Address::default(),
+        None,
+        None,
+        false,
+    );
+
+    let genesis_info = genesis_builder.generate(env.clone()).unwrap();
+
+    // The genesis block number must match the specs we are setting in Policy
+    let genesis_block = genesis_info.block;
+    let genesis_block = match genesis_block {
+        Block::Macro(mut block) => {
+            block.header.block_number = Policy::genesis_block_number();
+            Block::Macro(block)
+        }
+        Block::Micro(_) => panic!(),
+    };
+
+    let blockchain = Arc::new(RwLock::new(
+        Blockchain::with_genesis(
+            env.clone(),
+            BlockchainConfig::default(),
+            Arc::new(OffsetTime::new()),
+            NetworkId::UnitAlbatross,
+            genesis_block,
+            genesis_info.accounts,
+        )
+        .unwrap(),
+    ));
+
+    let mempool = Mempool::new(blockchain, MempoolConfig::default());
+    assert_eq!(mempool.add_transaction(txns[0].clone(), None), Ok(None));
+
+    // Fees below the bump ratio don't replace the pending transaction
+    for tx in &txns[1..] {
+        assert_eq!(
+            mempool.add_transaction(tx.clone(), None),
+            Err(VerifyErr::ReplacementFeeTooLow)
+        );
+        assert!(!mempool.is_filtered(&tx.hash()));
+    }
+
+    // The pending transaction is kept and isn't blacklisted
+    assert_eq!(mempool.get_transactions(), vec![txns[0].clone()]);
+    assert!(!mempool.is_filtered(&txns[0].hash()));
+}
+
+#[test(tokio::test)]
+async fn rejects_replacement_that_would_be_evicted() {
+    let env = MdbxDatabase::new_volatile(Default::default()).unwrap();
+    let mut genesis_builder = GenesisBuilder::default();
+    genesis_builder.with_network(NetworkId::UnitAlbatross);
+
+    let mut rng = test_rng(false);
+    let recipient_accounts = generate_accounts(vec![0; 2], &mut genesis_builder, false, &mut rng);
+    let sender_accounts = generate_accounts(vec![10000; 2], &mut genesis_builder, true, &mut rng);
+
+    // The first sender pays a high fee, the second one pays no fee at all
+    let mempool_transactions = [(0, 1000), (1, 0), (1, 100)]
+        .into_iter()
+        .map(|(i, fee)| TestTransaction {
+            fee,
+            value: 10,
+            recipient: recipient_accounts[i].clone(),
+            sender: sender_accounts[i].clone(),
+        })
+        .collect();
+    let (txns, _) = generate_transactions(mempool_transactions, true);
+
+    // A larger replacement of the second sender's transaction, which carries some data but pays
+    // only a small fee
+    let sender = &sender_accounts[1];
+    let mut large_tx = Transaction::new_extended(
+        sender.address.clone(),
+        AccountType::Basic,
+        vec![],
+        recipient_accounts[1].address.clone(),
+        AccountType::Basic,
+        vec![42; Policy::MAX_BASIC_TX_RECIPIENT_DATA_SIZE],
+        Coin::from_u64_unchecked(10),
+        Coin::from_u64_unchecked(10),
+        txns[1].validity_start_height,
+        NetworkId::UnitAlbatross,
+    );
+    large_tx.proof = SignatureProof::from_ed25519(
+        sender.keypair.public,
+        sender.keypair.sign(&large_tx.serialize_content()),
+    )
+    .serialize_to_vec();
+
+    let mut rng = test_rng(true);
+    genesis_builder.with_genesis_validator(
+        Address::from(&SchnorrKeyPair::generate(&mut rng)),
+        SchnorrPublicKey::from([0u8; 32]),
+        BlsKeyPair::generate(&mut rng).public_key,
+        Address::default(),
+        None,
+        None,
+        false,
+    );
+
+    let genesis_info = genesis_builder.generate(env.clone()).unwrap();
+
+    // The genesis block number must match the specs we are setting in Policy
+    let genesis_block = genesis_info.block;
+    let genesis_block = match genesis_block {
+        Block::Macro(mut block) => {
+            block.header.block_number = Policy::genesis_block_number();
+            Block::Macro(block)
+        }
+        Block::Micro(_) => panic!(),
+    };
+
+    let blockchain = Arc::new(RwLock::new(
+        Blockchain::with_genesis(
+            env.clone(),
+            BlockchainConfig::default(),
+            Arc::new(OffsetTime::new()),
+            NetworkId::UnitAlbatross,
+            genesis_block,
+            genesis_info.accounts,
+        )
+        .unwrap(),
+    ));
+
+    // The mempool is full with the first two transactions
+    let mempool_config = MempoolConfig {
+        size_limit: txns[0].serialized_size() + txns[1].serialized_size(),
+        ..Default::default()
+    };
+    let mempool = Mempool::new(blockchain, mempool_config);
+    assert_eq!(mempool.add_transaction(txns[0].clone(), None), Ok(None));
+    assert_eq!(mempool.add_transaction(txns[1].clone(), None), Ok(None));
+
+    // The larger replacement would overflow the mempool and be evicted right away, so the
+    // pending transaction is kept
+    assert!(large_tx.fee_per_byte() > txns[1].fee_per_byte() * 1.1);
+    assert_eq!(
+        mempool.add_transaction(large_tx.clone(), None),
+        Err(VerifyErr::ReplacementEvicted)
+    );
+    assert_eq!(mempool.num_transactions(), 2);
+    assert!(mempool.contains_transaction_by_hash(&txns[1].hash()));
+    assert!(!mempool.contains_transaction_by_hash(&large_tx.hash()));
+    assert!(!mempool.is_filtered(&txns[1].hash()));
+
+    // A replacement that fits in place of the pending transaction is accepted
+    assert_eq!(
+        mempool.add_transaction(txns[2].clone(), None),
+        Ok(Some(txns[1].hash()))
+    );
+    assert_eq!(mempool.num_transactions(), 2);
+    assert!(mempool.contains_transaction_by_hash(&txns[0].hash()));
+    assert!(mempool.contains_transaction_by_hash(&txns[2].hash()));
+}
+
+#[test(tokio::test)]
+async fn applies_per_sender_limits() {
+    let env = MdbxDatabase::new_volatile(Default::default()).unwrap();
+    let mut genesis_builder = GenesisBuilder::default();
+    genesis_builder.with_network(NetworkId::UnitAlbatross);
+
+    // Generate three transactions from the first sender and one from the second
+    let num_txns = 4;
+    let mut rng = test_rng(false);
+    let recipient_accounts =
+        generate_accounts(vec![0; num_txns], &mut genesis_builder, false, &mut rng);
+    let sender_accounts = generate_accounts(vec![10000; 2], &mut genesis_builder, true, &mut rng);
+
+    let mempool_transactions = (0..num_txns)
+        .map(|i| TestTransaction {
+            fee: 100,
+            value: 10,
+            recipient: recipient_accounts[i].clone(),
+            sender: sender_accounts[i / 3].clone(),
+        })
+        .collect();
+    let (txns, _) = generate_transactions(mempool_transactions, true);
+
+    let mut rng = test_rng(true);
+    genesis_builder.with_genesis_validator(
+        Address::from(&SchnorrKeyPair::generate(&mut rng)),
+        SchnorrPublicKey::from([0u8; 32]),
+        BlsKeyPair::generate(&mut rng).public_key,
+        Address::default(),
+        None,
+        None,
+        false,
+    );
+
+    let genesis_info = genesis_builder.generate(env.clone()).unwrap();
+
+    // The genesis block number must match the specs we are setting in Policy
+    let genesis_block = genesis_info.block;
+    let genesis_block = match genesis_block {
+        Block::Macro(mut block) => {
+            block.header.block_number = Policy::genesis_block_number();
+            Block::Macro(block)
+        }
+        Block::Micro(_) => panic!(),
+    };
+
+    let blockchain = Arc::new(RwLock::new(
+        Blockchain::with_genesis(
+            env.clone(),
+            BlockchainConfig::default(),
+            Arc::new(OffsetTime::new()),
+            NetworkId::UnitAlbatross,
+            genesis_block,
+            genesis_info.accounts,
+        )
+        .unwrap(),
+    ));
+
+    // Allow two transactions per sender
+    let mempool_config = MempoolConfig {
+        sender_limit: 2,
+        ..Default::default()
+    };
+    let mempool = Mempool::new(Arc::clone(&blockchain), mempool_config);
+
+    assert_eq!(mempool.add_transaction(txns[0].clone(), None), Ok(None));
+    assert_eq!(mempool.add_transaction(txns[1].clone(), None), Ok(None));
+    assert_eq!(
+        mempool.add_transaction(txns[2].clone(), None),
+        Err(VerifyErr::SenderLimitExceeded)
+    );
+    // Other senders are not affected
+    assert_eq!(mempool.add_transaction(txns[3].clone(), None), Ok(None));
+    assert_eq!(mempool.num_transactions(), 3);
+
+    // Allow only a single transaction worth of bytes per sender
+    let mempool_config = MempoolConfig {
+        sender_size_limit: txns[0].serialized_size(),
+        ..Default::default()
+    };
+    let mempool = Mempool::new(blockchain, mempool_config);
+
+    assert_eq!(mempool.add_transaction(txns[0].clone(), None), Ok(None));
+    assert_eq!(
+        mempool.add_transaction(txns[1].clone(), None),
+        Err(VerifyErr::SenderSizeLimitExceeded)
+    );
+    assert_eq!(mempool.num_transactions(), 1);
+}
+
+#[test(tokio::test)]
+async fn evicts_flooding_sender_first() {
+    let env = MdbxDatabase::new_volatile(Default::default()).unwrap();
+    let mut genesis_builder = GenesisBuilder::default();
+    genesis_builder.with_network(NetworkId::UnitAlbatross);
+
+    // The first sender floods the mempool with three well paying transactions, the second one
+    // sends a single transaction with a lower fee
+    let num_txns = 4;
+    let mut rng = test_rng(false);
+    let recipient_accounts =
+        generate_accounts(vec![0; num_txns], &mut genesis_builder, false, &mut rng);
+    let sender_accounts = generate_accounts(vec![10000; 2], &mut genesis_builder, true, &mut rng);
+
+    let mempool_transactions = (0..num_txns)
+        .map(|i| TestTransaction {
+            fee: if i < 3 { 500 } else { 100 },
+            value: 10,
+            recipient: recipient_accounts[i].clone(),
+            sender: sender_accounts[i / 3].clone(),
+        })
+        .collect();
+    let (txns, _) = generate_transactions(mempool_transactions, true);
+
+    let mut rng = test_rng(true);
+    genesis_builder.with_genesis_validator(
+        Address::from(&SchnorrKeyPair::generate(&mut rng)),
+        SchnorrPublicKey::from([0u8; 32]),
+        BlsKeyPair::generate(&mut rng).public_key,
+        Address::default(),
+        None,
+        None,
+        false,
+    );
+
+    let genesis_info = genesis_builder.generate(env.clone()).unwrap();
+
+    // The genesis block number must match the specs we are setting in Policy
+    let genesis_block = genesis_info.block;
+    let genesis_block = match genesis_block {
+        Block::Macro(mut block) => {
+            block.header.block_number = Policy::genesis_block_number();
+            Block::Macro(block)
+        }
+        Block::Micro(_) => panic!(),
+    };
+
+    let blockchain = Arc::new(RwLock::new(
+        Blockchain::with_genesis(
+            env.clone(),
+            BlockchainConfig::default(),
+            Arc::new(OffsetTime::new()),
+            NetworkId::UnitAlbatross,
+            genesis_block,
+            genesis_info.accounts,
+        )
+        .unwrap(),
+    ));
+
+    // The mempool only fits three transactions
+    let mempool_config = MempoolConfig {
+        size_limit: 3 * txns[0].serialized_size(),
+        ..Default::default()
+    };
+    let mempool = Mempool::new(blockchain, mempool_config);
+
+    for tx in &txns {
+        assert_eq!(mempool.add_transaction(tx.clone(), None), Ok(None));
+    }
+
+    // The flooding sender exceeds its fair share, so its newest transaction is evicted even
+    // though the other sender's transaction pays a lower fee
+    assert_eq!(mempool.num_transactions(), 3);
+    assert!(mempool.contains_transaction_by_hash(&txns[3].hash()));
+    assert!(!mempool.contains_transaction_by_hash(&txns[2].hash()));
+    assert_eq!(
+        mempool.get_drop_reason(&txns[2].hash()),
+        Some(DropReason::TooFull)
+    );
+}
+
+#[test(tokio::test)]
+async fn estimate_fee_respects_min_fee_per_byte() {
+    let time = Arc::new(OffsetTime::new());
+    let env = MdbxDatabase::new_volatile(Default::default()).unwrap();
+    let blockchain = Arc::new(RwLock::new(
+        Blockchain::new(
+            env,
+            BlockchainConfig::default(),
+            NetworkId::UnitAlbatross,
+            time,
+        )
+        .unwrap(),
+    ));
+
+    // Without any blocks or pending transactions, the estimate is the minimum fee per byte.
+    let mempool = Mempool::new(Arc::clone(&blockchain), MempoolConfig::default());
+    assert_eq!(mempool.estimate_fee(1, 0.95), 0.0);
+
+    let mempool_config = MempoolConfig {
+        filter_rules: MempoolRules {
+            tx_fee_per_byte: 2.0,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let mempool = Mempool::new(blockchain, mempool_config);
+    assert_eq!(mempool.estimate_fee(1, 0.95), 2.0);
+    assert_eq!(mempool.estimate_fee(10, 0.5), 2.0);
+}
+
+#[test(tokio::test)]
+async fn persists_and_restores_transactions() {
+    let env = MdbxDatabase::new_volatile(Default::default()).unwrap();
+    let mut genesis_builder = GenesisBuilder::default();
+    genesis_builder.with_network(NetworkId::UnitAlbatross);
+
+    let num_txns = 2;
+    let mut rng = test_rng(false);
+    let recipient_accounts =
+        generate_accounts(vec![0; num_txns], &mut genesis_builder, false, &mut rng);
+    let sender_accounts =
+        generate_accounts(vec![10000; num_txns], &mut genesis_builder, true, &mut rng);
+
+    let mempool_transactions = (0..num_txns)
+        .map(|i| TestTransaction {
+            fee: 100,
+            value: 10,
+            recipient: recipient_accounts[i].clone(),
+            sender: sender_accounts[i].clone(),
+        })
+        .collect();
+    let (txns, _) = generate_transactions(mempool_transactions, true);
+
+    let mut rng = test_rng(true);
+    genesis_builder.with_genesis_validator(
+        Address::from(&SchnorrKeyPair::generate(&mut rng)),
+        SchnorrPublicKey::from([0u8; 32]),
+        BlsKeyPair::generate(&mut rng).public_key,
+        Address::default(),
+        None,
+        None,
+        false,
+    );
+
+    let genesis_info = genesis_builder.generate(env.clone()).unwrap();
+
+    // The genesis block number must match the specs we are setting in Policy
+    let genesis_block = genesis_info.block;
+    let genesis_block = match genesis_block {
+        Block::Macro(mut block) => {
+            block.header.block_number = Policy::genesis_block_number();
+            Block::Macro(block)
+        }
+        Block::Micro(_) => panic!(),
+    };
+
+    let blockchain = Arc::new(RwLock::new(
+        Blockchain::with_genesis(
+            env.clone(),
+            BlockchainConfig::default(),
+            Arc::new(OffsetTime::new()),
+            NetworkId::UnitAlbatross,
+            genesis_block,
+            genesis_info.accounts,
+        )
+        .unwrap(),
+    ));
+
+    let mempool = Mempool::new_persistent(
+        Arc::clone(&blockchain),
+        MempoolConfig::default(),
+        MempoolStore::new(env.clone()),
+    );
+    assert_eq!(mempool.restore(), 0);
+    assert_eq!(mempool.add_transaction(txns[0].clone(), None), Ok(None));
+    assert_eq!(
+        mempool.add_transaction(txns[1].clone(), Some(TxPriority::High)),
+        Ok(None)
+    );
+    mempool.persist();
+    drop(mempool);
+
+    // A new mempool doesn't overwrite the persisted transactions before restoring them.
+    let mempool =
+        Mempool::new_persistent(blockchain, MempoolConfig::default(), MempoolStore::new(env));
+    mempool.persist();
+    assert_eq!(mempool.num_transactions(), 0);
+
+    assert_eq!(mempool.restore(), 2);
+    assert_eq!(mempool.num_transactions(), 2);
+    assert!(mempool.contains_transaction_by_hash(&txns[0].hash()));
+    assert!(mempool.contains_transaction_by_hash(&txns[1].hash()));
+
+    // The high priority transaction is still returned first.
+    let (block_txns, _) = mempool.get_transactions_for_block(10_000);
+    assert_eq!(block_txns, vec![txns[1].clone(), txns[0].clone()]);
+}
@@ -5,6 +5,7 @@ use nimiq_consensus::ConsensusProxy;
 use nimiq_mempool::mempool::Mempool;
 use nimiq_network_interface::network::Network;
 use nimiq_utils::spawn;
+use nimiq_validator_network::ValidatorNetworkStatus;
 use parking_lot::RwLock;
 use prometheus_client::{
     encoding::{EncodeGaugeValue, EncodeMetric, MetricEncoder},
@@ -20,6 +21,7 @@ use crate::tokio_runtime::TokioRuntimeMetrics;
 use crate::{
     chain::BlockMetrics, consensus::ConsensusMetrics, mempool::MempoolMetrics,
     network::NetworkMetrics, server::metrics_server, tokio_task::TokioTaskMetrics,
+    validator_network::ValidatorNetworkMetrics,
 };
 
 mod chain;
@@ -30,6 +32,7 @@ mod server;
 #[cfg(tokio_unstable)]
 mod tokio_runtime;
 mod tokio_task;
+mod validator_network;
 
 /// Monitor (metrics) for a Nimiq task
 #[derive(Clone)]
@@ -98,6 +101,7 @@ pub fn start_metrics_server<TNetwork: Network>(
     mempool: Option<Arc<Mempool>>,
     consensus_proxy: ConsensusProxy<TNetwork>,
     network: Arc<nimiq_network_libp2p::Network>,
+    validator_network: Option<Arc<dyn ValidatorNetworkStatus>>,
     task_monitors: &[NimiqTaskMonitor],
 ) {
     let mut registry = Registry::default();
@@ -111,6 +115,10 @@ pub fn start_metrics_server<TNetwork: Network>(
         MempoolMetrics::register(nimiq_registry, mempool);
     }
 
+    if let Some(validator_network) = validator_network {
+        ValidatorNetworkMetrics::register(nimiq_registry, validator_network);
+    }
+
     // Setup the task metrics
     let task_metrics = Arc::new(RwLock::new(TokioTaskMetrics::new()));
     task_metrics.write().register(
@@ -0,0 +1,105 @@
+use std::{
+    sync::{atomic::AtomicU64, Arc},
+    time::{Duration, SystemTime},
+};
+
+use futures::StreamExt as _;
+use nimiq_time::interval;
+use nimiq_validator_network::ValidatorNetworkStatus;
+use prometheus_client::{
+    encoding::EncodeLabelSet,
+    metrics::{family::Family, gauge::Gauge},
+    registry::Registry,
+};
+
+const VALIDATOR_NETWORK_METRICS_FREQ_SECS: u64 = 5;
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
+struct ValidatorLabels {
+    validator_id: u16,
+    address: String,
+}
+
+/// Connection health of the elected validators. The metrics only cover the validators of the
+/// current epoch and are refreshed periodically.
+#[derive(Default)]
+pub struct ValidatorNetworkMetrics {
+    connected: Family<ValidatorLabels, Gauge>,
+    rtt: Family<ValidatorLabels, Gauge<f64, AtomicU64>>,
+    record_age: Family<ValidatorLabels, Gauge>,
+    resolution_failures: Family<ValidatorLabels, Gauge>,
+}
+
+impl ValidatorNetworkMetrics {
+    pub fn register(registry: &mut Registry, network: Arc<dyn ValidatorNetworkStatus>) {
+        let sub_registry = registry.sub_registry_with_prefix("validator_network");
+        let metrics = ValidatorNetworkMetrics::default();
+
+        sub_registry.register(
+            "connected",
+            "Whether we are connected to the validator",
+            metrics.connected.clone(),
+        );
+        sub_registry.register(
+            "rtt_seconds",
+            "Round-trip time of the last ping to the validator",
+            metrics.rtt.clone(),
+        );
+        sub_registry.register(
+            "record_age_seconds",
+            "Age of the last resolved validator record",
+            metrics.record_age.clone(),
+        );
+        sub_registry.register(
+            "resolution_failures",
+            "Failed peer ID lookups of the validator during the current epoch",
+            metrics.resolution_failures.clone(),
+        );
+
+        nimiq_utils::spawn(metrics.update_metric_values(network));
+    }
+
+    async fn update_metric_values(self, network: Arc<dyn ValidatorNetworkStatus>) {
+        let mut interval = interval(Duration::from_secs(VALIDATOR_NETWORK_METRICS_FREQ_SECS));
+
+        loop {
+            interval.next().await;
+            self.update(network.as_ref());
+        }
+    }
+
+    fn update(&self, network: &dyn ValidatorNetworkStatus) {
+        let now = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as u64;
+
+        // Drop the validators of previous epochs.
+        self.connected.clear();
+        self.rtt.clear();
+        self.record_age.clear();
+        self.resolution_failures.clear();
+
+        for status in network.connection_statuses() {
+            let labels = ValidatorLabels {
+                validator_id: status.validator_id,
+                address: status.address.to_user_friendly_address(),
+            };
+
+            self.connected
+                .get_or_create(&labels)
+                .set(status.connected as i64);
+            self.resolution_failures
+                .get_or_create(&labels)
+                .set(status.resolution_failures as i64);
+            if let Some(rtt) = status.rtt {
+                self.rtt.get_or_create(&labels).set(rtt.as_secs_f64());
+            }
+            if let Some(timestamp) = status.record_timestamp {
+                self.record_age
+                    .get_or_create(&labels)
+                    .set((now.saturating_sub(timestamp) / 1000) as i64);
+            }
+        }
+    }
+}
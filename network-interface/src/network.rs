@@ -103,6 +103,10 @@ pub trait Network: Send + Sync + Unpin + 'static {
     /// If the peer isn't found, `None` is returned.
     fn get_peer_info(&self, peer_id: Self::PeerId) -> Option<PeerInfo>;
 
+    /// Gets the round-trip time to a connected peer as measured by the last ping.
+    /// If the peer isn't found or hasn't been pinged yet, `None` is returned.
+    fn get_peer_rtt(&self, peer_id: Self::PeerId) -> Option<Duration>;
+
     /// Gets the set of connected peers that provide the supplied services.
     /// If we currently don't have min number of connected peer that provides those services,
     /// we dial peers.
@@ -188,6 +192,16 @@ pub trait Network: Send + Sync + Unpin + 'static {
     /// Gets the local peer ID
     fn get_local_peer_id(&self) -> Self::PeerId;
 
+    /// Tells the network through which peers another peer can be reached if it doesn't accept
+    /// connections itself, e.g. the sentries of a validator running in private mode.
+    fn set_peer_relays(&self, _peer_id: Self::PeerId, _relays: Vec<Self::PeerId>) {}
+
+    /// Gets the peers through which we can be reached, i.e. our sentries if we run in private
+    /// mode. Empty if we accept connections ourselves.
+    fn get_local_relays(&self) -> Vec<Self::PeerId> {
+        vec![]
+    }
+
     /// Sends a message to a specific peer
     async fn message<M: Message>(
         &self,
@@ -112,6 +112,9 @@ pub enum InboundRequestError {
     /// The request exceeded the maximum defined rate limit for its request type.
     #[error("Request exceeds the maximum rate limit")]
     ExceedsRateLimit = 5,
+    /// A relaying peer could not forward the request or its response.
+    #[error("Request could not be relayed")]
+    RelayFailed = 6,
 }
 
 pub trait RequestKind {
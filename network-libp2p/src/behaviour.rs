@@ -59,13 +59,20 @@ impl Behaviour {
         #[cfg(feature = "kad")]
         let mut dht = kad::Behaviour::with_config(peer_id, store, config.kademlia);
         #[cfg(feature = "kad")]
-        if force_dht_server_mode {
+        if !config.allow_list.is_empty() {
+            // In private mode, the DHT must not spread our addresses.
+            dht.set_mode(Some(kad::Mode::Client));
+        } else if force_dht_server_mode {
             dht.set_mode(Some(kad::Mode::Server));
         }
 
         // Discovery behaviour
+        let discovery_config = discovery::Config {
+            private_mode: !config.allow_list.is_empty(),
+            ..config.discovery.clone()
+        };
         let discovery = discovery::Behaviour::new(
-            config.discovery.clone(),
+            discovery_config,
             config.keypair.clone(),
             Arc::clone(&contacts),
         );
@@ -92,6 +99,9 @@ impl Behaviour {
             peer_count_max: config.peer_count_max,
             peer_count_per_ip_max: config.peer_count_per_ip_max,
             peer_count_per_subnet_max: config.peer_count_per_subnet_max,
+            deny_list: config.deny_list,
+            ban_list_path: config.ban_list_path,
+            allow_list: config.allow_list,
             ..Default::default()
         };
 
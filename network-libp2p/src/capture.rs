@@ -0,0 +1,434 @@
+use std::{
+    collections::HashMap,
+    ffi::OsString,
+    fs::{self, File},
+    io::{self, BufReader, BufWriter, Read, Write},
+    path::{Path, PathBuf},
+    sync::mpsc::{self, Receiver, RecvTimeoutError, SyncSender, TrySendError},
+    thread,
+    time::Duration,
+};
+
+use instant::{Instant, SystemTime};
+use libp2p::{
+    request_response::{InboundRequestId, OutboundRequestId},
+    PeerId,
+};
+use nimiq_serde::{Deserialize, Serialize};
+
+/// A network event recorded by the traffic capture.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct CaptureRecord {
+    /// The time at which the event was observed, in milliseconds since the unix epoch.
+    pub timestamp: u64,
+    /// The remote peer. For published gossipsub messages, this is the local peer.
+    pub peer_id: PeerId,
+    pub event: CaptureEvent,
+}
+
+/// The kind of a recorded network event. Requests and their responses share an ID that is unique
+/// within the capture.
+///
+/// All data is recorded as it is sent over the wire, i.e. requests are prefixed with their
+/// request type and responses are serialized as `Result<Response, InboundRequestError>`.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum CaptureEvent {
+    GossipReceived {
+        topic: String,
+        data: Vec<u8>,
+    },
+    GossipPublished {
+        topic: String,
+        data: Vec<u8>,
+    },
+    RequestReceived {
+        id: u64,
+        data: Vec<u8>,
+    },
+    ResponseSent {
+        id: u64,
+        data: Vec<u8>,
+    },
+    RequestSent {
+        id: u64,
+        data: Vec<u8>,
+    },
+    /// `data` is `None` if the request failed or timed out.
+    ResponseReceived {
+        id: u64,
+        data: Option<Vec<u8>>,
+    },
+}
+
+/// The number of records that may wait for the writer before new records are dropped.
+const CHANNEL_CAPACITY: usize = 4096;
+
+/// The maximum time records stay buffered before they are flushed to the file.
+const FLUSH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The size at which the capture file is rotated.
+const MAX_FILE_SIZE: u64 = 256 * 1024 * 1024;
+
+/// Records the traffic of the swarm to a file.
+///
+/// The file is a sequence of records, each prefixed by its length as a big-endian `u32`.
+/// Records are written by a dedicated thread, such that the swarm never waits for the disk, and
+/// flushed at least every second. Once the file reaches its maximum size, it is moved to
+/// `<path>.1`, replacing the previous one, and a new file is started.
+pub(crate) struct TrafficCapture {
+    sender: SyncSender<CaptureRecord>,
+    local_peer_id: PeerId,
+    next_id: u64,
+    inbound: HashMap<InboundRequestId, (u64, PeerId)>,
+    outbound: HashMap<OutboundRequestId, (u64, PeerId)>,
+    /// The number of records dropped since the writer last kept up.
+    dropped: u64,
+}
+
+impl TrafficCapture {
+    /// Creates the capture file at `path`, replacing an existing one, and starts the writer.
+    pub(crate) fn create(path: PathBuf, local_peer_id: PeerId) -> io::Result<Self> {
+        let writer = CaptureWriter::create(path, MAX_FILE_SIZE)?;
+        let (sender, receiver) = mpsc::sync_channel(CHANNEL_CAPACITY);
+        thread::Builder::new()
+            .name("traffic-capture".to_owned())
+            .spawn(move || writer.run(receiver))?;
+
+        Ok(TrafficCapture {
+            sender,
+            local_peer_id,
+            next_id: 0,
+            inbound: HashMap::new(),
+            outbound: HashMap::new(),
+            dropped: 0,
+        })
+    }
+
+    pub(crate) fn gossip_received(&mut self, peer_id: PeerId, topic: &str, data: &[u8]) {
+        self.record(
+            peer_id,
+            CaptureEvent::GossipReceived {
+                topic: topic.to_owned(),
+                data: data.to_vec(),
+            },
+        );
+    }
+
+    pub(crate) fn gossip_published(&mut self, topic: &str, data: &[u8]) {
+        self.record(
+            self.local_peer_id,
+            CaptureEvent::GossipPublished {
+                topic: topic.to_owned(),
+                data: data.to_vec(),
+            },
+        );
+    }
+
+    pub(crate) fn request_received(
+        &mut self,
+        peer_id: PeerId,
+        request_id: InboundRequestId,
+        data: &[u8],
+    ) {
+        let id = self.next_id();
+        self.inbound.insert(request_id, (id, peer_id));
+        self.record(
+            peer_id,
+            CaptureEvent::RequestReceived {
+                id,
+                data: data.to_vec(),
+            },
+        );
+    }
+
+    /// Records our response to a request. Responses to requests that weren't recorded are
+    /// ignored.
+    pub(crate) fn response_sent(&mut self, request_id: InboundRequestId, data: &[u8]) {
+        if let Some((id, peer_id)) = self.inbound.remove(&request_id) {
+            self.record(
+                peer_id,
+                CaptureEvent::ResponseSent {
+                    id,
+                    data: data.to_vec(),
+                },
+            );
+        }
+    }
+
+    /// Forgets a request that will never be answered.
+    pub(crate) fn request_failed(&mut self, request_id: InboundRequestId) {
+        self.inbound.remove(&request_id);
+    }
+
+    pub(crate) fn request_sent(
+        &mut self,
+        peer_id: PeerId,
+        request_id: OutboundRequestId,
+        data: &[u8],
+    ) {
+        let id = self.next_id();
+        self.outbound.insert(request_id, (id, peer_id));
+        self.record(
+            peer_id,
+            CaptureEvent::RequestSent {
+                id,
+                data: data.to_vec(),
+            },
+        );
+    }
+
+    /// Records the response to one of our requests, `None` if the request failed.
+    pub(crate) fn response_received(&mut self, request_id: OutboundRequestId, data: Option<&[u8]>) {
+        if let Some((id, peer_id)) = self.outbound.remove(&request_id) {
+            self.record(
+                peer_id,
+                CaptureEvent::ResponseReceived {
+                    id,
+                    data: data.map(<[u8]>::to_vec),
+                },
+            );
+        }
+    }
+
+    fn next_id(&mut self) -> u64 {
+        let id = self.next_id;
+        self.next_id += 1;
+        id
+    }
+
+    fn record(&mut self, peer_id: PeerId, event: CaptureEvent) {
+        let record = CaptureRecord {
+            timestamp: SystemTime::now()
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .unwrap()
+                .as_millis() as u64,
+            peer_id,
+            event,
+        };
+
+        match self.sender.try_send(record) {
+            Ok(()) => {
+                if self.dropped > 0 {
+                    warn!(
+                        dropped = self.dropped,
+                        "Traffic capture dropped records because the writer fell behind"
+                    );
+                    self.dropped = 0;
+                }
+            }
+            Err(TrySendError::Full(_)) => self.dropped += 1,
+            // The writer already logged why it stopped.
+            Err(TrySendError::Disconnected(_)) => {}
+        }
+    }
+}
+
+/// Writes the records of a [`TrafficCapture`] to the capture file.
+struct CaptureWriter {
+    path: PathBuf,
+    writer: BufWriter<File>,
+    max_size: u64,
+    /// The number of bytes written to the current file.
+    size: u64,
+}
+
+impl CaptureWriter {
+    fn create(path: PathBuf, max_size: u64) -> io::Result<Self> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let writer = BufWriter::new(File::create(&path)?);
+        info!(path = %path.display(), "Capturing network traffic");
+
+        Ok(CaptureWriter {
+            path,
+            writer,
+            max_size,
+            size: 0,
+        })
+    }
+
+    /// Writes the received records until the capture is dropped.
+    fn run(mut self, receiver: Receiver<CaptureRecord>) {
+        if let Err(error) = self.write_records(receiver) {
+            error!(path = %self.path.display(), %error, "Failed to write traffic capture");
+        }
+    }
+
+    fn write_records(&mut self, receiver: Receiver<CaptureRecord>) -> io::Result<()> {
+        let mut last_flush = Instant::now();
+        loop {
+            match receiver.recv_timeout(FLUSH_INTERVAL) {
+                Ok(record) => self.write(&record)?,
+                Err(RecvTimeoutError::Timeout) => {}
+                Err(RecvTimeoutError::Disconnected) => return self.writer.flush(),
+            }
+
+            if last_flush.elapsed() >= FLUSH_INTERVAL {
+                self.writer.flush()?;
+                last_flush = Instant::now();
+            }
+        }
+    }
+
+    fn write(&mut self, record: &CaptureRecord) -> io::Result<()> {
+        if self.size >= self.max_size {
+            self.rotate()?;
+        }
+        self.size += write_record(&mut self.writer, record)?;
+        Ok(())
+    }
+
+    /// Moves the current file to `<path>.1` and starts a new one.
+    fn rotate(&mut self) -> io::Result<()> {
+        self.writer.flush()?;
+
+        let mut rotated_path = OsString::from(self.path.as_os_str());
+        rotated_path.push(".1");
+        fs::rename(&self.path, &rotated_path)?;
+
+        self.writer = BufWriter::new(File::create(&self.path)?);
+        self.size = 0;
+        debug!(path = %self.path.display(), "Rotated traffic capture");
+        Ok(())
+    }
+}
+
+/// Writes a record and returns the number of bytes written.
+fn write_record<W: Write>(writer: &mut W, record: &CaptureRecord) -> io::Result<u64> {
+    let data = record.serialize_to_vec();
+    let len = u32::try_from(data.len())
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "record too large"))?;
+    writer.write_all(&len.to_be_bytes())?;
+    writer.write_all(&data)?;
+    Ok(4 + data.len() as u64)
+}
+
+/// Reads the records of a traffic capture in the order they were recorded.
+pub struct CaptureReader<R> {
+    reader: R,
+}
+
+impl CaptureReader<BufReader<File>> {
+    /// Opens the capture file at `path`.
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Ok(Self::new(BufReader::new(File::open(path)?)))
+    }
+}
+
+impl<R: Read> CaptureReader<R> {
+    pub fn new(reader: R) -> Self {
+        CaptureReader { reader }
+    }
+
+    /// Returns the next record, or `None` at the end of the capture. A record that was
+    /// truncated by a crash of the node is treated as the end of the capture.
+    pub fn read_record(&mut self) -> io::Result<Option<CaptureRecord>> {
+        let mut len = [0u8; 4];
+        match self.reader.read_exact(&mut len) {
+            Err(error) if error.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+            result => result?,
+        }
+
+        let mut data = vec![0u8; u32::from_be_bytes(len) as usize];
+        match self.reader.read_exact(&mut data) {
+            Err(error) if error.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+            result => result?,
+        }
+
+        CaptureRecord::deserialize_from_vec(&data)
+            .map(Some)
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))
+    }
+}
+
+impl<R: Read> Iterator for CaptureReader<R> {
+    type Item = io::Result<CaptureRecord>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.read_record().transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::mpsc;
+
+    use libp2p::PeerId;
+
+    use super::{write_record, CaptureEvent, CaptureReader, CaptureRecord, CaptureWriter};
+
+    #[test]
+    fn records_round_trip() {
+        let records = vec![
+            CaptureRecord {
+                timestamp: 1,
+                peer_id: PeerId::random(),
+                event: CaptureEvent::GossipReceived {
+                    topic: "blocks".to_owned(),
+                    data: vec![1, 2, 3],
+                },
+            },
+            CaptureRecord {
+                timestamp: 2,
+                peer_id: PeerId::random(),
+                event: CaptureEvent::ResponseReceived { id: 7, data: None },
+            },
+        ];
+
+        let mut file = vec![];
+        for record in &records {
+            write_record(&mut file, record).unwrap();
+        }
+
+        let read: Vec<_> = CaptureReader::new(&file[..])
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(read, records);
+
+        // A truncated record ends the capture.
+        file.pop();
+        let read: Vec<_> = CaptureReader::new(&file[..])
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(read, records[..1]);
+    }
+
+    #[test]
+    fn writer_rotates_the_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("capture.dat");
+
+        let records: Vec<_> = (0..3)
+            .map(|timestamp| CaptureRecord {
+                timestamp,
+                peer_id: PeerId::random(),
+                event: CaptureEvent::GossipPublished {
+                    topic: "blocks".to_owned(),
+                    data: vec![0; 100],
+                },
+            })
+            .collect();
+
+        // Every record exceeds the maximum size, so each one after the first starts a new file.
+        let writer = CaptureWriter::create(path.clone(), 100).unwrap();
+        let (sender, receiver) = mpsc::sync_channel(records.len());
+        for record in &records {
+            sender.send(record.clone()).unwrap();
+        }
+        drop(sender);
+        writer.run(receiver);
+
+        let read: Vec<_> = CaptureReader::open(&path)
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(read, records[2..]);
+
+        let read: Vec<_> = CaptureReader::open(dir.path().join("capture.dat.1"))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(read, records[1..2]);
+    }
+}
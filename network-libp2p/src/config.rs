@@ -1,13 +1,13 @@
-use std::{num::NonZeroU8, time::Duration};
+use std::{num::NonZeroU8, path::PathBuf, time::Duration};
 
-use libp2p::{gossipsub, identity::Keypair, kad, Multiaddr, StreamProtocol};
+use libp2p::{gossipsub, identity::Keypair, kad, Multiaddr, PeerId, StreamProtocol};
 use nimiq_hash::Blake2bHash;
 use nimiq_network_interface::{network::MIN_SUPPORTED_MSG_SIZE, peer_info::Services};
 use sha2::{Digest, Sha256};
 
 use crate::{
     discovery::{self, peer_contacts::PeerContact},
-    DHT_PROTOCOL,
+    BanTarget, RateLimitOverrides, ReputationConfig, DHT_PROTOCOL,
 };
 
 /// TLS settings for configuring a secure WebSocket
@@ -42,6 +42,36 @@ pub struct Config {
     pub only_secure_ws_connections: bool,
     pub allow_loopback_addresses: bool,
     pub dht_quorum: NonZeroU8,
+
+    /// Dial and accept QUIC connections on `/udp/<port>/quic-v1` addresses in addition to
+    /// websocket connections. Requires the `quic` feature and is ignored if
+    /// `only_secure_ws_connections` is set.
+    pub quic: bool,
+
+    /// File to persist the known peer contacts to. If absent, they are only kept in memory.
+    pub peer_contacts_path: Option<PathBuf>,
+
+    /// File to persist the ban list to. If absent, bans are only kept in memory.
+    pub ban_list_path: Option<PathBuf>,
+    /// Peer IDs, addresses and subnets that are permanently banned.
+    pub deny_list: Vec<BanTarget>,
+    /// If not empty, the node runs in private mode: Only these peers are dialed or accepted and
+    /// discovery neither advertises our own contact nor accepts the contacts of others.
+    /// Entries are addresses ending in `/p2p/<peer ID>`, or just `/p2p/<peer ID>` for peers that
+    /// are accepted but not dialed.
+    pub allow_list: Vec<Multiaddr>,
+    /// Peers in private mode we are a sentry for. We relay their gossip, requests and DHT
+    /// records, and forward requests from other peers to them.
+    pub sentry_for: Vec<PeerId>,
+
+    /// Rate limits replacing the ones defined by the request and topic types.
+    pub rate_limits: RateLimitOverrides,
+    /// Penalties and thresholds of the peer reputation.
+    pub reputation: ReputationConfig,
+
+    /// File to record the gossipsub and request-response traffic to, for debugging. If absent,
+    /// no traffic is recorded.
+    pub capture_path: Option<PathBuf>,
 }
 
 impl Config {
@@ -60,6 +90,7 @@ impl Config {
         only_secure_ws_connections: bool,
         allow_loopback_addresses: bool,
         dht_quorum: NonZeroU8,
+        peer_contacts_path: Option<PathBuf>,
     ) -> Self {
         // Hardcoding the minimum number of peers in mesh network before adding more
         // TODO: Maybe change this to a mesh limits configuration argument of this function
@@ -111,6 +142,15 @@ impl Config {
             only_secure_ws_connections,
             allow_loopback_addresses,
             dht_quorum,
+            quic: false,
+            peer_contacts_path,
+            ban_list_path: None,
+            deny_list: vec![],
+            allow_list: vec![],
+            sentry_for: vec![],
+            rate_limits: RateLimitOverrides::default(),
+            reputation: ReputationConfig::default(),
+            capture_path: None,
         }
     }
 }
@@ -0,0 +1,730 @@
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    fmt,
+    future::Future,
+    io,
+    net::IpAddr,
+    path::PathBuf,
+    pin::Pin,
+    str::FromStr,
+    sync::Arc,
+    task::{Context, Poll, Waker},
+    time::Duration,
+};
+
+use futures::FutureExt;
+use instant::SystemTime;
+use ip_network::IpNetwork;
+use libp2p::{multiaddr::Protocol, Multiaddr, PeerId};
+use nimiq_time::{sleep_until, Instant, Sleep};
+use nimiq_utils::{
+    file_store::{self, FileStore},
+    WakerExt as _,
+};
+use parking_lot::Mutex;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use thiserror::Error;
+
+use crate::discovery::handler;
+
+/// Upper bound for the duration of a ban.
+const MAX_BAN_TIME: Duration = Duration::from_secs(60 * 60 * 24 * 365); // 1 year
+/// Maximum number of times the ban time is doubled for repeated offenders.
+const MAX_BAN_ESCALATIONS: u32 = 8;
+/// Offenses are forgotten once the offender didn't misbehave for this long.
+const OFFENSE_EXPIRY: Duration = Duration::from_secs(60 * 60 * 24 * 30); // 30 days
+
+/// Something that can be banned from connecting to us.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum BanTarget {
+    /// A peer ID.
+    Peer(PeerId),
+    /// An outer protocol address, for example `/ip4/x.x.x.x` or `/dns4/foo.bar`.
+    Address(Multiaddr),
+    /// An IP subnet, for example `192.168.0.0/16`.
+    Subnet(IpNetwork),
+}
+
+impl From<PeerId> for BanTarget {
+    fn from(peer_id: PeerId) -> Self {
+        BanTarget::Peer(peer_id)
+    }
+}
+
+impl From<IpAddr> for BanTarget {
+    fn from(ip: IpAddr) -> Self {
+        BanTarget::Address(Multiaddr::from(ip))
+    }
+}
+
+impl From<IpNetwork> for BanTarget {
+    fn from(subnet: IpNetwork) -> Self {
+        BanTarget::Subnet(subnet)
+    }
+}
+
+#[derive(Debug, Error)]
+#[error("Not a peer ID, IP address, subnet or multiaddress: {0}")]
+pub struct ParseBanTargetError(String);
+
+impl FromStr for BanTarget {
+    type Err = ParseBanTargetError;
+
+    /// Parses a peer ID, an IP address, a subnet in CIDR notation or an outer protocol
+    /// multiaddress like `/dns4/foo.bar`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Ok(peer_id) = PeerId::from_str(s) {
+            return Ok(BanTarget::Peer(peer_id));
+        }
+        if let Ok(ip) = IpAddr::from_str(s) {
+            return Ok(BanTarget::from(ip));
+        }
+        if let Ok(subnet) = IpNetwork::from_str(s) {
+            return Ok(BanTarget::Subnet(subnet));
+        }
+        if let Some(address) = Multiaddr::from_str(s)
+            .ok()
+            .and_then(|address| handler::outer_protocol_address(&address))
+        {
+            return Ok(BanTarget::Address(address));
+        }
+        Err(ParseBanTargetError(s.to_string()))
+    }
+}
+
+impl fmt::Display for BanTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BanTarget::Peer(peer_id) => write!(f, "{peer_id}"),
+            BanTarget::Address(address) => match ip_address(address) {
+                Some(ip) if address.iter().count() == 1 => write!(f, "{ip}"),
+                _ => write!(f, "{address}"),
+            },
+            BanTarget::Subnet(subnet) => write!(f, "{subnet}"),
+        }
+    }
+}
+
+impl Serialize for BanTarget {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for BanTarget {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// The reason for a ban.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BanReason {
+    /// The peer was disconnected for being malicious.
+    MaliciousPeer,
+    /// The ban was requested by the node operator.
+    Manual,
+    /// The target is in the deny list of the configuration. These bans are permanent.
+    DenyList,
+}
+
+/// An active ban.
+#[derive(Clone, Debug)]
+pub struct Ban {
+    /// The banned peer ID, address or subnet.
+    pub target: BanTarget,
+    /// The reason for the ban.
+    pub reason: BanReason,
+    /// The time left until the ban is lifted. `None` for permanent bans.
+    pub expires_in: Option<Duration>,
+}
+
+/// A ban as it is persisted to disk.
+#[derive(Serialize, Deserialize)]
+struct PersistedBan {
+    target: BanTarget,
+    reason: BanReason,
+    /// Timestamp when the ban is lifted in *seconds* since unix epoch.
+    expires_at: u64,
+}
+
+/// The offenses of a target that got banned for being malicious.
+#[derive(Clone, Serialize, Deserialize)]
+struct Offenses {
+    /// Number of times the target got banned for being malicious.
+    count: u32,
+    /// Timestamp of the last offense in *seconds* since unix epoch.
+    last_offense: u64,
+}
+
+/// The ban list as it is persisted to disk.
+#[derive(Serialize, Deserialize)]
+struct PersistedBanList {
+    bans: Vec<PersistedBan>,
+    offenses: Vec<(BanTarget, Offenses)>,
+}
+
+struct BanEntry {
+    reason: BanReason,
+    /// The deadline after which the ban is lifted. `None` for permanent bans.
+    deadline: Option<Instant>,
+}
+
+/// The ban list at some point in time, ready to be written to disk.
+struct BanListSnapshot {
+    path: PathBuf,
+    /// Sequence number of the snapshot. Snapshots are only written if they are more recent than
+    /// the one written last.
+    generation: u64,
+    /// The generation of the snapshot written last.
+    last_written: Arc<Mutex<u64>>,
+    persisted: PersistedBanList,
+}
+
+impl BanListSnapshot {
+    fn store(self) {
+        let mut last_written = self.last_written.lock();
+        if *last_written >= self.generation {
+            return;
+        }
+
+        if let Err(error) = FileStore::new(&self.path).store(&self.persisted) {
+            warn!(%error, path = %self.path.display(), "Failed to persist ban list");
+            return;
+        }
+        *last_written = self.generation;
+    }
+}
+
+fn unix_time() -> Duration {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap()
+}
+
+/// Returns the IP address of an address starting with an IP protocol.
+fn ip_address(address: &Multiaddr) -> Option<IpAddr> {
+    match address.iter().next()? {
+        Protocol::Ip4(ip) => Some(IpAddr::V4(ip)),
+        Protocol::Ip6(ip) => Some(IpAddr::V6(ip)),
+        _ => None,
+    }
+}
+
+/// The list of banned peer IDs, addresses and subnets.
+///
+/// Bans of malicious peers get longer with every repeated offense. Together with the offenses,
+/// temporary bans can be persisted to disk so they survive restarts. Bans from the static deny
+/// list are permanent.
+pub(crate) struct BanList {
+    /// Active bans.
+    bans: HashMap<BanTarget, BanEntry>,
+    /// Deadlines of the temporary bans in ascending order.
+    deadlines: BTreeSet<(Instant, BanTarget)>,
+    /// Deadline for the first target that can be unbanned.
+    unban_timeout: Option<Pin<Box<Sleep>>>,
+    /// Number of banned subnets per IP version and netmask. Used to only look up the netmasks
+    /// in use when checking whether an IP address is in a banned subnet.
+    subnet_netmasks: BTreeMap<(bool, u8), usize>,
+    /// Offenses per target that got banned for being malicious.
+    offenses: HashMap<BanTarget, Offenses>,
+    /// The time a target is banned for at its first offense.
+    ban_time: Duration,
+    /// File the ban list is persisted to.
+    persistence_path: Option<PathBuf>,
+    /// Whether the bans or offenses changed since they were last persisted.
+    dirty: bool,
+    /// Number of snapshots taken for persisting the ban list.
+    generation: u64,
+    /// The generation of the snapshot written last, shared with the writing threads.
+    last_written: Arc<Mutex<u64>>,
+    /// Waker used for the next poll
+    waker: Option<Waker>,
+}
+
+impl BanList {
+    pub fn new(ban_time: Duration, deny_list: Vec<BanTarget>) -> Self {
+        let mut ban_list = Self {
+            bans: HashMap::new(),
+            deadlines: BTreeSet::new(),
+            unban_timeout: None,
+            subnet_netmasks: BTreeMap::new(),
+            offenses: HashMap::new(),
+            ban_time,
+            persistence_path: None,
+            dirty: false,
+            generation: 0,
+            last_written: Arc::new(Mutex::new(0)),
+            waker: None,
+        };
+
+        for target in deny_list {
+            ban_list.add_subnet(&target);
+            ban_list.bans.insert(
+                target,
+                BanEntry {
+                    reason: BanReason::DenyList,
+                    deadline: None,
+                },
+            );
+        }
+
+        ban_list
+    }
+
+    /// Loads the bans and offenses persisted in the given file and persists them to it from now on.
+    /// Bans that expired in the meantime are dropped.
+    pub fn load_persisted(&mut self, path: PathBuf) {
+        match FileStore::new(&path).load::<PersistedBanList>() {
+            Ok(persisted) => {
+                let unix_time = unix_time();
+                let now = Instant::now();
+
+                for ban in persisted.bans {
+                    if let Some(remaining) =
+                        Duration::from_secs(ban.expires_at).checked_sub(unix_time)
+                    {
+                        self.insert(ban.target, ban.reason, now + remaining);
+                    }
+                }
+                self.offenses.extend(persisted.offenses);
+                // The loaded state matches the persisted one.
+                self.dirty = false;
+
+                debug!(
+                    num_bans = self.deadlines.len(),
+                    path = %path.display(),
+                    "Loaded persisted ban list"
+                );
+            }
+            Err(file_store::Error::Io(error)) if error.kind() == io::ErrorKind::NotFound => {}
+            Err(error) => {
+                warn!(%error, path = %path.display(), "Failed to load persisted ban list")
+            }
+        }
+
+        self.persistence_path = Some(path);
+    }
+
+    /// Persists the temporary bans and the offenses if they changed since they were last
+    /// persisted and a file to persist them to has been set.
+    pub fn persist(&mut self) {
+        if let Some(snapshot) = self.snapshot() {
+            snapshot.store();
+        }
+    }
+
+    /// Like [`BanList::persist`], but writes the file on a blocking thread.
+    pub fn persist_in_background(&mut self) {
+        let Some(snapshot) = self.snapshot() else {
+            return;
+        };
+
+        #[cfg(not(target_family = "wasm"))]
+        tokio::task::spawn_blocking(move || snapshot.store());
+
+        #[cfg(target_family = "wasm")]
+        snapshot.store();
+    }
+
+    /// Takes a snapshot of the temporary bans and the offenses for persisting them, if they
+    /// changed since the last snapshot and a file to persist them to has been set.
+    fn snapshot(&mut self) -> Option<BanListSnapshot> {
+        if !self.dirty {
+            return None;
+        }
+        let path = self.persistence_path.clone()?;
+
+        let unix_time = unix_time();
+        let now = Instant::now();
+        let persisted = PersistedBanList {
+            bans: self
+                .deadlines
+                .iter()
+                .map(|(deadline, target)| PersistedBan {
+                    target: target.clone(),
+                    reason: self.bans[target].reason,
+                    expires_at: (unix_time + deadline.saturating_duration_since(now)).as_secs(),
+                })
+                .collect(),
+            offenses: self
+                .offenses
+                .iter()
+                .map(|(target, offenses)| (target.clone(), offenses.clone()))
+                .collect(),
+        };
+
+        self.dirty = false;
+        self.generation += 1;
+        Some(BanListSnapshot {
+            path,
+            generation: self.generation,
+            last_written: Arc::clone(&self.last_written),
+            persisted,
+        })
+    }
+
+    /// Bans a target for the given duration.
+    /// Permanent bans are not replaced.
+    pub fn ban(&mut self, target: BanTarget, reason: BanReason, duration: Duration) {
+        self.insert(target, reason, Instant::now() + duration.min(MAX_BAN_TIME));
+    }
+
+    /// Bans a target for being malicious. The ban time doubles with every repeated offense.
+    /// Returns the duration of the ban.
+    pub fn ban_offender(&mut self, target: BanTarget) -> Duration {
+        let offenses = self.offenses.entry(target.clone()).or_insert(Offenses {
+            count: 0,
+            last_offense: 0,
+        });
+        let duration = self
+            .ban_time
+            .saturating_mul(1 << offenses.count.min(MAX_BAN_ESCALATIONS));
+        offenses.count = offenses.count.saturating_add(1);
+        offenses.last_offense = unix_time().as_secs();
+        self.dirty = true;
+
+        self.ban(target, BanReason::MaliciousPeer, duration);
+        duration
+    }
+
+    fn insert(&mut self, target: BanTarget, reason: BanReason, deadline: Instant) {
+        if let Some(entry) = self.bans.get(&target) {
+            let Some(previous_deadline) = entry.deadline else {
+                return;
+            };
+            self.deadlines.remove(&(previous_deadline, target.clone()));
+        } else {
+            self.add_subnet(&target);
+        }
+
+        self.deadlines.insert((deadline, target.clone()));
+        self.dirty = true;
+        self.bans.insert(
+            target,
+            BanEntry {
+                reason,
+                deadline: Some(deadline),
+            },
+        );
+        self.schedule_unban_timeout();
+    }
+
+    /// Lifts a temporary ban. Returns whether the target was banned.
+    /// Bans from the deny list can't be lifted.
+    pub fn unban(&mut self, target: &BanTarget) -> bool {
+        let Some(deadline) = self.bans.get(target).and_then(|entry| entry.deadline) else {
+            return false;
+        };
+
+        self.remove(target);
+        self.deadlines.remove(&(deadline, target.clone()));
+        self.schedule_unban_timeout();
+        true
+    }
+
+    /// Removes the ban of a target, without touching the deadlines.
+    fn remove(&mut self, target: &BanTarget) {
+        if self.bans.remove(target).is_some() {
+            self.remove_subnet(target);
+            self.dirty = true;
+        }
+    }
+
+    /// Counts the netmask of a target if it is a subnet.
+    fn add_subnet(&mut self, target: &BanTarget) {
+        if let BanTarget::Subnet(subnet) = target {
+            *self
+                .subnet_netmasks
+                .entry((subnet.is_ipv4(), subnet.netmask()))
+                .or_default() += 1;
+        }
+    }
+
+    /// Stops counting the netmask of a target if it is a subnet.
+    fn remove_subnet(&mut self, target: &BanTarget) {
+        if let BanTarget::Subnet(subnet) = target {
+            let key = (subnet.is_ipv4(), subnet.netmask());
+            if let Some(count) = self.subnet_netmasks.get_mut(&key) {
+                *count -= 1;
+                if *count == 0 {
+                    self.subnet_netmasks.remove(&key);
+                }
+            }
+        }
+    }
+
+    /// Schedules the unban timeout for the first target in `deadlines`.
+    fn schedule_unban_timeout(&mut self) {
+        self.unban_timeout = self
+            .deadlines
+            .first()
+            .map(|(deadline, _)| Box::pin(sleep_until(*deadline)));
+        self.waker.wake();
+    }
+
+    /// Returns whether a peer ID is banned.
+    pub fn is_peer_banned(&self, peer_id: &PeerId) -> bool {
+        self.bans.contains_key(&BanTarget::Peer(*peer_id))
+    }
+
+    /// Returns whether an address is banned, either by its outer protocol address or by the
+    /// subnet its IP address is in.
+    pub fn is_address_banned(&self, address: &Multiaddr) -> bool {
+        if let Some(outer_protocol_address) = handler::outer_protocol_address(address) {
+            if self
+                .bans
+                .contains_key(&BanTarget::Address(outer_protocol_address))
+            {
+                return true;
+            }
+        }
+
+        let Some(ip) = ip_address(address) else {
+            return false;
+        };
+        // Only look up the subnets of the IP address with a netmask that is in use.
+        self.subnet_netmasks
+            .keys()
+            .filter(|(is_ipv4, _)| *is_ipv4 == ip.is_ipv4())
+            .filter_map(|(_, netmask)| IpNetwork::new_truncate(ip, *netmask).ok())
+            .any(|subnet| self.bans.contains_key(&BanTarget::Subnet(subnet)))
+    }
+
+    /// Returns all active bans.
+    pub fn bans(&self) -> Vec<Ban> {
+        let now = Instant::now();
+        self.bans
+            .iter()
+            .map(|(target, entry)| Ban {
+                target: target.clone(),
+                reason: entry.reason,
+                expires_in: entry
+                    .deadline
+                    .map(|deadline| deadline.saturating_duration_since(now)),
+            })
+            .collect()
+    }
+
+    /// Forgets the offenses of targets that didn't misbehave in a while and persists the ban list
+    /// if it changed.
+    pub fn housekeeping(&mut self) {
+        let unix_time = unix_time().as_secs();
+        let num_offenses = self.offenses.len();
+        self.offenses.retain(|_, offenses| {
+            unix_time.saturating_sub(offenses.last_offense) < OFFENSE_EXPIRY.as_secs()
+        });
+        self.dirty |= self.offenses.len() != num_offenses;
+
+        self.persist_in_background();
+    }
+}
+
+impl Future for BanList {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        while let Some(sleep) = self.unban_timeout.as_mut() {
+            if sleep.poll_unpin(cx).is_pending() {
+                break;
+            }
+
+            // Unban all targets whose deadline has been reached and schedule a sleep until
+            // the next target can be unbanned.
+            let now = Instant::now();
+            while let Some((deadline, target)) = self.deadlines.first() {
+                if *deadline > now {
+                    break;
+                }
+                let target = target.clone();
+                self.remove(&target);
+                self.deadlines.pop_first();
+            }
+            self.schedule_unban_timeout();
+        }
+
+        if self.unban_timeout.is_none() {
+            self.waker.store_waker(cx);
+        }
+
+        Poll::Pending
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{str::FromStr, task::Context};
+
+    use futures::{task::noop_waker, FutureExt};
+    use instant::Duration;
+    use libp2p::{Multiaddr, PeerId};
+    use nimiq_test_log::test;
+    use nimiq_time::sleep;
+
+    use super::{BanList, BanReason, BanTarget};
+
+    #[test(tokio::test)]
+    async fn unban_peers_after_timeout() {
+        let mut bans = BanList::new(Duration::from_secs(2), vec![]);
+
+        let waker = noop_waker();
+        let cx = &mut Context::from_waker(&waker);
+
+        let p1 = PeerId::random();
+        let p2 = PeerId::random();
+        let p3 = PeerId::random();
+
+        bans.ban_offender(p1.into());
+
+        sleep(Duration::from_secs(1)).await;
+
+        bans.ban_offender(p2.into());
+        bans.ban_offender(p3.into());
+
+        sleep(Duration::from_secs(1)).await;
+
+        // Mimic a wake
+        let _ = bans.poll_unpin(cx);
+
+        // p1 should be unbanned
+        assert!(!bans.is_peer_banned(&p1));
+        // Other banned peers should still be banned
+        assert_eq!(bans.bans().len(), 2);
+
+        sleep(Duration::from_secs(1)).await;
+
+        // Mimic a wake
+        let _ = bans.poll_unpin(cx);
+
+        // p2 and p3 should both be unbanned
+        assert!(bans.bans().is_empty());
+    }
+
+    #[test]
+    fn ban_time_escalates_for_repeated_offenses() {
+        let mut bans = BanList::new(Duration::from_secs(60), vec![]);
+        let peer_id = PeerId::random();
+
+        assert_eq!(bans.ban_offender(peer_id.into()), Duration::from_secs(60));
+        assert_eq!(bans.ban_offender(peer_id.into()), Duration::from_secs(120));
+        assert_eq!(bans.ban_offender(peer_id.into()), Duration::from_secs(240));
+
+        // Manual bans don't count as offenses.
+        bans.ban(peer_id.into(), BanReason::Manual, Duration::from_secs(1));
+        assert_eq!(bans.ban_offender(peer_id.into()), Duration::from_secs(480));
+    }
+
+    #[test]
+    fn ban_by_address_and_subnet() {
+        let deny_list = vec![BanTarget::from_str("10.0.0.0/8").unwrap()];
+        let mut bans = BanList::new(Duration::from_secs(60), deny_list);
+
+        let address = Multiaddr::from_str("/ip4/1.2.3.4/tcp/8443/ws").unwrap();
+        assert!(!bans.is_address_banned(&address));
+        bans.ban(
+            BanTarget::from_str("1.2.3.4").unwrap(),
+            BanReason::Manual,
+            Duration::from_secs(60),
+        );
+        assert!(bans.is_address_banned(&address));
+
+        let address = Multiaddr::from_str("/ip4/10.1.2.3/tcp/8443/ws").unwrap();
+        assert!(bans.is_address_banned(&address));
+
+        // Bans from the deny list are permanent.
+        let subnet = BanTarget::from_str("10.0.0.0/8").unwrap();
+        assert!(!bans.unban(&subnet));
+        bans.ban(subnet.clone(), BanReason::Manual, Duration::from_secs(1));
+        let ban = bans
+            .bans()
+            .into_iter()
+            .find(|ban| ban.target == subnet)
+            .unwrap();
+        assert_eq!(ban.reason, BanReason::DenyList);
+        assert!(ban.expires_in.is_none());
+    }
+
+    #[test]
+    fn persist_bans() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("ban_list.dat");
+        let peer_id = PeerId::random();
+        let subnet = BanTarget::from_str("192.168.0.0/16").unwrap();
+
+        let mut bans = BanList::new(Duration::from_secs(60), vec![]);
+        bans.load_persisted(path.clone());
+        bans.ban_offender(peer_id.into());
+        bans.ban(subnet.clone(), BanReason::Manual, Duration::from_secs(600));
+        bans.persist();
+
+        let mut bans = BanList::new(Duration::from_secs(60), vec![]);
+        bans.load_persisted(path);
+        assert!(bans.is_peer_banned(&peer_id));
+        assert_eq!(bans.bans().len(), 2);
+
+        // The offense is remembered, so the next ban is longer.
+        bans.unban(&peer_id.into());
+        assert_eq!(bans.ban_offender(peer_id.into()), Duration::from_secs(120));
+    }
+
+    #[test]
+    fn persist_only_if_changed() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("ban_list.dat");
+
+        let mut bans = BanList::new(Duration::from_secs(60), vec![]);
+        bans.load_persisted(path.clone());
+        bans.ban_offender(PeerId::random().into());
+        bans.persist();
+        assert!(path.exists());
+
+        // Nothing changed, so the file is not written again.
+        std::fs::remove_file(&path).unwrap();
+        bans.persist();
+        bans.housekeeping();
+        assert!(!path.exists());
+
+        bans.ban(
+            BanTarget::from_str("1.2.3.4").unwrap(),
+            BanReason::Manual,
+            Duration::from_secs(60),
+        );
+        bans.persist();
+        assert!(path.exists());
+    }
+
+    #[test]
+    fn ban_and_unban_subnets() {
+        let mut bans = BanList::new(Duration::from_secs(60), vec![]);
+        let subnet_v4 = BanTarget::from_str("192.168.0.0/16").unwrap();
+        let subnet_v6 = BanTarget::from_str("2001:db8::/32").unwrap();
+        let address_v4 = Multiaddr::from_str("/ip4/192.168.1.2/tcp/8443/ws").unwrap();
+        let address_v6 = Multiaddr::from_str("/ip6/2001:db8::1/tcp/8443/ws").unwrap();
+
+        bans.ban(
+            subnet_v4.clone(),
+            BanReason::Manual,
+            Duration::from_secs(60),
+        );
+        bans.ban(
+            subnet_v6.clone(),
+            BanReason::Manual,
+            Duration::from_secs(60),
+        );
+        assert!(bans.is_address_banned(&address_v4));
+        assert!(bans.is_address_banned(&address_v6));
+        assert!(
+            !bans.is_address_banned(&Multiaddr::from_str("/ip4/192.169.1.2/tcp/8443/ws").unwrap())
+        );
+
+        assert!(bans.unban(&subnet_v4));
+        assert!(!bans.is_address_banned(&address_v4));
+        assert!(bans.is_address_banned(&address_v6));
+        assert_eq!(bans.subnet_netmasks.len(), 1);
+
+        assert!(bans.unban(&subnet_v6));
+        assert!(!bans.is_address_banned(&address_v6));
+        assert!(bans.subnet_netmasks.is_empty());
+    }
+}
@@ -1,8 +1,7 @@
 use std::{
     collections::{hash_map::Entry, BTreeMap, BTreeSet, HashMap, HashSet, VecDeque},
-    future::Future,
     net::IpAddr,
-    pin::Pin,
+    path::PathBuf,
     sync::Arc,
     task::{Context, Poll, Waker},
     time::Duration,
@@ -21,14 +20,17 @@ use libp2p::{
     Multiaddr, PeerId, TransportError,
 };
 use nimiq_network_interface::{network::CloseReason, peer_info::Services};
-use nimiq_time::{interval, sleep_until, Instant, Interval, Sleep};
+use nimiq_time::{interval, Instant, Interval};
 use nimiq_utils::WakerExt as _;
 use parking_lot::RwLock;
 use rand::{seq::IteratorRandom, thread_rng};
 use void::Void;
 
 use super::Error;
-use crate::discovery::{handler, peer_contacts::PeerContactBook};
+use crate::{
+    connection_pool::ban_list::{Ban, BanList, BanReason, BanTarget},
+    discovery::peer_contacts::PeerContactBook,
+};
 
 /// Current state of connections and peers for connection limits
 #[derive(Clone, Debug)]
@@ -62,6 +64,15 @@ pub(crate) struct Config {
     pub retry_down_after: Duration,
     /// Interval duration for peer connections housekeeping
     pub housekeeping_interval: Duration,
+    /// Duration a malicious peer is banned for at its first offense
+    pub ban_time: Duration,
+    /// Peer IDs, addresses and subnets that are permanently banned
+    pub deny_list: Vec<BanTarget>,
+    /// File to persist the ban list to. If absent, bans are only kept in memory.
+    pub ban_list_path: Option<PathBuf>,
+    /// If not empty, the only peers that are dialed or accepted. Addresses ending in
+    /// `/p2p/<peer ID>` are dialed, bare `/p2p/<peer ID>` entries are only accepted.
+    pub allow_list: Vec<Multiaddr>,
 }
 
 /// Connection Peer information
@@ -85,26 +96,22 @@ impl Default for Config {
             dialing_count_max: 3,
             retry_down_after: Duration::from_secs(60 * 10), // 10 minutes
             housekeeping_interval: Duration::from_secs(60 * 2), // 2 minutes
+            ban_time: Duration::from_secs(60 * 10),         // 10 minutes
+            deny_list: vec![],
+            ban_list_path: None,
+            allow_list: vec![],
         }
     }
 }
 
 /// State of all of the connections the network has, like
 /// connected peers, peers being dialed, peers with failed dial attempts
-/// peers that are down or banned.
+/// or peers that are down.
 struct ConnectionState<T> {
     /// Set of connection IDs being dialed.
     dialing: BTreeSet<T>,
     /// Set of connection IDs marked as connected.
     connected: BTreeMap<T, Option<Services>>,
-    /// Set of connection IDs marked as banned.
-    banned: BTreeSet<T>,
-    /// List of subsequent banned peers with their unban deadlines in ascending order.
-    unban_deadlines: VecDeque<(T, Instant)>,
-    /// Deadline for first peer that can be unbanned.
-    unban_timeout: Option<(T, Pin<Box<Sleep>>)>,
-    /// The time that needs to pass to unban a banned peer.
-    ban_time: Duration,
     /// Set of connection IDs mark as failed.
     failed: BTreeMap<T, usize>,
     /// Set of connection IDs mark as down.
@@ -120,41 +127,6 @@ struct ConnectionState<T> {
     desired_connections: usize,
     /// The set of services that this peer requires.
     required_services: Services,
-    /// Waker used for the next poll
-    waker: Option<Waker>,
-}
-
-impl<T: Clone + Ord + Unpin> Future for ConnectionState<T> {
-    type Output = ();
-
-    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
-        while let Some((id, sleep)) = self.unban_timeout.as_mut() {
-            if sleep.poll_unpin(cx).is_ready() {
-                let id = id.clone();
-                self.banned.remove(&id);
-                self.unban_timeout = None;
-
-                // While we are unbanning, try to unban more peers if their deadline also have been reached.
-                // Otherwise schedule a sleep until for the first peer that can be unbanned.
-                while let Some((next_id, deadline)) = self.unban_deadlines.pop_front() {
-                    if Instant::now() >= deadline {
-                        self.banned.remove(&next_id);
-                    } else {
-                        self.unban_timeout = Some((next_id, Box::pin(sleep_until(deadline))));
-                        break;
-                    }
-                }
-            } else {
-                break;
-            }
-        }
-
-        if self.unban_timeout.is_none() {
-            self.waker.store_waker(cx);
-        }
-
-        Poll::Pending
-    }
 }
 
 impl<T: Clone + Ord> ConnectionState<T> {
@@ -163,22 +135,16 @@ impl<T: Clone + Ord> ConnectionState<T> {
         retry_down_after: Duration,
         desired_connections: usize,
         required_services: Services,
-        ban_time: Duration,
     ) -> Self {
         Self {
             dialing: BTreeSet::new(),
             connected: BTreeMap::new(),
-            banned: BTreeSet::new(),
-            ban_time,
-            unban_deadlines: VecDeque::new(),
-            unban_timeout: None,
             failed: BTreeMap::new(),
             down: BTreeMap::new(),
             max_failures,
             retry_down_after,
             desired_connections,
             required_services,
-            waker: None,
         }
     }
 
@@ -203,29 +169,6 @@ impl<T: Clone + Ord> ConnectionState<T> {
         self.connected.remove(&id);
     }
 
-    /// Marks a connection ID as banned. The connection ID will be also removed
-    /// from the IDs marked as down or failed.
-    fn mark_banned(&mut self, id: T) {
-        self.failed.remove(&id);
-        self.down.remove(&id);
-        self.banned.insert(id.clone());
-
-        let unban_deadline = Instant::now() + self.ban_time;
-        // If no peer is scheduled to be unbanned, put is as the first-to-be unbanned peer.
-        // Otherwise queue the peer such that it will be scheduled later when the peer is the first in line.
-        if self.unban_timeout.is_none() {
-            self.unban_timeout = Some((id, Box::pin(sleep_until(unban_deadline))));
-            self.waker.wake();
-        } else {
-            self.unban_deadlines.push_back((id.clone(), unban_deadline));
-        }
-    }
-
-    /// Returns whether a connection ID is banned
-    fn is_banned(&self, id: T) -> bool {
-        self.banned.contains(&id)
-    }
-
     /// Marks a connection ID as failed
     ///
     /// If the peers was marked as being dialed, it will be removed from such
@@ -270,7 +213,6 @@ impl<T: Clone + Ord> ConnectionState<T> {
         !self.dialing.contains(id)
             && !self.connected.contains_key(id)
             && !self.down.contains_key(id)
-            && !self.banned.contains(id)
     }
 
     /// Returns the number of connections being dialed
@@ -316,12 +258,11 @@ impl<T> std::fmt::Display for ConnectionState<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "connected={}, dialing={}, failed={}, down={}, banned={}",
+            "connected={}, dialing={}, failed={}, down={}",
             self.connected.len(),
             self.dialing.len(),
             self.failed.len(),
             self.down.len(),
-            self.banned.len(),
         )
     }
 }
@@ -356,6 +297,12 @@ pub struct Behaviour {
     /// Connection state per address
     addresses: ConnectionState<Multiaddr>,
 
+    /// Banned peer IDs, addresses and subnets
+    ban_list: BanList,
+
+    /// The only peer IDs we connect to, if running in private mode
+    allowed_peers: Option<HashSet<PeerId>>,
+
     /// Queue of actions this behaviour will emit for handler execution.
     actions: VecDeque<PoolToSwarm>,
 
@@ -379,9 +326,9 @@ impl Behaviour {
     pub fn new(
         contacts: Arc<RwLock<PeerContactBook>>,
         own_peer_id: PeerId,
-        seeds: Vec<Multiaddr>,
+        mut seeds: Vec<Multiaddr>,
         required_services: Services,
-        config: Config,
+        mut config: Config,
     ) -> Self {
         let limits = Limits {
             ip_count: HashMap::new(),
@@ -390,6 +337,34 @@ impl Behaviour {
         };
         let housekeeping_timer = interval(config.housekeeping_interval);
 
+        let mut ban_list = BanList::new(config.ban_time, config.deny_list.clone());
+        if let Some(path) = config.ban_list_path.clone() {
+            ban_list.load_persisted(path);
+        }
+
+        // In private mode, the allowed peers replace the seeds and we try to stay connected to
+        // all of them.
+        let allowed_peers = if config.allow_list.is_empty() {
+            None
+        } else {
+            let allowed_peers: HashSet<PeerId> = config
+                .allow_list
+                .iter()
+                .filter_map(|address| match address.iter().last() {
+                    Some(Protocol::P2p(peer_id)) => Some(peer_id),
+                    _ => None,
+                })
+                .collect();
+            seeds = config
+                .allow_list
+                .iter()
+                .filter(|address| address.len() > 1)
+                .cloned()
+                .collect();
+            config.desired_peer_count = allowed_peers.len();
+            Some(allowed_peers)
+        };
+
         Self {
             contacts,
             own_peer_id,
@@ -400,15 +375,15 @@ impl Behaviour {
                 config.retry_down_after,
                 config.desired_peer_count,
                 required_services,
-                Duration::from_secs(60 * 10), // 10 minutes
             ),
             addresses: ConnectionState::new(
                 4,
                 config.retry_down_after,
                 config.desired_peer_count,
                 required_services,
-                Duration::from_secs(60 * 10), // 10 minutes
             ),
+            ban_list,
+            allowed_peers,
             actions: VecDeque::new(),
             active: false,
             limits,
@@ -433,6 +408,19 @@ impl Behaviour {
         }
     }
 
+    /// Returns whether we run in private mode, only connecting to the peers on the allow list.
+    pub fn is_private(&self) -> bool {
+        self.allowed_peers.is_some()
+    }
+
+    /// Returns whether we may connect to the given peer. Outside of private mode, every peer
+    /// that is not banned is allowed.
+    fn is_peer_allowed(&self, peer_id: &PeerId) -> bool {
+        self.allowed_peers
+            .as_ref()
+            .map_or(true, |allowed_peers| allowed_peers.contains(peer_id))
+    }
+
     /// Tries to maintain at least `desired_peer_count` connections.
     ///
     /// For this it will try to select peers or seeds to dial in order to
@@ -508,7 +496,8 @@ impl Behaviour {
     /// Closes a peer connection with a reason
     ///
     /// This will take actions depending on the close reason. For instance:
-    /// - The close reason `MaliciousPeer` will cause the peer to be banned for a fixed amount of time.
+    /// - The close reason `MaliciousPeer` will cause the peer to be banned. The ban time increases
+    ///   with every repeated offense.
     /// - Going offline will signal the network to stop connecting to peers.
     pub fn close_connection(&mut self, peer_id: PeerId, reason: CloseReason) {
         self.actions.push_back(ToSwarm::CloseConnection {
@@ -524,6 +513,58 @@ impl Behaviour {
         }
     }
 
+    /// Bans a peer ID, an outer protocol address or a subnet for the given duration and closes
+    /// all connections to the banned peers.
+    pub fn ban(&mut self, target: BanTarget, duration: Duration) {
+        debug!(%target, ?duration, "Banned");
+        self.ban_list.ban(target, BanReason::Manual, duration);
+        self.ban_list.persist_in_background();
+        self.close_banned_connections();
+    }
+
+    /// Lifts a ban. Returns whether the peer ID, address or subnet was banned.
+    /// Bans from the deny list can't be lifted.
+    pub fn unban(&mut self, target: &BanTarget) -> bool {
+        let unbanned = self.ban_list.unban(target);
+        if unbanned {
+            self.ban_list.persist_in_background();
+        }
+        unbanned
+    }
+
+    /// Returns all active bans.
+    pub fn bans(&self) -> Vec<Ban> {
+        self.ban_list.bans()
+    }
+
+    /// Closes the connections to all connected peers that are banned by their peer ID or the
+    /// address they connected from.
+    fn close_banned_connections(&mut self) {
+        let contacts = self.contacts.read();
+        let banned_peers: Vec<PeerId> = self
+            .peer_ids
+            .connected
+            .keys()
+            .filter(|peer_id| {
+                self.ban_list.is_peer_banned(peer_id)
+                    || contacts
+                        .get(peer_id)
+                        .and_then(|contact| contact.get_outer_protocol_address())
+                        .is_some_and(|address| self.ban_list.is_address_banned(&address))
+            })
+            .copied()
+            .collect();
+        drop(contacts);
+
+        for peer_id in banned_peers {
+            self.actions.push_back(ToSwarm::CloseConnection {
+                peer_id,
+                connection: CloseConnection::All,
+            });
+        }
+        self.waker.wake();
+    }
+
     fn choose_peers_to_dial(&self) -> Vec<PeerId> {
         let num_peers = usize::min(
             self.config.desired_peer_count - self.peer_ids.num_connected(true),
@@ -539,6 +580,8 @@ impl Behaviour {
                 let peer_id = contact.peer_id();
                 if peer_id != own_peer_id
                     && self.peer_ids.can_dial(peer_id)
+                    && self.is_peer_allowed(peer_id)
+                    && !self.ban_list.is_peer_banned(peer_id)
                     && contact.addresses().count() > 0
                 {
                     Some(*peer_id)
@@ -567,6 +610,8 @@ impl Behaviour {
                 let peer_id = contact.peer_id();
                 if peer_id != own_peer_id
                     && self.peer_ids.can_dial(peer_id)
+                    && self.is_peer_allowed(peer_id)
+                    && !self.ban_list.is_peer_banned(peer_id)
                     && contact.addresses().count() > 0
                 {
                     Some(*peer_id)
@@ -578,19 +623,28 @@ impl Behaviour {
     }
 
     fn choose_seeds_to_dial(&self) -> Vec<Multiaddr> {
-        // We prefer to connect to non-seed peers. Thus, we only choose any seeds here if we're
-        // not already dialing any peers and at most one seed at a time.
-        if self.peer_ids.num_dialing() > 0 || self.addresses.num_dialing() > 0 {
-            return vec![];
-        }
+        // In private mode, the seeds are the allowed peers and we dial all of them.
+        let num_seeds = if self.is_private() {
+            self.seeds.len()
+        } else {
+            // We prefer to connect to non-seed peers. Thus, we only choose any seeds here if
+            // we're not already dialing any peers and at most one seed at a time.
+            if self.peer_ids.num_dialing() > 0 || self.addresses.num_dialing() > 0 {
+                return vec![];
+            }
+            1
+        };
 
-        let num_seeds = 1;
         let contacts = self.contacts.read();
         let own_contact = contacts.get_own_contact();
         let own_addresses: HashSet<&Multiaddr> = own_contact.addresses().collect();
         self.seeds
             .iter()
-            .filter(|address| !own_addresses.contains(address) && self.addresses.can_dial(*address))
+            .filter(|address| {
+                !own_addresses.contains(address)
+                    && self.addresses.can_dial(*address)
+                    && !self.ban_list.is_address_banned(address)
+            })
             .cloned()
             .choose_multiple(&mut thread_rng(), num_seeds)
     }
@@ -612,22 +666,30 @@ impl Behaviour {
 
         self.peer_ids.housekeeping();
         self.addresses.housekeeping();
+        self.ban_list.housekeeping();
 
         self.maintain_peers();
     }
 
     fn ban_connection(&mut self, peer_id: PeerId) {
-        // Mark the peer ID as banned
-        self.peer_ids.mark_banned(peer_id);
-        debug!(%peer_id, "Banned peer");
-
-        // Mark its outer protocol address as banned if we have it
-        if let Some(contact) = self.contacts.read().get(&peer_id) {
-            if let Some(outer_protocol_address) = contact.get_outer_protocol_address() {
-                debug!(address = %outer_protocol_address, "Banned address");
-                self.addresses.mark_banned(outer_protocol_address);
-            }
+        // Ban the peer ID
+        let duration = self.ban_list.ban_offender(peer_id.into());
+        debug!(%peer_id, ?duration, "Banned peer");
+
+        // Ban its outer protocol address as well if we have it
+        let outer_protocol_address = self
+            .contacts
+            .read()
+            .get(&peer_id)
+            .and_then(|contact| contact.get_outer_protocol_address());
+        if let Some(outer_protocol_address) = outer_protocol_address {
+            let duration = self
+                .ban_list
+                .ban_offender(BanTarget::Address(outer_protocol_address.clone()));
+            debug!(address = %outer_protocol_address, ?duration, "Banned address");
         }
+
+        self.ban_list.persist_in_background();
     }
 
     fn on_connection_established(
@@ -947,7 +1009,10 @@ impl NetworkBehaviour for Behaviour {
             .contacts
             .read()
             .get_addresses(&peer_id)
-            .unwrap_or_default())
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|address| !self.ban_list.is_address_banned(address))
+            .collect())
     }
 
     fn handle_pending_inbound_connection(
@@ -956,11 +1021,9 @@ impl NetworkBehaviour for Behaviour {
         _local_addr: &Multiaddr,
         remote_addr: &Multiaddr,
     ) -> Result<(), ConnectionDenied> {
-        if let Some(outer_protocol_address) = handler::outer_protocol_address(remote_addr) {
-            if self.addresses.is_banned(outer_protocol_address) {
-                debug!(%remote_addr, "Address is banned");
-                return Err(ConnectionDenied::new(Error::BannedIp));
-            }
+        if self.ban_list.is_address_banned(remote_addr) {
+            debug!(%remote_addr, "Address is banned");
+            return Err(ConnectionDenied::new(Error::BannedIp));
         }
 
         // Get IP from multiaddress if it exists.
@@ -980,10 +1043,14 @@ impl NetworkBehaviour for Behaviour {
     ) -> Result<THandler<Self>, ConnectionDenied> {
         // Peer IDs checks are performed here since it is in this point where we have
         // this information.
-        if self.peer_ids.is_banned(peer) {
+        if self.ban_list.is_peer_banned(&peer) {
             debug!(peer_id=%peer, "Peer is banned");
             return Err(ConnectionDenied::new(Error::BannedPeer));
         }
+        if !self.is_peer_allowed(&peer) {
+            debug!(peer_id = %peer, "Peer is not on the allow list");
+            return Err(ConnectionDenied::new(Error::NotAllowed));
+        }
         self.increment_and_check_peer_limit()?;
 
         Ok(dummy::ConnectionHandler)
@@ -992,11 +1059,23 @@ impl NetworkBehaviour for Behaviour {
     fn handle_established_outbound_connection(
         &mut self,
         _connection_id: ConnectionId,
-        _peer: PeerId,
+        peer: PeerId,
         addr: &Multiaddr,
         _role_override: Endpoint,
         _port_use: PortUse,
     ) -> Result<THandler<Self>, ConnectionDenied> {
+        if self.ban_list.is_peer_banned(&peer) {
+            debug!(peer_id = %peer, "Peer is banned");
+            return Err(ConnectionDenied::new(Error::BannedPeer));
+        }
+        if !self.is_peer_allowed(&peer) {
+            debug!(peer_id = %peer, "Peer is not on the allow list");
+            return Err(ConnectionDenied::new(Error::NotAllowed));
+        }
+        if self.ban_list.is_address_banned(addr) {
+            debug!(%addr, "Address is banned");
+            return Err(ConnectionDenied::new(Error::BannedIp));
+        }
         if let Some(ip_info) = self.get_ip_info_from_multiaddr(addr) {
             self.reached_ip_limit(&ip_info)?;
         }
@@ -1014,8 +1093,7 @@ impl NetworkBehaviour for Behaviour {
             return Poll::Ready(action);
         }
 
-        let _ = self.peer_ids.poll_unpin(cx);
-        let _ = self.addresses.poll_unpin(cx);
+        let _ = self.ban_list.poll_unpin(cx);
 
         // Perform housekeeping at regular intervals.
         if self.housekeeping_timer.poll_next_unpin(cx).is_ready() {
@@ -1027,60 +1105,3 @@ impl NetworkBehaviour for Behaviour {
         Poll::Pending
     }
 }
-
-#[cfg(test)]
-mod tests {
-    use std::task::Context;
-
-    use futures::{task::noop_waker, FutureExt};
-    use instant::Duration;
-    use libp2p::PeerId;
-    use nimiq_network_interface::peer_info::Services;
-    use nimiq_test_log::test;
-    use nimiq_time::sleep;
-
-    use crate::connection_pool::behaviour::ConnectionState;
-
-    #[test(tokio::test)]
-    async fn unban_peers_after_timeout() {
-        let mut cs = ConnectionState::new(
-            30,
-            Duration::from_secs(30),
-            1,
-            Services::empty(),
-            Duration::from_secs(2), // Ban time: 2 seconds
-        );
-
-        let waker = noop_waker();
-        let cx = &mut Context::from_waker(&waker);
-
-        let p1 = PeerId::random();
-        let p2 = PeerId::random();
-        let p3 = PeerId::random();
-
-        cs.mark_banned(p1);
-
-        sleep(Duration::from_secs(1)).await;
-
-        cs.mark_banned(p2);
-        cs.mark_banned(p3);
-
-        sleep(Duration::from_secs(1)).await;
-
-        // Mimic a wake
-        let _ = cs.poll_unpin(cx);
-
-        // p1 should be unbanned
-        assert!(!cs.banned.contains(&p1));
-        // Other banned peers should still be banned
-        assert_eq!(cs.banned.len(), 2);
-
-        sleep(Duration::from_secs(1)).await;
-
-        // Mimic a wake
-        let _ = cs.poll_unpin(cx);
-
-        // p2 and p3 should both be unbanned
-        assert!(cs.banned.is_empty());
-    }
-}
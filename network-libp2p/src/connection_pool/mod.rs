@@ -1,3 +1,4 @@
+pub mod ban_list;
 pub mod behaviour;
 pub use behaviour::Behaviour;
 use thiserror::Error;
@@ -13,6 +14,10 @@ pub enum Error {
     #[error("Peer is banned")]
     BannedPeer,
 
+    /// Peer is not on the allow list
+    #[error("Peer is not on the allow list")]
+    NotAllowed,
+
     /// Maximum connections per subnet has been reached
     #[error("Maximum connections per subnet has been reached")]
     MaxSubnetConnectionsReached,
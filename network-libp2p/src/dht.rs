@@ -34,7 +34,7 @@ impl Verifier for () {
         let peer_id = PeerId::random();
         Ok(DhtRecord::Validator(
             peer_id,
-            ValidatorRecord::<PeerId>::new(peer_id, Address::default(), 0u64),
+            ValidatorRecord::<PeerId>::new(peer_id, Address::default(), 0u64, vec![]),
             record.clone(),
         ))
     }
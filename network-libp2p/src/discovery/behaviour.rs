@@ -6,6 +6,7 @@ use std::{
 };
 
 use futures::StreamExt;
+use instant::SystemTime;
 use libp2p::{
     core::{transport::PortUse, Endpoint},
     identity::Keypair,
@@ -54,6 +55,10 @@ pub struct Config {
 
     /// Only secure websocket connections
     pub only_secure_ws_connections: bool,
+
+    /// Private mode: Neither advertise our own addresses nor share or accept the contacts of
+    /// other peers.
+    pub private_mode: bool,
 }
 
 impl Config {
@@ -72,6 +77,7 @@ impl Config {
             house_keeping_interval: Duration::from_secs(60),
             keep_alive: true,
             only_secure_ws_connections,
+            private_mode: false,
         }
     }
 }
@@ -126,6 +132,17 @@ impl Behaviour {
         let house_keeping_timer = interval(config.house_keeping_interval);
         peer_contact_book.write().update_own_contact(&keypair);
 
+        // In private mode, nobody should learn how to reach us.
+        if config.private_mode {
+            let mut peer_contact_book = peer_contact_book.write();
+            let own_addresses: Vec<Multiaddr> = peer_contact_book
+                .get_own_contact()
+                .addresses()
+                .cloned()
+                .collect();
+            peer_contact_book.remove_own_addresses(own_addresses, &keypair);
+        }
+
         // Report our own known addresses as candidates to the swarm
         let mut events = VecDeque::new();
         for address in peer_contact_book.read().get_own_contact().addresses() {
@@ -144,6 +161,9 @@ impl Behaviour {
 
     /// Adds addresses into our own contact within the peer contact book
     pub fn add_own_addresses(&self, addresses: Vec<Multiaddr>) {
+        if self.config.private_mode {
+            return;
+        }
         self.peer_contact_book
             .write()
             .add_own_addresses(addresses, &self.keypair)
@@ -226,9 +246,16 @@ impl NetworkBehaviour for Behaviour {
         match self.house_keeping_timer.poll_next_unpin(cx) {
             Poll::Ready(Some(_)) => {
                 trace!("Doing house-keeping in peer address book");
-                let mut peer_address_book = self.peer_contact_book.write();
-                peer_address_book.update_own_contact(&self.keypair);
-                peer_address_book.house_keeping();
+                let persisted_contacts = {
+                    let mut peer_address_book = self.peer_contact_book.write();
+                    peer_address_book.update_own_contact(&self.keypair);
+                    peer_address_book.house_keeping();
+                    peer_address_book.persisted_contacts()
+                };
+                // Write the contacts without holding the lock on the contact book.
+                if let Some(persisted_contacts) = persisted_contacts {
+                    persisted_contacts.store();
+                }
             }
             Poll::Ready(None) => unreachable!(),
             Poll::Pending => {}
@@ -275,6 +302,12 @@ impl NetworkBehaviour for Behaviour {
                 peer_contact: signed_peer_contact,
             } => {
                 if let Some(peer_contact) = self.peer_contact_book.read().get(&peer_id) {
+                    peer_contact.set_last_seen(
+                        SystemTime::now()
+                            .duration_since(SystemTime::UNIX_EPOCH)
+                            .unwrap()
+                            .as_secs(),
+                    );
                     self.events
                         .push_back(ToSwarm::GenerateEvent(Event::Established {
                             peer_id: signed_peer_contact.public_key().clone().to_peer_id(),
@@ -456,19 +456,29 @@ impl ConnectionHandler for Handler {
 
                                     let peer_contact_book = self.peer_contact_book.read();
 
+                                    // In private mode, we neither share the contacts we
+                                    // know nor want to receive updates.
+                                    let (update_interval, peer_contacts) =
+                                        if self.config.private_mode {
+                                            (None, vec![])
+                                        } else {
+                                            (
+                                                Some(self.config.update_interval.as_secs()),
+                                                self.get_peer_contacts(
+                                                    &peer_contact_book,
+                                                    self.peer_list_limit.unwrap() as usize,
+                                                ),
+                                            )
+                                        };
+
                                     let msg = DiscoveryMessage::HandshakeAck {
                                         peer_contact: peer_contact_book
                                             .get_own_contact()
                                             .signed()
                                             .clone(),
                                         response_signature,
-                                        update_interval: Some(
-                                            self.config.update_interval.as_secs(),
-                                        ),
-                                        peer_contacts: self.get_peer_contacts(
-                                            &peer_contact_book,
-                                            self.peer_list_limit.unwrap() as usize,
-                                        ),
+                                        update_interval,
+                                        peer_contacts,
                                     };
 
                                     drop(peer_contact_book);
@@ -594,16 +604,21 @@ impl ConnectionHandler for Handler {
                                     peer_contact_book.insert(peer_contact.clone());
 
                                     // Insert the peer's contacts (filtered) into my contact book
-                                    peer_contact_book.insert_all_filtered(
-                                        peer_contacts,
-                                        self.config.required_services,
-                                        self.config.only_secure_ws_connections,
-                                    );
+                                    if !self.config.private_mode {
+                                        peer_contact_book.insert_all_filtered(
+                                            peer_contacts,
+                                            self.config.required_services,
+                                            self.config.only_secure_ws_connections,
+                                        );
+                                    }
 
                                     drop(peer_contact_book);
 
-                                    // Timer for periodic updates
-                                    if let Some(mut update_interval) = update_interval {
+                                    // Timer for periodic updates. In private mode, we don't send
+                                    // any updates.
+                                    if let Some(mut update_interval) =
+                                        update_interval.filter(|_| !self.config.private_mode)
+                                    {
                                         let min_secs =
                                             self.config.min_send_update_interval.as_secs();
                                         if update_interval < min_secs {
@@ -700,11 +715,13 @@ impl ConnectionHandler for Handler {
                                     }
 
                                     // Insert the new peer contacts into the peer contact book.
-                                    self.peer_contact_book.write().insert_all_filtered(
-                                        peer_contacts,
-                                        self.config.required_services,
-                                        self.config.only_secure_ws_connections,
-                                    );
+                                    if !self.config.private_mode {
+                                        self.peer_contact_book.write().insert_all_filtered(
+                                            peer_contacts,
+                                            self.config.required_services,
+                                            self.config.only_secure_ws_connections,
+                                        );
+                                    }
 
                                     return Poll::Ready(ConnectionHandlerEvent::NotifyBehaviour(
                                         HandlerOutEvent::Update,
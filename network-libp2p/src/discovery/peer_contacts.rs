@@ -1,5 +1,7 @@
 use std::{
     collections::{HashMap, HashSet},
+    io,
+    path::PathBuf,
     sync::Arc,
     time::Duration,
 };
@@ -16,7 +18,10 @@ use nimiq_network_interface::{
     network::Network as NetworkInterface,
     peer_info::{PeerInfo, Services},
 };
-use nimiq_utils::tagged_signing::{TaggedKeyPair, TaggedSignable, TaggedSignature};
+use nimiq_utils::{
+    file_store::{self, FileStore},
+    tagged_signing::{TaggedKeyPair, TaggedSignable, TaggedSignature},
+};
 use nimiq_validator_network::validator_record::ValidatorRecord;
 use parking_lot::RwLock;
 use serde::{Deserialize, Serialize};
@@ -209,6 +214,33 @@ impl SignedPeerContact {
 struct PeerContactMeta {
     outer_protocol_address: Option<Multiaddr>,
     score: f64,
+    /// Timestamp when this peer contact was last received or the peer was last successfully
+    /// contacted in *seconds* since unix epoch.
+    last_seen: u64,
+}
+
+/// A peer contact together with its meta-data, as it is persisted to disk.
+#[derive(Serialize, Deserialize)]
+struct PersistedPeerContact {
+    contact: SignedPeerContact,
+    score: f64,
+    last_seen: u64,
+}
+
+/// The peer contacts of a contact book collected for persisting them. They can be written to disk
+/// without holding a lock on the contact book.
+pub struct PersistedPeerContacts {
+    path: PathBuf,
+    contacts: Vec<PersistedPeerContact>,
+}
+
+impl PersistedPeerContacts {
+    /// Writes the peer contacts to disk.
+    pub fn store(self) {
+        if let Err(error) = FileStore::new(&self.path).store(&self.contacts) {
+            warn!(%error, path = %self.path.display(), "Failed to persist peer contacts");
+        }
+    }
 }
 
 /// This encapsulates a peer contact (signed), but also pre-computes frequently used values such as `peer_id` and
@@ -228,6 +260,10 @@ pub struct PeerContactInfo {
 impl From<SignedPeerContact> for PeerContactInfo {
     fn from(contact: SignedPeerContact) -> Self {
         let peer_id = contact.inner.peer_id();
+        let last_seen = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
 
         Self {
             peer_id,
@@ -235,6 +271,7 @@ impl From<SignedPeerContact> for PeerContactInfo {
             meta: RwLock::new(PeerContactMeta {
                 score: 0.,
                 outer_protocol_address: None,
+                last_seen,
             }),
         }
     }
@@ -293,6 +330,18 @@ impl PeerContactInfo {
         self.meta.write().score = score;
     }
 
+    /// Gets the timestamp when this peer contact was last received or the peer was last
+    /// successfully contacted in seconds since unix epoch
+    pub fn get_last_seen(&self) -> u64 {
+        self.meta.read().last_seen
+    }
+
+    /// Sets the timestamp when this peer contact was last received or the peer was last
+    /// successfully contacted in seconds since unix epoch
+    pub fn set_last_seen(&self, last_seen: u64) {
+        self.meta.write().last_seen = last_seen;
+    }
+
     /// Gets the outer protocol address of the peer. For example `/ip4/x.x.x.x` or `/dns4/foo.bar`
     pub fn get_outer_protocol_address(&self) -> Option<Multiaddr> {
         self.meta.read().outer_protocol_address.clone()
@@ -329,6 +378,10 @@ pub struct PeerContactBook {
     allow_loopback_addresses: bool,
     /// Flag to indicate whether to support memory transport addresses
     memory_transport: bool,
+    /// Flag to indicate whether to return also QUIC addresses
+    quic_addresses: bool,
+    /// File the peer contacts are persisted to during house-keeping, if any.
+    persistence_path: Option<PathBuf>,
 }
 
 impl PeerContactBook {
@@ -350,6 +403,91 @@ impl PeerContactBook {
             only_secure_addresses,
             allow_loopback_addresses,
             memory_transport,
+            quic_addresses: false,
+            persistence_path: None,
+        }
+    }
+
+    /// Sets whether QUIC addresses are dialable. They never are if only secure addresses are
+    /// allowed.
+    pub fn set_quic_addresses(&mut self, quic_addresses: bool) {
+        self.quic_addresses = quic_addresses;
+    }
+
+    /// Loads the peer contacts persisted in the given file and persists the peer contacts to it
+    /// from now on. The contacts are inserted like received ones, additionally contacts with an
+    /// invalid signature or that exceeded the maximum age are dropped.
+    pub fn load_persisted(&mut self, path: PathBuf) {
+        match FileStore::new(&path).load::<Vec<PersistedPeerContact>>() {
+            Ok(persisted_contacts) => {
+                let unix_time = SystemTime::now()
+                    .duration_since(SystemTime::UNIX_EPOCH)
+                    .unwrap();
+
+                for persisted in persisted_contacts {
+                    let peer_id = persisted.contact.peer_id();
+                    if !persisted.contact.verify() {
+                        debug!(%peer_id, "Dropping persisted peer contact with invalid signature");
+                        continue;
+                    }
+
+                    self.insert(persisted.contact);
+
+                    // Restore the meta-data if the contact was accepted.
+                    let Some(info) = self.peer_contacts.get(&peer_id) else {
+                        continue;
+                    };
+                    if info.exceeds_age(Duration::from_secs(Self::MAX_PEER_AGE), unix_time) {
+                        trace!(%peer_id, "Dropping persisted peer contact because of old age");
+                        self.peer_contacts.remove(&peer_id);
+                        continue;
+                    }
+
+                    let mut meta = info.meta.write();
+                    meta.score = persisted.score;
+                    meta.last_seen = persisted.last_seen;
+                }
+
+                debug!(
+                    num_contacts = self.peer_contacts.len(),
+                    path = %path.display(),
+                    "Loaded persisted peer contacts"
+                );
+            }
+            Err(file_store::Error::Io(error)) if error.kind() == io::ErrorKind::NotFound => {}
+            Err(error) => {
+                warn!(%error, path = %path.display(), "Failed to load persisted peer contacts")
+            }
+        }
+
+        self.persistence_path = Some(path);
+    }
+
+    /// Collects the peer contacts to persist, if a file to persist them to has been set. Use
+    /// [`PersistedPeerContacts::store`] to write them once the contact book has been unlocked.
+    pub fn persisted_contacts(&self) -> Option<PersistedPeerContacts> {
+        let path = self.persistence_path.clone()?;
+
+        let contacts = self
+            .peer_contacts
+            .values()
+            .map(|info| {
+                let meta = info.meta.read();
+                PersistedPeerContact {
+                    contact: info.contact.clone(),
+                    score: meta.score,
+                    last_seen: meta.last_seen,
+                }
+            })
+            .collect();
+
+        Some(PersistedPeerContacts { path, contacts })
+    }
+
+    /// Persists the peer contacts, if a file to persist them to has been set.
+    pub fn persist(&self) {
+        if let Some(persisted_contacts) = self.persisted_contacts() {
+            persisted_contacts.store();
         }
     }
 
@@ -378,6 +516,8 @@ impl PeerContactBook {
                     && info.contact().timestamp <= current_ts
                 {
                     *entry_value = Arc::new(info);
+                } else {
+                    entry_value.set_last_seen(current_ts);
                 }
             }
             std::collections::hash_map::Entry::Vacant(entry) => {
@@ -610,37 +750,50 @@ impl PeerContactBook {
         if self.memory_transport {
             return true;
         }
-        // Otherwise check for an appropriate WS address
+        // Otherwise check for an appropriate WS or QUIC address
         let mut protocols = address.iter();
         let mut ip = protocols.next();
-        let mut tcp = protocols.next();
-        // The encapsulating protocol must be based on TCP/IP, possibly via DNS.
-        let is_dns = loop {
-            match (ip, tcp) {
-                (Some(Protocol::Ip4(ip)), Some(Protocol::Tcp(_))) => {
+        let mut transport = protocols.next();
+        // The encapsulating protocol must be based on TCP/IP or UDP/IP, possibly via DNS.
+        let (is_dns, is_udp) = loop {
+            match (ip, transport) {
+                (Some(Protocol::Ip4(ip)), Some(t @ (Protocol::Tcp(_) | Protocol::Udp(_)))) => {
                     if !self.allow_loopback_addresses && ip.is_loopback() {
                         return false;
                     }
-                    break false;
+                    break (false, matches!(t, Protocol::Udp(_)));
                 }
-                (Some(Protocol::Ip6(ip)), Some(Protocol::Tcp(_))) => {
+                (Some(Protocol::Ip6(ip)), Some(t @ (Protocol::Tcp(_) | Protocol::Udp(_)))) => {
                     if !self.allow_loopback_addresses && ip.is_loopback() {
                         return false;
                     }
-                    break false;
+                    break (false, matches!(t, Protocol::Udp(_)));
                 }
-                (Some(Protocol::Dns(_)), Some(Protocol::Tcp(_)))
-                | (Some(Protocol::Dns4(_)), Some(Protocol::Tcp(_)))
-                | (Some(Protocol::Dns6(_)), Some(Protocol::Tcp(_)))
-                | (Some(Protocol::Dnsaddr(_)), Some(Protocol::Tcp(_))) => break true,
+                (
+                    Some(
+                        Protocol::Dns(_)
+                        | Protocol::Dns4(_)
+                        | Protocol::Dns6(_)
+                        | Protocol::Dnsaddr(_),
+                    ),
+                    Some(t @ (Protocol::Tcp(_) | Protocol::Udp(_))),
+                ) => break (true, matches!(t, Protocol::Udp(_))),
                 (Some(_), Some(p)) => {
                     ip = Some(p);
-                    tcp = protocols.next();
+                    transport = protocols.next();
                 }
                 _ => return false,
             }
         };
 
+        // QUIC addresses must end in `QuicV1`, possibly followed by a `P2p` protocol.
+        if is_udp {
+            return self.quic_addresses
+                && !self.only_secure_addresses
+                && matches!(protocols.next(), Some(Protocol::QuicV1))
+                && matches!(protocols.next(), None | Some(Protocol::P2p(_)));
+        }
+
         // Now check the `Ws` / `Wss` protocol from the end of the address,
         // that could also have a trailing `P2p` protocol that identifies the remote.
         let mut protocols: Multiaddr = address.clone();
@@ -53,6 +53,9 @@ pub enum NetworkError {
 
     #[error("Peer contact error: {0}")]
     PeerContactError(#[from] PeerContactError),
+
+    #[error("Relayed request failed: {0}")]
+    Relay(#[from] nimiq_network_interface::request::RequestError),
 }
 
 impl<T> From<tokio::sync::mpsc::error::SendError<T>> for NetworkError {
@@ -3,6 +3,7 @@ extern crate log;
 
 mod autonat;
 mod behaviour;
+mod capture;
 mod config;
 mod connection_pool;
 pub mod dht;
@@ -15,6 +16,8 @@ mod network_metrics;
 mod network_types;
 mod only_secure_ws_transport;
 mod rate_limiting;
+mod relay;
+mod reputation;
 mod swarm;
 mod utils;
 
@@ -23,7 +26,9 @@ pub const DHT_PROTOCOL: &str = "/nimiq/kad/0.0.1";
 pub const AUTONAT_DIAL_REQUEST_PROTOCOL: &str = "/libp2p/autonat/2/dial-request";
 pub const AUTONAT_DIAL_BACK_PROTOCOL: &str = "/libp2p/autonat/2/dial-back";
 
+pub use capture::{CaptureEvent, CaptureReader, CaptureRecord};
 pub use config::{Config, TlsConfig};
+pub use connection_pool::ban_list::{Ban, BanReason, BanTarget, ParseBanTargetError};
 pub use error::NetworkError;
 pub use libp2p::{
     self,
@@ -32,6 +37,8 @@ pub use libp2p::{
     PeerId,
 };
 pub use network::Network;
+pub use rate_limiting::{InvalidRateLimitError, RateLimitConfig, RateLimitOverrides};
+pub use reputation::{PeerReputation, ReputationConfig};
 use serde::{
     de::Error, ser::Error as SerializationError, Deserialize, Deserializer, Serialize, Serializer,
 };
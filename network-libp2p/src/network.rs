@@ -10,8 +10,10 @@ use std::{
 use async_trait::async_trait;
 use bytes::Bytes;
 use futures::{future::BoxFuture, ready, stream::BoxStream, Stream, StreamExt};
+use instant::SystemTime;
 use libp2p::{
-    gossipsub, request_response::InboundRequestId, swarm::NetworkInfo, Multiaddr, PeerId, Swarm,
+    gossipsub, multiaddr::Protocol, request_response::InboundRequestId, swarm::NetworkInfo,
+    Multiaddr, PeerId, Swarm,
 };
 use nimiq_network_interface::{
     network::{
@@ -37,10 +39,14 @@ use tokio_stream::wrappers::{BroadcastStream, ReceiverStream};
 #[cfg(feature = "metrics")]
 use crate::network_metrics::NetworkMetrics;
 use crate::{
+    capture::TrafficCapture,
+    connection_pool::ban_list::{Ban, BanTarget},
     dht,
     discovery::peer_contacts::PeerContactBook,
-    network_types::{GossipsubId, NetworkAction, ValidateMessage},
+    network_types::{ConnectedPeer, GossipsubId, NetworkAction, ValidateMessage},
     rate_limiting::RateLimitConfig,
+    relay::{self, RelayDhtGet, RelayDhtPut, RelayRequest, Sentry},
+    reputation::PeerReputation,
     swarm::{new_swarm, swarm_task},
     Config, NetworkError,
 };
@@ -52,8 +58,8 @@ pub struct Network {
     local_peer_id: PeerId,
     /// This hash map maintains an association between PeerIds and PeerInfo:
     /// If the peer is interesting, i.e.: it provides services that are interested to us,
-    /// we store an entry with the peer contact itself.
-    connected_peers: Arc<RwLock<HashMap<PeerId, PeerInfo>>>,
+    /// we store an entry with the peer contact itself and the time it joined.
+    connected_peers: Arc<RwLock<HashMap<PeerId, ConnectedPeer>>>,
     /// Stream used to send event messages
     events_tx: broadcast::Sender<NetworkEvent<PeerId>>,
     /// Stream used to send action messages
@@ -67,6 +73,10 @@ pub struct Network {
     required_services: Services,
     /// Reference to PeerContactBook, used to satisfy rpc requests for it.
     contacts: Arc<RwLock<PeerContactBook>>,
+    /// Our sentries if we run in private mode, i.e. the peers on the allow list.
+    local_relays: Vec<PeerId>,
+    /// Peers relaying the traffic of other peers running in private mode.
+    peer_relays: RwLock<HashMap<PeerId, Vec<PeerId>>>,
 }
 
 impl Network {
@@ -82,19 +92,35 @@ impl Network {
         #[cfg(feature = "kad")] dht_verifier: impl dht::Verifier + 'static,
     ) -> Self {
         let required_services = config.required_services;
-        // TODO: persist to disk
         let own_peer_contact = config.peer_contact.clone();
-        let contacts = Arc::new(RwLock::new(PeerContactBook::new(
+        let mut contact_book = PeerContactBook::new(
             own_peer_contact.sign(&config.keypair),
             config.only_secure_ws_connections,
             config.allow_loopback_addresses,
             config.memory_transport,
-        )));
+        );
+        contact_book.set_quic_addresses(cfg!(feature = "quic") && config.quic);
+        if let Some(path) = config.peer_contacts_path.clone() {
+            contact_book.load_persisted(path);
+        }
+        let contacts = Arc::new(RwLock::new(contact_book));
         let params = gossipsub::PeerScoreParams {
             ip_colocation_factor_threshold: 20.0,
             ..Default::default()
         };
         let dht_quorum = config.dht_quorum;
+        let rate_limit_overrides = config.rate_limits.clone();
+        let reputation_config = config.reputation.clone();
+        let capture_path = config.capture_path.clone();
+        let local_relays = config
+            .allow_list
+            .iter()
+            .filter_map(|address| match address.iter().last() {
+                Some(Protocol::P2p(peer_id)) => Some(peer_id),
+                _ => None,
+            })
+            .collect();
+        let sentry_for = config.sentry_for.clone();
         // Only force the server mode if we are doing a memory transport.
         // Otherwise expect the regular flow: DHT will get in server mode once a confirmed address is obtained using Autonat.
         // In memory transport we don't have a mechanism that sets the DHT in server mode such as confirming an address
@@ -108,6 +134,15 @@ impl Network {
         );
 
         let local_peer_id = *Swarm::local_peer_id(&swarm);
+        let capture = capture_path.and_then(|path| {
+            match TrafficCapture::create(path.clone(), local_peer_id) {
+                Ok(capture) => Some(capture),
+                Err(error) => {
+                    error!(path = %path.display(), %error, "Failed to create traffic capture");
+                    None
+                }
+            }
+        });
         let connected_peers = Arc::new(RwLock::new(HashMap::new()));
 
         let events_tx = broadcast::Sender::new(64);
@@ -131,11 +166,15 @@ impl Network {
             dht_verifier,
             force_dht_server_mode,
             dht_quorum,
+            rate_limit_overrides,
+            reputation_config,
+            capture,
+            sentry_for.clone(),
             #[cfg(feature = "metrics")]
             metrics.clone(),
         )));
 
-        Self {
+        let network = Self {
             contacts,
             local_peer_id,
             connected_peers,
@@ -145,7 +184,26 @@ impl Network {
             #[cfg(feature = "metrics")]
             metrics,
             required_services,
+            local_relays,
+            peer_relays: RwLock::new(HashMap::new()),
+        };
+
+        if !sentry_for.is_empty() {
+            let sentry = Sentry::new(network.action_tx.clone(), sentry_for);
+            spawn(
+                sentry
+                    .clone()
+                    .relay_requests(network.receive_requests_impl::<RelayRequest>()),
+            );
+            spawn(
+                sentry
+                    .clone()
+                    .relay_dht_gets(network.receive_requests_impl::<RelayDhtGet>()),
+            );
+            spawn(sentry.relay_dht_puts(network.receive_requests_impl::<RelayDhtPut>()));
         }
+
+        network
     }
 
     pub fn local_peer_id(&self) -> &PeerId {
@@ -199,6 +257,37 @@ impl Network {
         }
     }
 
+    /// Returns the peers a request to `peer_id` needs to be relayed through, in order. These are
+    /// one of our sentries if we run in private mode and one of the relays of `peer_id` if it
+    /// runs in private mode itself.
+    fn relay_hops(&self, peer_id: PeerId) -> Vec<PeerId> {
+        if self.has_peer(peer_id) {
+            return vec![];
+        }
+
+        let mut hops: Vec<PeerId> = self
+            .choose_relay(&self.local_relays)
+            .filter(|sentry| *sentry != peer_id)
+            .into_iter()
+            .collect();
+        if let Some(relays) = self.peer_relays.read().get(&peer_id) {
+            // If our sentry is a relay of the peer as well, it is connected to the peer.
+            if !relays.iter().any(|relay| hops.contains(relay)) {
+                hops.extend(self.choose_relay(relays));
+            }
+        }
+        hops
+    }
+
+    /// Chooses one of the given relays, preferring the ones we are connected to.
+    fn choose_relay(&self, relays: &[PeerId]) -> Option<PeerId> {
+        relays
+            .iter()
+            .find(|relay| self.has_peer(**relay))
+            .or(relays.first())
+            .copied()
+    }
+
     async fn request_impl<Req: RequestCommon>(
         &self,
         request: Req,
@@ -207,9 +296,13 @@ impl Network {
         let (output_tx, output_rx) = oneshot::channel();
         let (response_tx, response_rx) = oneshot::channel();
 
+        // Peers we can't connect to are reached through their relays or our sentries.
+        let hops = self.relay_hops(peer_id);
+        let (next_hop, request) = relay::wrap_request(peer_id, request.serialize_request(), &hops);
+
         let action = NetworkAction::SendRequest {
-            peer_id,
-            request: request.serialize_request()[..].into(),
+            peer_id: next_hop,
+            request,
             response_channel: response_tx,
             output: output_tx,
         };
@@ -249,7 +342,10 @@ impl Network {
             return Err(OutboundRequestError::SenderFutureDropped.into());
         };
 
-        let data = result?;
+        let mut data = result?.to_vec();
+        for _ in &hops {
+            data = relay::unwrap_response(&data)?;
+        }
 
         let result = <Result<Req::Response, InboundRequestError>>::deserialize_take(&data);
         let Ok((message, left_over)) = result else {
@@ -259,6 +355,11 @@ impl Network {
                 %peer_id,
                 "Failed to deserialize response",
             );
+            // Let the peer's reputation take the hit. The network task might already be gone.
+            self.action_tx
+                .send(NetworkAction::InvalidResponse { peer_id: next_hop })
+                .await
+                .ok();
             return Err(InboundRequestError::DeSerializationError.into());
         };
 
@@ -368,6 +469,68 @@ impl Network {
         self.connected_peers.read().len()
     }
 
+    /// Gets the known addresses of a peer from the peer contact book
+    pub fn get_peer_addresses(&self, peer_id: &PeerId) -> Vec<Multiaddr> {
+        self.contacts
+            .read()
+            .get(peer_id)
+            .map(|contact| contact.addresses().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Gets the time at which a connected peer joined
+    pub fn get_peer_connected_since(&self, peer_id: &PeerId) -> Option<SystemTime> {
+        self.connected_peers
+            .read()
+            .get(peer_id)
+            .map(|peer| peer.connected_since)
+    }
+
+    /// Bans a peer ID or address for the given duration.
+    /// Existing connections to the banned peer or address are closed.
+    pub async fn ban(&self, target: BanTarget, duration: Duration) -> Result<(), NetworkError> {
+        self.action_tx
+            .send(NetworkAction::Ban { target, duration })
+            .await?;
+        Ok(())
+    }
+
+    /// Lifts a ban. Returns whether the peer ID or address was banned.
+    pub async fn unban(&self, target: BanTarget) -> Result<bool, NetworkError> {
+        let (output_tx, output_rx) = oneshot::channel();
+
+        self.action_tx
+            .send(NetworkAction::Unban {
+                target,
+                output: output_tx,
+            })
+            .await?;
+        Ok(output_rx.await?)
+    }
+
+    /// Gets all active bans
+    pub async fn get_bans(&self) -> Result<Vec<Ban>, NetworkError> {
+        let (output_tx, output_rx) = oneshot::channel();
+
+        self.action_tx
+            .send(NetworkAction::GetBans { output: output_tx })
+            .await?;
+        Ok(output_rx.await?)
+    }
+
+    /// Gets the reputation of every peer that has misbehaved. All other peers have the default
+    /// reputation.
+    pub async fn get_peer_reputations(
+        &self,
+    ) -> Result<HashMap<PeerId, PeerReputation>, NetworkError> {
+        let (output_tx, output_rx) = oneshot::channel();
+
+        self.action_tx
+            .send(NetworkAction::GetReputations { output: output_tx })
+            .await?;
+        Ok(output_rx.await?)
+    }
+
     /// Disconnects from (closes the connection to) all peers with a reason
     pub async fn disconnect(&self, reason: CloseReason) {
         for peer_id in self.get_peers() {
@@ -439,12 +602,15 @@ impl Network {
         T: Topic + Sync,
     {
         let (output_tx, output_rx) = oneshot::channel();
+        let data = item.serialize_to_vec();
+        #[cfg(feature = "metrics")]
+        let size = data.len();
 
         self.action_tx
             .clone()
             .send(NetworkAction::Publish {
                 topic_name,
-                data: item.serialize_to_vec(),
+                data,
                 output: output_tx,
             })
             .await?;
@@ -453,7 +619,7 @@ impl Network {
 
         #[cfg(feature = "metrics")]
         self.metrics
-            .note_published_pubsub_message(<T as Topic>::NAME);
+            .note_published_pubsub_message(<T as Topic>::NAME, size);
 
         Ok(())
     }
@@ -476,7 +642,17 @@ impl NetworkInterface for Network {
     }
 
     fn get_peer_info(&self, peer_id: Self::PeerId) -> Option<PeerInfo> {
-        self.connected_peers.read().get(&peer_id).cloned()
+        self.connected_peers
+            .read()
+            .get(&peer_id)
+            .map(|peer| peer.info.clone())
+    }
+
+    fn get_peer_rtt(&self, peer_id: Self::PeerId) -> Option<Duration> {
+        self.connected_peers
+            .read()
+            .get(&peer_id)
+            .and_then(|peer| peer.rtt)
     }
 
     async fn get_peers_by_services(
@@ -523,8 +699,8 @@ impl NetworkInterface for Network {
     }
 
     fn peer_provides_required_services(&self, peer_id: PeerId) -> bool {
-        if let Some(peer_info) = self.connected_peers.read().get(&peer_id) {
-            peer_info.get_services().contains(self.required_services)
+        if let Some(peer) = self.connected_peers.read().get(&peer_id) {
+            peer.info.get_services().contains(self.required_services)
         } else {
             // If we don't know the peer we return false
             false
@@ -532,8 +708,8 @@ impl NetworkInterface for Network {
     }
 
     fn peer_provides_services(&self, peer_id: PeerId, services: Services) -> bool {
-        if let Some(peer_info) = self.connected_peers.read().get(&peer_id) {
-            peer_info.get_services().contains(services)
+        if let Some(peer) = self.connected_peers.read().get(&peer_id) {
+            peer.info.get_services().contains(services)
         } else {
             // If we don't know the peer we return false
             false
@@ -631,16 +807,26 @@ impl NetworkInterface for Network {
         V: Deserialize + Send + Sync + TaggedSignable + Ord,
         T: TaggedKeyPair + Send + Sync + Serialize + Deserialize,
     {
-        let (output_tx, output_rx) = oneshot::channel();
-        self.action_tx
-            .clone()
-            .send(NetworkAction::DhtGet {
+        let data = if let Some(sentry) = self.choose_relay(&self.local_relays) {
+            // In private mode, our sentries look up records for us.
+            let request = RelayDhtGet {
                 key: k.as_ref().to_owned(),
-                output: output_tx,
-            })
-            .await?;
-
-        let data = output_rx.await??;
+            };
+            match self.request_impl(request, sentry).await? {
+                Some(data) => data,
+                None => return Ok(None),
+            }
+        } else {
+            let (output_tx, output_rx) = oneshot::channel();
+            self.action_tx
+                .clone()
+                .send(NetworkAction::DhtGet {
+                    key: k.as_ref().to_owned(),
+                    output: output_tx,
+                })
+                .await?;
+            output_rx.await??
+        };
         // Now decode the signed record and returned the tagged signable record
         let signed_record: TaggedSigned<V, T> = Deserialize::deserialize_from_vec(&data)?;
         Ok(Some(signed_record.record))
@@ -655,6 +841,19 @@ impl NetworkInterface for Network {
         // Sign the record before transmitting it to the swarm
         let signature = keypair.tagged_sign(v);
         let signed_record = TaggedSigned::new(v.clone(), signature);
+
+        if let Some(sentry) = self.choose_relay(&self.local_relays) {
+            // In private mode, our sentries publish records on our behalf.
+            let request = RelayDhtPut {
+                key: k.as_ref().to_owned(),
+                value: signed_record.serialize_to_vec(),
+            };
+            if !self.request_impl(request, sentry).await? {
+                return Err(NetworkError::Relay(InboundRequestError::RelayFailed.into()));
+            }
+            return Ok(());
+        }
+
         let (output_tx, output_rx) = oneshot::channel();
 
         self.action_tx
@@ -696,6 +895,19 @@ impl NetworkInterface for Network {
         self.local_peer_id
     }
 
+    fn set_peer_relays(&self, peer_id: PeerId, relays: Vec<PeerId>) {
+        let mut peer_relays = self.peer_relays.write();
+        if relays.is_empty() {
+            peer_relays.remove(&peer_id);
+        } else {
+            peer_relays.insert(peer_id, relays);
+        }
+    }
+
+    fn get_local_relays(&self) -> Vec<PeerId> {
+        self.local_relays.clone()
+    }
+
     async fn message<M: Message>(&self, message: M, peer_id: PeerId) -> Result<(), RequestError> {
         self.request_impl(message, peer_id).await
     }
@@ -1,16 +1,28 @@
 use std::time::Duration;
 
-use libp2p::gossipsub::TopicHash;
+use libp2p::{
+    gossipsub::TopicHash,
+    request_response::{InboundFailure, OutboundFailure},
+};
+use nimiq_network_interface::{peer_info::Services, request::RequestType};
 use prometheus_client::{
     encoding::EncodeLabelSet,
     metrics::{counter::Counter, family::Family, histogram::Histogram},
     registry::Registry,
 };
 
+use crate::rate_limiting::RateLimitId;
+
 pub struct NetworkMetrics {
-    gossipsub_messages_received: Family<TopicLabels, Counter>,
+    gossipsub_messages_received: Family<ReceivedTopicLabels, Counter>,
+    gossipsub_bytes_received: Family<ReceivedTopicLabels, Counter>,
     gossipsub_messages_published: Family<TopicLabels, Counter>,
+    gossipsub_bytes_published: Family<TopicLabels, Counter>,
     response_times: Histogram,
+    request_messages: Family<RequestLabels, Counter>,
+    request_bytes: Family<RequestLabels, Counter>,
+    request_errors: Family<RequestErrorLabels, Counter>,
+    rate_limit_hits: Family<RateLimitLabels, Counter>,
 }
 
 #[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
@@ -18,12 +30,61 @@ struct TopicLabels {
     topic: String,
 }
 
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
+struct ReceivedTopicLabels {
+    topic: String,
+    node_type: &'static str,
+}
+
+/// Whether traffic was received from or sent to a peer.
+#[derive(Clone, Copy, Debug)]
+pub(crate) enum Direction {
+    Inbound,
+    Outbound,
+}
+
+impl Direction {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Direction::Inbound => "inbound",
+            Direction::Outbound => "outbound",
+        }
+    }
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
+struct RequestLabels {
+    request_type: String,
+    /// Either `request` or `response`.
+    message: &'static str,
+    direction: &'static str,
+    node_type: &'static str,
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
+struct RequestErrorLabels {
+    request_type: String,
+    direction: &'static str,
+    error: &'static str,
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
+struct RateLimitLabels {
+    rate_limit: String,
+}
+
 impl Default for NetworkMetrics {
     fn default() -> Self {
         NetworkMetrics {
             gossipsub_messages_received: Default::default(),
+            gossipsub_bytes_received: Default::default(),
             gossipsub_messages_published: Default::default(),
+            gossipsub_bytes_published: Default::default(),
             response_times: Histogram::new([0.01, 0.02, 0.05, 0.1, 0.2, 0.5, 1.0, 2.0].into_iter()),
+            request_messages: Default::default(),
+            request_bytes: Default::default(),
+            request_errors: Default::default(),
+            rate_limit_hits: Default::default(),
         }
     }
 }
@@ -36,21 +97,64 @@ impl NetworkMetrics {
             self.gossipsub_messages_received.clone(),
         );
 
+        registry.register(
+            "gossipsub_bytes_received",
+            "Size of received gossipsub messages in bytes",
+            self.gossipsub_bytes_received.clone(),
+        );
+
         registry.register(
             "gossipsub_messages_published",
             "Number of published gossipsub messages",
             self.gossipsub_messages_published.clone(),
         );
 
+        registry.register(
+            "gossipsub_bytes_published",
+            "Size of published gossipsub messages in bytes",
+            self.gossipsub_bytes_published.clone(),
+        );
+
         registry.register(
             "request_durations",
             "Time between requests and responses",
             self.response_times.clone(),
         );
+
+        registry.register(
+            "request_messages",
+            "Number of requests and responses per request type",
+            self.request_messages.clone(),
+        );
+
+        registry.register(
+            "request_bytes",
+            "Size of requests and responses per request type in bytes",
+            self.request_bytes.clone(),
+        );
+
+        registry.register(
+            "request_errors",
+            "Number of failed requests per request type",
+            self.request_errors.clone(),
+        );
+
+        registry.register(
+            "rate_limit_hits",
+            "Number of requests and messages dropped because a peer exceeded a rate limit",
+            self.rate_limit_hits.clone(),
+        );
     }
 
-    pub(crate) fn note_received_pubsub_message(&self, topic: &TopicHash) {
-        if [
+    pub(crate) fn note_received_pubsub_message(
+        &self,
+        topic: &TopicHash,
+        services: Option<Services>,
+        size: usize,
+    ) {
+        // Only known topics get their own label to bound the number of time series.
+        let topic = topic.to_string();
+        let topic = if [
             "address-subscription",
             "block-body",
             "block-header",
@@ -59,31 +163,141 @@ impl NetworkMetrics {
             "tendermint-proposal",
             "zk-proof",
         ]
-        .contains(&&*topic.to_string())
+        .contains(&topic.as_str())
         {
-            self.gossipsub_messages_received
-                .get_or_create(&TopicLabels {
-                    topic: topic.to_string(),
-                })
-                .inc();
+            topic
         } else {
-            self.gossipsub_messages_received
-                .get_or_create(&TopicLabels {
-                    topic: "unknown".into(),
-                })
-                .inc();
-        }
+            "unknown".into()
+        };
+
+        let labels = ReceivedTopicLabels {
+            topic,
+            node_type: node_type(services),
+        };
+        self.gossipsub_messages_received
+            .get_or_create(&labels)
+            .inc();
+        self.gossipsub_bytes_received
+            .get_or_create(&labels)
+            .inc_by(size as u64);
     }
 
-    pub(crate) fn note_published_pubsub_message(&self, topic_str: &str) {
+    pub(crate) fn note_published_pubsub_message(&self, topic_str: &str, size: usize) {
+        let labels = TopicLabels {
+            topic: String::from(topic_str),
+        };
         self.gossipsub_messages_published
-            .get_or_create(&TopicLabels {
-                topic: String::from(topic_str),
+            .get_or_create(&labels)
+            .inc();
+        self.gossipsub_bytes_published
+            .get_or_create(&labels)
+            .inc_by(size as u64);
+    }
+
+    /// Counts a request sent to or received from a peer with the given services.
+    pub(crate) fn note_request(
+        &self,
+        request_type: RequestType,
+        direction: Direction,
+        services: Option<Services>,
+        size: usize,
+    ) {
+        self.note_request_message(request_type, "request", direction, services, size);
+    }
+
+    /// Counts a response sent to or received from a peer with the given services.
+    pub(crate) fn note_response(
+        &self,
+        request_type: RequestType,
+        direction: Direction,
+        services: Option<Services>,
+        size: usize,
+    ) {
+        self.note_request_message(request_type, "response", direction, services, size);
+    }
+
+    fn note_request_message(
+        &self,
+        request_type: RequestType,
+        message: &'static str,
+        direction: Direction,
+        services: Option<Services>,
+        size: usize,
+    ) {
+        let labels = RequestLabels {
+            request_type: request_type.to_string(),
+            message,
+            direction: direction.as_str(),
+            node_type: node_type(services),
+        };
+        self.request_messages.get_or_create(&labels).inc();
+        self.request_bytes
+            .get_or_create(&labels)
+            .inc_by(size as u64);
+    }
+
+    /// Counts a failed request. Outbound errors are requests we sent that failed, inbound errors
+    /// are requests we received but couldn't respond to.
+    pub(crate) fn note_request_error(
+        &self,
+        request_type: RequestType,
+        direction: Direction,
+        error: &'static str,
+    ) {
+        self.request_errors
+            .get_or_create(&RequestErrorLabels {
+                request_type: request_type.to_string(),
+                direction: direction.as_str(),
+                error,
             })
             .inc();
     }
 
+    /// Counts a request we sent that failed.
+    pub(crate) fn note_outbound_failure(&self, request_type: RequestType, error: &OutboundFailure) {
+        let error = match error {
+            OutboundFailure::DialFailure => "dial_failure",
+            OutboundFailure::Timeout => "timeout",
+            OutboundFailure::ConnectionClosed => "connection_closed",
+            OutboundFailure::UnsupportedProtocols => "unsupported_protocols",
+            OutboundFailure::Io(_) => "io",
+        };
+        self.note_request_error(request_type, Direction::Outbound, error);
+    }
+
+    /// Counts a request we received but couldn't respond to.
+    pub(crate) fn note_inbound_failure(&self, request_type: RequestType, error: &InboundFailure) {
+        let error = match error {
+            InboundFailure::Timeout => "timeout",
+            InboundFailure::ConnectionClosed => "connection_closed",
+            InboundFailure::UnsupportedProtocols => "unsupported_protocols",
+            InboundFailure::ResponseOmission => "response_omission",
+            InboundFailure::Io(_) => "io",
+        };
+        self.note_request_error(request_type, Direction::Inbound, error);
+    }
+
     pub(crate) fn note_response_time(&self, duration: Duration) {
         self.response_times.observe(duration.as_secs_f64());
     }
+
+    pub(crate) fn note_rate_limit_hit(&self, rate_limit_id: &RateLimitId) {
+        self.rate_limit_hits
+            .get_or_create(&RateLimitLabels {
+                rate_limit: rate_limit_id.to_string(),
+            })
+            .inc();
+    }
+}
+
+/// Classifies a peer by the services it announced. Peers that haven't completed the discovery
+/// handshake yet are `unknown`.
+fn node_type(services: Option<Services>) -> &'static str {
+    match services {
+        None => "unknown",
+        Some(services) if services.contains(Services::VALIDATOR) => "validator",
+        Some(services) if services.contains(Services::HISTORY) => "history",
+        Some(services) if services.contains(Services::FULL_BLOCKS) => "full",
+        Some(_) => "light",
+    }
 }
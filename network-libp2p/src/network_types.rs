@@ -1,8 +1,12 @@
-use std::collections::HashMap;
+use std::{
+    collections::{HashMap, HashSet},
+    time::Duration,
+};
 
 use bytes::Bytes;
 #[cfg(feature = "metrics")]
 use instant::Instant;
+use instant::SystemTime;
 use libp2p::{
     gossipsub,
     kad::{QueryId, Record},
@@ -13,7 +17,7 @@ use libp2p::{
 use nimiq_keys::KeyPair;
 use nimiq_network_interface::{
     network::{CloseReason, MsgAcceptance, PubsubId, Topic},
-    peer_info::Services,
+    peer_info::{PeerInfo, Services},
     request::{RequestError, RequestType},
 };
 use nimiq_serde::{Deserialize, DeserializeError};
@@ -24,8 +28,11 @@ use tokio::sync::{mpsc, oneshot};
 
 use crate::{
     autonat::NatState,
+    capture::TrafficCapture,
+    connection_pool::ban_list::{Ban, BanTarget},
     dispatch::codecs::{IncomingRequest, OutgoingResponse},
-    rate_limiting::RateLimitConfig,
+    rate_limiting::{RateLimitConfig, RateLimitOverrides},
+    reputation::{PeerReputation, PeerReputations},
     NetworkError,
 };
 
@@ -101,6 +108,34 @@ pub(crate) enum NetworkAction {
         peer_id: PeerId,
         reason: CloseReason,
     },
+    Ban {
+        target: BanTarget,
+        duration: Duration,
+    },
+    Unban {
+        target: BanTarget,
+        output: oneshot::Sender<bool>,
+    },
+    GetBans {
+        output: oneshot::Sender<Vec<Ban>>,
+    },
+    GetReputations {
+        output: oneshot::Sender<HashMap<PeerId, PeerReputation>>,
+    },
+    InvalidResponse {
+        peer_id: PeerId,
+    },
+}
+
+/// A peer we are connected to.
+#[derive(Clone, Debug)]
+pub(crate) struct ConnectedPeer {
+    /// The peer information as announced when the peer joined.
+    pub(crate) info: PeerInfo,
+    /// The time at which the peer joined.
+    pub(crate) connected_since: SystemTime,
+    /// The round-trip time measured by the last successful ping.
+    pub(crate) rtt: Option<Duration>,
 }
 
 pub(crate) struct ValidateMessage<P: Clone> {
@@ -244,9 +279,12 @@ pub(crate) struct TaskState {
     pub(crate) nat_status: NatState,
     /// Senders per `OutboundRequestId` for request-response
     pub(crate) requests: HashMap<OutboundRequestId, oneshot::Sender<Result<Bytes, RequestError>>>,
-    /// Time spent per `OutboundRequestId` for request-response
+    /// Start time and request type per `OutboundRequestId` for request-response
+    #[cfg(feature = "metrics")]
+    pub(crate) requests_initiated: HashMap<OutboundRequestId, (Instant, RequestType)>,
+    /// Request type and requesting peer per `InboundRequestId` that awaits our response
     #[cfg(feature = "metrics")]
-    pub(crate) requests_initiated: HashMap<OutboundRequestId, Instant>,
+    pub(crate) requests_received: HashMap<InboundRequestId, (RequestType, PeerId)>,
     /// Senders for receiving responses per `InboundRequestId` for request-response
     pub(crate) response_channels:
         HashMap<InboundRequestId, ResponseChannel<Option<OutgoingResponse>>>,
@@ -260,6 +298,17 @@ pub(crate) struct TaskState {
     >,
     /// DHT quorum value
     pub(crate) dht_quorum: u8,
+    /// Rate limits configured by the operator, replacing the ones of the request and topic types
+    pub(crate) rate_limit_overrides: RateLimitOverrides,
+    /// Reputation of peers, lowered by rate limit violations and invalid responses
+    pub(crate) reputations: PeerReputations,
+    /// Records gossipsub messages and requests if traffic capture is enabled
+    pub(crate) capture: Option<TrafficCapture>,
+    /// Peers in private mode we are a sentry for
+    pub(crate) sentry_for: HashSet<PeerId>,
+    /// Topics we are subscribed to on behalf of the peers we are a sentry for, with the peers
+    /// subscribed to them
+    pub(crate) relayed_topics: HashMap<gossipsub::TopicHash, HashSet<PeerId>>,
 }
 
 #[derive(Clone, Debug)]
@@ -1,6 +1,7 @@
 use std::{
     cmp::Ordering,
     collections::{BTreeSet, HashMap},
+    fmt,
     time::Duration,
 };
 
@@ -10,15 +11,16 @@ use nimiq_network_interface::{
     network::Topic,
     request::{RequestCommon, RequestType},
 };
+use thiserror::Error;
 
 /// The rate limiting request metadata that will be passed on between the network and the swarm.
 /// This is not sent through the wire.
-#[derive(Debug, PartialEq)]
-pub(crate) struct RateLimitConfig {
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct RateLimitConfig {
     /// Maximum requests allowed in the time window.
-    pub(crate) max_requests: u32,
+    pub max_requests: u32,
     ///  The range/window of time to consider.
-    pub(crate) time_window: Duration,
+    pub time_window: Duration,
 }
 
 impl RateLimitConfig {
@@ -37,12 +39,91 @@ impl RateLimitConfig {
     }
 }
 
+/// Error returned for rate limits that would deny every request or message.
+#[derive(Debug, Error, Eq, PartialEq)]
+pub enum InvalidRateLimitError {
+    #[error("The maximum number of requests must not be zero")]
+    ZeroMaxRequests,
+    #[error("The time window must not be zero")]
+    ZeroTimeWindow,
+}
+
+/// Rate limits that replace the ones defined by the request and topic types, so they can be tuned
+/// without recompiling.
+#[derive(Clone, Debug, Default)]
+pub struct RateLimitOverrides {
+    /// Overrides by request type ID.
+    requests: HashMap<u16, RateLimitConfig>,
+    /// Overrides by gossipsub topic name.
+    topics: HashMap<String, RateLimitConfig>,
+}
+
+impl RateLimitOverrides {
+    /// Overrides the rate limit of the request type with the given type ID.
+    pub fn set_request_limit(
+        &mut self,
+        type_id: u16,
+        rate_limit: RateLimitConfig,
+    ) -> Result<(), InvalidRateLimitError> {
+        Self::validate(&rate_limit)?;
+        self.requests.insert(type_id, rate_limit);
+        Ok(())
+    }
+
+    /// Overrides the rate limit of the gossipsub topic with the given name.
+    pub fn set_topic_limit(
+        &mut self,
+        topic_name: String,
+        rate_limit: RateLimitConfig,
+    ) -> Result<(), InvalidRateLimitError> {
+        Self::validate(&rate_limit)?;
+        self.topics.insert(topic_name, rate_limit);
+        Ok(())
+    }
+
+    fn validate(rate_limit: &RateLimitConfig) -> Result<(), InvalidRateLimitError> {
+        if rate_limit.max_requests == 0 {
+            return Err(InvalidRateLimitError::ZeroMaxRequests);
+        }
+        if rate_limit.time_window.is_zero() {
+            return Err(InvalidRateLimitError::ZeroTimeWindow);
+        }
+        Ok(())
+    }
+
+    /// Returns the rate limit to enforce for the given request type.
+    pub(crate) fn for_request(
+        &self,
+        request_type: RequestType,
+        default: RateLimitConfig,
+    ) -> RateLimitConfig {
+        self.requests
+            .get(&request_type.type_id())
+            .copied()
+            .unwrap_or(default)
+    }
+
+    /// Returns the rate limit to enforce for the given gossipsub topic.
+    pub(crate) fn for_topic(&self, topic_name: &str, default: RateLimitConfig) -> RateLimitConfig {
+        self.topics.get(topic_name).copied().unwrap_or(default)
+    }
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub(crate) enum RateLimitId {
     Request(RequestType),
     Gossipsub(TopicHash),
 }
 
+impl fmt::Display for RateLimitId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RateLimitId::Request(request_type) => write!(f, "request-{}", request_type),
+            RateLimitId::Gossipsub(topic) => write!(f, "topic-{}", topic),
+        }
+    }
+}
+
 /// Holds the expiration time for a given peer and request type. This struct defines the ordering for the btree set.
 /// The smaller expiration times come first.
 #[derive(Debug, Eq, Hash, PartialEq, Clone)]
@@ -287,3 +368,77 @@ impl RateLimits {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use nimiq_network_interface::request::RequestType;
+
+    use super::{InvalidRateLimitError, RateLimitConfig, RateLimitOverrides};
+
+    const DEFAULT: RateLimitConfig = RateLimitConfig {
+        max_requests: 10,
+        time_window: Duration::from_secs(60),
+    };
+
+    #[test]
+    fn overrides_replace_defaults() {
+        let rate_limit = RateLimitConfig {
+            max_requests: 100,
+            time_window: Duration::from_secs(10),
+        };
+        let mut overrides = RateLimitOverrides::default();
+        overrides.set_request_limit(204, rate_limit).unwrap();
+        overrides
+            .set_topic_limit("block-header".to_string(), rate_limit)
+            .unwrap();
+
+        assert_eq!(
+            overrides.for_request(RequestType::request(204), DEFAULT),
+            rate_limit
+        );
+        assert_eq!(
+            overrides.for_request(RequestType::message(204), DEFAULT),
+            rate_limit
+        );
+        assert_eq!(
+            overrides.for_request(RequestType::request(205), DEFAULT),
+            DEFAULT
+        );
+        assert_eq!(overrides.for_topic("block-header", DEFAULT), rate_limit);
+        assert_eq!(overrides.for_topic("transactions", DEFAULT), DEFAULT);
+    }
+
+    #[test]
+    fn overrides_reject_limits_denying_everything() {
+        let mut overrides = RateLimitOverrides::default();
+        assert_eq!(
+            overrides.set_request_limit(
+                204,
+                RateLimitConfig {
+                    max_requests: 0,
+                    time_window: Duration::from_secs(10),
+                }
+            ),
+            Err(InvalidRateLimitError::ZeroMaxRequests)
+        );
+        assert_eq!(
+            overrides.set_topic_limit(
+                "block-header".to_string(),
+                RateLimitConfig {
+                    max_requests: 10,
+                    time_window: Duration::ZERO,
+                }
+            ),
+            Err(InvalidRateLimitError::ZeroTimeWindow)
+        );
+
+        // Rejected overrides don't replace the defaults.
+        assert_eq!(
+            overrides.for_request(RequestType::request(204), DEFAULT),
+            DEFAULT
+        );
+        assert_eq!(overrides.for_topic("block-header", DEFAULT), DEFAULT);
+    }
+}
@@ -0,0 +1,278 @@
+//! Relaying for nodes in private mode.
+//!
+//! A node in private mode only connects to the peers on its allow list, its sentries. Requests
+//! between the private node and any other peer are wrapped in a [`RelayRequest`] which a sentry
+//! unwraps and forwards to the target. Requests a sentry forwards to the private node are wrapped
+//! in a [`RelayedRequest`] naming their original sender. DHT records are read and written by the
+//! sentries on behalf of the private node.
+
+use std::{collections::HashSet, sync::Arc, time::Duration};
+
+use bytes::Bytes;
+use futures::{stream::BoxStream, StreamExt};
+use libp2p::{request_response::InboundRequestId, PeerId};
+use nimiq_network_interface::request::{
+    peek_type, InboundRequestError, OutboundRequestError, RequestCommon, RequestError,
+    RequestMarker, RequestSerialize, RequestType,
+};
+use nimiq_serde::{Deserialize, Serialize};
+use nimiq_time::timeout;
+use tokio::sync::{mpsc, oneshot};
+
+use crate::network_types::NetworkAction;
+
+/// Shorter than the request timeout, such that the requester learns about a failed relay before
+/// its own request times out.
+const RELAY_TIMEOUT: Duration = Duration::from_secs(8);
+
+/// Number of relayed requests a sentry handles concurrently.
+const MAX_CONCURRENT_RELAYS: usize = 256;
+
+/// Asks a sentry to forward a request to `target`. The response is the serialized response of
+/// `target`.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub(crate) struct RelayRequest {
+    /// The peer to forward the request to.
+    pub(crate) target: PeerId,
+    /// The serialized request, including its type.
+    pub(crate) request: Vec<u8>,
+}
+
+impl RequestCommon for RelayRequest {
+    type Kind = RequestMarker;
+    const TYPE_ID: u16 = 300;
+    type Response = Vec<u8>;
+    const MAX_REQUESTS: u32 = 1000;
+}
+
+/// A request a sentry forwards to the node in private mode it protects. The private node handles
+/// the inner request as if it was sent by `origin` and responds to it directly.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub(crate) struct RelayedRequest {
+    /// The peer that sent the request to the sentry.
+    pub(crate) origin: PeerId,
+    /// The serialized request, including its type.
+    pub(crate) request: Vec<u8>,
+}
+
+impl RequestCommon for RelayedRequest {
+    type Kind = RequestMarker;
+    const TYPE_ID: u16 = 301;
+    type Response = Vec<u8>;
+    const MAX_REQUESTS: u32 = 1000;
+}
+
+/// Asks a sentry to get a DHT record. The response is `None` if the record couldn't be found.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub(crate) struct RelayDhtGet {
+    pub(crate) key: Vec<u8>,
+}
+
+impl RequestCommon for RelayDhtGet {
+    type Kind = RequestMarker;
+    const TYPE_ID: u16 = 302;
+    type Response = Option<Vec<u8>>;
+    const MAX_REQUESTS: u32 = 100;
+}
+
+/// Asks a sentry to put a signed DHT record. The response tells whether the record was stored.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub(crate) struct RelayDhtPut {
+    pub(crate) key: Vec<u8>,
+    pub(crate) value: Vec<u8>,
+}
+
+impl RequestCommon for RelayDhtPut {
+    type Kind = RequestMarker;
+    const TYPE_ID: u16 = 303;
+    type Response = bool;
+    const MAX_REQUESTS: u32 = 100;
+}
+
+/// Wraps a serialized request for `target` such that it is relayed through `hops`, in order.
+/// Returns the peer to send the wrapped request to, which is `target` itself if there are no hops.
+pub(crate) fn wrap_request(target: PeerId, request: Vec<u8>, hops: &[PeerId]) -> (PeerId, Vec<u8>) {
+    hops.iter()
+        .rev()
+        .fold((target, request), |(target, request), hop| {
+            (*hop, RelayRequest { target, request }.serialize_request())
+        })
+}
+
+/// Unwraps the serialized response to a [`RelayRequest`] into the serialized response of the peer
+/// it was forwarded to.
+pub(crate) fn unwrap_response(data: &[u8]) -> Result<Vec<u8>, InboundRequestError> {
+    <Result<Vec<u8>, InboundRequestError>>::deserialize_from_vec(data)
+        .map_err(|_| InboundRequestError::DeSerializationError)?
+}
+
+/// Unwraps a [`RelayedRequest`] into its original sender, the inner request and its type.
+pub(crate) fn unwrap_relayed_request(
+    data: &[u8],
+) -> Result<(PeerId, Vec<u8>, RequestType), nimiq_serde::DeserializeError> {
+    let relayed = RelayedRequest::deserialize_request(data)?;
+    let type_id = peek_type(&relayed.request)?;
+    Ok((relayed.origin, relayed.request, type_id))
+}
+
+/// Relays the traffic of the nodes in private mode we are a sentry for.
+#[derive(Clone)]
+pub(crate) struct Sentry {
+    action_tx: mpsc::Sender<NetworkAction>,
+    /// The peers we are a sentry for.
+    protected_peers: Arc<HashSet<PeerId>>,
+}
+
+impl Sentry {
+    pub(crate) fn new(
+        action_tx: mpsc::Sender<NetworkAction>,
+        protected_peers: Vec<PeerId>,
+    ) -> Self {
+        Self {
+            action_tx,
+            protected_peers: Arc::new(protected_peers.into_iter().collect()),
+        }
+    }
+
+    /// Forwards requests from the protected peers to any other peer and requests from any other
+    /// peer to the protected peers.
+    pub(crate) async fn relay_requests(
+        self,
+        requests: BoxStream<'static, (RelayRequest, InboundRequestId, PeerId)>,
+    ) {
+        requests
+            .for_each_concurrent(MAX_CONCURRENT_RELAYS, |(request, request_id, sender)| {
+                let sentry = self.clone();
+                async move {
+                    let result = if sentry.protected_peers.contains(&sender) {
+                        send_request(&sentry.action_tx, request.target, request.request).await
+                    } else if sentry.protected_peers.contains(&request.target) {
+                        let relayed = RelayedRequest {
+                            origin: sender,
+                            request: request.request,
+                        };
+                        send_request(&sentry.action_tx, request.target, relayed.serialize_request())
+                            .await
+                    } else {
+                        debug!(%sender, target = %request.target, "Refusing to relay request between unprotected peers");
+                        Err(InboundRequestError::RelayFailed.into())
+                    };
+
+                    let response: Result<Vec<u8>, InboundRequestError> = result
+                        .map(|data| data.to_vec())
+                        .map_err(|error| {
+                            debug!(%sender, target = %request.target, %error, "Failed to relay request");
+                            InboundRequestError::RelayFailed
+                        });
+                    sentry
+                        .respond(request_id, response.serialize_to_vec())
+                        .await;
+                }
+            })
+            .await
+    }
+
+    /// Gets DHT records for the protected peers.
+    pub(crate) async fn relay_dht_gets(
+        self,
+        requests: BoxStream<'static, (RelayDhtGet, InboundRequestId, PeerId)>,
+    ) {
+        requests
+            .for_each_concurrent(MAX_CONCURRENT_RELAYS, |(request, request_id, sender)| {
+                let sentry = self.clone();
+                async move {
+                    let mut value = None;
+                    if sentry.protected_peers.contains(&sender) {
+                        let (output_tx, output_rx) = oneshot::channel();
+                        let action = NetworkAction::DhtGet {
+                            key: request.key,
+                            output: output_tx,
+                        };
+                        if sentry.action_tx.send(action).await.is_ok() {
+                            value = output_rx.await.ok().and_then(Result::ok);
+                        }
+                    }
+
+                    let response: Result<Option<Vec<u8>>, InboundRequestError> = Ok(value);
+                    sentry
+                        .respond(request_id, response.serialize_to_vec())
+                        .await;
+                }
+            })
+            .await
+    }
+
+    /// Puts DHT records of the protected peers. The records are published by us, which is why
+    /// validator records list their relays.
+    pub(crate) async fn relay_dht_puts(
+        self,
+        requests: BoxStream<'static, (RelayDhtPut, InboundRequestId, PeerId)>,
+    ) {
+        requests
+            .for_each_concurrent(MAX_CONCURRENT_RELAYS, |(request, request_id, sender)| {
+                let sentry = self.clone();
+                async move {
+                    let mut stored = false;
+                    if sentry.protected_peers.contains(&sender) {
+                        let (output_tx, output_rx) = oneshot::channel();
+                        let action = NetworkAction::DhtPut {
+                            key: request.key,
+                            value: request.value,
+                            output: output_tx,
+                        };
+                        if sentry.action_tx.send(action).await.is_ok() {
+                            stored = matches!(output_rx.await, Ok(Ok(())));
+                        }
+                    }
+
+                    let response: Result<bool, InboundRequestError> = Ok(stored);
+                    sentry
+                        .respond(request_id, response.serialize_to_vec())
+                        .await;
+                }
+            })
+            .await
+    }
+
+    async fn respond(&self, request_id: InboundRequestId, response: Vec<u8>) {
+        let (output_tx, output_rx) = oneshot::channel();
+        let action = NetworkAction::SendResponse {
+            request_id,
+            response,
+            output: output_tx,
+        };
+        if self.action_tx.send(action).await.is_err() {
+            return;
+        }
+        // The requesting peer might have disconnected already.
+        if let Ok(Err(error)) = output_rx.await {
+            debug!(%request_id, %error, "Failed to send relay response");
+        }
+    }
+}
+
+/// Sends a serialized request to a peer and returns its serialized response.
+async fn send_request(
+    action_tx: &mpsc::Sender<NetworkAction>,
+    peer_id: PeerId,
+    request: Vec<u8>,
+) -> Result<Bytes, RequestError> {
+    let (output_tx, output_rx) = oneshot::channel();
+    let (response_tx, response_rx) = oneshot::channel();
+
+    let action = NetworkAction::SendRequest {
+        peer_id,
+        request,
+        response_channel: response_tx,
+        output: output_tx,
+    };
+    if action_tx.send(action).await.is_err() || output_rx.await.is_err() {
+        return Err(OutboundRequestError::SendError.into());
+    }
+
+    match timeout(RELAY_TIMEOUT, response_rx).await {
+        Ok(Ok(result)) => result,
+        Ok(Err(_)) => Err(OutboundRequestError::SenderFutureDropped.into()),
+        Err(_) => Err(OutboundRequestError::Timeout.into()),
+    }
+}
@@ -0,0 +1,234 @@
+use std::collections::HashMap;
+
+use instant::Instant;
+use libp2p::PeerId;
+
+/// Configuration of the peer reputation.
+///
+/// Every peer starts with a score of zero. Misbehaviour lowers the score, which then slowly
+/// recovers back to zero over time.
+#[derive(Clone, Debug)]
+pub struct ReputationConfig {
+    /// Score deducted whenever a peer exceeds a request or message rate limit.
+    pub rate_limit_penalty: f64,
+    /// Score deducted whenever a peer sends a response that can't be deserialized.
+    pub invalid_response_penalty: f64,
+    /// Score regained per second.
+    pub recovery_per_second: f64,
+    /// Peers are disconnected once their score drops to this value.
+    pub disconnect_threshold: f64,
+    /// Peers are banned once their score drops to this value.
+    pub ban_threshold: f64,
+}
+
+impl Default for ReputationConfig {
+    fn default() -> Self {
+        Self {
+            rate_limit_penalty: 1.0,
+            invalid_response_penalty: 10.0,
+            recovery_per_second: 0.1,
+            disconnect_threshold: -50.0,
+            ban_threshold: -100.0,
+        }
+    }
+}
+
+/// Misbehaviour that affects the reputation of a peer.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum Offense {
+    RateLimitExceeded,
+    InvalidResponse,
+}
+
+/// What to do with a peer after its reputation changed.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum Verdict {
+    Keep,
+    Disconnect,
+    Ban,
+}
+
+/// The reputation of a peer as reported to the node operator.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct PeerReputation {
+    /// The current score. Zero is the best possible score.
+    pub score: f64,
+    /// Number of times the peer exceeded a request or message rate limit.
+    pub rate_limit_hits: u64,
+}
+
+#[derive(Debug)]
+struct Reputation {
+    score: f64,
+    last_update: Instant,
+    rate_limit_hits: u64,
+}
+
+impl Reputation {
+    /// Returns the score including the recovery since the last update.
+    fn score_at(&self, now: Instant, recovery_per_second: f64) -> f64 {
+        let elapsed = now.duration_since(self.last_update);
+        (self.score + elapsed.as_secs_f64() * recovery_per_second).min(0.0)
+    }
+}
+
+/// Keeps track of the reputation of peers. Reputations are kept while a peer is connected and
+/// after it disconnects until they fully recovered, such that a peer can't reset its score by
+/// reconnecting.
+#[derive(Debug, Default)]
+pub(crate) struct PeerReputations {
+    config: ReputationConfig,
+    reputations: HashMap<PeerId, Reputation>,
+}
+
+impl PeerReputations {
+    pub(crate) fn new(config: ReputationConfig) -> Self {
+        Self {
+            config,
+            reputations: HashMap::new(),
+        }
+    }
+
+    /// Returns the current score of a peer.
+    pub(crate) fn score(&self, peer_id: &PeerId) -> f64 {
+        self.get(peer_id).score
+    }
+
+    /// Returns the current reputation of a peer.
+    pub(crate) fn get(&self, peer_id: &PeerId) -> PeerReputation {
+        self.reputations
+            .get(peer_id)
+            .map(|reputation| self.to_peer_reputation(reputation, Instant::now()))
+            .unwrap_or_default()
+    }
+
+    /// Returns the current reputation of every peer that has misbehaved. All other peers have
+    /// the default reputation.
+    pub(crate) fn all(&self) -> HashMap<PeerId, PeerReputation> {
+        let now = Instant::now();
+        self.reputations
+            .iter()
+            .map(|(peer_id, reputation)| (*peer_id, self.to_peer_reputation(reputation, now)))
+            .collect()
+    }
+
+    fn to_peer_reputation(&self, reputation: &Reputation, now: Instant) -> PeerReputation {
+        PeerReputation {
+            score: reputation.score_at(now, self.config.recovery_per_second),
+            rate_limit_hits: reputation.rate_limit_hits,
+        }
+    }
+
+    /// Lowers the score of a peer for the given offense and returns what should happen to it.
+    /// Once a peer is banned, its score is reset as the ban list takes over.
+    pub(crate) fn penalize(&mut self, peer_id: PeerId, offense: Offense) -> Verdict {
+        let penalty = match offense {
+            Offense::RateLimitExceeded => self.config.rate_limit_penalty,
+            Offense::InvalidResponse => self.config.invalid_response_penalty,
+        };
+
+        let now = Instant::now();
+        let reputation = self.reputations.entry(peer_id).or_insert(Reputation {
+            score: 0.0,
+            last_update: now,
+            rate_limit_hits: 0,
+        });
+        reputation.score = reputation.score_at(now, self.config.recovery_per_second) - penalty;
+        reputation.last_update = now;
+        if offense == Offense::RateLimitExceeded {
+            reputation.rate_limit_hits += 1;
+        }
+
+        if reputation.score <= self.config.ban_threshold {
+            self.reputations.remove(&peer_id);
+            Verdict::Ban
+        } else if reputation.score <= self.config.disconnect_threshold {
+            Verdict::Disconnect
+        } else {
+            Verdict::Keep
+        }
+    }
+
+    /// Forgets the disconnected peers that fully recovered their reputation.
+    pub(crate) fn prune(&mut self, is_connected: impl Fn(&PeerId) -> bool) {
+        let now = Instant::now();
+        let recovery_per_second = self.config.recovery_per_second;
+        self.reputations.retain(|peer_id, reputation| {
+            is_connected(peer_id) || reputation.score_at(now, recovery_per_second) < 0.0
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use libp2p::PeerId;
+
+    use super::{Offense, PeerReputation, PeerReputations, ReputationConfig, Verdict};
+
+    #[test]
+    fn penalties_lead_to_disconnect_and_ban() {
+        let mut reputations = PeerReputations::new(ReputationConfig {
+            rate_limit_penalty: 1.0,
+            invalid_response_penalty: 10.0,
+            recovery_per_second: 0.0,
+            disconnect_threshold: -20.0,
+            ban_threshold: -30.0,
+        });
+        let peer_id = PeerId::random();
+
+        assert_eq!(
+            reputations.penalize(peer_id, Offense::InvalidResponse),
+            Verdict::Keep
+        );
+        for _ in 0..9 {
+            assert_eq!(
+                reputations.penalize(peer_id, Offense::RateLimitExceeded),
+                Verdict::Keep
+            );
+        }
+        assert_eq!(
+            reputations.get(&peer_id),
+            PeerReputation {
+                score: -19.0,
+                rate_limit_hits: 9,
+            }
+        );
+
+        assert_eq!(
+            reputations.penalize(peer_id, Offense::RateLimitExceeded),
+            Verdict::Disconnect
+        );
+        assert_eq!(
+            reputations.penalize(peer_id, Offense::InvalidResponse),
+            Verdict::Ban
+        );
+
+        // The score is reset after the ban.
+        assert_eq!(reputations.score(&peer_id), 0.0);
+        assert_eq!(reputations.score(&PeerId::random()), 0.0);
+    }
+
+    #[test]
+    fn reputation_recovers() {
+        let mut reputations = PeerReputations::new(ReputationConfig {
+            recovery_per_second: 1000.0,
+            ..Default::default()
+        });
+        let peer_id = PeerId::random();
+
+        reputations.penalize(peer_id, Offense::InvalidResponse);
+        assert!(reputations.score(&peer_id) < 0.0);
+
+        std::thread::sleep(Duration::from_millis(20));
+        assert_eq!(reputations.score(&peer_id), 0.0);
+
+        // Connected peers keep their rate limit hits.
+        reputations.prune(|_| true);
+        assert_eq!(reputations.all().len(), 1);
+
+        reputations.prune(|_| false);
+        assert!(reputations.reputations.is_empty());
+    }
+}
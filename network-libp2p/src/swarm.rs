@@ -3,6 +3,7 @@ use std::{collections::HashMap, num::NonZeroU8, sync::Arc, time::Duration};
 use futures::StreamExt;
 #[cfg(feature = "metrics")]
 use instant::Instant;
+use instant::SystemTime;
 #[cfg(all(target_family = "wasm", not(feature = "tokio-websocket")))]
 use libp2p::websocket_websys;
 use libp2p::{
@@ -30,10 +31,12 @@ use libp2p::{
 #[cfg(feature = "tokio-websocket")]
 use libp2p::{dns, tcp, websocket};
 use log::Instrument;
+#[cfg(feature = "metrics")]
+use nimiq_network_interface::peer_info::Services;
 use nimiq_network_interface::{
     network::{CloseReason, NetworkEvent},
     peer_info::PeerInfo,
-    request::{peek_type, InboundRequestError, OutboundRequestError, RequestError},
+    request::{peek_type, InboundRequestError, OutboundRequestError, RequestError, RequestType},
 };
 use nimiq_serde::Serialize;
 use nimiq_time::Interval;
@@ -41,16 +44,20 @@ use parking_lot::RwLock;
 use tokio::sync::{broadcast, mpsc};
 
 #[cfg(feature = "metrics")]
-use crate::network_metrics::NetworkMetrics;
+use crate::network_metrics::{Direction, NetworkMetrics};
 use crate::{
     autonat::NatStatus,
-    behaviour, dht,
+    behaviour,
+    capture::TrafficCapture,
+    dht,
     discovery::{self, peer_contacts::PeerContactBook},
     network_types::{
-        DhtBootStrapState, DhtRecord, DhtResults, GossipsubTopicInfo, NetworkAction, TaskState,
-        ValidateMessage,
+        ConnectedPeer, DhtBootStrapState, DhtRecord, DhtResults, GossipsubTopicInfo, NetworkAction,
+        TaskState, ValidateMessage,
     },
-    rate_limiting::{RateLimitId, RateLimits},
+    rate_limiting::{RateLimitId, RateLimitOverrides, RateLimits},
+    relay::{self, RelayedRequest},
+    reputation::{Offense, PeerReputations, ReputationConfig, Verdict},
     Config, NetworkError, TlsConfig,
 };
 
@@ -60,7 +67,7 @@ struct EventInfo<'a> {
     events_tx: &'a broadcast::Sender<NetworkEvent<PeerId>>,
     swarm: &'a mut NimiqSwarm,
     state: &'a mut TaskState,
-    connected_peers: &'a RwLock<HashMap<PeerId, PeerInfo>>,
+    connected_peers: &'a RwLock<HashMap<PeerId, ConnectedPeer>>,
     rate_limiting: &'a mut RateLimits,
     #[cfg(feature = "kad")]
     dht_verifier: &'a dyn dht::Verifier,
@@ -80,6 +87,7 @@ pub(crate) fn new_swarm(
         config.memory_transport,
         config.only_secure_ws_connections,
         config.tls.as_ref(),
+        config.quic,
     )
     .unwrap();
 
@@ -111,17 +119,25 @@ pub(crate) async fn swarm_task(
     events_tx: broadcast::Sender<NetworkEvent<PeerId>>,
     mut action_rx: mpsc::Receiver<NetworkAction>,
     mut validate_rx: mpsc::UnboundedReceiver<ValidateMessage<PeerId>>,
-    connected_peers: Arc<RwLock<HashMap<PeerId, PeerInfo>>>,
+    connected_peers: Arc<RwLock<HashMap<PeerId, ConnectedPeer>>>,
     mut update_scores: Interval,
     contacts: Arc<RwLock<PeerContactBook>>,
     #[cfg(feature = "kad")] dht_verifier: impl dht::Verifier,
     force_dht_server_mode: bool,
     dht_quorum: NonZeroU8,
+    rate_limit_overrides: RateLimitOverrides,
+    reputation_config: ReputationConfig,
+    capture: Option<TrafficCapture>,
+    sentry_for: Vec<PeerId>,
     #[cfg(feature = "metrics")] metrics: Arc<NetworkMetrics>,
 ) {
     let mut task_state = TaskState {
         dht_server_mode: force_dht_server_mode,
         dht_quorum: dht_quorum.into(),
+        rate_limit_overrides,
+        reputations: PeerReputations::new(reputation_config),
+        capture,
+        sentry_for: sentry_for.into_iter().collect(),
         ..Default::default()
     };
     let mut rate_limiting = RateLimits::default();
@@ -170,7 +186,13 @@ pub(crate) async fn swarm_task(
                 },
                 action = action_rx.recv() => {
                     if let Some(action) = action {
-                        perform_action(action, &mut swarm, &mut task_state);
+                        perform_action(
+                            action,
+                            &mut swarm,
+                            &mut task_state,
+                            #[cfg(feature = "metrics")] &connected_peers,
+                            #[cfg(feature = "metrics")] &metrics,
+                        );
                     }
                     else {
                         // `action_rx.next()` will return `None` if all senders (i.e. the `Network` object) are dropped.
@@ -192,6 +214,7 @@ fn new_transport(
     memory_transport: bool,
     only_secure_ws_connections: bool,
     tls: Option<&TlsConfig>,
+    quic: bool,
 ) -> std::io::Result<Boxed<(PeerId, StreamMuxerBox)>> {
     let yamux = yamux::Config::default();
 
@@ -266,21 +289,46 @@ fn new_transport(
         #[cfg(all(not(feature = "tokio-websocket"), not(target_family = "wasm")))]
         let transport = MemoryTransport::default();
 
-        if only_secure_ws_connections {
-            Ok(crate::only_secure_ws_transport::Transport::new(transport)
+        let transport = if only_secure_ws_connections {
+            crate::only_secure_ws_transport::Transport::new(transport)
                 .upgrade(core::upgrade::Version::V1)
                 .authenticate(noise::Config::new(keypair).unwrap())
                 .multiplex(yamux)
                 .timeout(Duration::from_secs(20))
-                .boxed())
+                .boxed()
         } else {
-            Ok(transport
+            transport
                 .upgrade(core::upgrade::Version::V1)
                 .authenticate(noise::Config::new(keypair).unwrap())
                 .multiplex(yamux)
                 .timeout(Duration::from_secs(20))
-                .boxed())
+                .boxed()
+        };
+
+        // QUIC brings its own encryption and multiplexing.
+        #[cfg(feature = "quic")]
+        if quic {
+            if only_secure_ws_connections {
+                warn!(
+                    "QUIC transport disabled because only secure websocket connections are allowed"
+                );
+            } else {
+                let quic_transport =
+                    libp2p::quic::tokio::Transport::new(libp2p::quic::Config::new(keypair))
+                        .map(|(peer_id, connection), _| (peer_id, StreamMuxerBox::new(connection)));
+                return Ok(quic_transport
+                    .or_transport(transport)
+                    .map(|output, _| output.into_inner())
+                    .boxed());
+            }
+        }
+
+        #[cfg(not(feature = "quic"))]
+        if quic {
+            warn!("QUIC transport requested, but this build doesn't support it");
         }
+
+        Ok(transport)
     }
 }
 
@@ -364,6 +412,25 @@ fn handle_event(event: SwarmEvent<behaviour::BehaviourEvent>, event_info: EventI
                 // Removes or marks to remove the respective rate limits.
                 // Also cleans up the expired rate limits pending to delete.
                 event_info.rate_limiting.remove_rate_limits(peer_id);
+                // Forget the reputation of peers that behaved well for long enough.
+                let connected_peers = event_info.connected_peers;
+                event_info
+                    .state
+                    .reputations
+                    .prune(|peer_id| connected_peers.read().contains_key(peer_id));
+
+                if event_info.state.sentry_for.contains(&peer_id) {
+                    let topics: Vec<_> = event_info
+                        .state
+                        .relayed_topics
+                        .iter()
+                        .filter(|(_, peers)| peers.contains(&peer_id))
+                        .map(|(topic, _)| topic.clone())
+                        .collect();
+                    for topic in topics {
+                        stop_relaying_topic(peer_id, topic, event_info.swarm, event_info.state);
+                    }
+                }
 
                 let _ = event_info.events_tx.send(NetworkEvent::PeerLeft(peer_id));
             }
@@ -657,7 +724,9 @@ fn handle_dht_bootstrap(
             }
             debug!(?result, "DHT bootstrap successful");
             event_info.state.dht_bootstrap_state = DhtBootStrapState::Completed;
-            if event_info.state.dht_server_mode {
+            // A private node never switches to server mode, its records are stored by the
+            // peers it is allowed to connect to.
+            if event_info.state.dht_server_mode || event_info.swarm.behaviour().pool.is_private() {
                 let _ = event_info.events_tx.send(NetworkEvent::DhtReady);
             }
         }
@@ -717,10 +786,15 @@ fn handle_discovery_event(event: discovery::Event, event_info: EventInfo) {
         } => {
             let peer_info = PeerInfo::new(peer_address.clone(), peer_contact.services);
 
+            let connected_peer = ConnectedPeer {
+                info: peer_info.clone(),
+                connected_since: SystemTime::now(),
+                rtt: None,
+            };
             if event_info
                 .connected_peers
                 .write()
-                .insert(peer_id, peer_info.clone())
+                .insert(peer_id, connected_peer)
                 .is_some()
             {
                 error!(%peer_id, "Peer joined but it already exists");
@@ -744,14 +818,36 @@ fn handle_gossipsup_event(event: gossipsub::Event, event_info: EventInfo) {
             message,
         } => {
             #[cfg(feature = "metrics")]
-            event_info
-                .metrics
-                .note_received_pubsub_message(&message.topic);
+            event_info.metrics.note_received_pubsub_message(
+                &message.topic,
+                peer_services(event_info.connected_peers, &propagation_source),
+                message.data.len(),
+            );
+            if let Some(capture) = &mut event_info.state.capture {
+                capture.gossip_received(propagation_source, message.topic.as_str(), &message.data);
+            }
 
             let topic = message.topic.clone();
 
             let Some(topic_info) = event_info.state.gossip_topics.get_mut(&topic) else {
-                warn!(topic = %message.topic, "unknown topic hash");
+                if event_info.state.relayed_topics.contains_key(&topic) {
+                    // We only subscribed for the peers we are a sentry for. They validate the
+                    // messages themselves.
+                    if let Err(error) = event_info
+                        .swarm
+                        .behaviour_mut()
+                        .gossipsub
+                        .report_message_validation_result(
+                            &message_id,
+                            &propagation_source,
+                            gossipsub::MessageAcceptance::Accept,
+                        )
+                    {
+                        error!(%message_id, %error, "Failed to report message validation result");
+                    }
+                } else {
+                    warn!(topic = %message.topic, "unknown topic hash");
+                }
                 return;
             };
 
@@ -767,6 +863,16 @@ fn handle_gossipsup_event(event: gossipsub::Event, event_info: EventInfo) {
                     time_window = ?topic_info.rate_limit_config.time_window,
                     "Dropping gossipsub message - rate limit exceeded",
                 );
+                #[cfg(feature = "metrics")]
+                event_info
+                    .metrics
+                    .note_rate_limit_hit(&RateLimitId::Gossipsub(topic.clone()));
+                penalize_peer(
+                    propagation_source,
+                    Offense::RateLimitExceeded,
+                    event_info.swarm,
+                    event_info.state,
+                );
                 return;
             }
 
@@ -795,9 +901,15 @@ fn handle_gossipsup_event(event: gossipsub::Event, event_info: EventInfo) {
         }
         gossipsub::Event::Subscribed { peer_id, topic } => {
             trace!(%peer_id, %topic, "peer subscribed to topic");
+            if event_info.state.sentry_for.contains(&peer_id) {
+                start_relaying_topic(peer_id, topic, event_info.swarm, event_info.state);
+            }
         }
         gossipsub::Event::Unsubscribed { peer_id, topic } => {
             trace!(%peer_id, %topic, "peer unsubscribed");
+            if event_info.state.sentry_for.contains(&peer_id) {
+                stop_relaying_topic(peer_id, topic, event_info.swarm, event_info.state);
+            }
         }
         gossipsub::Event::GossipsubNotSupported { peer_id } => {
             debug!(%peer_id, "gossipsub not supported");
@@ -805,6 +917,53 @@ fn handle_gossipsup_event(event: gossipsub::Event, event_info: EventInfo) {
     }
 }
 
+/// Subscribes to a topic on behalf of a peer we are a sentry for, such that its messages reach
+/// the rest of the network and the messages of the rest of the network reach it.
+fn start_relaying_topic(
+    peer_id: PeerId,
+    topic: gossipsub::TopicHash,
+    swarm: &mut NimiqSwarm,
+    state: &mut TaskState,
+) {
+    let peers = state.relayed_topics.entry(topic.clone()).or_default();
+    let first_peer = peers.is_empty();
+    peers.insert(peer_id);
+
+    if first_peer && !state.gossip_topics.contains_key(&topic) {
+        debug!(%peer_id, %topic, "Relaying topic");
+        let topic = gossipsub::IdentTopic::new(topic.as_str());
+        if let Err(error) = swarm.behaviour_mut().gossipsub.subscribe(&topic) {
+            error!(%topic, %error, "Failed to subscribe to relayed topic");
+        }
+    }
+}
+
+/// Stops relaying a topic for a peer we are a sentry for. We unsubscribe once no such peer is
+/// subscribed to it anymore, unless we are interested in the topic ourselves.
+fn stop_relaying_topic(
+    peer_id: PeerId,
+    topic: gossipsub::TopicHash,
+    swarm: &mut NimiqSwarm,
+    state: &mut TaskState,
+) {
+    let Some(peers) = state.relayed_topics.get_mut(&topic) else {
+        return;
+    };
+    peers.remove(&peer_id);
+    if !peers.is_empty() {
+        return;
+    }
+
+    state.relayed_topics.remove(&topic);
+    if !state.gossip_topics.contains_key(&topic) {
+        debug!(%peer_id, %topic, "No longer relaying topic");
+        let topic = gossipsub::IdentTopic::new(topic.as_str());
+        if let Err(error) = swarm.behaviour_mut().gossipsub.unsubscribe(&topic) {
+            error!(%topic, %error, "Failed to unsubscribe from relayed topic");
+        }
+    }
+}
+
 fn handle_ping_event(event: ping::Event, event_info: EventInfo) {
     match event.result {
         Err(error) => {
@@ -817,6 +976,9 @@ fn handle_ping_event(event: ping::Event, event_info: EventInfo) {
         }
         Ok(duration) => {
             trace!(?duration, peer_id = %event.peer, "Ping completed");
+            if let Some(peer) = event_info.connected_peers.write().get_mut(&event.peer) {
+                peer.rtt = Some(duration);
+            }
         }
     };
 }
@@ -867,11 +1029,42 @@ fn handle_request_response_request(
     };
 
     // Peek the request type, if it fails return as the request cannot be determined.
-    let Ok(type_id) = peek_type(&request) else {
+    let Ok(mut type_id) = peek_type(&request) else {
         debug!(%request_id, %peer_id, "Could not parse request type");
         return;
     };
 
+    // In private mode, our sentries forward the requests of other peers. Handle them as if they
+    // came from the original sender.
+    let (peer_id, request) = if type_id == RequestType::from_request::<RelayedRequest>()
+        && event_info.swarm.behaviour().pool.is_private()
+    {
+        match relay::unwrap_relayed_request(&request) {
+            Ok((origin, request, inner_type_id)) => {
+                trace!(%request_id, sentry = %peer_id, %origin, "Relayed request");
+                type_id = inner_type_id;
+                (origin, request)
+            }
+            Err(error) => {
+                debug!(%request_id, %peer_id, %error, "Could not parse relayed request");
+                return;
+            }
+        }
+    } else {
+        (peer_id, request)
+    };
+
+    #[cfg(feature = "metrics")]
+    event_info.metrics.note_request(
+        type_id,
+        Direction::Inbound,
+        peer_services(event_info.connected_peers, &peer_id),
+        request.len(),
+    );
+    if let Some(capture) = &mut event_info.state.capture {
+        capture.request_received(peer_id, request_id, &request);
+    }
+
     // Filter off sender if not alive.
     let sender_data = event_info
         .state
@@ -894,32 +1087,55 @@ fn handle_request_response_request(
                 time_window = ?rate_limit_config.time_window,
                 "Denied request - exceeded max requests rate",
             );
+            #[cfg(feature = "metrics")]
+            event_info
+                .metrics
+                .note_rate_limit_hit(&RateLimitId::Request(type_id));
 
             let response: Result<(), InboundRequestError> =
                 Err(InboundRequestError::ExceedsRateLimit);
+            let response = response.serialize_to_vec();
+            if let Some(capture) = &mut event_info.state.capture {
+                capture.response_sent(request_id, &response);
+            }
             if event_info
                 .swarm
                 .behaviour_mut()
                 .request_response
-                .send_response(channel, Some(response.serialize_to_vec()))
+                .send_response(channel, Some(response))
                 .is_err()
             {
                 error!(%type_id, %request_id, %peer_id, "Could not send rate limit error response");
             }
+            penalize_peer(
+                peer_id,
+                Offense::RateLimitExceeded,
+                event_info.swarm,
+                event_info.state,
+            );
         } else {
             if type_id.requires_response() {
                 event_info
                     .state
                     .response_channels
                     .insert(request_id, channel);
+                #[cfg(feature = "metrics")]
+                event_info
+                    .state
+                    .requests_received
+                    .insert(request_id, (type_id, peer_id));
             } else {
                 // Respond on behalf of the actual receiver because the actual receiver isn't interested in responding.
                 let response: Result<(), InboundRequestError> = Ok(());
+                let response = response.serialize_to_vec();
+                if let Some(capture) = &mut event_info.state.capture {
+                    capture.response_sent(request_id, &response);
+                }
                 if event_info
                     .swarm
                     .behaviour_mut()
                     .request_response
-                    .send_response(channel, Some(response.serialize_to_vec()))
+                    .send_response(channel, Some(response))
                     .is_err()
                 {
                     error!(%type_id, %request_id, %peer_id, "Could not send auto response");
@@ -932,11 +1148,15 @@ fn handle_request_response_request(
     } else {
         trace!(%type_id, %request_id, %peer_id, "No request handler registered, replying with a 'NoReceiver' error");
         let err: Result<(), InboundRequestError> = Err(InboundRequestError::NoReceiver);
+        let response = err.serialize_to_vec();
+        if let Some(capture) = &mut event_info.state.capture {
+            capture.response_sent(request_id, &response);
+        }
         if event_info
             .swarm
             .behaviour_mut()
             .request_response
-            .send_response(channel, Some(err.serialize_to_vec()))
+            .send_response(channel, Some(response))
             .is_err()
         {
             error!(%type_id, %request_id, %peer_id, "Could not send default response");
@@ -948,16 +1168,37 @@ fn handle_request_response_request(
 }
 
 fn handle_request_response_response(
-    _peer_id: PeerId,
+    peer_id: PeerId,
     request_id: OutboundRequestId,
     response: Option<Vec<u8>>,
     event_info: EventInfo,
 ) {
+    if let Some(capture) = &mut event_info.state.capture {
+        capture.response_received(request_id, response.as_deref());
+    }
+
     let Some(channel) = event_info.state.requests.remove(&request_id) else {
-        debug!(%request_id, "No request found for response");
+        debug!(%request_id, %peer_id, "No request found for response");
         return;
     };
 
+    #[cfg(feature = "metrics")]
+    if let Some((instant, type_id)) = event_info.state.requests_initiated.remove(&request_id) {
+        if let Some(response) = &response {
+            event_info.metrics.note_response_time(instant.elapsed());
+            event_info.metrics.note_response(
+                type_id,
+                Direction::Inbound,
+                peer_services(event_info.connected_peers, &peer_id),
+                response.len(),
+            );
+        } else {
+            event_info
+                .metrics
+                .note_request_error(type_id, Direction::Outbound, "timeout");
+        }
+    }
+
     // We might get empty responses (None) because of the implementation of our codecs.
     let response = response
         .ok_or(RequestError::OutboundRequest(OutboundRequestError::Timeout))
@@ -966,11 +1207,6 @@ fn handle_request_response_response(
     // The initiator of the request might no longer exist, so we
     // silently ignore any errors when delivering the response.
     channel.send(response).ok();
-
-    #[cfg(feature = "metrics")]
-    if let Some(instant) = event_info.state.requests_initiated.remove(&request_id) {
-        event_info.metrics.note_response_time(instant.elapsed());
-    }
 }
 
 fn handle_request_response_outbound_failure(
@@ -981,6 +1217,15 @@ fn handle_request_response_outbound_failure(
 ) {
     error!(%request_id, %peer_id, %error, "Failed to send request to peer");
 
+    if let Some(capture) = &mut event_info.state.capture {
+        capture.response_received(request_id, None);
+    }
+
+    #[cfg(feature = "metrics")]
+    if let Some((_, type_id)) = event_info.state.requests_initiated.remove(&request_id) {
+        event_info.metrics.note_outbound_failure(type_id, &error);
+    }
+
     let Some(channel) = event_info.state.requests.remove(&request_id) else {
         debug!(%request_id, %peer_id, "No request found for outbound failure");
         return;
@@ -995,12 +1240,27 @@ fn handle_request_response_inbound_failure(
     peer_id: PeerId,
     request_id: InboundRequestId,
     error: InboundFailure,
-    _event_info: EventInfo,
+    event_info: EventInfo,
 ) {
     error!(%request_id, %peer_id, %error, "Inbound request failed");
+
+    if let Some(capture) = &mut event_info.state.capture {
+        capture.request_failed(request_id);
+    }
+
+    #[cfg(feature = "metrics")]
+    if let Some((type_id, _)) = event_info.state.requests_received.remove(&request_id) {
+        event_info.metrics.note_inbound_failure(type_id, &error);
+    }
 }
 
-fn perform_action(action: NetworkAction, swarm: &mut NimiqSwarm, state: &mut TaskState) {
+fn perform_action(
+    action: NetworkAction,
+    swarm: &mut NimiqSwarm,
+    state: &mut TaskState,
+    #[cfg(feature = "metrics")] connected_peers: &RwLock<HashMap<PeerId, ConnectedPeer>>,
+    #[cfg(feature = "metrics")] metrics: &NetworkMetrics,
+) {
     match action {
         NetworkAction::Dial { peer_id, output } => {
             let dial_opts = DialOpts::peer_id(peer_id)
@@ -1053,8 +1313,10 @@ fn perform_action(action: NetworkAction, swarm: &mut NimiqSwarm, state: &mut Tas
             let topic = gossipsub::IdentTopic::new(topic_name.clone());
 
             match swarm.behaviour_mut().gossipsub.subscribe(&topic) {
-                // New subscription. Insert the sender into our subscription table.
-                Ok(true) => {
+                // New subscription. Insert the sender into our subscription table. If gossipsub
+                // was already subscribed but the topic isn't in our table, it was relayed for a
+                // peer we are a sentry for.
+                Ok(new) if new || !state.gossip_topics.contains_key(&topic.hash()) => {
                     let (tx, rx) = mpsc::channel(buffer_size);
 
                     state.gossip_topics.insert(
@@ -1062,7 +1324,9 @@ fn perform_action(action: NetworkAction, swarm: &mut NimiqSwarm, state: &mut Tas
                         GossipsubTopicInfo {
                             output: tx,
                             validate,
-                            rate_limit_config,
+                            rate_limit_config: state
+                                .rate_limit_overrides
+                                .for_topic(&topic_name, rate_limit_config),
                         },
                     );
 
@@ -1108,6 +1372,13 @@ fn perform_action(action: NetworkAction, swarm: &mut NimiqSwarm, state: &mut Tas
                 return;
             }
 
+            // Stay subscribed to topics we relay for peers we are a sentry for.
+            if state.relayed_topics.contains_key(&topic.hash()) {
+                drop(state.gossip_topics.remove(&topic.hash()).unwrap().output);
+                output.send(Ok(())).ok();
+                return;
+            }
+
             match swarm.behaviour_mut().gossipsub.unsubscribe(&topic) {
                 // Unsubscription. Remove the topic from the subscription table.
                 Ok(true) => {
@@ -1137,6 +1408,10 @@ fn perform_action(action: NetworkAction, swarm: &mut NimiqSwarm, state: &mut Tas
         } => {
             let topic = gossipsub::IdentTopic::new(topic_name.clone());
 
+            if let Some(capture) = &mut state.capture {
+                capture.gossip_published(&topic_name, &data);
+            }
+
             let result = swarm
                 .behaviour_mut()
                 .gossipsub
@@ -1160,6 +1435,9 @@ fn perform_action(action: NetworkAction, swarm: &mut NimiqSwarm, state: &mut Tas
             output,
             rate_limit_config,
         } => {
+            let rate_limit_config = state
+                .rate_limit_overrides
+                .for_request(type_id, rate_limit_config);
             state
                 .receive_requests
                 .insert(type_id, (output, rate_limit_config));
@@ -1170,14 +1448,34 @@ fn perform_action(action: NetworkAction, swarm: &mut NimiqSwarm, state: &mut Tas
             response_channel,
             output,
         } => {
+            #[cfg(feature = "metrics")]
+            let type_id = peek_type(&request).ok();
+            #[cfg(feature = "metrics")]
+            if let Some(type_id) = type_id {
+                metrics.note_request(
+                    type_id,
+                    Direction::Outbound,
+                    peer_services(connected_peers, &peer_id),
+                    request.len(),
+                );
+            }
+
+            let captured_request = state.capture.is_some().then(|| request.clone());
             let request_id = swarm
                 .behaviour_mut()
                 .request_response
                 .send_request(&peer_id, Some(request));
+            if let (Some(capture), Some(request)) = (&mut state.capture, captured_request) {
+                capture.request_sent(peer_id, request_id, &request);
+            }
 
             state.requests.insert(request_id, response_channel);
             #[cfg(feature = "metrics")]
-            state.requests_initiated.insert(request_id, Instant::now());
+            if let Some(type_id) = type_id {
+                state
+                    .requests_initiated
+                    .insert(request_id, (Instant::now(), type_id));
+            }
 
             // The request initiator might no longer exist, so we silently ignore any errors here.
             output.send(request_id).ok();
@@ -1194,6 +1492,20 @@ fn perform_action(action: NetworkAction, swarm: &mut NimiqSwarm, state: &mut Tas
                 return;
             };
 
+            #[cfg(feature = "metrics")]
+            if let Some((type_id, peer_id)) = state.requests_received.remove(&request_id) {
+                metrics.note_response(
+                    type_id,
+                    Direction::Outbound,
+                    peer_services(connected_peers, &peer_id),
+                    response.len(),
+                );
+            }
+
+            if let Some(capture) = &mut state.capture {
+                capture.response_sent(request_id, &response);
+            }
+
             let result = swarm
                 .behaviour_mut()
                 .request_response
@@ -1237,6 +1549,60 @@ fn perform_action(action: NetworkAction, swarm: &mut NimiqSwarm, state: &mut Tas
         NetworkAction::DisconnectPeer { peer_id, reason } => {
             swarm.behaviour_mut().pool.close_connection(peer_id, reason)
         }
+        NetworkAction::Ban { target, duration } => swarm.behaviour_mut().pool.ban(target, duration),
+        NetworkAction::Unban { target, output } => {
+            // The initiator might no longer exist, so we silently ignore any errors here.
+            output.send(swarm.behaviour_mut().pool.unban(&target)).ok();
+        }
+        NetworkAction::GetBans { output } => {
+            // The initiator might no longer exist, so we silently ignore any errors here.
+            output.send(swarm.behaviour().pool.bans()).ok();
+        }
+        NetworkAction::GetReputations { output } => {
+            // The initiator might no longer exist, so we silently ignore any errors here.
+            output.send(state.reputations.all()).ok();
+        }
+        NetworkAction::InvalidResponse { peer_id } => {
+            penalize_peer(peer_id, Offense::InvalidResponse, swarm, state);
+        }
+    }
+}
+
+/// Returns the services announced by a connected peer.
+#[cfg(feature = "metrics")]
+fn peer_services(
+    connected_peers: &RwLock<HashMap<PeerId, ConnectedPeer>>,
+    peer_id: &PeerId,
+) -> Option<Services> {
+    connected_peers
+        .read()
+        .get(peer_id)
+        .map(|peer| peer.info.get_services())
+}
+
+/// Lowers the reputation of a peer and disconnects or bans it if it dropped too low.
+fn penalize_peer(peer_id: PeerId, offense: Offense, swarm: &mut NimiqSwarm, state: &mut TaskState) {
+    match state.reputations.penalize(peer_id, offense) {
+        Verdict::Keep => {}
+        Verdict::Disconnect => {
+            debug!(
+                %peer_id,
+                ?offense,
+                score = state.reputations.score(&peer_id),
+                "Disconnecting peer - reputation too low",
+            );
+            swarm
+                .behaviour_mut()
+                .pool
+                .close_connection(peer_id, CloseReason::Other);
+        }
+        Verdict::Ban => {
+            debug!(%peer_id, ?offense, "Banning peer - reputation too low");
+            swarm
+                .behaviour_mut()
+                .pool
+                .close_connection(peer_id, CloseReason::MaliciousPeer);
+        }
     }
 }
 
@@ -61,6 +61,7 @@ impl TestNode {
             house_keeping_interval: Duration::from_secs(1),
             keep_alive: true,
             only_secure_ws_connections: false,
+            private_mode: false,
         };
 
         let peer_contact = PeerContact::new(
@@ -308,3 +309,89 @@ fn test_housekeeping() {
         .get(&old_contact.public_key().clone().to_peer_id())
         .is_none());
 }
+
+#[test]
+fn test_persisting_peer_contacts() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("peer_contacts.dat");
+
+    let own_contact = random_peer_contact(1, Services::FULL_BLOCKS);
+    let mut peer_contact_book = PeerContactBook::new(own_contact.clone(), false, true, true);
+
+    // Nothing is loaded if the file doesn't exist yet.
+    peer_contact_book.load_persisted(path.clone());
+
+    let fresh_contact = random_peer_contact(2, Services::FULL_BLOCKS);
+    let old_contact = {
+        let keypair = Keypair::generate_ed25519();
+
+        let peer_contact = PeerContact::new(
+            Some("/dns/test_old.local/tcp/443/wss".parse().unwrap()),
+            keypair.public(),
+            Services::FULL_BLOCKS,
+            SystemTime::now()
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .unwrap()
+                .as_secs()
+                .saturating_sub(PeerContactBook::MAX_PEER_AGE * 2),
+        )
+        .expect("Peer contact must be creatable");
+
+        peer_contact.sign(&keypair)
+    };
+
+    peer_contact_book.insert(fresh_contact.clone());
+    peer_contact_book.insert(old_contact.clone());
+    let fresh_info = peer_contact_book.get(&fresh_contact.peer_id()).unwrap();
+    fresh_info.set_score(4.2);
+    peer_contact_book.persist();
+
+    // Load the persisted contacts into a new contact book.
+    let mut peer_contact_book = PeerContactBook::new(own_contact, false, true, true);
+    peer_contact_book.load_persisted(path);
+
+    test_peers_in_contact_book(&peer_contact_book, &[fresh_contact.clone()]);
+    let loaded_info = peer_contact_book.get(&fresh_contact.peer_id()).unwrap();
+    assert_eq!(loaded_info.get_score(), 4.2);
+    assert_eq!(loaded_info.get_last_seen(), fresh_info.get_last_seen());
+
+    // The old contact exceeded its age and is dropped when loading.
+    assert!(peer_contact_book.get(&old_contact.peer_id()).is_none());
+
+    // Receiving a contact again updates when it was last seen.
+    loaded_info.set_last_seen(0);
+    peer_contact_book.insert(fresh_contact.clone());
+    assert!(loaded_info.get_last_seen() > 0);
+
+    // Persisted contacts are inserted like received ones, so our own contact is not loaded.
+    let mut peer_contact_book = PeerContactBook::new(fresh_contact.clone(), false, true, true);
+    peer_contact_book.load_persisted(dir.path().join("peer_contacts.dat"));
+    assert!(peer_contact_book.get(&fresh_contact.peer_id()).is_none());
+}
+
+#[test]
+fn test_quic_addresses_dialable() {
+    let own_contact = random_peer_contact(1, Services::FULL_BLOCKS);
+    let quic_address: Multiaddr = "/ip4/1.2.3.4/udp/8443/quic-v1".parse().unwrap();
+    let ws_address: Multiaddr = "/ip4/1.2.3.4/tcp/8443/ws".parse().unwrap();
+
+    // QUIC addresses are only dialable if QUIC is enabled.
+    let mut peer_contact_book = PeerContactBook::new(own_contact.clone(), false, false, false);
+    assert!(!peer_contact_book.is_address_dialable(&quic_address));
+    assert!(peer_contact_book.is_address_dialable(&ws_address));
+
+    peer_contact_book.set_quic_addresses(true);
+    assert!(peer_contact_book.is_address_dialable(&quic_address));
+    assert!(
+        peer_contact_book.is_address_dialable(&"/dns4/test.local/udp/443/quic-v1".parse().unwrap())
+    );
+    assert!(!peer_contact_book.is_address_dialable(&"/ip4/1.2.3.4/udp/8443".parse().unwrap()));
+    assert!(
+        !peer_contact_book.is_address_dialable(&"/ip4/127.0.0.1/udp/8443/quic-v1".parse().unwrap())
+    );
+
+    // Only secure websocket addresses are dialable if only secure connections are allowed.
+    let mut peer_contact_book = PeerContactBook::new(own_contact, true, false, false);
+    peer_contact_book.set_quic_addresses(true);
+    assert!(!peer_contact_book.is_address_dialable(&quic_address));
+}
@@ -5,13 +5,14 @@ use instant::SystemTime;
 use libp2p::{
     gossipsub,
     identity::Keypair,
-    multiaddr::{multiaddr, Multiaddr},
+    multiaddr::{multiaddr, Multiaddr, Protocol},
     PeerId,
 };
 use nimiq_keys::{Address, KeyPair};
 use nimiq_network_interface::{
     network::{CloseReason, MsgAcceptance, Network as NetworkInterface, NetworkEvent, Topic},
     peer_info::Services,
+    request::{RequestCommon, RequestMarker},
 };
 use nimiq_network_libp2p::{
     dht,
@@ -66,6 +67,7 @@ fn network_config(address: Multiaddr) -> Config {
             house_keeping_interval: Duration::from_secs(60),
             keep_alive: false,
             only_secure_ws_connections: false,
+            private_mode: false,
         },
         kademlia: Default::default(),
         gossipsub,
@@ -79,6 +81,15 @@ fn network_config(address: Multiaddr) -> Config {
         only_secure_ws_connections: false,
         allow_loopback_addresses: true,
         dht_quorum: NonZeroU8::new(1).unwrap(),
+        quic: false,
+        peer_contacts_path: None,
+        ban_list_path: None,
+        deny_list: vec![],
+        allow_list: vec![],
+        sentry_for: vec![],
+        rate_limits: Default::default(),
+        reputation: Default::default(),
+        capture_path: None,
     }
 }
 
@@ -501,6 +512,7 @@ async fn dht_put_and_get() {
         peer_id: net1.get_local_peer_id(),
         validator_address: key.clone(),
         timestamp: 0x42u64,
+        relays: vec![],
     };
 
     assert!(keys.write().insert(key.clone(), keypair.public).is_none());
@@ -602,3 +614,117 @@ async fn test_gossipsub() {
     }
     net1.network_info().await.unwrap();
 }
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct TestRequest {
+    x: i32,
+}
+
+impl RequestCommon for TestRequest {
+    type Kind = RequestMarker;
+    const TYPE_ID: u16 = 42;
+    type Response = i32;
+    const MAX_REQUESTS: u32 = 10;
+}
+
+/// Answers test requests with `x + 1` after checking that they were sent by `expected_sender`.
+fn respond_to_test_requests(net: &Arc<Network>, expected_sender: PeerId) {
+    let mut requests = net.receive_requests::<TestRequest>();
+    let net = Arc::clone(net);
+    spawn(async move {
+        while let Some((request, request_id, sender)) = requests.next().await {
+            assert_eq!(sender, expected_sender);
+            net.respond::<TestRequest>(request_id, request.x + 1)
+                .await
+                .unwrap();
+        }
+    });
+}
+
+#[test(tokio::test)]
+async fn private_node_is_reached_through_its_sentry() {
+    let mut rng = thread_rng();
+    let sentry_address = multiaddr![Memory(rng.gen::<u64>())];
+    let public_address = multiaddr![Memory(rng.gen::<u64>())];
+    let private_address = multiaddr![Memory(rng.gen::<u64>())];
+
+    let mut private_config = network_config(private_address.clone());
+    let private_peer_id = private_config.keypair.public().to_peer_id();
+    let mut sentry_config = network_config(sentry_address.clone());
+    let sentry_peer_id = sentry_config.keypair.public().to_peer_id();
+    sentry_config.sentry_for = vec![private_peer_id];
+    private_config.allow_list = vec![sentry_address.clone().with(Protocol::P2p(sentry_peer_id))];
+
+    let sentry = Arc::new(Network::new(sentry_config, ()).await);
+    sentry.listen_on(vec![sentry_address.clone()]).await;
+    let public = Arc::new(Network::new(network_config(public_address.clone()), ()).await);
+    public.listen_on(vec![public_address]).await;
+    let public_peer_id = public.get_local_peer_id();
+
+    let mut sentry_events = sentry.subscribe_events();
+    public.dial_address(sentry_address).await.unwrap();
+    let event = helper::get_next_peer_event(&mut sentry_events).await;
+    helper::assert_peer_joined(&event, &public_peer_id);
+
+    // The private node connects to its sentry on its own.
+    let private = Arc::new(Network::new(private_config, ()).await);
+    private.listen_on(vec![private_address.clone()]).await;
+    let mut private_events = private.subscribe_events();
+    private.start_connecting().await;
+    let event = timeout(
+        Duration::from_secs(10),
+        helper::get_next_peer_event(&mut private_events),
+    )
+    .await
+    .unwrap();
+    helper::assert_peer_joined(&event, &sentry_peer_id);
+
+    // Other peers can't connect to the private node.
+    public.dial_address(private_address).await.ok();
+    sleep(Duration::from_secs(1)).await;
+    assert!(!public.has_peer(private_peer_id));
+    assert_eq!(private.get_peers(), vec![sentry_peer_id]);
+
+    // Requests to the private node are forwarded by the sentry on behalf of the sender.
+    respond_to_test_requests(&private, public_peer_id);
+    public.set_peer_relays(private_peer_id, vec![sentry_peer_id]);
+    // Requests of the private node reach other peers through the sentry.
+    respond_to_test_requests(&public, sentry_peer_id);
+    sleep(Duration::from_secs(1)).await;
+
+    let response = public
+        .request(TestRequest { x: 1 }, private_peer_id)
+        .await
+        .unwrap();
+    assert_eq!(response, 2);
+    let response = private
+        .request(TestRequest { x: 41 }, public_peer_id)
+        .await
+        .unwrap();
+    assert_eq!(response, 42);
+
+    // The sentry relays the topics the private node is subscribed to.
+    let mut private_messages = private.subscribe::<TestTopic>().await.unwrap();
+    let mut public_messages = public.subscribe::<TestTopic>().await.unwrap();
+    sleep(Duration::from_secs(5)).await;
+
+    public
+        .publish::<TestTopic>(TestRecord { x: 1 })
+        .await
+        .unwrap();
+    let (message, _) = timeout(Duration::from_secs(10), private_messages.next())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(message, TestRecord { x: 1 });
+
+    private
+        .publish::<TestTopic>(TestRecord { x: 2 })
+        .await
+        .unwrap();
+    let (message, _) = timeout(Duration::from_secs(10), public_messages.next())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(message, TestRecord { x: 2 });
+}
@@ -240,6 +240,7 @@ fn network_config(address: Multiaddr) -> Config {
             house_keeping_interval: Duration::from_secs(60),
             keep_alive: true,
             only_secure_ws_connections: false,
+            private_mode: false,
         },
         kademlia: Default::default(),
         gossipsub,
@@ -253,6 +254,15 @@ fn network_config(address: Multiaddr) -> Config {
         only_secure_ws_connections: false,
         allow_loopback_addresses: true,
         dht_quorum: NonZeroU8::new(1).unwrap(),
+        quic: false,
+        peer_contacts_path: None,
+        ban_list_path: None,
+        deny_list: vec![],
+        allow_list: vec![],
+        sentry_for: vec![],
+        rate_limits: Default::default(),
+        reputation: Default::default(),
+        capture_path: None,
     }
 }
 
@@ -0,0 +1,156 @@
+use std::{
+    collections::BTreeMap,
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll, Waker},
+    time::Duration,
+};
+
+use parking_lot::Mutex;
+
+/// The clock of a mock hub. It only advances when told to, such that the delays of the links
+/// don't depend on the wall clock and runs with the same seed are reproducible.
+///
+/// Times are given as the virtual time elapsed since the hub was created.
+#[derive(Debug, Default)]
+pub(crate) struct VirtualClock {
+    state: Mutex<ClockState>,
+}
+
+#[derive(Debug, Default)]
+struct ClockState {
+    now: Duration,
+    /// Wakers of the pending sleeps, ordered by their deadline.
+    sleepers: BTreeMap<(Duration, u64), Waker>,
+    next_sleeper_id: u64,
+}
+
+impl VirtualClock {
+    /// Returns the current virtual time.
+    pub(crate) fn now(&self) -> Duration {
+        self.state.lock().now
+    }
+
+    /// Moves the clock forward to the given time and wakes all sleeps whose deadline is reached.
+    /// The clock never moves backwards.
+    pub(crate) fn advance_to(&self, time: Duration) {
+        let mut state = self.state.lock();
+        state.now = state.now.max(time);
+
+        while let Some(entry) = state.sleepers.first_entry() {
+            if entry.key().0 > state.now {
+                break;
+            }
+            entry.remove().wake();
+        }
+    }
+
+    /// Returns a future that completes once the clock reached the given time.
+    pub(crate) fn sleep_until(self: &Arc<Self>, deadline: Duration) -> Sleep {
+        Sleep {
+            clock: Arc::clone(self),
+            deadline,
+            sleeper_id: None,
+        }
+    }
+}
+
+/// A future that completes once a [`VirtualClock`] reached a deadline.
+#[derive(Debug)]
+pub(crate) struct Sleep {
+    clock: Arc<VirtualClock>,
+    deadline: Duration,
+    /// The ID under which the waker is registered with the clock, if it is.
+    sleeper_id: Option<u64>,
+}
+
+impl Sleep {
+    pub(crate) fn deadline(&self) -> Duration {
+        self.deadline
+    }
+}
+
+impl Future for Sleep {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let deadline = self.deadline;
+        let mut state = self.clock.state.lock();
+
+        if state.now >= deadline {
+            if let Some(sleeper_id) = self.sleeper_id {
+                state.sleepers.remove(&(deadline, sleeper_id));
+            }
+            drop(state);
+            self.sleeper_id = None;
+            return Poll::Ready(());
+        }
+
+        let sleeper_id = match self.sleeper_id {
+            Some(sleeper_id) => sleeper_id,
+            None => {
+                let sleeper_id = state.next_sleeper_id;
+                state.next_sleeper_id += 1;
+                sleeper_id
+            }
+        };
+        state
+            .sleepers
+            .insert((deadline, sleeper_id), cx.waker().clone());
+        drop(state);
+        self.sleeper_id = Some(sleeper_id);
+
+        Poll::Pending
+    }
+}
+
+impl Drop for Sleep {
+    fn drop(&mut self) {
+        if let Some(sleeper_id) = self.sleeper_id {
+            self.clock
+                .state
+                .lock()
+                .sleepers
+                .remove(&(self.deadline, sleeper_id));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::Arc, time::Duration};
+
+    use futures::FutureExt;
+
+    use super::VirtualClock;
+
+    #[test]
+    fn sleeps_complete_when_the_clock_advances() {
+        let clock = Arc::new(VirtualClock::default());
+        let mut sleep1 = clock.sleep_until(Duration::from_secs(1));
+        let mut sleep2 = clock.sleep_until(Duration::from_secs(2));
+
+        assert!((&mut sleep1).now_or_never().is_none());
+        assert!((&mut sleep2).now_or_never().is_none());
+
+        clock.advance_to(Duration::from_secs(1));
+        assert_eq!(clock.now(), Duration::from_secs(1));
+        assert!((&mut sleep1).now_or_never().is_some());
+        assert!((&mut sleep2).now_or_never().is_none());
+
+        // The clock never moves backwards.
+        clock.advance_to(Duration::ZERO);
+        assert_eq!(clock.now(), Duration::from_secs(1));
+
+        clock.advance_to(Duration::from_secs(5));
+        assert!((&mut sleep2).now_or_never().is_some());
+        assert!(clock.state.lock().sleepers.is_empty());
+
+        // Sleeps in the past complete immediately.
+        assert!(clock
+            .sleep_until(Duration::from_secs(3))
+            .now_or_never()
+            .is_some());
+    }
+}
@@ -1,7 +1,8 @@
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{BTreeMap, HashMap, HashSet},
     hash::Hash,
     sync::{atomic::AtomicBool, Arc},
+    time::Duration,
 };
 
 use nimiq_network_interface::{peer_info::PeerInfo, request::RequestType};
@@ -9,6 +10,8 @@ use parking_lot::{Mutex, RwLock};
 use tokio::sync::{broadcast, mpsc, oneshot};
 
 use crate::{
+    clock::VirtualClock,
+    link::{LinkEvent, LinkModel, LinkModelError, Links},
     network::{MockNetwork, MockRequestId},
     MockAddress, MockPeerId, ObservableHashMap,
 };
@@ -32,16 +35,25 @@ pub(crate) struct ResponseSender {
     pub sender: oneshot::Sender<Vec<u8>>,
 }
 
+#[derive(Clone, Debug)]
+pub(crate) struct GossipMessage {
+    pub data: Arc<Vec<u8>>,
+    pub sender: MockPeerId,
+    /// The virtual time at which the message must be delivered to each subscriber. Subscribers
+    /// that are missing lost the message.
+    pub deliveries: Arc<HashMap<MockAddress, Duration>>,
+}
+
 #[derive(Debug)]
 pub(crate) struct MockTopic {
     /// Subscribed peer list
-    peers: HashSet<MockAddress>,
+    pub peers: HashSet<MockAddress>,
 
     /// Sender channel for the topic
-    pub sender: broadcast::Sender<(Arc<Vec<u8>>, MockPeerId)>,
+    pub sender: broadcast::Sender<GossipMessage>,
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug)]
 pub(crate) struct MockHubInner {
     /// Peer maps of all networks.
     pub peer_maps: HashMap<MockAddress, Arc<RwLock<ObservableHashMap<MockPeerId, PeerInfo>>>>,
@@ -68,12 +80,36 @@ pub(crate) struct MockHubInner {
 
     /// Arcs to `AtomicBool`s for each network if they're connected.
     pub is_connected: HashMap<MockAddress, Arc<AtomicBool>>,
+
+    /// The links between the networks, which decide about latency and message loss.
+    pub links: Links,
+
+    /// The clock that delays messages according to the links.
+    pub clock: Arc<VirtualClock>,
+
+    /// Scripted link events, ordered by the virtual time at which they are applied.
+    pub link_script: BTreeMap<(Duration, u64), LinkEvent>,
+
+    /// Counter that keeps scripted link events with the same time in order.
+    pub next_link_event_id: u64,
 }
 
 impl MockHubInner {
-    /// Returns the requested MockTopic.
-    pub fn get_topic(&mut self, topic_name: &String) -> Option<&MockTopic> {
-        self.gossipsub_topics.get(topic_name)
+    fn new(seed: u64) -> Self {
+        Self {
+            peer_maps: HashMap::default(),
+            network_senders: HashMap::default(),
+            gossipsub_topics: HashMap::default(),
+            request_senders: HashMap::default(),
+            response_senders: HashMap::default(),
+            next_request_id: 0,
+            dht: HashMap::default(),
+            is_connected: HashMap::default(),
+            links: Links::new(seed),
+            clock: Arc::default(),
+            link_script: BTreeMap::default(),
+            next_link_event_id: 0,
+        }
     }
 
     /// Subscribe to a MockTopic; if the topic doesn't exist yet, this function creates it.
@@ -121,18 +157,130 @@ impl MockHubInner {
     }
 }
 
-#[derive(Debug, Default)]
+/// Connects mock networks with each other.
+///
+/// By default, messages are delivered instantly and reliably. Latency, message loss, bandwidth
+/// limits and partitions can be configured with [`LinkModel`]s and [`LinkEvent`]s. All random
+/// decisions are derived from the hub's seed, which is logged on creation. Every link draws from
+/// its own generator, so a failing run can be replayed with [`MockHub::with_seed`].
+///
+/// Delays are measured on a virtual clock owned by the hub, which only moves forward with
+/// [`MockHub::advance`]. Delayed messages and scripted link events thus don't take effect before
+/// the clock is advanced, which makes runs independent of the wall clock.
+#[derive(Debug)]
 pub struct MockHub {
     last_address: u64,
 
     inner: Arc<Mutex<MockHubInner>>,
 }
 
+impl Default for MockHub {
+    fn default() -> Self {
+        Self::with_seed(rand::random())
+    }
+}
+
 impl MockHub {
     pub fn new() -> Self {
         Self::default()
     }
 
+    /// Creates a hub whose link faults are derived from the given seed.
+    pub fn with_seed(seed: u64) -> Self {
+        log::info!(seed, "New mock hub");
+        Self {
+            last_address: 0,
+            inner: Arc::new(Mutex::new(MockHubInner::new(seed))),
+        }
+    }
+
+    /// Returns the seed of the link faults.
+    pub fn seed(&self) -> u64 {
+        self.inner.lock().links.seed()
+    }
+
+    /// Returns the current time of the hub's virtual clock, i.e. the virtual time elapsed since
+    /// the hub was created.
+    pub fn now(&self) -> Duration {
+        self.inner.lock().clock.now()
+    }
+
+    /// Advances the virtual clock by the given duration. Scripted link events that become due are
+    /// applied in order, and messages are delivered once their delivery time is reached.
+    pub fn advance(&self, duration: Duration) {
+        let mut inner = self.inner.lock();
+        let target = inner.clock.now() + duration;
+
+        while let Some(entry) = inner.link_script.first_entry() {
+            let time = entry.key().0;
+            if time > target {
+                break;
+            }
+            let event = entry.remove();
+            inner.clock.advance_to(time);
+            inner
+                .links
+                .apply(event)
+                .expect("Scripted link events are checked when scheduled");
+        }
+
+        inner.clock.advance_to(target);
+    }
+
+    /// Sets the model of all links that don't have a specific model.
+    pub fn set_default_link(&self, model: LinkModel) -> Result<(), LinkModelError> {
+        self.apply_link_event(LinkEvent::SetDefault(model))
+    }
+
+    /// Sets the model of the link from one network to another. Links are directed, the link in
+    /// the other direction is not affected.
+    pub fn set_link<A: Into<MockAddress>>(
+        &self,
+        from: A,
+        to: A,
+        model: LinkModel,
+    ) -> Result<(), LinkModelError> {
+        self.apply_link_event(LinkEvent::Set {
+            from: from.into(),
+            to: to.into(),
+            model,
+        })
+    }
+
+    /// Splits the networks into groups that can't reach each other.
+    /// See [`LinkEvent::Partition`].
+    pub fn partition(&self, groups: Vec<Vec<MockAddress>>) {
+        self.inner.lock().links.partition(groups);
+    }
+
+    /// Removes a partition.
+    pub fn heal(&self) {
+        self.inner.lock().links.heal();
+    }
+
+    pub fn apply_link_event(&self, event: LinkEvent) -> Result<(), LinkModelError> {
+        self.inner.lock().links.apply(event)
+    }
+
+    /// Schedules the given link events, each one at its offset from the current virtual time.
+    /// The events are applied as [`MockHub::advance`] moves the clock past them. Nothing is
+    /// scheduled if any of the events is invalid.
+    pub fn run_script(&self, script: Vec<(Duration, LinkEvent)>) -> Result<(), LinkModelError> {
+        script
+            .iter()
+            .try_for_each(|(_, event)| Links::check_event(event))?;
+
+        let mut inner = self.inner.lock();
+        let now = inner.clock.now();
+        for (offset, event) in script {
+            let id = inner.next_link_event_id;
+            inner.next_link_event_id += 1;
+            inner.link_script.insert((now + offset, id), event);
+        }
+
+        Ok(())
+    }
+
     pub fn new_address(&mut self) -> MockAddress {
         self.last_address += 1;
         MockAddress(self.last_address)
@@ -1,10 +1,13 @@
+mod clock;
 mod hub;
+mod link;
 mod network;
 mod observable_hash_map;
 
 use derive_more::{Display, From, Into};
 pub use hub::MockHub;
-pub use network::{MockId, MockNetwork};
+pub use link::{Latency, LinkEvent, LinkModel, LinkModelError};
+pub use network::{MockId, MockNetwork, MockRequestId};
 use nimiq_network_interface::{multiaddr, Multiaddr};
 pub use observable_hash_map::ObservableHashMap;
 use serde::{Deserialize, Serialize};
@@ -70,13 +73,18 @@ pub async fn create_mock_validator_network(n: usize, dial: bool) -> Vec<MockNetw
 
 #[cfg(test)]
 pub mod tests {
-    use futures::{Stream, StreamExt};
-    use nimiq_network_interface::network::{Network, NetworkEvent, SubscribeEvents, Topic};
+    use std::time::Duration;
+
+    use futures::{FutureExt, Stream, StreamExt};
+    use nimiq_network_interface::network::{
+        Network, NetworkEvent, PubsubId, SubscribeEvents, Topic,
+    };
     use nimiq_test_log::test;
+    use nimiq_time::timeout;
     use nimiq_utils::{spawn, tagged_signing::TaggedSignable};
     use serde::{Deserialize, Serialize};
 
-    use super::{network::MockNetworkError, MockHub, MockPeerId};
+    use super::{network::MockNetworkError, Latency, LinkEvent, LinkModel, MockHub, MockPeerId};
 
     pub async fn assert_peer_joined(
         events: &mut SubscribeEvents<MockPeerId>,
@@ -218,4 +226,143 @@ pub mod tests {
             net1.unsubscribe::<TestTopic>().await
         );
     }
+
+    #[test(tokio::test)]
+    async fn test_gossipsub_link_faults() {
+        let mut hub = MockHub::new();
+        let net1 = hub.new_network();
+        let net2 = hub.new_network();
+        net1.dial_mock(&net2);
+
+        let mut messages = net1.subscribe::<TestTopic>().await.unwrap();
+        consume_stream(net2.subscribe::<TestTopic>().await.unwrap());
+
+        // Messages from net2 to net1 are delayed until the hub's clock reaches their delivery.
+        let latency = Duration::from_millis(200);
+        hub.set_link(
+            net2.address(),
+            net1.address(),
+            LinkModel {
+                latency: Latency::Fixed(latency),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        net2.publish::<TestTopic>(TestRecord { x: 1 })
+            .await
+            .unwrap();
+        hub.advance(latency / 2);
+        assert!(messages.next().now_or_never().is_none());
+        hub.advance(latency / 2);
+        let (received_message, _) = messages.next().await.unwrap();
+        assert_eq!(received_message, TestRecord { x: 1 });
+
+        // Messages sent during a partition are lost, even if it heals before they are received.
+        hub.partition(vec![vec![net1.address()], vec![net2.address()]]);
+        net2.publish::<TestTopic>(TestRecord { x: 2 })
+            .await
+            .unwrap();
+        hub.heal();
+        net2.publish::<TestTopic>(TestRecord { x: 3 })
+            .await
+            .unwrap();
+        hub.advance(latency);
+
+        let (received_message, _) = timeout(Duration::from_secs(1), messages.next())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(received_message, TestRecord { x: 3 });
+    }
+
+    #[test(tokio::test)]
+    async fn test_gossipsub_delayed_message_does_not_block() {
+        let mut hub = MockHub::new();
+        let net1 = hub.new_network();
+        let net2 = hub.new_network();
+        let net3 = hub.new_network();
+        net1.dial_mock(&net2);
+        net1.dial_mock(&net3);
+
+        let mut messages = net1.subscribe::<TestTopic>().await.unwrap();
+        consume_stream(net2.subscribe::<TestTopic>().await.unwrap());
+        consume_stream(net3.subscribe::<TestTopic>().await.unwrap());
+
+        // The link from net2 is slow, the one from net3 is fast.
+        let slow = LinkModel {
+            latency: Latency::Fixed(Duration::from_secs(10)),
+            ..Default::default()
+        };
+        let fast = LinkModel {
+            latency: Latency::Fixed(Duration::from_secs(1)),
+            ..Default::default()
+        };
+        hub.set_link(net2.address(), net1.address(), slow).unwrap();
+        hub.set_link(net3.address(), net1.address(), fast).unwrap();
+
+        net2.publish::<TestTopic>(TestRecord { x: 1 })
+            .await
+            .unwrap();
+        net3.publish::<TestTopic>(TestRecord { x: 2 })
+            .await
+            .unwrap();
+
+        hub.advance(Duration::from_secs(1));
+        let (received_message, id) = messages.next().await.unwrap();
+        assert_eq!(received_message, TestRecord { x: 2 });
+        assert_eq!(id.propagation_source(), net3.peer_id());
+        assert!(messages.next().now_or_never().is_none());
+
+        hub.advance(Duration::from_secs(9));
+        let (received_message, id) = messages.next().await.unwrap();
+        assert_eq!(received_message, TestRecord { x: 1 });
+        assert_eq!(id.propagation_source(), net2.peer_id());
+    }
+
+    #[test(tokio::test)]
+    async fn test_link_script() {
+        let mut hub = MockHub::new();
+        let net1 = hub.new_network();
+        let net2 = hub.new_network();
+        net1.dial_mock(&net2);
+
+        let mut messages = net1.subscribe::<TestTopic>().await.unwrap();
+        consume_stream(net2.subscribe::<TestTopic>().await.unwrap());
+
+        // Invalid scripts are rejected as a whole.
+        let invalid = LinkModel {
+            drop_probability: 2.0,
+            ..Default::default()
+        };
+        assert!(hub
+            .run_script(vec![
+                (Duration::from_secs(1), LinkEvent::Heal),
+                (Duration::from_secs(2), LinkEvent::SetDefault(invalid)),
+            ])
+            .is_err());
+
+        hub.run_script(vec![
+            (
+                Duration::from_secs(2),
+                LinkEvent::Partition(vec![vec![net1.address()], vec![net2.address()]]),
+            ),
+            (Duration::from_secs(3), LinkEvent::Heal),
+        ])
+        .unwrap();
+
+        hub.advance(Duration::from_secs(2));
+        assert_eq!(hub.now(), Duration::from_secs(2));
+        net2.publish::<TestTopic>(TestRecord { x: 1 })
+            .await
+            .unwrap();
+
+        hub.advance(Duration::from_secs(1));
+        net2.publish::<TestTopic>(TestRecord { x: 2 })
+            .await
+            .unwrap();
+
+        let (received_message, _) = messages.next().await.unwrap();
+        assert_eq!(received_message, TestRecord { x: 2 });
+        assert!(messages.next().now_or_never().is_none());
+    }
 }
@@ -0,0 +1,364 @@
+use std::{collections::HashMap, time::Duration};
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use thiserror::Error;
+
+use crate::MockAddress;
+
+/// The latency distribution of a link.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Latency {
+    /// Every message takes the same time.
+    Fixed(Duration),
+    /// The latency is uniformly distributed between `min` and `max`.
+    Uniform { min: Duration, max: Duration },
+    /// The latency is `min` plus an exponentially distributed delay with the given mean, which
+    /// models the long tail of real-world links.
+    Exponential { min: Duration, mean: Duration },
+}
+
+impl Latency {
+    fn sample<R: Rng>(&self, rng: &mut R) -> Duration {
+        match self {
+            Latency::Fixed(latency) => *latency,
+            Latency::Uniform { min, max } => {
+                if min >= max {
+                    *min
+                } else {
+                    rng.gen_range(*min..=*max)
+                }
+            }
+            Latency::Exponential { min, mean } => {
+                let u: f64 = rng.gen_range(f64::EPSILON..1.0);
+                *min + mean.mul_f64(-u.ln())
+            }
+        }
+    }
+}
+
+impl Default for Latency {
+    fn default() -> Self {
+        Latency::Fixed(Duration::ZERO)
+    }
+}
+
+/// Describes how messages travel over a (directed) link between two mock networks.
+///
+/// The default model delivers every message instantly and reliably.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LinkModel {
+    /// The latency of a message once it has been transmitted.
+    pub latency: Latency,
+    /// The probability in `[0, 1]` that a message is lost.
+    pub drop_probability: f64,
+    /// The maximum throughput in bytes per second. Messages queue up behind each other if the
+    /// link is busy.
+    pub bandwidth: Option<u64>,
+}
+
+impl LinkModel {
+    fn is_perfect(&self) -> bool {
+        *self == LinkModel::default()
+    }
+}
+
+/// The reasons a [`LinkModel`] is rejected.
+#[derive(Clone, Debug, Error, PartialEq)]
+pub enum LinkModelError {
+    #[error("Drop probability must be within [0, 1]: {0}")]
+    InvalidDropProbability(f64),
+
+    #[error("Bandwidth must not be zero")]
+    ZeroBandwidth,
+}
+
+/// A change to the links of a hub, used to script faults with [`MockHub::run_script`].
+///
+/// [`MockHub::run_script`]: crate::MockHub::run_script
+#[derive(Clone, Debug)]
+pub enum LinkEvent {
+    /// Splits the networks into the given groups. Messages are only delivered between networks
+    /// of the same group. All networks that are not part of any group form another group.
+    Partition(Vec<Vec<MockAddress>>),
+    /// Removes the partition.
+    Heal,
+    /// Replaces the model of all links without a specific model.
+    SetDefault(LinkModel),
+    /// Sets the model of the link from one network to another.
+    Set {
+        from: MockAddress,
+        to: MockAddress,
+        model: LinkModel,
+    },
+}
+
+#[derive(Debug)]
+struct LinkState {
+    /// Each link has its own random number generator derived from the hub's seed, such that its
+    /// faults don't depend on the traffic of other links.
+    rng: StdRng,
+    /// The virtual time at which the link finished transmitting the last message.
+    free_at: Duration,
+    /// The virtual time at which the last message is delivered. Links never reorder messages.
+    last_delivery: Duration,
+}
+
+/// The links between the networks of a hub.
+#[derive(Debug)]
+pub(crate) struct Links {
+    seed: u64,
+    default_model: LinkModel,
+    models: HashMap<(MockAddress, MockAddress), LinkModel>,
+    /// The group of each network, if the hub is partitioned.
+    groups: HashMap<MockAddress, usize>,
+    states: HashMap<(MockAddress, MockAddress), LinkState>,
+}
+
+impl Links {
+    pub fn new(seed: u64) -> Self {
+        Links {
+            seed,
+            default_model: LinkModel::default(),
+            models: HashMap::new(),
+            groups: HashMap::new(),
+            states: HashMap::new(),
+        }
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn apply(&mut self, event: LinkEvent) -> Result<(), LinkModelError> {
+        log::debug!(?event, "Applying link event");
+        match event {
+            LinkEvent::Partition(groups) => self.partition(groups),
+            LinkEvent::Heal => self.heal(),
+            LinkEvent::SetDefault(model) => {
+                Self::check_model(&model)?;
+                self.default_model = model;
+            }
+            LinkEvent::Set { from, to, model } => {
+                Self::check_model(&model)?;
+                self.models.insert((from, to), model);
+            }
+        }
+        Ok(())
+    }
+
+    pub fn partition(&mut self, groups: Vec<Vec<MockAddress>>) {
+        self.groups = groups
+            .into_iter()
+            .enumerate()
+            .flat_map(|(i, group)| group.into_iter().map(move |address| (address, i)))
+            .collect();
+    }
+
+    pub fn heal(&mut self) {
+        self.groups.clear();
+    }
+
+    /// Checks an event without applying it.
+    pub fn check_event(event: &LinkEvent) -> Result<(), LinkModelError> {
+        match event {
+            LinkEvent::SetDefault(model) | LinkEvent::Set { model, .. } => Self::check_model(model),
+            LinkEvent::Partition(_) | LinkEvent::Heal => Ok(()),
+        }
+    }
+
+    fn check_model(model: &LinkModel) -> Result<(), LinkModelError> {
+        // This also rejects NaN.
+        if !(0.0..=1.0).contains(&model.drop_probability) {
+            return Err(LinkModelError::InvalidDropProbability(
+                model.drop_probability,
+            ));
+        }
+        if model.bandwidth == Some(0) {
+            return Err(LinkModelError::ZeroBandwidth);
+        }
+        Ok(())
+    }
+
+    /// Decides the fate of a message of `size` bytes sent at the virtual time `sent_at` from one
+    /// network to another. Returns the virtual time at which it must be delivered, or `None` if it
+    /// is lost.
+    pub fn deliver(
+        &mut self,
+        from: MockAddress,
+        to: MockAddress,
+        size: usize,
+        sent_at: Duration,
+    ) -> Option<Duration> {
+        if from == to {
+            return Some(sent_at);
+        }
+
+        if self.groups.get(&from) != self.groups.get(&to) {
+            log::trace!(%from, %to, "Message dropped due to partition");
+            return None;
+        }
+
+        let model = self.models.get(&(from, to)).unwrap_or(&self.default_model);
+        if model.is_perfect() {
+            return Some(sent_at);
+        }
+
+        let seed = self.seed;
+        let state = self.states.entry((from, to)).or_insert_with(|| LinkState {
+            rng: StdRng::seed_from_u64(
+                seed ^ from.0.rotate_left(32) ^ to.0.wrapping_mul(0x9e37_79b9_7f4a_7c15),
+            ),
+            free_at: sent_at,
+            last_delivery: sent_at,
+        });
+
+        if state.rng.gen_bool(model.drop_probability) {
+            log::trace!(%from, %to, "Message dropped by link");
+            return None;
+        }
+
+        let transmission = model.bandwidth.map_or(Duration::ZERO, |bandwidth| {
+            Duration::from_secs_f64(size as f64 / bandwidth as f64)
+        });
+        state.free_at = state.free_at.max(sent_at) + transmission;
+
+        let delivery =
+            (state.free_at + model.latency.sample(&mut state.rng)).max(state.last_delivery);
+        state.last_delivery = delivery;
+
+        Some(delivery)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{Latency, LinkEvent, LinkModel, LinkModelError, Links};
+    use crate::MockAddress;
+
+    fn lossy_link() -> LinkModel {
+        LinkModel {
+            latency: Latency::Uniform {
+                min: Duration::from_millis(10),
+                max: Duration::from_millis(100),
+            },
+            drop_probability: 0.3,
+            bandwidth: Some(1000),
+        }
+    }
+
+    #[test]
+    fn same_seed_same_faults() {
+        let a = MockAddress(1);
+        let b = MockAddress(2);
+        let c = MockAddress(3);
+        let start = Duration::ZERO;
+
+        let mut links1 = Links::new(42);
+        let mut links2 = Links::new(42);
+        links1.apply(LinkEvent::SetDefault(lossy_link())).unwrap();
+        links2.apply(LinkEvent::SetDefault(lossy_link())).unwrap();
+
+        // Traffic on other links must not influence the faults of a link.
+        let deliveries1: Vec<_> = (0..100)
+            .map(|i| links1.deliver(a, b, 100, start + Duration::from_millis(i)))
+            .collect();
+        let deliveries2: Vec<_> = (0..100)
+            .map(|i| {
+                links2.deliver(a, c, 100, start);
+                links2.deliver(a, b, 100, start + Duration::from_millis(i))
+            })
+            .collect();
+        assert_eq!(deliveries1, deliveries2);
+
+        // Some messages are lost, the others are delivered in order.
+        let delivered: Vec<_> = deliveries1.into_iter().flatten().collect();
+        assert!(delivered.len() < 100);
+        assert!(delivered.windows(2).all(|pair| pair[0] <= pair[1]));
+    }
+
+    #[test]
+    fn bandwidth_queues_messages() {
+        let a = MockAddress(1);
+        let b = MockAddress(2);
+        let start = Duration::ZERO;
+
+        let mut links = Links::new(0);
+        links
+            .apply(LinkEvent::Set {
+                from: a,
+                to: b,
+                model: LinkModel {
+                    bandwidth: Some(1000),
+                    ..Default::default()
+                },
+            })
+            .unwrap();
+
+        assert_eq!(
+            links.deliver(a, b, 500, start),
+            Some(start + Duration::from_millis(500))
+        );
+        assert_eq!(
+            links.deliver(a, b, 500, start),
+            Some(start + Duration::from_secs(1))
+        );
+        // The other direction is not affected.
+        assert_eq!(links.deliver(b, a, 500, start), Some(start));
+    }
+
+    #[test]
+    fn partition_and_heal() {
+        let a = MockAddress(1);
+        let b = MockAddress(2);
+        let c = MockAddress(3);
+        let d = MockAddress(4);
+        let now = Duration::from_secs(1);
+
+        let mut links = Links::new(0);
+        links
+            .apply(LinkEvent::Partition(vec![vec![a, b], vec![c]]))
+            .unwrap();
+
+        assert_eq!(links.deliver(a, b, 0, now), Some(now));
+        assert_eq!(links.deliver(a, c, 0, now), None);
+        assert_eq!(links.deliver(c, b, 0, now), None);
+        assert_eq!(links.deliver(d, a, 0, now), None);
+
+        links.apply(LinkEvent::Heal).unwrap();
+        assert_eq!(links.deliver(a, c, 0, now), Some(now));
+        assert_eq!(links.deliver(d, a, 0, now), Some(now));
+    }
+
+    #[test]
+    fn invalid_models_are_rejected() {
+        let mut links = Links::new(0);
+
+        for drop_probability in [-0.1, 1.5, f64::NAN] {
+            let result = links.apply(LinkEvent::SetDefault(LinkModel {
+                drop_probability,
+                ..Default::default()
+            }));
+            assert!(matches!(
+                result,
+                Err(LinkModelError::InvalidDropProbability(_))
+            ));
+        }
+        assert_eq!(
+            links.apply(LinkEvent::Set {
+                from: MockAddress(1),
+                to: MockAddress(2),
+                model: LinkModel {
+                    bandwidth: Some(0),
+                    ..Default::default()
+                },
+            }),
+            Err(LinkModelError::ZeroBandwidth)
+        );
+
+        // Rejected models are not applied.
+        assert_eq!(links.default_model, LinkModel::default());
+        assert!(links.models.is_empty());
+    }
+}
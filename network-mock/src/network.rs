@@ -1,21 +1,27 @@
 use std::{
+    collections::BTreeMap,
+    pin::Pin,
     sync::{
         atomic::{AtomicBool, Ordering},
         Arc,
     },
+    task::{Context, Poll},
     time::Duration,
 };
 
 use async_trait::async_trait;
-use futures::{stream::BoxStream, StreamExt};
+use futures::{
+    stream::{BoxStream, Stream},
+    FutureExt, StreamExt,
+};
 use nimiq_network_interface::{
     network::{
         CloseReason, MsgAcceptance, Network, NetworkEvent, PubsubId, SubscribeEvents, Topic,
     },
     peer_info::{PeerInfo, Services},
     request::{
-        InboundRequestError, Message, OutboundRequestError, Request, RequestCommon, RequestError,
-        RequestKind, RequestSerialize, RequestType,
+        peek_type, InboundRequestError, Message, OutboundRequestError, Request, RequestCommon,
+        RequestError, RequestSerialize, RequestType,
     },
 };
 use nimiq_serde::{Deserialize, DeserializeError, Serialize};
@@ -27,7 +33,8 @@ use tokio::sync::{broadcast, mpsc, oneshot};
 use tokio_stream::wrappers::{errors::BroadcastStreamRecvError, BroadcastStream, ReceiverStream};
 
 use crate::{
-    hub::{MockHubInner, RequestKey, ResponseSender},
+    clock::{Sleep, VirtualClock},
+    hub::{GossipMessage, MockHubInner, RequestKey, ResponseSender},
     observable_hash_map, MockAddress, MockPeerId, ObservableHashMap,
 };
 
@@ -175,10 +182,27 @@ impl MockNetwork {
         request: Req,
         peer_id: MockPeerId,
     ) -> Result<Req::Response, RequestError> {
+        let data = self
+            .request_raw(request.serialize_request(), peer_id)
+            .await?;
+        Req::Response::deserialize_from_vec(&data[..])
+            .map_err(|_| RequestError::InboundRequest(InboundRequestError::DeSerializationError))
+    }
+
+    /// Sends a serialized request, prefixed by its request type, to a peer and returns the
+    /// serialized response.
+    pub async fn request_raw(
+        &self,
+        data: Vec<u8>,
+        peer_id: MockPeerId,
+    ) -> Result<Vec<u8>, RequestError> {
+        let message_type = peek_type(&data)
+            .map_err(|_| RequestError::OutboundRequest(OutboundRequestError::SerializationError))?;
+
         if !self.peers.read().contains_key(&peer_id) {
             log::warn!(
                 "Cannot send request {} from {} to {} - peers not connected",
-                std::any::type_name::<Req>(),
+                message_type,
                 self.address,
                 peer_id,
             );
@@ -190,12 +214,12 @@ impl MockNetwork {
         let sender_id = MockPeerId::from(self.address);
         let (tx, rx) = oneshot::channel::<Vec<u8>>();
 
-        let (sender, request_id) = {
+        let (sender, request_id, delivery) = {
             let mut hub = self.hub.lock();
 
             let key = RequestKey {
                 recipient: peer_id.into(),
-                message_type: RequestType::from_request::<Req>(),
+                message_type,
             };
             let sender = if let Some(sender) = hub.request_senders.get(&key) {
                 sender.clone()
@@ -207,7 +231,7 @@ impl MockNetwork {
             };
 
             let request_id = hub.next_request_id;
-            if Req::Kind::EXPECT_RESPONSE {
+            if message_type.requires_response() {
                 hub.response_senders.insert(
                     request_id,
                     ResponseSender {
@@ -221,34 +245,38 @@ impl MockNetwork {
             }
             hub.next_request_id += 1;
 
-            (sender, request_id)
+            let now = hub.clock.now();
+            let delivery = hub
+                .links
+                .deliver(self.address, peer_id.into(), data.len(), now)
+                .map(|delivery| hub.clock.sleep_until(delivery));
+
+            (sender, request_id, delivery)
         };
 
-        let data = request.serialize_request();
+        // A lost request is never answered, so it eventually times out.
+        if let Some(delivery) = delivery {
+            delivery.await;
 
-        let request = (data, request_id, sender_id);
-        if let Err(e) = sender.send(request).await {
-            log::warn!(
-                "Cannot send request {} from {} to {} - {:?}",
-                std::any::type_name::<Req>(),
-                self.address,
-                peer_id,
-                e
-            );
-            self.hub.lock().response_senders.remove(&request_id);
-            return Err(RequestError::OutboundRequest(
-                OutboundRequestError::SendError,
-            ));
+            let request = (data, request_id, sender_id);
+            if let Err(e) = sender.send(request).await {
+                log::warn!(
+                    "Cannot send request {} from {} to {} - {:?}",
+                    message_type,
+                    self.address,
+                    peer_id,
+                    e
+                );
+                self.hub.lock().response_senders.remove(&request_id);
+                return Err(RequestError::OutboundRequest(
+                    OutboundRequestError::SendError,
+                ));
+            }
         }
 
         let result = timeout(MockNetwork::REQUEST_TIMEOUT, rx).await;
         match result {
-            Ok(Ok(data)) => match Req::Response::deserialize_from_vec(&data[..]) {
-                Ok(message) => Ok(message),
-                Err(_) => Err(RequestError::InboundRequest(
-                    InboundRequestError::DeSerializationError,
-                )),
-            },
+            Ok(Ok(data)) => Ok(data),
             Ok(Err(_)) => Err(RequestError::InboundRequest(
                 InboundRequestError::SenderFutureDropped,
             )),
@@ -262,21 +290,7 @@ impl MockNetwork {
     fn receive_requests_impl<Req: RequestCommon>(
         &self,
     ) -> BoxStream<'static, (Req, MockRequestId, MockPeerId)> {
-        let mut hub = self.hub.lock();
-        let (tx, rx) = mpsc::channel(16);
-
-        let key = RequestKey {
-            recipient: self.address,
-            message_type: RequestType::from_request::<Req>(),
-        };
-        if hub.request_senders.insert(key, tx).is_some() {
-            log::warn!(
-                "Replacing existing request sender for {}",
-                std::any::type_name::<Req>()
-            );
-        }
-
-        ReceiverStream::new(rx)
+        self.receive_requests_raw(RequestType::from_request::<Req>())
             .filter_map(|(data, request_id, sender)| async move {
                 match Req::deserialize_request(&data) {
                     Ok(message) => Some((message, request_id, sender)),
@@ -289,6 +303,60 @@ impl MockNetwork {
             .boxed()
     }
 
+    /// Receives the serialized requests of the given type, prefixed by their request type.
+    pub fn receive_requests_raw(
+        &self,
+        message_type: RequestType,
+    ) -> BoxStream<'static, (Vec<u8>, MockRequestId, MockPeerId)> {
+        let mut hub = self.hub.lock();
+        let (tx, rx) = mpsc::channel(16);
+
+        let key = RequestKey {
+            recipient: self.address,
+            message_type,
+        };
+        if hub.request_senders.insert(key, tx).is_some() {
+            log::warn!("Replacing existing request sender for {}", message_type);
+        }
+
+        ReceiverStream::new(rx).boxed()
+    }
+
+    /// Responds to a request with a serialized response.
+    pub async fn respond_raw(
+        &self,
+        request_id: MockRequestId,
+        data: Vec<u8>,
+    ) -> Result<(), MockNetworkError> {
+        let (responder, delivery) = {
+            let mut hub = self.hub.lock();
+            let Some(responder) = hub.response_senders.remove(&request_id) else {
+                return Err(MockNetworkError::CantRespond(request_id));
+            };
+            if !self.peers.read().contains_key(&responder.peer) {
+                return Err(MockNetworkError::NotConnected);
+            }
+
+            let now = hub.clock.now();
+            let delivery = hub
+                .links
+                .deliver(self.address, responder.peer.into(), data.len(), now)
+                .map(|delivery| hub.clock.sleep_until(delivery));
+            (responder, delivery)
+        };
+
+        // A lost response looks like a successful one to the responder.
+        let Some(delivery) = delivery else {
+            return Ok(());
+        };
+        delivery.await;
+
+        responder
+            .sender
+            .send(data)
+            .map_err(|_| MockNetworkError::CantRespond(request_id))
+    }
+
     /// Disconnect from all peers
     pub fn disconnect(&self) {
         let hub = self.hub.lock();
@@ -326,6 +394,8 @@ impl MockNetwork {
     {
         let mut hub = self.hub.lock();
         let is_connected = Arc::clone(&self.is_connected);
+        let address = self.address;
+        let clock = Arc::clone(&hub.clock);
 
         log::debug!(
             "Peer {} subscribing to topic '{}'",
@@ -334,27 +404,24 @@ impl MockNetwork {
         );
 
         // Add this peer to the topic list
-        let sender: &broadcast::Sender<(Arc<Vec<u8>>, MockPeerId)> =
+        let sender: &broadcast::Sender<GossipMessage> =
             if let Some(topic) = hub.subscribe(topic_name.clone(), self.address) {
                 &topic.sender
             } else {
                 return Err(MockNetworkError::AlreadySubscribed(topic_name));
             };
 
-        let stream = BroadcastStream::new(sender.subscribe()).filter_map(move |r| {
+        let deliveries =
+            GossipDeliveries::new(BroadcastStream::new(sender.subscribe()), address, clock);
+        let stream = deliveries.filter_map(move |(data, peer_id)| {
             let is_connected = Arc::clone(&is_connected);
 
             async move {
                 if is_connected.load(Ordering::SeqCst) {
-                    match r {
-                        Ok((data, peer_id)) => match T::Item::deserialize_from_vec(&data) {
-                            Ok(item) => return Some((item, peer_id)),
-                            Err(e) => {
-                                log::warn!("Dropped item because deserialization failed: {}", e)
-                            }
-                        },
-                        Err(BroadcastStreamRecvError::Lagged(_)) => {
-                            log::warn!("Mock gossipsub channel is lagging")
+                    match T::Item::deserialize_from_vec(&data) {
+                        Ok(item) => return Some((item, peer_id)),
+                        Err(e) => {
+                            log::warn!("Dropped item because deserialization failed: {}", e)
                         }
                     }
                 } else {
@@ -401,8 +468,6 @@ impl MockNetwork {
     where
         T: Topic + Sync,
     {
-        let mut hub = self.hub.lock();
-
         let data = item.serialize_to_vec();
 
         log::debug!(
@@ -412,11 +477,38 @@ impl MockNetwork {
             item
         );
 
+        self.publish_raw(topic_name, data).await
+    }
+
+    /// Publishes a serialized message on the topic with the given name.
+    pub async fn publish_raw(
+        &self,
+        topic_name: String,
+        data: Vec<u8>,
+    ) -> Result<(), MockNetworkError> {
+        let mut hub = self.hub.lock();
+
         if self.is_connected.load(Ordering::SeqCst) {
-            if let Some(topic) = hub.get_topic(&topic_name) {
+            let hub = &mut *hub;
+            if let Some(topic) = hub.gossipsub_topics.get(&topic_name) {
+                // The link model decides about each subscriber when the message is published.
+                let now = hub.clock.now();
+                let deliveries = topic
+                    .peers
+                    .iter()
+                    .filter_map(|&address| {
+                        let delivery = hub.links.deliver(self.address, address, data.len(), now)?;
+                        Some((address, delivery))
+                    })
+                    .collect();
+
                 topic
                     .sender
-                    .send((Arc::new(data), self.address.into()))
+                    .send(GossipMessage {
+                        data: Arc::new(data),
+                        sender: self.address.into(),
+                        deliveries: Arc::new(deliveries),
+                    })
                     .unwrap();
                 Ok(())
             } else {
@@ -429,6 +521,85 @@ impl MockNetwork {
     }
 }
 
+/// The gossip messages of a topic as they arrive at one subscriber. Messages wait in a queue until
+/// their delivery time, such that a delayed message doesn't hold back the messages behind it.
+struct GossipDeliveries {
+    /// The published messages, `None` once the topic is gone.
+    messages: Option<BroadcastStream<GossipMessage>>,
+    address: MockAddress,
+    clock: Arc<VirtualClock>,
+    /// The messages on their way, ordered by delivery time and then by publication.
+    queue: BTreeMap<(Duration, u64), (Arc<Vec<u8>>, MockPeerId)>,
+    next_id: u64,
+    /// Waits for the earliest message in the queue.
+    sleep: Option<Sleep>,
+}
+
+impl GossipDeliveries {
+    fn new(
+        messages: BroadcastStream<GossipMessage>,
+        address: MockAddress,
+        clock: Arc<VirtualClock>,
+    ) -> Self {
+        Self {
+            messages: Some(messages),
+            address,
+            clock,
+            queue: BTreeMap::new(),
+            next_id: 0,
+            sleep: None,
+        }
+    }
+}
+
+impl Stream for GossipDeliveries {
+    type Item = (Arc<Vec<u8>>, MockPeerId);
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+
+        // Queue all messages published so far.
+        while let Some(messages) = &mut this.messages {
+            match messages.poll_next_unpin(cx) {
+                Poll::Ready(Some(Ok(message))) => {
+                    // Subscribers without a delivery time lost the message.
+                    if let Some(&delivery) = message.deliveries.get(&this.address) {
+                        this.queue
+                            .insert((delivery, this.next_id), (message.data, message.sender));
+                        this.next_id += 1;
+                    }
+                }
+                Poll::Ready(Some(Err(BroadcastStreamRecvError::Lagged(_)))) => {
+                    log::warn!("Mock gossipsub channel is lagging")
+                }
+                Poll::Ready(None) => this.messages = None,
+                Poll::Pending => break,
+            }
+        }
+
+        let Some((&(delivery, _), _)) = this.queue.first_key_value() else {
+            return if this.messages.is_some() {
+                Poll::Pending
+            } else {
+                Poll::Ready(None)
+            };
+        };
+
+        if delivery > this.clock.now() {
+            // A message that arrived in the meantime might be due before the one we waited for.
+            if this.sleep.as_ref().map(Sleep::deadline) != Some(delivery) {
+                this.sleep = Some(this.clock.sleep_until(delivery));
+            }
+            if this.sleep.as_mut().unwrap().poll_unpin(cx).is_pending() {
+                return Poll::Pending;
+            }
+        }
+
+        this.sleep = None;
+        Poll::Ready(this.queue.pop_first().map(|(_, message)| message))
+    }
+}
+
 #[async_trait]
 impl Network for MockNetwork {
     type PeerId = MockPeerId;
@@ -615,22 +786,9 @@ impl Network for MockNetwork {
         request_id: Self::RequestId,
         response: Req::Response,
     ) -> Result<(), Self::Error> {
-        let mut hub = self.hub.lock();
-        if let Some(responder) = hub.response_senders.remove(&request_id) {
-            if !self.peers.read().contains_key(&responder.peer) {
-                return Err(MockNetworkError::NotConnected);
-            }
-
-            let mut data = Vec::with_capacity(response.serialized_size());
-            response.serialize(&mut data).unwrap();
-
-            responder
-                .sender
-                .send(data)
-                .map_err(|_| MockNetworkError::CantRespond(request_id))
-        } else {
-            Err(MockNetworkError::CantRespond(request_id))
-        }
+        let mut data = Vec::with_capacity(response.serialized_size());
+        response.serialize(&mut data).unwrap();
+        self.respond_raw(request_id, data).await
     }
 
     fn peer_provides_required_services(&self, _peer_id: Self::PeerId) -> bool {
@@ -645,6 +803,11 @@ impl Network for MockNetwork {
         self.peers.read().get(&peer_id).cloned()
     }
 
+    fn get_peer_rtt(&self, _peer_id: Self::PeerId) -> Option<Duration> {
+        // Mock networks don't ping their peers.
+        None
+    }
+
     async fn get_peers_by_services(
         &self,
         _services: Services,
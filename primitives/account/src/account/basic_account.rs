@@ -209,7 +209,7 @@ impl AccountPruningInteraction for BasicAccount {
         self.balance.is_zero()
     }
 
-    fn prune(self, _data_store: DataStoreRead) -> Option<AccountReceipt> {
+    fn prune(self, _data_store: DataStoreWrite) -> Option<AccountReceipt> {
         None
     }
 
@@ -364,7 +364,7 @@ impl AccountPruningInteraction for HashedTimeLockedContract {
         self.balance.is_zero()
     }
 
-    fn prune(self, _data_store: DataStoreRead) -> Option<AccountReceipt> {
+    fn prune(self, _data_store: DataStoreWrite) -> Option<AccountReceipt> {
         Some(PrunedHashedTimeLockContract::from(self).into())
     }
 
@@ -315,7 +315,7 @@ impl AccountPruningInteraction for Account {
         gen_account_match!(self, can_be_pruned)
     }
 
-    fn prune(self, data_store: DataStoreRead) -> Option<AccountReceipt> {
+    fn prune(self, data_store: DataStoreWrite) -> Option<AccountReceipt> {
         gen_account_match!(self, prune, data_store)
     }
 
@@ -878,7 +878,7 @@ impl AccountPruningInteraction for StakingContract {
         false
     }
 
-    fn prune(self, _data_store: DataStoreRead) -> Option<AccountReceipt> {
+    fn prune(self, _data_store: DataStoreWrite) -> Option<AccountReceipt> {
         unreachable!()
     }
 
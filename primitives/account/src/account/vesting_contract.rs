@@ -1,15 +1,16 @@
 use nimiq_keys::Address;
+use nimiq_primitives::{account::AccountError, coin::Coin, key_nibbles::KeyNibbles};
 #[cfg(feature = "interaction-traits")]
-use nimiq_primitives::account::AccountType;
-use nimiq_primitives::{account::AccountError, coin::Coin};
+use nimiq_primitives::{account::AccountType, policy::Policy, transaction::TransactionError};
 use nimiq_serde::{Deserialize, Serialize};
+use nimiq_transaction::account::vesting_contract::{VestingSchedule, VestingTranche};
 #[cfg(feature = "interaction-traits")]
 use nimiq_transaction::{
     account::vesting_contract::CreationTransactionData, inherent::Inherent, SignatureProof,
     Transaction,
 };
 
-use crate::{convert_receipt, AccountReceipt};
+use crate::{convert_receipt, data_store_ops::DataStoreReadOps, AccountReceipt};
 #[cfg(feature = "interaction-traits")]
 use crate::{
     data_store::{DataStoreRead, DataStoreWrite},
@@ -38,6 +39,35 @@ pub struct VestingContract {
     pub total_amount: Coin,
 }
 
+impl VestingContract {
+    /// The key of the release schedule in the contract's data store.
+    const SCHEDULE_KEY: u8 = 0;
+
+    fn schedule_key() -> KeyNibbles {
+        KeyNibbles::from(&[Self::SCHEDULE_KEY][..])
+    }
+
+    /// Get the release schedule of the contract, if it was created with one.
+    /// Contracts without a schedule release their funds as described by their fields.
+    pub fn get_schedule<T: DataStoreReadOps>(&self, data_store: &T) -> Option<VestingSchedule> {
+        data_store.get(&Self::schedule_key())
+    }
+
+    /// Returns the amount that is still locked at the given time.
+    pub fn min_cap(&self, time: u64, schedule: Option<&VestingSchedule>) -> Coin {
+        match schedule {
+            Some(schedule) => schedule.min_cap(time),
+            None => VestingTranche {
+                start_time: self.start_time,
+                time_step: self.time_step,
+                step_amount: self.step_amount,
+                total_amount: self.total_amount,
+            }
+            .min_cap(time),
+        }
+    }
+}
+
 #[cfg(feature = "interaction-traits")]
 impl VestingContract {
     fn can_change_balance(
@@ -45,9 +75,10 @@ impl VestingContract {
         transaction: &Transaction,
         new_balance: Coin,
         block_state: &BlockState,
+        schedule: Option<&VestingSchedule>,
     ) -> Result<(), AccountError> {
         // Check vesting min cap.
-        let min_cap = self.min_cap(block_state.time);
+        let min_cap = self.min_cap(block_state.time, schedule);
 
         if new_balance < min_cap {
             return Err(AccountError::InsufficientFunds {
@@ -65,18 +96,6 @@ impl VestingContract {
 
         Ok(())
     }
-
-    fn min_cap(&self, time: u64) -> Coin {
-        if self.time_step > 0 && self.step_amount > Coin::ZERO {
-            let steps = (time as i128 - self.start_time as i128) / self.time_step as i128;
-            let min_cap =
-                u64::from(self.total_amount) as i128 - steps * u64::from(self.step_amount) as i128;
-            // Since all parameters have been validated, this will be safe as well.
-            Coin::from_u64_unchecked(min_cap.max(0) as u64)
-        } else {
-            Coin::ZERO
-        }
-    }
 }
 
 #[cfg(feature = "interaction-traits")]
@@ -84,12 +103,25 @@ impl AccountTransactionInteraction for VestingContract {
     fn create_new_contract(
         transaction: &Transaction,
         initial_balance: Coin,
-        _block_state: &BlockState,
-        _data_store: DataStoreWrite,
+        block_state: &BlockState,
+        mut data_store: DataStoreWrite,
         tx_logger: &mut TransactionLog,
     ) -> Result<Account, AccountError> {
         let data = CreationTransactionData::parse(transaction)?;
 
+        // Release schedules are only supported from their activation block on.
+        if data.schedule.is_some()
+            && block_state.number < Policy::vesting_schedule_activation_block()
+        {
+            return Err(TransactionError::InvalidData.into());
+        }
+
+        // The schedule is kept in the data store, such that the serialization of the contract
+        // stays the same for contracts without a schedule.
+        if let Some(schedule) = &data.schedule {
+            data_store.put(&Self::schedule_key(), schedule.clone());
+        }
+
         tx_logger.push_log(Log::VestingCreate {
             contract_address: transaction.recipient.clone(),
             owner: data.owner.clone(),
@@ -97,6 +129,7 @@ impl AccountTransactionInteraction for VestingContract {
             time_step: data.time_step,
             step_amount: data.step_amount,
             total_amount: data.total_amount,
+            schedule: data.schedule,
         });
 
         Ok(Account::Vesting(VestingContract {
@@ -113,11 +146,16 @@ impl AccountTransactionInteraction for VestingContract {
         &mut self,
         transaction: &Transaction,
         _block_state: &BlockState,
-        _data_store: DataStoreWrite,
+        mut data_store: DataStoreWrite,
         tx_logger: &mut TransactionLog,
     ) -> Result<(), AccountError> {
         self.balance -= transaction.value;
 
+        let schedule: Option<VestingSchedule> = data_store.get(&Self::schedule_key());
+        if schedule.is_some() {
+            data_store.remove(&Self::schedule_key());
+        }
+
         tx_logger.push_log(Log::VestingCreate {
             contract_address: transaction.recipient.clone(),
             owner: self.owner.clone(),
@@ -125,6 +163,7 @@ impl AccountTransactionInteraction for VestingContract {
             time_step: self.time_step,
             step_amount: self.step_amount,
             total_amount: self.total_amount,
+            schedule,
         });
 
         Ok(())
@@ -155,11 +194,12 @@ impl AccountTransactionInteraction for VestingContract {
         &mut self,
         transaction: &Transaction,
         block_state: &BlockState,
-        _data_store: DataStoreWrite,
+        data_store: DataStoreWrite,
         tx_logger: &mut TransactionLog,
     ) -> Result<Option<AccountReceipt>, AccountError> {
         let new_balance = self.balance.safe_sub(transaction.total_value())?;
-        self.can_change_balance(transaction, new_balance, block_state)?;
+        let schedule: Option<VestingSchedule> = data_store.get(&Self::schedule_key());
+        self.can_change_balance(transaction, new_balance, block_state, schedule.as_ref())?;
         self.balance = new_balance;
 
         tx_logger.push_log(Log::pay_fee_log(transaction));
@@ -188,12 +228,13 @@ impl AccountTransactionInteraction for VestingContract {
         &mut self,
         transaction: &Transaction,
         block_state: &BlockState,
-        _data_store: DataStoreWrite,
+        data_store: DataStoreWrite,
         tx_logger: &mut TransactionLog,
     ) -> Result<Option<AccountReceipt>, AccountError> {
         let new_balance = self.balance.safe_sub(transaction.fee)?;
         // XXX This check should not be necessary since are also checking this in reserve_balance()
-        self.can_change_balance(transaction, new_balance, block_state)?;
+        let schedule: Option<VestingSchedule> = data_store.get(&Self::schedule_key());
+        self.can_change_balance(transaction, new_balance, block_state, schedule.as_ref())?;
         self.balance = new_balance;
 
         tx_logger.push_log(Log::pay_fee_log(transaction));
@@ -221,14 +262,15 @@ impl AccountTransactionInteraction for VestingContract {
         transaction: &Transaction,
         reserved_balance: &mut ReservedBalance,
         block_state: &BlockState,
-        _data_store: DataStoreRead,
+        data_store: DataStoreRead,
     ) -> Result<(), AccountError> {
         let needed = reserved_balance
             .balance()
             .checked_add(transaction.total_value())
             .ok_or(AccountError::InvalidCoinValue)?;
         let new_balance = self.balance.safe_sub(needed)?;
-        self.can_change_balance(transaction, new_balance, block_state)?;
+        let schedule = self.get_schedule(&data_store);
+        self.can_change_balance(transaction, new_balance, block_state, schedule.as_ref())?;
 
         reserved_balance.reserve(self.balance, transaction.total_value())
     }
@@ -274,16 +316,40 @@ impl AccountPruningInteraction for VestingContract {
         self.balance.is_zero()
     }
 
-    fn prune(self, _data_store: DataStoreRead) -> Option<AccountReceipt> {
-        Some(PrunedVestingContract::from(self).into())
+    fn prune(self, mut data_store: DataStoreWrite) -> Option<AccountReceipt> {
+        // The release schedule is removed from the data store together with the contract, so it
+        // needs to be part of the receipt.
+        let schedule: Option<VestingSchedule> = data_store.get(&Self::schedule_key());
+        match schedule {
+            Some(schedule) => {
+                data_store.remove(&Self::schedule_key());
+                Some(
+                    PrunedVestingContractWithSchedule {
+                        contract: PrunedVestingContract::from(self),
+                        schedule,
+                    }
+                    .into(),
+                )
+            }
+            None => Some(PrunedVestingContract::from(self).into()),
+        }
     }
 
     fn restore(
         _ty: AccountType,
         pruned_account: Option<&AccountReceipt>,
-        _data_store: DataStoreWrite,
+        mut data_store: DataStoreWrite,
     ) -> Result<Account, AccountError> {
         let receipt = pruned_account.ok_or(AccountError::InvalidReceipt)?;
+
+        // A receipt without schedule is too short to be parsed as one with schedule.
+        if let Ok(pruned_account) = PrunedVestingContractWithSchedule::try_from(receipt) {
+            data_store.put(&Self::schedule_key(), pruned_account.schedule);
+            return Ok(Account::Vesting(VestingContract::from(
+                pruned_account.contract,
+            )));
+        }
+
         let pruned_account = PrunedVestingContract::try_from(receipt)?;
         Ok(Account::Vesting(VestingContract::from(pruned_account)))
     }
@@ -324,3 +390,13 @@ impl From<PrunedVestingContract> for VestingContract {
 }
 
 convert_receipt!(PrunedVestingContract);
+
+/// The receipt of a pruned contract that was created with a release schedule. It extends the
+/// serialization of [`PrunedVestingContract`] by the schedule.
+#[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
+struct PrunedVestingContractWithSchedule {
+    contract: PrunedVestingContract,
+    schedule: VestingSchedule,
+}
+
+convert_receipt!(PrunedVestingContractWithSchedule);
@@ -208,7 +208,7 @@ impl Accounts {
     ) -> Option<AccountReceipt> {
         if account.can_be_pruned() {
             let store = DataStore::new(&self.tree, address);
-            let pruned_account = account.prune(store.read(txn));
+            let pruned_account = account.prune(store.write(txn));
             self.prune(txn, address);
             pruned_account
         } else {
@@ -131,7 +131,7 @@ pub trait AccountInherentInteraction: Sized {
 pub trait AccountPruningInteraction: Sized {
     fn can_be_pruned(&self) -> bool;
 
-    fn prune(self, data_store: DataStoreRead) -> Option<AccountReceipt>;
+    fn prune(self, data_store: DataStoreWrite) -> Option<AccountReceipt>;
 
     fn restore(
         ty: AccountType,
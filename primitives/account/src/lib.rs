@@ -7,7 +7,7 @@ use nimiq_transaction::{inherent::Inherent, Transaction};
 use thiserror::Error;
 
 #[cfg(feature = "accounts")]
-pub use crate::accounts::{Accounts, AccountsTrie};
+pub use crate::accounts::{Accounts, AccountsTrie, AccountsTrieTable};
 #[cfg(feature = "interaction-traits")]
 pub use crate::data_store::{DataStore, DataStoreRead, DataStoreWrite};
 #[cfg(feature = "interaction-traits")]
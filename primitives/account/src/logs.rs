@@ -6,7 +6,10 @@ use nimiq_primitives::{
 };
 use nimiq_serde::{Deserialize, Serialize};
 use nimiq_transaction::{
-    account::htlc_contract::{AnyHash, PreImage},
+    account::{
+        htlc_contract::{AnyHash, PreImage},
+        vesting_contract::VestingSchedule,
+    },
     Transaction,
 };
 
@@ -61,6 +64,8 @@ pub enum Log {
         time_step: u64,
         step_amount: Coin,
         total_amount: Coin,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        schedule: Option<VestingSchedule>,
     },
 
     #[serde(rename_all = "camelCase")]
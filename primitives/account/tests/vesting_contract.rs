@@ -1,8 +1,8 @@
 use std::convert::{TryFrom, TryInto};
 
 use nimiq_account::{
-    Account, AccountTransactionInteraction, BasicAccount, BlockState, Log, ReservedBalance,
-    TransactionLog, VestingContract,
+    Account, AccountTransactionInteraction, BasicAccount, BlockLogger, BlockState, Log,
+    ReservedBalance, TransactionLog, VestingContract,
 };
 use nimiq_database::traits::Database;
 use nimiq_keys::{Address, KeyPair};
@@ -10,12 +10,16 @@ use nimiq_primitives::{
     account::{AccountError, AccountType},
     coin::Coin,
     networks::NetworkId,
+    policy::Policy,
     transaction::TransactionError,
 };
 use nimiq_serde::{Deserialize, Serialize};
 use nimiq_test_log::test;
 use nimiq_test_utils::{accounts_revert::TestCommitRevert, test_rng::test_rng};
-use nimiq_transaction::{SignatureProof, Transaction};
+use nimiq_transaction::{
+    account::vesting_contract::{CreationTransactionData, VestingSchedule, VestingTranche},
+    SignatureProof, Transaction,
+};
 use nimiq_utils::key_rng::SecureGenerate;
 
 const CONTRACT: &str = "00002fbf9bd9c800fd34ab7265a0e48c454ccbf4c9c61dfdf68f9a220000000000000001000000000003f480000002632e314a0000002fbf9bd9c800";
@@ -150,7 +154,8 @@ fn it_can_create_contract_from_transaction() {
             start_time: 0,
             time_step: 1000,
             step_amount: 100.try_into().unwrap(),
-            total_amount: 100.try_into().unwrap()
+            total_amount: 100.try_into().unwrap(),
+            schedule: None,
         }]
     );
 
@@ -553,3 +558,271 @@ fn can_reserve_balance_after_time_step() {
         })
     );
 }
+
+#[test]
+fn it_can_create_and_use_contract_with_schedule() {
+    let (accounts, _vesting_contract, key_1, key_2) = init_tree();
+    let owner = Address::from(&key_1);
+
+    // A cliff releasing 300 at time 1000, followed by 7 steps of 100 every 100.
+    let schedule = VestingSchedule::new(vec![
+        VestingTranche {
+            start_time: 0,
+            time_step: 1000,
+            step_amount: Coin::from_u64_unchecked(300),
+            total_amount: Coin::from_u64_unchecked(300),
+        },
+        VestingTranche {
+            start_time: 1000,
+            time_step: 100,
+            step_amount: Coin::from_u64_unchecked(100),
+            total_amount: Coin::from_u64_unchecked(700),
+        },
+    ])
+    .unwrap();
+    let data = CreationTransactionData::with_schedule(owner.clone(), schedule.clone());
+
+    let tx = Transaction::new_contract_creation(
+        owner.clone(),
+        AccountType::Basic,
+        vec![],
+        AccountType::Vesting,
+        data.to_tx_data(),
+        1000.try_into().unwrap(),
+        0.try_into().unwrap(),
+        0,
+        NetworkId::UnitAlbatross,
+    );
+    let contract_address = tx.contract_creation_address();
+
+    let mut tx_logger = TransactionLog::empty();
+    let contract = accounts
+        .test_create_new_contract::<VestingContract>(
+            &tx,
+            Coin::ZERO,
+            &BlockState::new(Policy::vesting_schedule_activation_block(), 1),
+            &mut tx_logger,
+            true,
+        )
+        .expect("Failed to create contract");
+
+    assert_eq!(
+        tx_logger.logs,
+        vec![Log::VestingCreate {
+            contract_address: contract_address.clone(),
+            owner: owner.clone(),
+            start_time: 0,
+            time_step: 1700,
+            step_amount: 1000.try_into().unwrap(),
+            total_amount: 1000.try_into().unwrap(),
+            schedule: Some(schedule.clone()),
+        }]
+    );
+
+    let mut contract = match contract {
+        Account::Vesting(contract) => contract,
+        _ => panic!("Wrong account type created"),
+    };
+
+    // The contract itself releases everything at the end of the schedule.
+    assert_eq!(contract.balance, 1000.try_into().unwrap());
+    assert_eq!(contract.min_cap(1699, None), 1000.try_into().unwrap());
+    assert_eq!(contract.min_cap(1700, None), Coin::ZERO);
+
+    let db_txn = accounts.env().read_transaction();
+    let data_store = accounts.data_store(&contract_address);
+    assert_eq!(
+        contract.get_schedule(&data_store.read(&db_txn)),
+        Some(schedule.clone())
+    );
+    drop(db_txn);
+
+    assert_eq!(
+        contract.min_cap(999, Some(&schedule)),
+        1000.try_into().unwrap()
+    );
+    assert_eq!(
+        contract.min_cap(1000, Some(&schedule)),
+        700.try_into().unwrap()
+    );
+    assert_eq!(
+        contract.min_cap(1250, Some(&schedule)),
+        500.try_into().unwrap()
+    );
+    assert_eq!(contract.min_cap(1700, Some(&schedule)), Coin::ZERO);
+
+    let make_redeem_transaction = |value: u64| {
+        let mut tx = Transaction::new_basic(
+            contract_address.clone(),
+            Address::from(&key_2),
+            Coin::from_u64_unchecked(value),
+            Coin::ZERO,
+            1,
+            NetworkId::UnitAlbatross,
+        );
+        tx.sender_type = AccountType::Vesting;
+        let signature = key_1.sign(&tx.serialize_content());
+        tx.proof = SignatureProof::from_ed25519(key_1.public, signature).serialize_to_vec();
+        tx
+    };
+
+    // Nothing is released before the cliff.
+    let mut tx_logger = TransactionLog::empty();
+    let result = accounts.test_commit_outgoing_transaction(
+        &mut contract,
+        &make_redeem_transaction(1),
+        &BlockState::new(2, 999),
+        &mut tx_logger,
+        true,
+    );
+    assert_eq!(
+        result,
+        Err(AccountError::InsufficientFunds {
+            balance: Coin::ZERO,
+            needed: Coin::from_u64_unchecked(1)
+        })
+    );
+
+    // The cliff and the first two steps of the linear release are available.
+    let block_state = BlockState::new(3, 1250);
+    let mut tx_logger = TransactionLog::empty();
+    accounts
+        .test_commit_outgoing_transaction(
+            &mut contract,
+            &make_redeem_transaction(500),
+            &block_state,
+            &mut tx_logger,
+            true,
+        )
+        .expect("Failed to commit transaction");
+    assert_eq!(contract.balance, 500.try_into().unwrap());
+
+    let mut tx_logger = TransactionLog::empty();
+    let result = accounts.test_commit_outgoing_transaction(
+        &mut contract,
+        &make_redeem_transaction(1),
+        &block_state,
+        &mut tx_logger,
+        true,
+    );
+    assert_eq!(
+        result,
+        Err(AccountError::InsufficientFunds {
+            balance: Coin::ZERO,
+            needed: Coin::from_u64_unchecked(1)
+        })
+    );
+}
+
+fn make_schedule_creation_transaction(owner: &Address) -> (Transaction, VestingSchedule) {
+    // A cliff releasing 300 at time 1000, followed by 7 steps of 100 every 100.
+    let schedule = VestingSchedule::new(vec![
+        VestingTranche {
+            start_time: 0,
+            time_step: 1000,
+            step_amount: Coin::from_u64_unchecked(300),
+            total_amount: Coin::from_u64_unchecked(300),
+        },
+        VestingTranche {
+            start_time: 1000,
+            time_step: 100,
+            step_amount: Coin::from_u64_unchecked(100),
+            total_amount: Coin::from_u64_unchecked(700),
+        },
+    ])
+    .unwrap();
+    let data = CreationTransactionData::with_schedule(owner.clone(), schedule.clone());
+
+    let tx = Transaction::new_contract_creation(
+        owner.clone(),
+        AccountType::Basic,
+        vec![],
+        AccountType::Vesting,
+        data.to_tx_data(),
+        1000.try_into().unwrap(),
+        0.try_into().unwrap(),
+        0,
+        NetworkId::UnitAlbatross,
+    );
+
+    (tx, schedule)
+}
+
+#[test]
+fn it_only_accepts_schedules_after_activation() {
+    let (accounts, _vesting_contract, key_1, _key_2) = init_tree();
+    let (tx, _schedule) = make_schedule_creation_transaction(&Address::from(&key_1));
+
+    let mut tx_logger = TransactionLog::empty();
+    let result = accounts.test_create_new_contract::<VestingContract>(
+        &tx,
+        Coin::ZERO,
+        &BlockState::new(Policy::vesting_schedule_activation_block() - 1, 1),
+        &mut tx_logger,
+        false,
+    );
+    assert_eq!(
+        result,
+        Err(AccountError::InvalidTransaction(
+            TransactionError::InvalidData
+        ))
+    );
+
+    let mut tx_logger = TransactionLog::empty();
+    let result = accounts.test_create_new_contract::<VestingContract>(
+        &tx,
+        Coin::ZERO,
+        &BlockState::new(Policy::vesting_schedule_activation_block(), 1),
+        &mut tx_logger,
+        false,
+    );
+    assert!(result.is_ok());
+}
+
+#[test]
+fn it_removes_the_schedule_when_pruning() {
+    let (accounts, vesting_contract, key_1, key_2) = init_tree();
+    let (create_tx, schedule) = make_schedule_creation_transaction(&Address::from(&key_1));
+    let contract_address = create_tx.contract_creation_address();
+
+    let mut redeem_tx = Transaction::new_basic(
+        contract_address.clone(),
+        Address::from(&key_2),
+        Coin::from_u64_unchecked(1000),
+        Coin::ZERO,
+        1,
+        NetworkId::UnitAlbatross,
+    );
+    redeem_tx.sender_type = AccountType::Vesting;
+    let signature = key_1.sign(&redeem_tx.serialize_content());
+    redeem_tx.proof = SignatureProof::from_ed25519(key_1.public, signature).serialize_to_vec();
+
+    // Create the contract with a schedule and redeem everything in the same block. The revert
+    // check of `commit_and_test` requires the schedule to be restored from the pruning receipt.
+    let block_state = BlockState::new(Policy::vesting_schedule_activation_block(), 1700);
+    accounts
+        .commit_and_test(&[create_tx], &[], &block_state, &mut BlockLogger::empty())
+        .expect("Failed to create contract");
+
+    let db_txn = accounts.env().read_transaction();
+    let data_store = accounts.data_store(&contract_address);
+    assert_eq!(
+        vesting_contract.get_schedule(&data_store.read(&db_txn)),
+        Some(schedule)
+    );
+    drop(db_txn);
+
+    accounts
+        .commit_and_test(&[redeem_tx], &[], &block_state, &mut BlockLogger::empty())
+        .expect("Failed to redeem contract");
+
+    let db_txn = accounts.env().read_transaction();
+    assert_eq!(
+        accounts.get_complete(&contract_address, Some(&db_txn)),
+        Account::default()
+    );
+    assert_eq!(
+        vesting_contract.get_schedule(&data_store.read(&db_txn)),
+        None
+    );
+}
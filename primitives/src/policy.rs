@@ -27,6 +27,10 @@ pub struct Policy {
     /// Genesis block number
     #[cfg_attr(feature = "ts-types", wasm_bindgen(skip))]
     pub genesis_block_number: u32,
+    /// Block number from which on vesting contracts can be created with a release schedule
+    /// consisting of several tranches.
+    #[cfg_attr(feature = "ts-types", wasm_bindgen(skip))]
+    pub vesting_schedule_activation_block: u32,
 }
 
 impl Policy {
@@ -220,6 +224,16 @@ impl Policy {
             .genesis_block_number
     }
 
+    /// Block number from which on vesting contracts can be created with a release schedule
+    /// consisting of several tranches.
+    #[inline]
+    #[cfg_attr(feature = "ts-types", wasm_bindgen(getter = VESTING_SCHEDULE_ACTIVATION_BLOCK))]
+    pub fn vesting_schedule_activation_block() -> u32 {
+        GLOBAL_POLICY
+            .get_or_init(Self::default)
+            .vesting_schedule_activation_block
+    }
+
     /// Maximum size of accounts trie chunks.
     #[inline]
     #[cfg_attr(feature = "ts-types", wasm_bindgen(getter = STATE_CHUNKS_MAX_SIZE))]
@@ -650,6 +664,8 @@ impl Default for Policy {
             state_chunks_max_size: 1000,
             transaction_validity_window: 120,
             genesis_block_number: 0,
+            // Not activated yet. Clients set the activation block of their network.
+            vesting_schedule_activation_block: u32::MAX,
         }
     }
 }
@@ -660,6 +676,7 @@ pub const TEST_POLICY: Policy = Policy {
     state_chunks_max_size: 3,
     transaction_validity_window: 2,
     genesis_block_number: 0,
+    vesting_schedule_activation_block: 100,
 };
 
 #[cfg(test)]
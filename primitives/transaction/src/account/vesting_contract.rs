@@ -1,5 +1,5 @@
 use nimiq_keys::Address;
-use nimiq_primitives::{account::AccountType, coin::Coin};
+use nimiq_primitives::{account::AccountType, coin::Coin, policy::Policy};
 use nimiq_serde::{Deserialize, Serialize, SerializedSize};
 
 use crate::{
@@ -40,7 +40,22 @@ impl AccountTransactionVerification for VestingContractVerifier {
         }
 
         if transaction.network_id.is_albatross() {
-            CreationTransactionData::parse(transaction).map(|_| ())
+            let data = CreationTransactionData::parse(transaction)?;
+
+            // Release schedules are only supported from their activation block on. Since a
+            // transaction can't be included in a block before its validity start height, this
+            // keeps blocks before the activation free of them.
+            if data.schedule.is_some()
+                && transaction.validity_start_height < Policy::vesting_schedule_activation_block()
+            {
+                warn!(
+                    "Release schedules are not activated yet for this transaction:\n{:?}",
+                    transaction
+                );
+                return Err(TransactionError::InvalidData);
+            }
+
+            Ok(())
         } else {
             PoWCreationTransactionData::parse(transaction).map(|_| ())
         }
@@ -88,6 +103,138 @@ pub struct CreationTransactionData {
     pub step_amount: Coin,
     /// Initially locked balance.
     pub total_amount: Coin,
+    /// The release schedule for contracts that release their funds in several tranches.
+    ///
+    /// If this is set, the fields above describe a conservative approximation of the schedule:
+    /// The whole [`total_amount`](Self::total_amount) is released at once when the last tranche
+    /// has been released.
+    pub schedule: Option<VestingSchedule>,
+}
+
+/// The maximum number of tranches in a [`VestingSchedule`].
+pub const MAX_VESTING_TRANCHES: usize = 32;
+
+/// A tranche of a [`VestingSchedule`].
+///
+/// Each tranche releases its funds like a contract without schedule: `step_amount` is released
+/// every `time_step`, starting at `start_time + time_step`, until `total_amount` has been released.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, Deserialize, Serialize, SerializedSize)]
+pub struct VestingTranche {
+    /// The time at which the release of this tranche starts.
+    #[serde(with = "nimiq_serde::fixint::be")]
+    #[serialize_size(fixed_size)]
+    pub start_time: u64,
+    /// The frequency at which funds of this tranche are released.
+    #[serde(with = "nimiq_serde::fixint::be")]
+    #[serialize_size(fixed_size)]
+    pub time_step: u64,
+    /// The amount released at each [`time_step`](Self::time_step).
+    pub step_amount: Coin,
+    /// The amount locked in this tranche.
+    pub total_amount: Coin,
+}
+
+impl VestingTranche {
+    /// Returns the amount of this tranche that is still locked at the given time.
+    pub fn min_cap(&self, time: u64) -> Coin {
+        if self.time_step > 0 && self.step_amount > Coin::ZERO {
+            let steps = (time as i128 - self.start_time as i128) / self.time_step as i128;
+            let min_cap =
+                u64::from(self.total_amount) as i128 - steps * u64::from(self.step_amount) as i128;
+            // Since all parameters have been validated, this will be safe as well.
+            Coin::from_u64_unchecked(min_cap.max(0) as u64)
+        } else {
+            Coin::ZERO
+        }
+    }
+
+    /// Returns the time at which the whole tranche has been released, or `None` if it overflows.
+    fn end_time(&self) -> Option<u64> {
+        let num_steps = u64::from(self.total_amount).div_ceil(u64::from(self.step_amount));
+        num_steps
+            .checked_mul(self.time_step)?
+            .checked_add(self.start_time)
+    }
+}
+
+/// A release schedule that consists of several tranches, e.g. a cliff followed by a linear release.
+/// The locked amount of the schedule is the sum of the locked amounts of its tranches.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub struct VestingSchedule {
+    tranches: Vec<VestingTranche>,
+}
+
+impl VestingSchedule {
+    /// Creates a new schedule from the given tranches.
+    ///
+    /// There must be at least one and at most [`MAX_VESTING_TRANCHES`] tranches. Each tranche must
+    /// release a non-zero amount in non-zero steps.
+    pub fn new(tranches: Vec<VestingTranche>) -> Result<Self, TransactionError> {
+        let schedule = VestingSchedule { tranches };
+        schedule.verify()?;
+        Ok(schedule)
+    }
+
+    fn verify(&self) -> Result<(), TransactionError> {
+        if self.tranches.is_empty() || self.tranches.len() > MAX_VESTING_TRANCHES {
+            return Err(TransactionError::InvalidData);
+        }
+
+        let mut total_amount = Coin::ZERO;
+        for tranche in &self.tranches {
+            if tranche.time_step == 0
+                || tranche.step_amount.is_zero()
+                || tranche.total_amount.is_zero()
+                || tranche.end_time().is_none()
+            {
+                return Err(TransactionError::InvalidData);
+            }
+            total_amount = total_amount
+                .checked_add(tranche.total_amount)
+                .ok_or(TransactionError::Overflow)?;
+        }
+
+        Ok(())
+    }
+
+    /// The tranches of this schedule.
+    pub fn tranches(&self) -> &[VestingTranche] {
+        &self.tranches
+    }
+
+    /// The sum of the amounts of all tranches.
+    pub fn total_amount(&self) -> Coin {
+        self.tranches
+            .iter()
+            .map(|tranche| tranche.total_amount)
+            .sum()
+    }
+
+    /// The earliest start time of all tranches.
+    pub fn start_time(&self) -> u64 {
+        self.tranches
+            .iter()
+            .map(|tranche| tranche.start_time)
+            .min()
+            .unwrap_or_default()
+    }
+
+    /// The time at which all tranches have been released.
+    pub fn end_time(&self) -> u64 {
+        self.tranches
+            .iter()
+            .filter_map(VestingTranche::end_time)
+            .max()
+            .unwrap_or_default()
+    }
+
+    /// Returns the amount that is still locked at the given time.
+    pub fn min_cap(&self, time: u64) -> Coin {
+        self.tranches
+            .iter()
+            .map(|tranche| tranche.min_cap(time))
+            .sum()
+    }
 }
 
 #[derive(Deserialize, Serialize, SerializedSize)]
@@ -121,7 +268,27 @@ struct CreationTransactionData32 {
     pub total_amount: Coin,
 }
 
+/// The serialized size of this struct is `Address::SIZE + 1 + n * VestingTranche::SIZE` for
+/// `n <= MAX_VESTING_TRANCHES`, which never collides with the sizes of the formats above.
+#[derive(Deserialize, Serialize)]
+struct CreationTransactionDataSchedule {
+    pub owner: Address,
+    pub schedule: VestingSchedule,
+}
+
 impl CreationTransactionData {
+    /// Creates the creation data for a contract that releases its funds according to `schedule`.
+    pub fn with_schedule(owner: Address, schedule: VestingSchedule) -> Self {
+        CreationTransactionData {
+            owner,
+            start_time: schedule.start_time(),
+            time_step: schedule.end_time() - schedule.start_time(),
+            step_amount: schedule.total_amount(),
+            total_amount: schedule.total_amount(),
+            schedule: Some(schedule),
+        }
+    }
+
     pub fn parse_data(data: &[u8], tx_value: Coin) -> Result<Self, TransactionError> {
         Ok(match data.len() {
             CreationTransactionData8::SIZE => {
@@ -134,6 +301,7 @@ impl CreationTransactionData {
                     time_step,
                     step_amount: tx_value,
                     total_amount: tx_value,
+                    schedule: None,
                 }
             }
             CreationTransactionData24::SIZE => {
@@ -149,6 +317,7 @@ impl CreationTransactionData {
                     time_step,
                     step_amount,
                     total_amount: tx_value,
+                    schedule: None,
                 }
             }
             CreationTransactionData32::SIZE => {
@@ -165,9 +334,16 @@ impl CreationTransactionData {
                     time_step,
                     step_amount,
                     total_amount,
+                    schedule: None,
                 }
             }
-            _ => return Err(TransactionError::InvalidData),
+            _ => {
+                let CreationTransactionDataSchedule { owner, schedule } =
+                    CreationTransactionDataSchedule::deserialize_all(data)
+                        .map_err(|_| TransactionError::InvalidData)?;
+                schedule.verify()?;
+                CreationTransactionData::with_schedule(owner, schedule)
+            }
         })
     }
 
@@ -182,8 +358,11 @@ impl CreationTransactionData {
             time_step,
             step_amount,
             total_amount,
+            schedule,
         } = self.clone();
-        if step_amount == total_amount {
+        if let Some(schedule) = schedule {
+            CreationTransactionDataSchedule { owner, schedule }.serialize_to_vec()
+        } else if step_amount == total_amount {
             if start_time == 0 {
                 CreationTransactionData8 { owner, time_step }.serialize_to_vec()
             } else {
@@ -328,6 +507,7 @@ impl PoWCreationTransactionData {
             time_step,
             step_amount: self.step_amount,
             total_amount: self.total_amount,
+            schedule: None,
         }
     }
 }
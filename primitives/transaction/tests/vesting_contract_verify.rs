@@ -1,10 +1,17 @@
 use nimiq_keys::{Address, KeyPair, PrivateKey};
 use nimiq_primitives::{
-    account::AccountType, coin::Coin, networks::NetworkId, transaction::TransactionError,
+    account::AccountType, coin::Coin, networks::NetworkId, policy::Policy,
+    transaction::TransactionError,
 };
 use nimiq_serde::{Deserialize, DeserializeError, Serialize};
+use nimiq_test_log::test;
 use nimiq_transaction::{
-    account::{vesting_contract::CreationTransactionData, AccountTransactionVerification},
+    account::{
+        vesting_contract::{
+            CreationTransactionData, VestingSchedule, VestingTranche, MAX_VESTING_TRANCHES,
+        },
+        AccountTransactionVerification,
+    },
     SignatureProof, Transaction, TransactionFlags,
 };
 
@@ -102,6 +109,7 @@ fn it_can_verify_creation_transaction() {
         time_step: 0,
         step_amount: Coin::try_from(1000).unwrap(),
         total_amount: Coin::try_from(100).unwrap(),
+        schedule: None,
     };
     transaction.recipient_data = data.to_tx_data();
     transaction.recipient = transaction.contract_creation_address();
@@ -151,3 +159,129 @@ fn it_can_verify_outgoing_transactions() {
         Err(TransactionError::InvalidProof)
     );
 }
+
+#[test]
+fn it_can_verify_creation_transaction_with_schedule() {
+    let owner = Address::from([0u8; 20]);
+    let cliff = VestingTranche {
+        start_time: 100,
+        time_step: 1000,
+        step_amount: Coin::try_from(250).unwrap(),
+        total_amount: Coin::try_from(250).unwrap(),
+    };
+    let linear = VestingTranche {
+        start_time: 1100,
+        time_step: 100,
+        step_amount: Coin::try_from(100).unwrap(),
+        total_amount: Coin::try_from(750).unwrap(),
+    };
+    let schedule = VestingSchedule::new(vec![cliff.clone(), linear]).unwrap();
+    let data = CreationTransactionData::with_schedule(owner.clone(), schedule.clone());
+
+    let mut transaction = Transaction::new_contract_creation(
+        owner.clone(),
+        AccountType::Basic,
+        vec![],
+        AccountType::Vesting,
+        data.to_tx_data(),
+        1000.try_into().unwrap(),
+        0.try_into().unwrap(),
+        Policy::vesting_schedule_activation_block(),
+        NetworkId::UnitAlbatross,
+    );
+    transaction.recipient = transaction.contract_creation_address();
+    assert_eq!(
+        AccountType::verify_incoming_transaction(&transaction),
+        Ok(())
+    );
+
+    let parsed = CreationTransactionData::parse(&transaction).unwrap();
+    assert_eq!(parsed.owner, owner);
+    assert_eq!(parsed.start_time, 100);
+    // The last step of the linear tranche is released at 1100 + 8 * 100 = 100 + 1800.
+    assert_eq!(parsed.time_step, 1800);
+    assert_eq!(parsed.step_amount, Coin::try_from(1000).unwrap());
+    assert_eq!(parsed.total_amount, Coin::try_from(1000).unwrap());
+    assert_eq!(parsed.schedule, Some(schedule));
+
+    // Tranches must release a non-zero amount in non-zero steps.
+    assert_eq!(
+        VestingSchedule::new(vec![]),
+        Err(TransactionError::InvalidData)
+    );
+    for tranche in [
+        VestingTranche {
+            time_step: 0,
+            ..cliff.clone()
+        },
+        VestingTranche {
+            step_amount: Coin::ZERO,
+            ..cliff.clone()
+        },
+        VestingTranche {
+            total_amount: Coin::ZERO,
+            ..cliff.clone()
+        },
+        VestingTranche {
+            time_step: u64::MAX,
+            ..cliff.clone()
+        },
+    ] {
+        assert_eq!(
+            VestingSchedule::new(vec![tranche]),
+            Err(TransactionError::InvalidData)
+        );
+    }
+
+    assert_eq!(
+        VestingSchedule::new(vec![cliff; MAX_VESTING_TRANCHES + 1]),
+        Err(TransactionError::InvalidData)
+    );
+}
+
+#[test]
+fn it_rejects_schedules_before_activation() {
+    let key_pair = key_pair();
+    let owner = Address::from(&key_pair.public);
+    let schedule = VestingSchedule::new(vec![VestingTranche {
+        start_time: 100,
+        time_step: 1000,
+        step_amount: Coin::try_from(1000).unwrap(),
+        total_amount: Coin::try_from(1000).unwrap(),
+    }])
+    .unwrap();
+    let data = CreationTransactionData::with_schedule(owner.clone(), schedule);
+
+    let create_transaction = |validity_start_height| {
+        let mut transaction = Transaction::new_contract_creation(
+            owner.clone(),
+            AccountType::Basic,
+            vec![],
+            AccountType::Vesting,
+            data.to_tx_data(),
+            1000.try_into().unwrap(),
+            0.try_into().unwrap(),
+            validity_start_height,
+            NetworkId::UnitAlbatross,
+        );
+        transaction.recipient = transaction.contract_creation_address();
+        let signature = key_pair.sign(&transaction.serialize_content());
+        transaction.proof =
+            SignatureProof::from_ed25519(key_pair.public, signature).serialize_to_vec();
+        transaction
+    };
+
+    // The schedule is rejected by the stateless verification, before any block state is involved.
+    let transaction = create_transaction(Policy::vesting_schedule_activation_block() - 1);
+    assert_eq!(
+        AccountType::verify_incoming_transaction(&transaction),
+        Err(TransactionError::InvalidData)
+    );
+    assert_eq!(
+        transaction.verify(NetworkId::UnitAlbatross),
+        Err(TransactionError::InvalidData)
+    );
+
+    let transaction = create_transaction(Policy::vesting_schedule_activation_block());
+    assert_eq!(transaction.verify(NetworkId::UnitAlbatross), Ok(()));
+}
@@ -1,4 +1,4 @@
-use std::ops;
+use std::{collections::BTreeMap, ops};
 
 use nimiq_database::{
     mdbx::{MdbxReadTransaction, MdbxWriteTransaction},
@@ -34,6 +34,57 @@ impl TransactionExt for WriteTransactionProxy<'_, '_> {
     }
 }
 
+pub(crate) trait WriteTransactionExt: TransactionExt {
+    fn put_node<T: TrieTable>(&mut self, table: &T, node: &TrieNode, old_value: OldValue);
+    fn remove_node<T: TrieTable>(&mut self, table: &T, key: &KeyNibbles, old_value: OldValue);
+}
+
+impl WriteTransactionExt for WriteTransactionProxy<'_, '_> {
+    fn put_node<T: TrieTable>(&mut self, table: &T, node: &TrieNode, old_value: OldValue) {
+        self.record_value_change(&node.key, old_value, node.value.as_deref());
+        self.raw.put_reserve(table, &node.key, node);
+    }
+    fn remove_node<T: TrieTable>(&mut self, table: &T, key: &KeyNibbles, old_value: OldValue) {
+        self.record_value_change(key, old_value, None);
+        self.raw.remove(table, key);
+    }
+}
+
+/// A read transaction with an in-memory overlay of changed trie nodes. Nothing is ever written to
+/// the database, so a trie can be modified without holding the database writer. The overlay is
+/// keyed by node key only and thus must only be used for a single trie.
+pub(crate) struct OverlayTransaction<'txn, 'env> {
+    raw: &'txn MdbxReadTransaction<'env>,
+    nodes: BTreeMap<KeyNibbles, Option<TrieNode>>,
+}
+
+impl<'txn, 'env> From<&'txn MdbxReadTransaction<'env>> for OverlayTransaction<'txn, 'env> {
+    fn from(raw: &'txn MdbxReadTransaction<'env>) -> OverlayTransaction<'txn, 'env> {
+        OverlayTransaction {
+            raw,
+            nodes: BTreeMap::new(),
+        }
+    }
+}
+
+impl TransactionExt for OverlayTransaction<'_, '_> {
+    fn get_node<T: TrieTable>(&self, table: &T, key: &KeyNibbles) -> Option<TrieNode> {
+        match self.nodes.get(key) {
+            Some(node) => node.clone(),
+            None => self.raw.get_node(table, key),
+        }
+    }
+}
+
+impl WriteTransactionExt for OverlayTransaction<'_, '_> {
+    fn put_node<T: TrieTable>(&mut self, _table: &T, node: &TrieNode, _old_value: OldValue) {
+        self.nodes.insert(node.key.clone(), Some(node.clone()));
+    }
+    fn remove_node<T: TrieTable>(&mut self, _table: &T, key: &KeyNibbles, _old_value: OldValue) {
+        self.nodes.insert(key.clone(), None);
+    }
+}
+
 pub(crate) enum OldValue {
     Unchanged,
     None,
@@ -95,24 +146,6 @@ impl<'env> WriteTransactionProxy<'_, 'env> {
     pub(crate) fn clear_table<T: TrieTable>(&mut self, table: &T) {
         self.raw.clear_table(table)
     }
-    pub(crate) fn put_node<T: TrieTable>(
-        &mut self,
-        table: &T,
-        node: &TrieNode,
-        old_value: OldValue,
-    ) {
-        self.record_value_change(&node.key, old_value, node.value.as_deref());
-        self.raw.put_reserve(table, &node.key, node);
-    }
-    pub(crate) fn remove_node<T: TrieTable>(
-        &mut self,
-        table: &T,
-        key: &KeyNibbles,
-        old_value: OldValue,
-    ) {
-        self.record_value_change(key, old_value, None);
-        self.raw.remove(table, key);
-    }
     pub fn raw(&mut self) -> &mut MdbxWriteTransaction<'env> {
         self.raw
     }
@@ -25,7 +25,7 @@ use nimiq_primitives::{
 use nimiq_serde::{Deserialize, Serialize};
 
 use crate::{
-    transaction::{OldValue, TransactionExt as _, TrieTable},
+    transaction::{OldValue, OverlayTransaction, TransactionExt, TrieTable, WriteTransactionExt},
     WriteTransactionProxy,
 };
 
@@ -281,15 +281,20 @@ impl<T: TrieTable> MerkleRadixTrie<T> {
         (num_branches, num_hybrids, num_leaves)
     }
 
-    fn get_node(&self, txn: &MdbxReadTransaction, key: &KeyNibbles) -> Option<TrieNode> {
+    fn get_node<R: TransactionExt>(&self, txn: &R, key: &KeyNibbles) -> Option<TrieNode> {
         txn.get_node(&self.table, key)
     }
 
-    fn put_node(&self, txn: &mut WriteTransactionProxy, node: &TrieNode, old_value: OldValue) {
+    fn put_node<W: WriteTransactionExt>(&self, txn: &mut W, node: &TrieNode, old_value: OldValue) {
         txn.put_node(&self.table, node, old_value)
     }
 
-    fn remove_node(&self, txn: &mut WriteTransactionProxy, key: &KeyNibbles, old_value: OldValue) {
+    fn remove_node<W: WriteTransactionExt>(
+        &self,
+        txn: &mut W,
+        key: &KeyNibbles,
+        old_value: OldValue,
+    ) {
         txn.remove_node(&self.table, key, old_value)
     }
 
@@ -309,7 +314,7 @@ impl<T: TrieTable> MerkleRadixTrie<T> {
             .map(|v| D::deserialize_from_vec(&v).unwrap()))
     }
 
-    fn get_raw(&self, txn: &MdbxReadTransaction, key: &KeyNibbles) -> Option<Vec<u8>> {
+    fn get_raw<R: TransactionExt>(&self, txn: &R, key: &KeyNibbles) -> Option<Vec<u8>> {
         self.get_node(txn, key)?.value
     }
 
@@ -348,9 +353,9 @@ impl<T: TrieTable> MerkleRadixTrie<T> {
 
     /// Insert a value into the Merkle Radix Trie at the given key. If the key already exists then
     /// it will overwrite it. You can't use this function to check the existence of a given key.
-    fn put_raw(
+    fn put_raw<W: WriteTransactionExt>(
         &self,
-        txn: &mut WriteTransactionProxy,
+        txn: &mut W,
         key: &KeyNibbles,
         value: Vec<u8>,
         missing_range: &Option<ops::RangeFrom<KeyNibbles>>,
@@ -477,9 +482,9 @@ impl<T: TrieTable> MerkleRadixTrie<T> {
     /// but we cannot provide the accurate hash until a new chunk has been pushed.
     ///
     /// Returns `true` if a new stump has been added, otherwise `false`.
-    fn update_within_missing_part_raw(
+    fn update_within_missing_part_raw<W: WriteTransactionExt>(
         &self,
-        txn: &mut WriteTransactionProxy,
+        txn: &mut W,
         key: &KeyNibbles,
         missing_range: &Option<ops::RangeFrom<KeyNibbles>>,
     ) -> Result<bool, MerkleRadixTrieError> {
@@ -992,9 +997,9 @@ impl<T: TrieTable> MerkleRadixTrie<T> {
     /// Removes the value in the Merkle Radix Trie at the given key. If the key doesn't exist
     /// then this function just returns silently. You can't use this to check the existence of a
     /// given prefix.
-    fn remove_raw(
+    fn remove_raw<W: WriteTransactionExt>(
         &self,
-        txn: &mut WriteTransactionProxy,
+        txn: &mut W,
         key: &KeyNibbles,
         missing_range: &Option<ops::RangeFrom<KeyNibbles>>,
     ) {
@@ -1181,12 +1186,39 @@ impl<T: TrieTable> MerkleRadixTrie<T> {
     pub fn get_proof(
         &self,
         txn: &MdbxReadTransaction,
+        keys: Vec<&KeyNibbles>,
+    ) -> Result<TrieProof, IncompleteTrie> {
+        self.get_proof_raw(txn, keys)
+    }
+
+    /// Produces a Merkle proof of the inclusion of the given keys in the Merkle Radix Trie that
+    /// results from applying the given diffs, in order. The diffs are applied to an in-memory
+    /// overlay on top of the read transaction, the trie in the database is left untouched.
+    pub fn get_proof_with_diffs(
+        &self,
+        txn: &MdbxReadTransaction,
+        diffs: Vec<TrieDiff>,
+        keys: Vec<&KeyNibbles>,
+    ) -> Result<TrieProof, MerkleRadixTrieError> {
+        let mut overlay: OverlayTransaction = txn.into();
+        for diff in diffs {
+            self.apply_diff_raw(&mut overlay, diff)?;
+        }
+        self.update_root_raw(&mut overlay)?;
+
+        self.get_proof_raw(&overlay, keys)
+            .map_err(|_| MerkleRadixTrieError::IncompleteTrie)
+    }
+
+    fn get_proof_raw<R: TransactionExt>(
+        &self,
+        txn: &R,
         mut keys: Vec<&KeyNibbles>,
     ) -> Result<TrieProof, IncompleteTrie> {
         // We sort the keys in post-order.
         keys.sort_by(|&k1, &k2| k1.post_order_cmp(k2));
 
-        let missing_range = self.get_missing_range(txn);
+        let missing_range = self.missing_range(txn);
         if let Some(missing) = &missing_range {
             if keys.iter().any(|&key| missing.contains(key)) {
                 return Err(IncompleteTrie);
@@ -1285,7 +1317,14 @@ impl<T: TrieTable> MerkleRadixTrie<T> {
     }
 
     pub fn update_root(&self, txn: &mut WriteTransactionProxy) -> Result<(), MerkleRadixTrieError> {
-        let missing_range = self.get_missing_range(txn);
+        self.update_root_raw(txn)
+    }
+
+    fn update_root_raw<W: WriteTransactionExt>(
+        &self,
+        txn: &mut W,
+    ) -> Result<(), MerkleRadixTrieError> {
+        let missing_range = self.missing_range(txn);
         self.update_hashes(txn, &KeyNibbles::ROOT, &missing_range)
             .map_err(|_| MerkleRadixTrieError::IncompleteTrie)?;
         Ok(())
@@ -1296,7 +1335,15 @@ impl<T: TrieTable> MerkleRadixTrie<T> {
         txn: &mut WriteTransactionProxy,
         diff: TrieDiff,
     ) -> Result<RevertTrieDiff, MerkleRadixTrieError> {
-        let missing_range = self.get_missing_range(txn);
+        self.apply_diff_raw(txn, diff)
+    }
+
+    fn apply_diff_raw<W: WriteTransactionExt>(
+        &self,
+        txn: &mut W,
+        diff: TrieDiff,
+    ) -> Result<RevertTrieDiff, MerkleRadixTrieError> {
+        let missing_range = self.missing_range(txn);
         let mut result = BTreeMap::default();
         for (key, value) in diff.0 {
             if self.is_within_complete_part(&key, &missing_range) {
@@ -1366,6 +1413,10 @@ impl<T: TrieTable> MerkleRadixTrie<T> {
         &self,
         txn: &MdbxReadTransaction,
     ) -> Option<ops::RangeFrom<KeyNibbles>> {
+        self.missing_range(txn)
+    }
+
+    fn missing_range<R: TransactionExt>(&self, txn: &R) -> Option<ops::RangeFrom<KeyNibbles>> {
         self.get_root(txn)
             .expect("trie needs root node")
             .root_data
@@ -1374,15 +1425,15 @@ impl<T: TrieTable> MerkleRadixTrie<T> {
     }
 
     /// Returns the root node, if there is one.
-    fn get_root(&self, txn: &MdbxReadTransaction) -> Option<TrieNode> {
+    fn get_root<R: TransactionExt>(&self, txn: &R) -> Option<TrieNode> {
         self.get_node(txn, &KeyNibbles::ROOT)
     }
 
     /// Updates the keys for a chain of nodes and marks those nodes as dirty. It assumes that the
     /// path starts at the root node and that each consecutive node is a child of the previous node.
-    fn update_keys(
+    fn update_keys<W: WriteTransactionExt>(
         &self,
-        txn: &mut WriteTransactionProxy,
+        txn: &mut W,
         mut root_path: Vec<TrieNode>,
         count_updates: CountUpdates,
     ) {
@@ -1422,9 +1473,9 @@ impl<T: TrieTable> MerkleRadixTrie<T> {
     }
 
     /// Updates the hashes of all dirty nodes in the subtree specified by `key`.
-    fn update_hashes(
+    fn update_hashes<W: WriteTransactionExt>(
         &self,
-        txn: &mut WriteTransactionProxy,
+        txn: &mut W,
         key: &KeyNibbles,
         missing_range: &Option<ops::RangeFrom<KeyNibbles>>,
     ) -> Result<Blake2bHash, IncompleteTrie> {
@@ -1875,6 +1926,62 @@ mod tests {
             .is_err());
     }
 
+    #[test]
+    fn get_proof_with_diffs_works() {
+        let key_1 = "cfb986f5a".parse().unwrap();
+        let key_2 = "cfb986ab9".parse().unwrap();
+        let key_3 = "cfb98e0f6".parse().unwrap();
+        let key_4 = "cfb98e0f5".parse().unwrap();
+
+        let env = MdbxDatabase::new_volatile(Default::default()).unwrap();
+        let trie = MerkleRadixTrie::new(&env, TestTrie);
+        let mut raw_txn = env.write_transaction();
+        let mut txn: WriteTransactionProxy = (&mut raw_txn).into();
+
+        trie.put(&mut txn, &key_1, 1u8).expect("complete trie");
+        trie.put(&mut txn, &key_2, 2u8).expect("complete trie");
+        trie.put(&mut txn, &key_3, 3u8).expect("complete trie");
+        trie.update_root(&mut txn).expect("complete trie");
+        raw_txn.commit();
+
+        let diffs = vec![
+            TrieDiff(BTreeMap::from([
+                (key_2.clone(), None),
+                (key_4.clone(), Some(vec![4])),
+            ])),
+            TrieDiff(BTreeMap::from([(key_1.clone(), Some(vec![5]))])),
+        ];
+
+        // Compute the expected root hash by applying the diffs to the database.
+        let mut raw_txn = env.write_transaction();
+        let mut txn: WriteTransactionProxy = (&mut raw_txn).into();
+        for diff in diffs.clone() {
+            trie.apply_diff(&mut txn, diff).expect("complete trie");
+        }
+        trie.update_root(&mut txn).expect("complete trie");
+        let expected_hash = trie.root_hash_assert(&txn);
+        raw_txn.abort();
+
+        let txn = env.read_transaction();
+        let root_hash = trie.root_hash_assert(&txn);
+        assert_ne!(root_hash, expected_hash);
+
+        let proof_values = trie
+            .get_proof_with_diffs(&txn, diffs, vec![&key_1, &key_2, &key_3, &key_4])
+            .unwrap()
+            .verify_values(&expected_hash, &[&key_1, &key_2, &key_3, &key_4])
+            .unwrap();
+        assert_eq!(proof_values[&key_1], Some(vec![5]));
+        assert_eq!(proof_values[&key_2], None);
+        assert_eq!(proof_values[&key_3], Some(vec![3]));
+        assert_eq!(proof_values[&key_4], Some(vec![4]));
+
+        // The trie in the database is left untouched.
+        assert_eq!(trie.root_hash_assert(&txn), root_hash);
+        assert_eq!(trie.get(&txn, &key_2), Ok(Some(2u8)));
+        assert_eq!(trie.get::<u8>(&txn, &key_4), Ok(None));
+    }
+
     #[test]
     fn hybrid_nodes_work() {
         let key_1 = "413f22".parse().unwrap();
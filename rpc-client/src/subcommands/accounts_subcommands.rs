@@ -1,8 +1,11 @@
 use anyhow::Error;
 use async_trait::async_trait;
 use clap::Parser;
+use nimiq_hash::Blake2bHash;
 use nimiq_keys::{Address, Ed25519PublicKey, Ed25519Signature};
-use nimiq_rpc_interface::{blockchain::BlockchainInterface, wallet::WalletInterface};
+use nimiq_rpc_interface::{
+    blockchain::BlockchainInterface, types::Account, wallet::WalletInterface,
+};
 
 use crate::Client;
 
@@ -52,6 +55,11 @@ pub enum AccountCommand {
         #[clap(short = 'P', long)]
         password: Option<String>,
 
+        /// The duration in seconds after which the account is locked again. If not given, the
+        /// account stays unlocked until it is explicitly locked.
+        #[clap(short, long)]
+        duration: Option<u64>,
+
         /// The account's address.
         address: Address,
     },
@@ -99,6 +107,15 @@ pub enum AccountCommand {
     Get {
         /// The account's address.
         address: Address,
+
+        /// Queries the account as it was after the given block. Only supported by history nodes.
+        #[clap(long, conflicts_with = "verify")]
+        at_block: Option<u32>,
+
+        /// Requests a proof of the account and verifies it against the given trusted block hash.
+        /// The block must be the current head of the node.
+        #[clap(long)]
+        verify: Option<Blake2bHash>,
     },
 }
 
@@ -114,7 +131,7 @@ impl HandleSubcommand for AccountCommand {
                     } else {
                         let account = client
                             .blockchain
-                            .get_account_by_address(address.clone())
+                            .get_account_by_address(address.clone(), None)
                             .await?;
                         println!("{}: {:#?}", address.to_user_friendly_address(), account);
                     }
@@ -134,14 +151,15 @@ impl HandleSubcommand for AccountCommand {
                 client.wallet.lock_account(address).await?;
             }
             AccountCommand::Unlock {
-                address, password, ..
+                address,
+                password,
+                duration,
             } => {
-                // TODO: Duration
                 println!(
                     "{:#?}",
                     client
                         .wallet
-                        .unlock_account(address, password, None)
+                        .unlock_account(address, password, duration)
                         .await?
                 );
             }
@@ -172,15 +190,55 @@ impl HandleSubcommand for AccountCommand {
                         .await?
                 );
             }
-            AccountCommand::Get { address } => {
+            AccountCommand::Get {
+                address,
+                at_block,
+                verify: None,
+            } => {
                 println!(
                     "{:#?}",
-                    client.blockchain.get_account_by_address(address).await?
+                    client
+                        .blockchain
+                        .get_account_by_address(address, at_block)
+                        .await?
                 );
             }
+            AccountCommand::Get {
+                address,
+                verify: Some(block_hash),
+                ..
+            } => {
+                let proven = client
+                    .blockchain
+                    .get_account_proof_by_address(address.clone(), Some(block_hash.clone()))
+                    .await?
+                    .data;
+                let account = proven
+                    .state_proof()?
+                    .verify_account(&block_hash, &address)?;
+                println!("Proof verified against block {block_hash}");
+                println!("{:#?}", Account::from_account(address, account));
+            }
 
             AccountCommand::GetAll {} => {
-                println!("{:#?}", client.blockchain.get_accounts().await?);
+                // All pages are read against the state of the block of the first page.
+                let mut start_key = None;
+                let mut block_hash = None;
+                loop {
+                    let page = client
+                        .blockchain
+                        .get_accounts_page(start_key, None, block_hash)
+                        .await?
+                        .data;
+                    for account in &page.accounts {
+                        println!("{account:#?}");
+                    }
+                    match page.next_key {
+                        Some(next_key) => start_key = Some(next_key.to_string()),
+                        None => break,
+                    }
+                    block_hash = Some(page.block_hash);
+                }
             }
         }
 
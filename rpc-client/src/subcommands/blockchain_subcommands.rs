@@ -4,7 +4,10 @@ use clap::{ArgGroup, Parser};
 use futures::StreamExt;
 use nimiq_hash::Blake2bHash;
 use nimiq_keys::Address;
-use nimiq_rpc_interface::{blockchain::BlockchainInterface, types::LogType};
+use nimiq_rpc_interface::{
+    blockchain::BlockchainInterface,
+    types::{LogType, Staker, Validator},
+};
 
 use super::accounts_subcommands::HandleSubcommand;
 use crate::Client;
@@ -103,6 +106,27 @@ pub enum BlockchainCommand {
         just_hash: bool,
     },
 
+    /// Returns the latest reward, penalty and jail inherents for a given address. Rewards are
+    /// considered for their reward address, penalties and jails for the validator address.
+    /// The inherents are returned in descending order, meaning the latest inherent is the first.
+    /// The returned cursor can be used to fetch the next page.
+    InherentsByAddress {
+        /// The address to query by.
+        address: Address,
+
+        /// List of log types to select. If empty it does not filter by log type.
+        #[clap(short = 'l', long, value_enum)]
+        log_types: Vec<LogType>,
+
+        /// Max number of inherents to fetch. If absent it defaults to 500.
+        #[clap(long)]
+        max: Option<u16>,
+
+        /// The cursor returned by a previous query to continue from.
+        #[clap(long)]
+        cursor: Option<String>,
+    },
+
     /// Returns the information for the slot owner at the given block height and offset. The
     /// offset is optional, it will default to the block number for micro blocks and to the round number for macro blocks.
     /// We only have this information available for the last 2 batches at most.
@@ -127,6 +151,11 @@ pub enum BlockchainCommand {
     ValidatorByAddress {
         /// The address to query by.
         address: Address,
+
+        /// Requests a proof of the validator and verifies it against the given trusted block hash.
+        /// The block must be the current head of the node.
+        #[clap(long)]
+        verify: Option<Blake2bHash>,
     },
 
     /// Tries to fetch all validators in the staking contract.
@@ -144,6 +173,11 @@ pub enum BlockchainCommand {
     Staker {
         /// The address to query by.
         address: Address,
+
+        /// Requests a proof of the staker and verifies it against the given trusted block hash.
+        /// The block must be the current head of the node.
+        #[clap(long)]
+        verify: Option<Blake2bHash>,
     },
 
     /// Lists the current stakes from the staking contract.
@@ -292,6 +326,18 @@ impl HandleSubcommand for BlockchainCommand {
                     )
                 }
             }
+            BlockchainCommand::InherentsByAddress {
+                address,
+                log_types,
+                max,
+                cursor,
+            } => println!(
+                "{:#?}",
+                client
+                    .blockchain
+                    .get_inherents_by_address(address, log_types, max, cursor)
+                    .await?
+            ),
             BlockchainCommand::PenalizedSlots { previous_penalized } => {
                 if previous_penalized {
                     println!(
@@ -305,10 +351,28 @@ impl HandleSubcommand for BlockchainCommand {
                     )
                 }
             }
-            BlockchainCommand::ValidatorByAddress { address } => println!(
+            BlockchainCommand::ValidatorByAddress {
+                address,
+                verify: None,
+            } => println!(
                 "{:#?}",
                 client.blockchain.get_validator_by_address(address).await?
             ),
+            BlockchainCommand::ValidatorByAddress {
+                address,
+                verify: Some(block_hash),
+            } => {
+                let proven = client
+                    .blockchain
+                    .get_validator_proof_by_address(address.clone(), Some(block_hash.clone()))
+                    .await?
+                    .data;
+                let validator = proven
+                    .state_proof()?
+                    .verify_validator(&block_hash, &address)?;
+                println!("Proof verified against block {block_hash}");
+                println!("{:#?}", validator.as_ref().map(Validator::from_validator));
+            }
 
             BlockchainCommand::Validators {} => {
                 println!("{:#?}", client.blockchain.get_validators().await?)
@@ -321,12 +385,28 @@ impl HandleSubcommand for BlockchainCommand {
                     .get_stakers_by_validator_address(address)
                     .await?
             ),
-            BlockchainCommand::Staker { address } => {
+            BlockchainCommand::Staker {
+                address,
+                verify: None,
+            } => {
                 println!(
                     "{:#?}",
                     client.blockchain.get_staker_by_address(address).await?
                 )
             }
+            BlockchainCommand::Staker {
+                address,
+                verify: Some(block_hash),
+            } => {
+                let proven = client
+                    .blockchain
+                    .get_staker_proof_by_address(address.clone(), Some(block_hash.clone()))
+                    .await?
+                    .data;
+                let staker = proven.state_proof()?.verify_staker(&block_hash, &address)?;
+                println!("Proof verified against block {block_hash}");
+                println!("{:#?}", staker.as_ref().map(Staker::from_staker));
+            }
             BlockchainCommand::Stakes {} => {
                 println!("{:#?}", client.blockchain.get_active_validators().await?);
             }
@@ -30,6 +30,17 @@ pub enum MempoolCommand {
 
     /// Returns the minimum fee per byte of the local mempool.
     MinFeePerByte {},
+
+    /// Estimates the fee per byte needed for a transaction to be included within a number of blocks.
+    EstimateFee {
+        /// The number of blocks within which the transaction should be included.
+        #[clap(short, long, default_value_t = 1)]
+        target_blocks: u32,
+
+        /// The probability (between 0 and 1) with which the transaction should be included.
+        #[clap(short, long)]
+        probability: Option<f64>,
+    },
 }
 
 #[async_trait]
@@ -66,6 +77,18 @@ impl HandleSubcommand for MempoolCommand {
             MempoolCommand::MinFeePerByte {} => {
                 println!("{:#?}", client.mempool.get_min_fee_per_byte().await?);
             }
+            MempoolCommand::EstimateFee {
+                target_blocks,
+                probability,
+            } => {
+                println!(
+                    "{:#?}",
+                    client
+                        .mempool
+                        .estimate_fee(target_blocks, probability)
+                        .await?
+                );
+            }
         }
         Ok(client)
     }
@@ -1,7 +1,7 @@
 use anyhow::Error;
 use async_trait::async_trait;
 use clap::Parser;
-use nimiq_rpc_interface::network::NetworkInterface;
+use nimiq_rpc_interface::{network::NetworkInterface, types::CloseReason};
 
 use super::accounts_subcommands::HandleSubcommand;
 use crate::Client;
@@ -16,7 +16,52 @@ pub enum NetworkCommand {
         /// To display only the number of peers.
         #[clap(short, long)]
         count: bool,
+
+        /// To display detailed information about each peer.
+        #[clap(short, long, conflicts_with = "count")]
+        detailed: bool,
+    },
+
+    /// Returns detailed information about a connected peer.
+    PeerInfo {
+        /// The peer ID.
+        peer_id: String,
+    },
+
+    /// Dials the given multiaddress.
+    Dial {
+        /// The multiaddress to dial, e.g. `/dns4/seed1.nimiq.com/tcp/8443/wss`.
+        address: String,
+    },
+
+    /// Closes the connection to a peer.
+    Disconnect {
+        /// The peer ID.
+        peer_id: String,
+
+        /// The reason for closing the connection. `malicious-peer` also bans the peer.
+        #[clap(short, long, value_enum, default_value = "other")]
+        reason: CloseReason,
+    },
+
+    /// Bans a peer ID, an IP address or a subnet and closes existing connections to it.
+    Ban {
+        /// The peer ID, IP address or subnet in CIDR notation, e.g. `198.51.100.0/24`.
+        target: String,
+
+        /// The duration of the ban in seconds.
+        #[clap(short, long, default_value_t = 600)]
+        duration: u64,
+    },
+
+    /// Lifts the ban of a peer ID, an IP address or a subnet.
+    Unban {
+        /// The peer ID, IP address or subnet in CIDR notation.
+        target: String,
     },
+
+    /// Lists all active bans.
+    Bans {},
 }
 
 #[async_trait]
@@ -26,13 +71,36 @@ impl HandleSubcommand for NetworkCommand {
             NetworkCommand::PeerId {} => {
                 println!("{:#?}", client.network.get_peer_id().await?);
             }
-            NetworkCommand::Peers { count } => {
+            NetworkCommand::Peers { count, detailed } => {
                 if count {
                     println!("{:#?}", client.network.get_peer_count().await?);
+                } else if detailed {
+                    println!("{:#?}", client.network.get_peer_info_list().await?);
                 } else {
                     println!("{:#?}", client.network.get_peer_list().await?);
                 }
             }
+            NetworkCommand::PeerInfo { peer_id } => {
+                println!("{:#?}", client.network.get_peer_info(peer_id).await?);
+            }
+            NetworkCommand::Dial { address } => {
+                println!("{:#?}", client.network.dial_address(address).await?);
+            }
+            NetworkCommand::Disconnect { peer_id, reason } => {
+                println!(
+                    "{:#?}",
+                    client.network.disconnect_peer(peer_id, reason).await?
+                );
+            }
+            NetworkCommand::Ban { target, duration } => {
+                println!("{:#?}", client.network.ban(target, duration).await?);
+            }
+            NetworkCommand::Unban { target } => {
+                println!("{:#?}", client.network.unban(target).await?);
+            }
+            NetworkCommand::Bans {} => {
+                println!("{:#?}", client.network.get_bans().await?);
+            }
         }
         Ok(client)
     }
@@ -4,8 +4,9 @@ use nimiq_hash::Blake2bHash;
 use nimiq_keys::Address;
 
 use crate::types::{
-    Account, Block, BlockLog, BlockchainState, ExecutedTransaction, Inherent, LogType,
-    PenalizedSlots, RPCData, RPCResult, Slot, Staker, Validator,
+    Account, AccountPage, Block, BlockEvent, BlockLog, BlockchainState, ExecutedTransaction,
+    Inherent, InherentPage, LogType, PenalizedSlots, ProvenValue, RPCData, RPCResult, Slot, Staker,
+    Validator,
 };
 
 #[nimiq_jsonrpc_derive::proxy(name = "BlockchainProxy", rename_all = "camelCase")]
@@ -116,17 +117,65 @@ pub trait BlockchainInterface {
         start_at: Option<Blake2bHash>,
     ) -> RPCResult<Vec<ExecutedTransaction>, (), Self::Error>;
 
-    /// Tries to fetch the account at the given address.
+    /// Returns the latest reward, penalty and jail inherents affecting a given address. Rewards are
+    /// considered for their reward address, penalties and jails for the validator address.
+    /// The inherents can be filtered by the log types `payout-reward`, `penalize` and
+    /// `jail-validator`. If log_types is empty it won't filter by log types.
+    /// It has an option to specify the maximum number of inherents to fetch, it defaults to 500.
+    /// The inherents are returned in descending order, meaning the latest inherent is the first.
+    /// Only a bounded number of inherents is scanned per call, so a page might contain fewer
+    /// inherents than the maximum, or none at all, and still have a cursor.
+    /// To fetch the next page, pass the returned cursor. If the cursor is not found anymore, e.g.
+    /// because the block it points to was reverted, it will return an empty page.
+    async fn get_inherents_by_address(
+        &mut self,
+        address: Address,
+        log_types: Vec<LogType>,
+        max: Option<u16>,
+        cursor: Option<String>,
+    ) -> RPCResult<InherentPage, (), Self::Error>;
+
+    /// Tries to fetch the account at the given address. It has an option to fetch the account as
+    /// it was after a past block of the main chain, e.g. the election block at the end of an
+    /// epoch. Past states are only available on history nodes and only for the blocks that
+    /// haven't been pruned yet, otherwise an error is returned.
     async fn get_account_by_address(
         &mut self,
         address: Address,
+        at_block: Option<u32>,
     ) -> RPCResult<Account, BlockchainState, Self::Error>;
 
+    /// Fetches the account at the given address together with a proof of it against the state root
+    /// of the head block. It has an option to prove the account against a past block of the main
+    /// chain instead, e.g. the latest election block. Past states are only available on history
+    /// nodes and only for the blocks that haven't been pruned yet, otherwise an error is returned.
+    async fn get_account_proof_by_address(
+        &mut self,
+        address: Address,
+        block_hash: Option<Blake2bHash>,
+    ) -> RPCResult<ProvenValue<Account>, BlockchainState, Self::Error>;
+
     /// Fetches all accounts in the accounts tree.
     /// IMPORTANT: This operation iterates over all accounts in the accounts tree
     /// and thus is extremely computationally expensive.
+    /// Prefer `getAccountsPage` or `subscribeForAccounts` to walk the accounts tree.
     async fn get_accounts(&mut self) -> RPCResult<Vec<Account>, BlockchainState, Self::Error>;
 
+    /// Fetches a page of the accounts in the accounts tree, starting at the given trie key (as
+    /// nibbles in hex), or at the beginning if none is given. It has an option to specify the
+    /// maximum number of trie entries to read, it defaults to 1000. As entries of the staking
+    /// contract are skipped, a page might contain fewer accounts.
+    /// The page is read against the state after the block with the given hash, or the head if none
+    /// is given. To fetch the next page, pass the returned key and block hash, such that all pages
+    /// of a walk are read against the same state. Past states are rebuilt from the stored revert
+    /// diffs, the walk fails once the state of its block can't be rebuilt anymore.
+    async fn get_accounts_page(
+        &mut self,
+        start_key: Option<String>,
+        limit: Option<u16>,
+        block_hash: Option<Blake2bHash>,
+    ) -> RPCResult<AccountPage, BlockchainState, Self::Error>;
+
     /// Returns a collection of the currently active validator's addresses and balances.
     async fn get_active_validators(
         &mut self,
@@ -150,6 +199,15 @@ pub trait BlockchainInterface {
         address: Address,
     ) -> RPCResult<Validator, BlockchainState, Self::Error>;
 
+    /// Fetches the validator with the given address together with a proof of it against the state
+    /// root of the head block. The proof also covers the absence of the validator.
+    /// See `get_account_proof_by_address` for the block hash.
+    async fn get_validator_proof_by_address(
+        &mut self,
+        address: Address,
+        block_hash: Option<Blake2bHash>,
+    ) -> RPCResult<ProvenValue<Option<Validator>>, BlockchainState, Self::Error>;
+
     /// Fetches all validators in the staking contract.
     /// IMPORTANT: This operation iterates over all validators in the staking contract
     /// and thus is extremely computationally expensive.
@@ -169,6 +227,15 @@ pub trait BlockchainInterface {
         address: Address,
     ) -> RPCResult<Staker, BlockchainState, Self::Error>;
 
+    /// Fetches the staker with the given address together with a proof of it against the state
+    /// root of the head block. The proof also covers the absence of the staker.
+    /// See `get_account_proof_by_address` for the block hash.
+    async fn get_staker_proof_by_address(
+        &mut self,
+        address: Address,
+        block_hash: Option<Blake2bHash>,
+    ) -> RPCResult<ProvenValue<Option<Staker>>, BlockchainState, Self::Error>;
+
     /// Subscribes to new block events (retrieves the full block).
     #[stream]
     async fn subscribe_for_head_block(
@@ -182,6 +249,21 @@ pub trait BlockchainInterface {
         &mut self,
     ) -> Result<BoxStream<'static, RPCData<Blake2bHash, ()>>, Self::Error>;
 
+    /// Subscribes to the changes of the main chain. Every block that is added to the main chain
+    /// is emitted as `applied` event with its transactions, every block that is removed by a
+    /// rebranch as `reverted` event, from the highest block downwards. Macro blocks are followed
+    /// by a `finalized` event, as blocks up to them can't be reverted anymore.
+    /// Every event carries a cursor, the block the subscriber's view of the main chain ends at
+    /// after processing it. To resume after a reconnect, pass the last processed cursor: the
+    /// blocks reverted and applied in the meantime are emitted before the live events. Without a
+    /// cursor, the subscription starts at the current head. If the main chain can't be followed
+    /// anymore, the subscription ends with an `error` event carrying the last cursor.
+    #[stream]
+    async fn subscribe_for_block_events(
+        &mut self,
+        cursor: Option<BlockchainState>,
+    ) -> Result<BoxStream<'static, RPCData<BlockEvent, BlockchainState>>, Self::Error>;
+
     /// Subscribes to pre epoch validators events.
     #[stream]
     async fn subscribe_for_validator_election_by_address(
@@ -189,6 +271,16 @@ pub trait BlockchainInterface {
         address: Address,
     ) -> Result<BoxStream<'static, RPCData<Validator, BlockchainState>>, Self::Error>;
 
+    /// Streams all accounts in the accounts tree in pages of the given size (defaults to 1000 trie
+    /// entries). All pages are read against the state after the head at the time of subscribing,
+    /// past states are rebuilt from the stored revert diffs. The stream ends after the page without
+    /// a next key, or early if the state can't be rebuilt anymore.
+    #[stream]
+    async fn subscribe_for_accounts(
+        &mut self,
+        limit: Option<u16>,
+    ) -> Result<BoxStream<'static, RPCData<AccountPage, BlockchainState>>, Self::Error>;
+
     /// Subscribes to log events related to a given list of addresses and of any of the log types provided.
     /// If addresses is empty it does not filter by address. If log_types is empty it won't filter by log types.
     /// Thus the behavior is to assume all addresses or log_types are to be provided if the corresponding vec is empty.
@@ -4,7 +4,7 @@ use nimiq_keys::Address;
 use nimiq_primitives::coin::Coin;
 use nimiq_transaction::account::htlc_contract::{AnyHash, PreImage};
 
-use crate::types::{RPCResult, Transaction, ValidityStartHeight};
+use crate::types::{RPCResult, Transaction, ValidityStartHeight, VestingTranche};
 
 #[nimiq_jsonrpc_derive::proxy(name = "ConsensusProxy", rename_all = "camelCase")]
 #[async_trait]
@@ -96,6 +96,62 @@ pub trait ConsensusInterface {
         validity_start_height: ValidityStartHeight,
     ) -> RPCResult<Blake2bHash, (), Self::Error>;
 
+    /// Returns a serialized transaction creating a new vesting contract with a cliff: Nothing is
+    /// released before `start_time + cliff_duration`, at which point `cliff_amount` is released.
+    /// The remaining value is then released in `num_steps` payouts every `time_step`.
+    async fn create_new_vesting_transaction_with_cliff(
+        &mut self,
+        wallet: Address,
+        owner: Address,
+        start_time: u64,
+        cliff_duration: u64,
+        cliff_amount: Coin,
+        time_step: u64,
+        num_steps: u32,
+        value: Coin,
+        fee: Coin,
+        validity_start_height: ValidityStartHeight,
+    ) -> RPCResult<String, (), Self::Error>;
+
+    /// Sends a transaction creating a new vesting contract with a cliff to the network.
+    async fn send_new_vesting_transaction_with_cliff(
+        &mut self,
+        wallet: Address,
+        owner: Address,
+        start_time: u64,
+        cliff_duration: u64,
+        cliff_amount: Coin,
+        time_step: u64,
+        num_steps: u32,
+        value: Coin,
+        fee: Coin,
+        validity_start_height: ValidityStartHeight,
+    ) -> RPCResult<Blake2bHash, (), Self::Error>;
+
+    /// Returns a serialized transaction creating a new vesting contract that releases its funds
+    /// in several tranches, e.g. a cliff followed by a linear release.
+    async fn create_new_vesting_transaction_with_schedule(
+        &mut self,
+        wallet: Address,
+        owner: Address,
+        tranches: Vec<VestingTranche>,
+        value: Coin,
+        fee: Coin,
+        validity_start_height: ValidityStartHeight,
+    ) -> RPCResult<String, (), Self::Error>;
+
+    /// Sends a transaction creating a new vesting contract that releases its funds in several
+    /// tranches to the network.
+    async fn send_new_vesting_transaction_with_schedule(
+        &mut self,
+        wallet: Address,
+        owner: Address,
+        tranches: Vec<VestingTranche>,
+        value: Coin,
+        fee: Coin,
+        validity_start_height: ValidityStartHeight,
+    ) -> RPCResult<Blake2bHash, (), Self::Error>;
+
     /// Returns a serialized transaction redeeming a vesting contract.
     async fn create_redeem_vesting_transaction(
         &mut self,
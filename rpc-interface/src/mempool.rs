@@ -1,8 +1,9 @@
 use async_trait::async_trait;
+use futures::stream::BoxStream;
 use nimiq_hash::Blake2bHash;
 use nimiq_transaction::Transaction;
 
-use crate::types::{HashOrTx, MempoolInfo, RPCResult};
+use crate::types::{BlockchainState, HashOrTx, MempoolInfo, RPCData, RPCResult, TransactionStatus};
 
 #[nimiq_jsonrpc_derive::proxy(name = "MempoolProxy", rename_all = "camelCase")]
 #[async_trait]
@@ -10,14 +11,18 @@ pub trait MempoolInterface {
     type Error;
 
     /// Pushes a raw transaction with a default priority assigned into the mempool and broadcast it to the network.
-    async fn push_transaction(&mut self, raw_tx: String)
-        -> RPCResult<Blake2bHash, (), Self::Error>;
+    /// If the transaction replaced a pending transaction, the metadata contains the hash of the replaced transaction.
+    async fn push_transaction(
+        &mut self,
+        raw_tx: String,
+    ) -> RPCResult<Blake2bHash, Option<Blake2bHash>, Self::Error>;
 
     /// Pushes a raw transaction with a high priority assigned into the mempool and broadcast it to the network.
+    /// If the transaction replaced a pending transaction, the metadata contains the hash of the replaced transaction.
     async fn push_high_priority_transaction(
         &mut self,
         raw_tx: String,
-    ) -> RPCResult<Blake2bHash, (), Self::Error>;
+    ) -> RPCResult<Blake2bHash, Option<Blake2bHash>, Self::Error>;
 
     /// Obtains the list of transactions that are currently in the mempool.
     async fn mempool_content(
@@ -31,9 +36,39 @@ pub trait MempoolInterface {
     /// Obtains the minimum fee per byte as per mempool configuration.
     async fn get_min_fee_per_byte(&mut self) -> RPCResult<f64, (), Self::Error>;
 
+    /// Estimates the fee per byte a transaction needs to pay to be included within `target_blocks`
+    /// blocks with the given `probability` (between 0 and 1, defaults to 0.95). The estimate is based
+    /// on the fees included in recent blocks and the transactions currently waiting in the mempool.
+    async fn estimate_fee(
+        &mut self,
+        target_blocks: u32,
+        probability: Option<f64>,
+    ) -> RPCResult<f64, (), Self::Error>;
+
     /// Tries to obtain the given transaction (using its hash) from the mempool.
     async fn get_transaction_from_mempool(
         &mut self,
         hash: Blake2bHash,
     ) -> RPCResult<Transaction, (), Self::Error>;
+
+    /// Returns the status of the transaction with the given hash: pending in the mempool,
+    /// included in a block that can still be reverted, finalized, expired, dropped or invalid.
+    /// The reasons transactions were dropped for are only kept for a limited number of them.
+    async fn get_transaction_status(
+        &mut self,
+        hash: Blake2bHash,
+    ) -> RPCResult<TransactionStatus, BlockchainState, Self::Error>;
+
+    /// Subscribes to the status of the transaction with the given hash. The current status is
+    /// emitted right away, changes are emitted as they are noticed with every new block. The
+    /// stream ends once the transaction is finalized or expired.
+    /// Transactions that are unknown, dropped or invalid are followed as well, as another node can
+    /// still include them. They are reported as expired once the validity window of any
+    /// transaction that was valid when they were last pending or included, or when subscribing,
+    /// has ended. Transactions that only become valid after subscribing are missed.
+    #[stream]
+    async fn subscribe_for_transaction_status(
+        &mut self,
+        hash: Blake2bHash,
+    ) -> Result<BoxStream<'static, RPCData<TransactionStatus, BlockchainState>>, Self::Error>;
 }
@@ -1,6 +1,6 @@
 use async_trait::async_trait;
 
-use crate::types::RPCResult;
+use crate::types::{CloseReason, PeerBan, PeerDetails, RPCResult};
 
 #[nimiq_jsonrpc_derive::proxy(name = "NetworkProxy", rename_all = "camelCase")]
 #[async_trait]
@@ -15,4 +15,31 @@ pub trait NetworkInterface {
 
     /// Returns a list with the IDs of all our peers.
     async fn get_peer_list(&mut self) -> RPCResult<Vec<String>, (), Self::Error>;
+
+    /// Returns detailed information about a connected peer.
+    async fn get_peer_info(&mut self, peer_id: String) -> RPCResult<PeerDetails, (), Self::Error>;
+
+    /// Returns detailed information about all connected peers.
+    async fn get_peer_info_list(&mut self) -> RPCResult<Vec<PeerDetails>, (), Self::Error>;
+
+    /// Dials the given multiaddress.
+    async fn dial_address(&mut self, address: String) -> RPCResult<(), (), Self::Error>;
+
+    /// Closes the connection to a peer for the given reason.
+    async fn disconnect_peer(
+        &mut self,
+        peer_id: String,
+        reason: CloseReason,
+    ) -> RPCResult<(), (), Self::Error>;
+
+    /// Bans a peer ID, an IP address or a subnet in CIDR notation for the given duration in
+    /// seconds. Existing connections to the banned peers are closed.
+    async fn ban(&mut self, target: String, duration: u64) -> RPCResult<(), (), Self::Error>;
+
+    /// Lifts the ban of a peer ID, an IP address or a subnet. Returns whether it was banned.
+    /// Bans from the deny list can't be lifted.
+    async fn unban(&mut self, target: String) -> RPCResult<bool, (), Self::Error>;
+
+    /// Returns all active bans.
+    async fn get_bans(&mut self) -> RPCResult<Vec<PeerBan>, (), Self::Error>;
 }
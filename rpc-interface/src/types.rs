@@ -10,16 +10,17 @@ use std::{
 use clap::ValueEnum;
 use nimiq_account::{BlockLog as BBlockLog, Log, TransactionLog};
 use nimiq_block::{MicroJustification, MultiSignature};
-use nimiq_blockchain_interface::{AbstractBlockchain, BlockchainError};
+use nimiq_blockchain_interface::{AbstractBlockchain, BlockchainError, StateProof};
 use nimiq_blockchain_proxy::BlockchainReadProxy;
 use nimiq_bls::CompressedPublicKey;
 use nimiq_collections::BitSet;
 use nimiq_hash::{Blake2bHash, Blake2sHash, Hash};
 use nimiq_keys::{Address, Ed25519PublicKey, Ed25519Signature, PrivateKey};
 use nimiq_primitives::{
-    coin::Coin, networks::NetworkId, policy::Policy, slots_allocation::Validators,
+    account::FailReason, coin::Coin, key_nibbles::KeyNibbles, networks::NetworkId, policy::Policy,
+    slots_allocation::Validators,
 };
-use nimiq_serde::Serialize as NimiqSerialize;
+use nimiq_serde::{Deserialize as NimiqDeserialize, Serialize as NimiqSerialize};
 use nimiq_transaction::{
     account::htlc_contract::AnyHash,
     historic_transaction::{
@@ -28,7 +29,7 @@ use nimiq_transaction::{
 };
 use nimiq_vrf::VrfSeed;
 use serde::{de::Error, Deserialize, Serialize};
-use serde_with::{serde_as, SerializeDisplay};
+use serde_with::{serde_as, DisplayFromStr, SerializeDisplay};
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -786,6 +787,34 @@ impl Inherent {
     }
 }
 
+/// A page of the inherents affecting an address.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InherentPage {
+    pub inherents: Vec<Inherent>,
+    /// An opaque cursor to fetch the next page with. It is absent if all inherents have been
+    /// scanned.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+}
+
+/// A page of the accounts in the accounts trie, ordered by their key in the trie.
+#[serde_as]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountPage {
+    pub accounts: Vec<Account>,
+    /// The trie key to start the next page at, as nibbles in hex. It is absent if this is the
+    /// last page.
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_key: Option<KeyNibbles>,
+    /// The block whose state the page was read from.
+    pub block_hash: Blake2bHash,
+    /// The root of the accounts trie the page was read from.
+    pub state_root: Blake2bHash,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Account {
     pub address: Address,
@@ -817,6 +846,33 @@ pub struct ReturnAccount {
     pub private_key: PrivateKey,
 }
 
+/// A tranche of the release schedule of a vesting contract.
+/// Each tranche releases `step_amount` every `time_step`, starting at `start_time + time_step`,
+/// until `total_amount` has been released.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VestingTranche {
+    /// The timestamp in milliseconds at which the release of this tranche commences.
+    pub start_time: u64,
+    /// The length of a step in milliseconds.
+    pub time_step: u64,
+    /// The amount (in Luna) released at every step.
+    pub step_amount: Coin,
+    /// The total amount (in Luna) released by this tranche.
+    pub total_amount: Coin,
+}
+
+impl From<VestingTranche> for nimiq_transaction::account::vesting_contract::VestingTranche {
+    fn from(tranche: VestingTranche) -> Self {
+        Self {
+            start_time: tranche.start_time,
+            time_step: tranche.time_step,
+            step_amount: tranche.step_amount,
+            total_amount: tranche.total_amount,
+        }
+    }
+}
+
 #[serde_as]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase", tag = "type")]
@@ -978,6 +1034,39 @@ impl Validator {
     }
 }
 
+/// A value of the accounts trie together with a proof of it against the state root of a block.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProvenValue<T> {
+    /// The value as it is proven by `proof`.
+    pub value: T,
+    /// The hash of the block whose state root the value was proven against.
+    pub block_hash: Blake2bHash,
+    pub block_number: u32,
+    /// The serialized state proof, hex encoded. It includes the header of the block, such that
+    /// it can be verified against a trusted block hash.
+    pub proof: String,
+}
+
+impl<T> ProvenValue<T> {
+    pub fn new(value: T, state_proof: &StateProof) -> Self {
+        ProvenValue {
+            value,
+            block_hash: state_proof.block.hash(),
+            block_number: state_proof.block.block_number(),
+            proof: hex::encode(state_proof.serialize_to_vec()),
+        }
+    }
+
+    /// Decodes the state proof, which can then be verified against a trusted block hash.
+    pub fn state_proof(&self) -> Result<StateProof, crate::error::Error> {
+        let bytes = hex::decode(&self.proof)
+            .map_err(|error| crate::error::Error::InvalidStateProof(error.to_string()))?;
+        StateProof::deserialize_from_vec(&bytes)
+            .map_err(|error| crate::error::Error::InvalidStateProof(error.to_string()))
+    }
+}
+
 pub type RPCResult<T, S, E> = Result<RPCData<T, S>, E>;
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -1052,6 +1141,26 @@ impl RPCData<BlockLog, BlockchainState> {
     }
 }
 
+/// A change of the main chain, as emitted by the block event subscription.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", tag = "type")]
+pub enum BlockEvent {
+    /// The block was added to the main chain.
+    Applied { block: Block },
+    /// The block was removed from the main chain by a rebranch. Its transactions are not part of
+    /// the main chain anymore, unless they are included again by an applied block.
+    Reverted { block: Block },
+    /// The macro block was applied. It and all blocks before it can't be reverted anymore.
+    #[serde(rename_all = "camelCase")]
+    Finalized {
+        block_number: u32,
+        block_hash: Blake2bHash,
+    },
+    /// The subscription failed to follow the main chain and ends with this event. Its metadata is
+    /// the last block the subscriber was notified about, which can be used to resubscribe.
+    Error { message: String },
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct BlockchainState {
@@ -1160,6 +1269,17 @@ impl LogType {
             Log::StakerFeeDeduction { .. } => Self::StakerFeeDeduction,
         }
     }
+
+    /// Returns the log type of the inherent contained in the given historic transaction.
+    /// Returns `None` for transactions and equivocation records.
+    pub fn from_historic_transaction(hist_tx: &HistoricTransaction) -> Option<Self> {
+        match hist_tx.data {
+            HistoricTransactionData::Reward(_) => Some(Self::PayoutReward),
+            HistoricTransactionData::Penalize(_) => Some(Self::Penalize),
+            HistoricTransactionData::Jail(_) => Some(Self::JailValidator),
+            HistoricTransactionData::Basic(_) | HistoricTransactionData::Equivocation(_) => None,
+        }
+    }
 }
 
 /// Checks if a given log is related to any of the addresses provided and if it is of any of the log types provided.
@@ -1202,6 +1322,58 @@ impl ZKPState {
     }
 }
 
+/// The reason a transaction was dropped from the mempool before it was included in a block.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TransactionDropReason {
+    /// The transaction was replaced by a transaction with a higher fee.
+    Replaced,
+    /// The transaction was evicted because the mempool was full.
+    MempoolFull,
+}
+
+/// The status of a transaction as seen by the node.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", tag = "status")]
+pub enum TransactionStatus {
+    /// The transaction is waiting in the mempool.
+    Pending,
+    /// The transaction was included in a micro block, which can still be reverted by a rebranch.
+    /// If it didn't succeed, only the fee was paid.
+    #[serde(rename_all = "camelCase")]
+    Included {
+        block_number: u32,
+        confirmations: u32,
+        succeeded: bool,
+    },
+    /// The transaction was included in a block that can't be reverted anymore.
+    #[serde(rename_all = "camelCase")]
+    Finalized {
+        block_number: u32,
+        confirmations: u32,
+        succeeded: bool,
+    },
+    /// The transaction wasn't included within its validity window.
+    Expired,
+    /// The transaction was dropped from the mempool.
+    Dropped { reason: TransactionDropReason },
+    /// The transaction became invalid while waiting in the mempool.
+    Invalid { reason: FailReason },
+    /// The transaction is unknown to the node, or it was dropped too long ago.
+    Unknown,
+}
+
+impl TransactionStatus {
+    /// Returns whether the status can't change anymore. Transactions that are unknown, dropped or
+    /// invalid can still be included by another node until their validity window ends.
+    pub fn is_final(&self) -> bool {
+        matches!(
+            self,
+            TransactionStatus::Finalized { .. } | TransactionStatus::Expired
+        )
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct MempoolInfo {
@@ -1380,3 +1552,102 @@ impl MempoolInfo {
         info
     }
 }
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+pub enum NodeType {
+    History,
+    Full,
+    Light,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PeerDetails {
+    pub peer_id: String,
+    /// The address the connection to the peer was established with.
+    pub address: String,
+    /// The addresses the peer advertises.
+    pub addresses: Vec<String>,
+    pub node_type: NodeType,
+    pub services: Vec<String>,
+    /// Unix timestamp in milliseconds of when the peer joined.
+    pub connected_since: u64,
+    /// Duration of the connection in seconds.
+    pub connection_duration: u64,
+    /// The reputation score of the peer. Zero is the best possible score, peers are disconnected
+    /// and eventually banned when it drops too low.
+    pub reputation: f64,
+    /// Number of times the peer exceeded a request or message rate limit.
+    pub rate_limit_hits: u64,
+}
+
+/// The reason given when closing the connection to a peer.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq, ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+pub enum CloseReason {
+    /// Reason is unknown or doesn't fit the other reasons.
+    Other,
+    /// We are going offline and don't want new connections.
+    GoingOffline,
+    /// There was an error with the peer.
+    Error,
+    /// The peer is malicious. This also bans its peer ID and address.
+    MaliciousPeer,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum BanReason {
+    /// The peer was disconnected for being malicious.
+    MaliciousPeer,
+    /// The ban was requested by the node operator.
+    Manual,
+    /// The target is in the deny list of the configuration.
+    DenyList,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PeerBan {
+    /// The banned peer ID, IP address or subnet.
+    pub target: String,
+    pub reason: BanReason,
+    /// Time left until the ban is lifted, in seconds. Absent for permanent bans.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires_in: Option<u64>,
+}
+
+/// The state of the lookup of a validator's peer ID.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum PeerIdResolution {
+    /// The peer ID hasn't been looked up yet.
+    Unknown,
+    /// The peer ID is currently being looked up.
+    InProgress,
+    Resolved,
+    /// The last lookup failed.
+    Failed,
+    /// A request to the resolved peer ID failed, so it might be outdated.
+    Outdated,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidatorConnectionStatus {
+    pub validator_id: u16,
+    pub address: Address,
+    pub peer_id: Option<String>,
+    pub resolution: PeerIdResolution,
+    /// Whether we are connected to the last known peer ID of the validator.
+    pub connected: bool,
+    /// Unix timestamp in milliseconds of the last successful peer ID lookup.
+    pub last_resolved: Option<u64>,
+    /// Age of the last resolved validator record in seconds.
+    pub record_age: Option<u64>,
+    /// Number of failed peer ID lookups during the current epoch.
+    pub resolution_failures: u64,
+    /// Round-trip time of the last ping in milliseconds.
+    pub rtt: Option<u64>,
+}
@@ -1,7 +1,7 @@
 use async_trait::async_trait;
 use nimiq_keys::Address;
 
-use crate::types::RPCResult;
+use crate::types::{RPCResult, ValidatorConnectionStatus};
 
 #[nimiq_jsonrpc_derive::proxy(name = "ValidatorProxy", rename_all = "camelCase")]
 #[async_trait]
@@ -34,4 +34,10 @@ pub trait ValidatorInterface {
 
     /// Returns if our validator is currently synced.
     async fn is_validator_synced(&mut self) -> RPCResult<bool, (), Self::Error>;
+
+    /// Returns the connection status of every other elected validator, including the state of
+    /// its peer ID resolution and the round-trip time of the connection.
+    async fn get_validator_network_status(
+        &mut self,
+    ) -> RPCResult<Vec<ValidatorConnectionStatus>, (), Self::Error>;
 }
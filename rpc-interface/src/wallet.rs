@@ -32,7 +32,8 @@ pub trait WalletInterface {
         passphrase: Option<String>,
     ) -> RPCResult<ReturnAccount, (), Self::Error>;
 
-    /// Unlocks the account.
+    /// Unlocks the account. If a `duration` (in seconds) is given, the account is locked again
+    /// automatically once it has elapsed. Otherwise, it stays unlocked until it is explicitly locked.
     async fn unlock_account(
         &mut self,
         address: Address,
@@ -44,10 +45,14 @@ pub trait WalletInterface {
     /// IMPORTANT: This action is irreversible, and the account can only be recovered with its private key.
     async fn remove_account(&mut self, address: Address) -> RPCResult<bool, (), Self::Error>;
 
-    /// Returns if the account currently is unlocked.
+    /// Returns if the account currently is unlocked. The metadata contains the time (in seconds)
+    /// remaining until the account is locked again, or `None` if it does not expire.
     // `nimiq_jsonrpc_derive::proxy` requires the receiver type to be a mutable reference.
     #[allow(clippy::wrong_self_convention)]
-    async fn is_account_unlocked(&mut self, address: Address) -> RPCResult<bool, (), Self::Error>;
+    async fn is_account_unlocked(
+        &mut self,
+        address: Address,
+    ) -> RPCResult<bool, Option<u64>, Self::Error>;
 
     async fn sign(
         &mut self,
@@ -1,5 +1,5 @@
 use nimiq_hash::Blake2bHash;
-use nimiq_rpc_interface::types::Block;
+use nimiq_rpc_interface::types::{AccountPage, Block};
 
 #[test]
 fn it_can_deserialize_result_blocks() {
@@ -7,3 +7,15 @@ fn it_can_deserialize_result_blocks() {
     let value = serde_json::from_str(data).unwrap();
     let _result: Result<Block, Blake2bHash> = serde_json::from_value(value).unwrap();
 }
+
+#[test]
+fn it_serializes_account_page_keys_as_nibbles() {
+    let data = r#"{"accounts":[],"nextKey":"0123a","stateRoot":"a3f9827a3bf99031a0d1117df82ad3c77c90e82fcd9cb3e9f3d61b730a14bef1"}"#;
+    let page: AccountPage = serde_json::from_str(data).unwrap();
+    assert_eq!(page.next_key.as_ref().unwrap().len(), 5);
+    assert_eq!(serde_json::to_string(&page).unwrap(), data);
+
+    let data = r#"{"accounts":[],"stateRoot":"a3f9827a3bf99031a0d1117df82ad3c77c90e82fcd9cb3e9f3d61b730a14bef1"}"#;
+    let page: AccountPage = serde_json::from_str(data).unwrap();
+    assert!(page.next_key.is_none());
+}
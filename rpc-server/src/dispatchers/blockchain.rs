@@ -1,8 +1,16 @@
 use async_trait::async_trait;
-use futures::{future, stream::BoxStream, StreamExt};
+use futures::{
+    future,
+    stream::{self, BoxStream},
+    StreamExt,
+};
 use nimiq_account::{BlockLog as BBlockLog, TransactionLog};
-use nimiq_blockchain::interface::{HistoryIndexInterface, HistoryInterface};
-use nimiq_blockchain_interface::{AbstractBlockchain, BlockchainEvent};
+use nimiq_block::Block as BBlock;
+use nimiq_blockchain::{
+    interface::{HistoryIndexInterface, HistoryInterface},
+    AccountsChunk, EpochBasedIndex,
+};
+use nimiq_blockchain_interface::{AbstractBlockchain, BlockchainEvent, StateProof};
 use nimiq_blockchain_proxy::{BlockchainProxy, BlockchainReadProxy};
 use nimiq_hash::Blake2bHash;
 use nimiq_keys::Address;
@@ -10,9 +18,9 @@ use nimiq_primitives::{key_nibbles::KeyNibbles, policy::Policy};
 use nimiq_rpc_interface::{
     blockchain::BlockchainInterface,
     types::{
-        is_of_log_type_and_related_to_addresses, Account, Block, BlockLog, BlockchainState,
-        ExecutedTransaction, Inherent, LogType, PenalizedSlots, RPCData, RPCResult, Slot, Staker,
-        Validator,
+        is_of_log_type_and_related_to_addresses, Account, AccountPage, Block, BlockEvent, BlockLog,
+        BlockchainState, ExecutedTransaction, Inherent, InherentPage, LogType, PenalizedSlots,
+        ProvenValue, RPCData, RPCResult, Slot, Staker, Validator,
     },
 };
 use tokio_stream::wrappers::BroadcastStream;
@@ -29,6 +37,119 @@ impl BlockchainDispatcher {
     }
 }
 
+/// Encodes the position of an inherent in the history store as an opaque pagination cursor.
+fn encode_inherent_cursor(index: &EpochBasedIndex) -> String {
+    hex::encode([index.epoch_number.to_be_bytes(), index.index.to_be_bytes()].concat())
+}
+
+/// Decodes a pagination cursor created by `encode_inherent_cursor`.
+fn decode_inherent_cursor(cursor: &str) -> Result<EpochBasedIndex, Error> {
+    let bytes: [u8; 8] = hex::decode(cursor)?
+        .try_into()
+        .map_err(|_| Error::InvalidArgument(format!("Invalid cursor: {cursor}")))?;
+    let (epoch_number, index) = bytes.split_at(4);
+    Ok(EpochBasedIndex::new(
+        u32::from_be_bytes(epoch_number.try_into().unwrap()),
+        u32::from_be_bytes(index.try_into().unwrap()),
+    ))
+}
+
+/// The default number of trie entries read for a page of accounts.
+const DEFAULT_ACCOUNTS_PAGE_LIMIT: u16 = 1000;
+
+fn accounts_page_limit(limit: Option<u16>) -> Result<usize, Error> {
+    match limit.unwrap_or(DEFAULT_ACCOUNTS_PAGE_LIMIT) {
+        0 => Err(Error::InvalidArgument(
+            "The limit must not be zero".to_owned(),
+        )),
+        limit => Ok(limit as usize),
+    }
+}
+
+/// Converts a chunk of the accounts trie read against the state of the given block to a page.
+fn account_page(chunk: AccountsChunk, block: &BBlock) -> AccountPage {
+    AccountPage {
+        accounts: chunk
+            .accounts
+            .into_iter()
+            .map(|(address, account)| Account::from_account(address, account))
+            .collect(),
+        next_key: chunk.end_key,
+        block_hash: block.hash(),
+        state_root: block.state_root().clone(),
+    }
+}
+
+/// The maximum number of blocks applied in one step of a block event subscription, such that
+/// resuming from an old cursor doesn't hold the blockchain lock for too long. The remaining blocks
+/// are emitted with the next changes of the main chain.
+const MAX_BLOCK_EVENTS_CATCH_UP: u32 = 500;
+
+/// Tracks the view of the main chain of a block event subscriber.
+struct BlockEventTracker {
+    /// The last block the subscriber was notified about.
+    cursor: BlockchainState,
+}
+
+impl BlockEventTracker {
+    /// Returns the events that bring the subscriber's view in line with the current main chain.
+    fn catch_up(
+        &mut self,
+        blockchain: &BlockchainReadProxy,
+    ) -> Result<Vec<RPCData<BlockEvent, BlockchainState>>, Error> {
+        // The cursor is only updated once all events were created, such that none are skipped
+        // if this fails.
+        let mut cursor = self.cursor.clone();
+        let mut events = vec![];
+
+        // Revert the blocks that are not part of the main chain anymore, highest first. This
+        // stops at the latest macro block, as macro blocks can't be reverted.
+        while !is_on_main_chain(blockchain, &cursor) {
+            let block = blockchain
+                .get_block(&cursor.block_hash, true)
+                .map_err(|_| Error::BlockNotFoundByHash(cursor.block_hash.clone()))?;
+            let parent =
+                BlockchainState::new(block.block_number() - 1, block.parent_hash().clone());
+            let block = Block::from_block(blockchain, block, true)?;
+
+            cursor = parent;
+            events.push(RPCData::new(BlockEvent::Reverted { block }, cursor.clone()));
+        }
+
+        let end = blockchain
+            .block_number()
+            .min(cursor.block_number + MAX_BLOCK_EVENTS_CATCH_UP);
+        for block_number in cursor.block_number + 1..=end {
+            let block = blockchain
+                .get_block_at(block_number, true)
+                .map_err(|_| Error::BlockNotFound(block_number))?;
+            let is_macro = block.is_macro();
+            cursor = BlockchainState::new(block_number, block.hash());
+            let block = Block::from_block(blockchain, block, true)?;
+
+            events.push(RPCData::new(BlockEvent::Applied { block }, cursor.clone()));
+            if is_macro {
+                events.push(RPCData::new(
+                    BlockEvent::Finalized {
+                        block_number,
+                        block_hash: cursor.block_hash.clone(),
+                    },
+                    cursor.clone(),
+                ));
+            }
+        }
+
+        self.cursor = cursor;
+        Ok(events)
+    }
+}
+
+fn is_on_main_chain(blockchain: &BlockchainReadProxy, cursor: &BlockchainState) -> bool {
+    blockchain
+        .get_block_at(cursor.block_number, false)
+        .is_ok_and(|block| block.hash() == cursor.block_hash)
+}
+
 /// Tries to fetch a block given its hash. It has an option to include the transactions in the
 /// block, which defaults to false.
 /// This function requires the read lock acquisition prior to its execution
@@ -46,6 +167,27 @@ fn get_block_by_hash(
         .map(|block| block.into())
 }
 
+/// Creates a proof of the given key against the state root of the head block, or of the block
+/// with the given hash. States of past blocks are rebuilt from the stored revert diffs, see
+/// `Blockchain::get_historical_state_proof`.
+/// This function requires the read lock acquisition prior to its execution.
+fn get_state_proof(
+    blockchain_proxy: &BlockchainReadProxy,
+    key: &KeyNibbles,
+    block_hash: Option<Blake2bHash>,
+) -> Result<StateProof, Error> {
+    if let BlockchainReadProxy::Full(blockchain) = blockchain_proxy {
+        match block_hash {
+            Some(block_hash) => Ok(blockchain.get_historical_state_proof(vec![key], &block_hash)?),
+            None => blockchain
+                .get_state_proof(vec![key])
+                .map_err(|_| Error::NoConsensus),
+        }
+    } else {
+        Err(Error::NotSupportedForLightBlockchain)
+    }
+}
+
 /// Tries to fetch a validator information given its address.
 /// This function requires the read lock acquisition prior to its execution.
 fn get_validator_by_address(
@@ -364,12 +506,70 @@ impl BlockchainInterface for BlockchainDispatcher {
         }
     }
 
+    async fn get_inherents_by_address(
+        &mut self,
+        address: Address,
+        log_types: Vec<LogType>,
+        max: Option<u16>,
+        cursor: Option<String>,
+    ) -> RPCResult<InherentPage, (), Self::Error> {
+        if let BlockchainReadProxy::Full(blockchain) = self.blockchain.read() {
+            let max = max.unwrap_or(500);
+            let start_at = cursor.as_deref().map(decode_inherent_cursor).transpose()?;
+
+            let (hist_txs, next) = blockchain
+                .history_store
+                .history_index()
+                .ok_or(Error::RequiresHistoryIndex)?
+                .get_inherents_by_address(
+                    &address,
+                    max,
+                    start_at,
+                    &|hist_tx| {
+                        LogType::from_historic_transaction(hist_tx).is_some_and(|log_type| {
+                            log_types.is_empty() || log_types.contains(&log_type)
+                        })
+                    },
+                    None,
+                );
+
+            let next_cursor = next.as_ref().map(encode_inherent_cursor);
+
+            let inherents = hist_txs
+                .into_iter()
+                .filter_map(|(_, hist_tx)| Inherent::try_from(hist_tx))
+                .collect();
+
+            Ok(InherentPage {
+                inherents,
+                next_cursor,
+            }
+            .into())
+        } else {
+            Err(Error::NotSupportedForLightBlockchain)
+        }
+    }
+
     async fn get_account_by_address(
         &mut self,
         address: Address,
+        at_block: Option<u32>,
     ) -> RPCResult<Account, BlockchainState, Self::Error> {
         let blockchain_proxy = self.blockchain.read();
         if let BlockchainReadProxy::Full(ref blockchain) = blockchain_proxy {
+            if let Some(block_number) = at_block {
+                let account = blockchain.get_historical_account(&address, block_number)?;
+                let block_hash = blockchain
+                    .get_block_at(block_number, false, None)
+                    .map_err(|_| Error::BlockNotFound(block_number))?
+                    .hash();
+                return Ok(Account::from_account_with_state(
+                    address,
+                    account,
+                    BlockchainState::new(block_number, block_hash),
+                ));
+            }
+
             let account = blockchain
                 .get_account_if_complete(&address)
                 .ok_or(Error::NoConsensus)?;
@@ -383,6 +583,29 @@ impl BlockchainInterface for BlockchainDispatcher {
         }
     }
 
+    async fn get_account_proof_by_address(
+        &mut self,
+        address: Address,
+        block_hash: Option<Blake2bHash>,
+    ) -> RPCResult<ProvenValue<Account>, BlockchainState, Self::Error> {
+        let blockchain_proxy = self.blockchain.read();
+        let state_proof = get_state_proof(
+            &blockchain_proxy,
+            &StateProof::account_key(&address),
+            block_hash,
+        )?;
+
+        // Take the value from the proof itself, such that both always match.
+        let account = state_proof
+            .clone()
+            .verify_account(&state_proof.block.hash(), &address)?;
+
+        Ok(RPCData::with_blockchain(
+            ProvenValue::new(Account::from_account(address, account), &state_proof),
+            &blockchain_proxy,
+        ))
+    }
+
     async fn get_accounts(&mut self) -> RPCResult<Vec<Account>, BlockchainState, Self::Error> {
         let blockchain_proxy = self.blockchain.read();
         if let BlockchainReadProxy::Full(ref blockchain) = blockchain_proxy {
@@ -402,6 +625,38 @@ impl BlockchainInterface for BlockchainDispatcher {
         }
     }
 
+    async fn get_accounts_page(
+        &mut self,
+        start_key: Option<String>,
+        limit: Option<u16>,
+        block_hash: Option<Blake2bHash>,
+    ) -> RPCResult<AccountPage, BlockchainState, Self::Error> {
+        let limit = accounts_page_limit(limit)?;
+        let start = match start_key {
+            Some(key) => key
+                .parse()
+                .map_err(|_| Error::InvalidArgument(format!("Invalid start key: {key}")))?,
+            None => KeyNibbles::default(),
+        };
+
+        let blockchain_proxy = self.blockchain.read();
+        if let BlockchainReadProxy::Full(ref blockchain) = blockchain_proxy {
+            if !blockchain.state.accounts.is_complete(None) {
+                return Err(Error::NoConsensus);
+            }
+
+            let block_hash = block_hash.unwrap_or_else(|| blockchain.head_hash());
+            let block = blockchain_proxy
+                .get_block(&block_hash, false)
+                .map_err(|_| Error::BlockNotFoundByHash(block_hash.clone()))?;
+            let chunk = blockchain.get_historical_accounts_chunk(start, limit, &block_hash)?;
+            let page = account_page(chunk, &block);
+            Ok(RPCData::with_blockchain(page, &blockchain_proxy))
+        } else {
+            Err(Error::NotSupportedForLightBlockchain)
+        }
+    }
+
     async fn get_active_validators(
         &mut self,
     ) -> RPCResult<Vec<Validator>, BlockchainState, Self::Error> {
@@ -483,6 +738,31 @@ impl BlockchainInterface for BlockchainDispatcher {
         get_validator_by_address(&self.blockchain.read(), &address)
     }
 
+    async fn get_validator_proof_by_address(
+        &mut self,
+        address: Address,
+        block_hash: Option<Blake2bHash>,
+    ) -> RPCResult<ProvenValue<Option<Validator>>, BlockchainState, Self::Error> {
+        let blockchain_proxy = self.blockchain.read();
+        let state_proof = get_state_proof(
+            &blockchain_proxy,
+            &StateProof::validator_key(&address),
+            block_hash,
+        )?;
+
+        let validator = state_proof
+            .clone()
+            .verify_validator(&state_proof.block.hash(), &address)?;
+
+        Ok(RPCData::with_blockchain(
+            ProvenValue::new(
+                validator.as_ref().map(Validator::from_validator),
+                &state_proof,
+            ),
+            &blockchain_proxy,
+        ))
+    }
+
     async fn get_validators(&mut self) -> RPCResult<Vec<Validator>, BlockchainState, Self::Error> {
         let blockchain_proxy = self.blockchain.read();
 
@@ -551,6 +831,28 @@ impl BlockchainInterface for BlockchainDispatcher {
         }
     }
 
+    async fn get_staker_proof_by_address(
+        &mut self,
+        address: Address,
+        block_hash: Option<Blake2bHash>,
+    ) -> RPCResult<ProvenValue<Option<Staker>>, BlockchainState, Self::Error> {
+        let blockchain_proxy = self.blockchain.read();
+        let state_proof = get_state_proof(
+            &blockchain_proxy,
+            &StateProof::staker_key(&address),
+            block_hash,
+        )?;
+
+        let staker = state_proof
+            .clone()
+            .verify_staker(&state_proof.block.hash(), &address)?;
+
+        Ok(RPCData::with_blockchain(
+            ProvenValue::new(staker.as_ref().map(Staker::from_staker), &state_proof),
+            &blockchain_proxy,
+        ))
+    }
+
     #[stream]
     async fn subscribe_for_head_block(
         &mut self,
@@ -596,6 +898,76 @@ impl BlockchainInterface for BlockchainDispatcher {
             .boxed())
     }
 
+    #[stream]
+    async fn subscribe_for_block_events(
+        &mut self,
+        cursor: Option<BlockchainState>,
+    ) -> Result<BoxStream<'static, RPCData<BlockEvent, BlockchainState>>, Self::Error> {
+        let blockchain_proxy = self.blockchain.read();
+        if !matches!(blockchain_proxy, BlockchainReadProxy::Full(_)) {
+            return Err(Error::NotSupportedForLightBlockchain);
+        }
+
+        // Subscribe before catching up, such that no change of the main chain is missed.
+        let notifications = blockchain_proxy.notifier_as_stream();
+
+        let cursor = match cursor {
+            Some(cursor) => {
+                let block = blockchain_proxy
+                    .get_block(&cursor.block_hash, false)
+                    .map_err(|_| Error::BlockNotFoundByHash(cursor.block_hash.clone()))?;
+                if block.block_number() != cursor.block_number {
+                    return Err(Error::InvalidArgument(format!(
+                        "Block {} is not at height {}",
+                        cursor.block_hash, cursor.block_number
+                    )));
+                }
+                cursor
+            }
+            None => BlockchainState::with_blockchain(&blockchain_proxy),
+        };
+        let mut tracker = BlockEventTracker { cursor };
+        let events = tracker.catch_up(&blockchain_proxy)?;
+        drop(blockchain_proxy);
+
+        // The notifications only trigger another catch up, which also covers missed notifications.
+        let notifications = notifications.filter(|event| {
+            future::ready(matches!(
+                event,
+                BlockchainEvent::Extended(_)
+                    | BlockchainEvent::HistoryAdopted(_)
+                    | BlockchainEvent::Rebranched(..)
+            ))
+        });
+        let blockchain = self.blockchain.clone();
+        let live_events = stream::unfold(Some((tracker, notifications)), move |state| {
+            let blockchain = blockchain.clone();
+            async move {
+                // The state is gone once catching up failed, which ends the stream.
+                let (mut tracker, mut notifications) = state?;
+                notifications.next().await?;
+
+                let result = tracker.catch_up(&blockchain.read());
+                match result {
+                    Ok(events) => Some((events, Some((tracker, notifications)))),
+                    Err(error) => {
+                        log::warn!(%error, "Failed to emit block events");
+                        let event = RPCData::new(
+                            BlockEvent::Error {
+                                message: error.to_string(),
+                            },
+                            tracker.cursor,
+                        );
+                        Some((vec![event], None))
+                    }
+                }
+            }
+        })
+        .flat_map(stream::iter);
+
+        Ok(stream::iter(events).chain(live_events).boxed())
+    }
+
     #[stream]
     async fn subscribe_for_validator_election_by_address(
         &mut self,
@@ -618,6 +990,50 @@ impl BlockchainInterface for BlockchainDispatcher {
             .boxed())
     }
 
+    #[stream]
+    async fn subscribe_for_accounts(
+        &mut self,
+        limit: Option<u16>,
+    ) -> Result<BoxStream<'static, RPCData<AccountPage, BlockchainState>>, Self::Error> {
+        let limit = accounts_page_limit(limit)?;
+        let block = match self.blockchain.read() {
+            BlockchainReadProxy::Full(blockchain) => {
+                if !blockchain.state.accounts.is_complete(None) {
+                    return Err(Error::NoConsensus);
+                }
+                blockchain.head().clone()
+            }
+            _ => return Err(Error::NotSupportedForLightBlockchain),
+        };
+
+        // Every page is read with its own transaction once the subscriber asks for it, such that
+        // no transaction is kept open between pages. All pages are read against the state of the
+        // head at the time of subscribing, the stream ends once that state can't be rebuilt.
+        let blockchain = self.blockchain.clone();
+        let block_hash = block.hash();
+        Ok(stream::unfold(Some(KeyNibbles::default()), move |start| {
+            let blockchain = blockchain.clone();
+            let block = block.clone();
+            let block_hash = block_hash.clone();
+            async move {
+                let start = start?;
+                let blockchain_proxy = blockchain.read();
+                let BlockchainReadProxy::Full(ref blockchain) = blockchain_proxy else {
+                    return None;
+                };
+
+                let chunk = blockchain
+                    .get_historical_accounts_chunk(start, limit, &block_hash)
+                    .ok()?;
+                let next_key = chunk.end_key.clone();
+                let page = account_page(chunk, &block);
+
+                Some((RPCData::with_blockchain(page, &blockchain_proxy), next_key))
+            }
+        })
+        .boxed())
+    }
+
     #[stream]
     async fn subscribe_for_logs_by_addresses_and_types(
         &mut self,
@@ -744,3 +1160,142 @@ impl BlockchainInterface for BlockchainDispatcher {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use nimiq_blockchain_interface::{AbstractBlockchain, PushResult};
+    use nimiq_blockchain_proxy::BlockchainReadProxy;
+    use nimiq_hash::Blake2bHash;
+    use nimiq_primitives::policy::Policy;
+    use nimiq_rpc_interface::types::{BlockEvent, BlockchainState, RPCData};
+    use nimiq_test_log::test;
+    use nimiq_test_utils::block_production::TemporaryBlockProducer;
+
+    use super::{BlockEventTracker, MAX_BLOCK_EVENTS_CATCH_UP};
+
+    fn tracker_at_head(producer: &TemporaryBlockProducer) -> BlockEventTracker {
+        let blockchain = BlockchainReadProxy::Full(producer.blockchain.read());
+        BlockEventTracker {
+            cursor: BlockchainState::with_blockchain(&blockchain),
+        }
+    }
+
+    fn catch_up(
+        tracker: &mut BlockEventTracker,
+        producer: &TemporaryBlockProducer,
+    ) -> Vec<(&'static str, Blake2bHash)> {
+        let blockchain = BlockchainReadProxy::Full(producer.blockchain.read());
+        let events = tracker.catch_up(&blockchain).unwrap();
+        events.iter().map(describe).collect()
+    }
+
+    fn describe(event: &RPCData<BlockEvent, BlockchainState>) -> (&'static str, Blake2bHash) {
+        match &event.data {
+            BlockEvent::Applied { block } => ("applied", block.hash.clone()),
+            BlockEvent::Reverted { block } => ("reverted", block.hash.clone()),
+            BlockEvent::Finalized { block_hash, .. } => ("finalized", block_hash.clone()),
+            BlockEvent::Error { .. } => ("error", Blake2bHash::default()),
+        }
+    }
+
+    #[test]
+    fn catch_up_reverts_rebranched_blocks_highest_first() {
+        let producer1 = TemporaryBlockProducer::new();
+        let producer2 = TemporaryBlockProducer::new();
+
+        // [0] - [a1] - [b1]
+        //    \- [a2] - [b2] - [c2]
+        let block = producer1.next_block(vec![], false);
+        producer2.push(block).unwrap();
+
+        let a1 = producer1.next_block(vec![0x48], false);
+        let b1 = producer1.next_block(vec![], false);
+        let mut tracker = tracker_at_head(&producer1);
+
+        let a2 = producer2.next_block(vec![], false);
+        let b2 = producer2.next_block(vec![], false);
+        let c2 = producer2.next_block(vec![], true);
+        assert_eq!(producer1.push(a2.clone()), Ok(PushResult::Forked));
+        assert_eq!(producer1.push(b2.clone()), Ok(PushResult::Forked));
+        assert_eq!(producer1.push(c2.clone()), Ok(PushResult::Rebranched));
+
+        assert_eq!(
+            catch_up(&mut tracker, &producer1),
+            vec![
+                ("reverted", b1.hash()),
+                ("reverted", a1.hash()),
+                ("applied", a2.hash()),
+                ("applied", b2.hash()),
+                ("applied", c2.hash()),
+            ]
+        );
+        assert_eq!(tracker.cursor.block_hash, c2.hash());
+
+        // Nothing changed since the last catch up.
+        assert!(catch_up(&mut tracker, &producer1).is_empty());
+    }
+
+    #[test]
+    fn catch_up_resumes_from_a_stale_cursor() {
+        let producer = TemporaryBlockProducer::new();
+        let mut tracker = tracker_at_head(&producer);
+        let start = tracker.cursor.block_number;
+
+        // The subscriber missed more than a batch, including a macro block.
+        let blocks: Vec<_> = (0..Policy::blocks_per_batch() + 2)
+            .map(|_| producer.next_block(vec![], false))
+            .collect();
+        assert!(blocks.iter().any(|block| block.is_macro()));
+
+        let expected: Vec<_> = blocks
+            .iter()
+            .flat_map(|block| {
+                let mut events = vec![("applied", block.hash())];
+                if block.is_macro() {
+                    events.push(("finalized", block.hash()));
+                }
+                events
+            })
+            .collect();
+        assert_eq!(catch_up(&mut tracker, &producer), expected);
+        assert_eq!(tracker.cursor.block_number, start + blocks.len() as u32);
+        assert_eq!(
+            tracker.cursor.block_hash,
+            producer.blockchain.read().head_hash()
+        );
+    }
+
+    #[test]
+    fn catch_up_applies_a_limited_number_of_blocks() {
+        let producer = TemporaryBlockProducer::new();
+        let mut tracker = tracker_at_head(&producer);
+        let start = tracker.cursor.block_number;
+
+        for _ in 0..MAX_BLOCK_EVENTS_CATCH_UP + 10 {
+            producer.next_block(vec![], false);
+        }
+
+        let applied = |events: Vec<(&str, Blake2bHash)>| {
+            events
+                .into_iter()
+                .filter(|(event, _)| *event == "applied")
+                .count()
+        };
+
+        assert_eq!(
+            applied(catch_up(&mut tracker, &producer)),
+            MAX_BLOCK_EVENTS_CATCH_UP as usize
+        );
+        assert_eq!(
+            tracker.cursor.block_number,
+            start + MAX_BLOCK_EVENTS_CATCH_UP
+        );
+
+        // The remaining blocks are applied by the next catch up.
+        assert_eq!(applied(catch_up(&mut tracker, &producer)), 10);
+        assert_eq!(
+            tracker.cursor.block_hash,
+            producer.blockchain.read().head_hash()
+        );
+    }
+}
@@ -11,7 +11,7 @@ use nimiq_network_libp2p::Network;
 use nimiq_primitives::{coin::Coin, networks::NetworkId};
 use nimiq_rpc_interface::{
     consensus::ConsensusInterface,
-    types::{RPCResult, Transaction as RPCTransaction, ValidityStartHeight},
+    types::{RPCResult, Transaction as RPCTransaction, ValidityStartHeight, VestingTranche},
 };
 use nimiq_serde::{Deserialize, Serialize};
 use nimiq_transaction::{
@@ -231,6 +231,112 @@ impl ConsensusInterface for ConsensusDispatcher {
         self.send_raw_transaction(raw_tx).await
     }
 
+    async fn create_new_vesting_transaction_with_cliff(
+        &mut self,
+        wallet: Address,
+        owner: Address,
+        start_time: u64,
+        cliff_duration: u64,
+        cliff_amount: Coin,
+        time_step: u64,
+        num_steps: u32,
+        value: Coin,
+        fee: Coin,
+        validity_start_height: ValidityStartHeight,
+    ) -> RPCResult<String, (), Self::Error> {
+        let transaction = TransactionBuilder::new_create_vesting_with_cliff(
+            &self.get_wallet_keypair(&wallet)?,
+            owner,
+            start_time,
+            cliff_duration,
+            cliff_amount,
+            time_step,
+            num_steps,
+            value,
+            fee,
+            self.validity_start_height(validity_start_height),
+            self.get_network_id(),
+        )?;
+
+        Ok(transaction_to_hex_string(&transaction).into())
+    }
+
+    async fn send_new_vesting_transaction_with_cliff(
+        &mut self,
+        wallet: Address,
+        owner: Address,
+        start_time: u64,
+        cliff_duration: u64,
+        cliff_amount: Coin,
+        time_step: u64,
+        num_steps: u32,
+        value: Coin,
+        fee: Coin,
+        validity_start_height: ValidityStartHeight,
+    ) -> RPCResult<Blake2bHash, (), Self::Error> {
+        let raw_tx = self
+            .create_new_vesting_transaction_with_cliff(
+                wallet,
+                owner,
+                start_time,
+                cliff_duration,
+                cliff_amount,
+                time_step,
+                num_steps,
+                value,
+                fee,
+                validity_start_height,
+            )
+            .await?
+            .data;
+        self.send_raw_transaction(raw_tx).await
+    }
+
+    async fn create_new_vesting_transaction_with_schedule(
+        &mut self,
+        wallet: Address,
+        owner: Address,
+        tranches: Vec<VestingTranche>,
+        value: Coin,
+        fee: Coin,
+        validity_start_height: ValidityStartHeight,
+    ) -> RPCResult<String, (), Self::Error> {
+        let transaction = TransactionBuilder::new_create_vesting_with_schedule(
+            &self.get_wallet_keypair(&wallet)?,
+            owner,
+            tranches.into_iter().map(Into::into).collect(),
+            value,
+            fee,
+            self.validity_start_height(validity_start_height),
+            self.get_network_id(),
+        )?;
+
+        Ok(transaction_to_hex_string(&transaction).into())
+    }
+
+    async fn send_new_vesting_transaction_with_schedule(
+        &mut self,
+        wallet: Address,
+        owner: Address,
+        tranches: Vec<VestingTranche>,
+        value: Coin,
+        fee: Coin,
+        validity_start_height: ValidityStartHeight,
+    ) -> RPCResult<Blake2bHash, (), Self::Error> {
+        let raw_tx = self
+            .create_new_vesting_transaction_with_schedule(
+                wallet,
+                owner,
+                tranches,
+                value,
+                fee,
+                validity_start_height,
+            )
+            .await?
+            .data;
+        self.send_raw_transaction(raw_tx).await
+    }
+
     async fn create_redeem_vesting_transaction(
         &mut self,
         wallet: Address,
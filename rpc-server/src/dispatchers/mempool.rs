@@ -1,20 +1,147 @@
 use std::sync::Arc;
 
 use async_trait::async_trait;
+use futures::{
+    future,
+    stream::{self, BoxStream},
+    StreamExt,
+};
+use nimiq_blockchain::interface::{HistoryIndexInterface, HistoryInterface};
+use nimiq_blockchain_interface::AbstractBlockchain;
+use nimiq_blockchain_proxy::{BlockchainProxy, BlockchainReadProxy};
 use nimiq_consensus::ConsensusProxy;
 use nimiq_hash::{Blake2bHash, Hash};
-use nimiq_mempool::{mempool::Mempool, mempool_transactions::TxPriority};
+use nimiq_mempool::{
+    mempool::{DropReason, Mempool},
+    mempool_transactions::TxPriority,
+};
 use nimiq_network_libp2p::Network;
+use nimiq_primitives::policy::Policy;
 use nimiq_rpc_interface::{
     mempool::MempoolInterface,
-    types::{HashOrTx, MempoolInfo, RPCResult},
+    types::{
+        BlockchainState, HashOrTx, MempoolInfo, RPCData, RPCResult, TransactionDropReason,
+        TransactionStatus,
+    },
 };
 use nimiq_serde::Deserialize;
-use nimiq_transaction::Transaction;
+use nimiq_transaction::{
+    historic_transaction::HistoricTransactionData, ExecutedTransaction, Transaction,
+};
 
 use crate::error::Error;
 
-#[allow(dead_code)]
+/// The inclusion probability used for fee estimations if none is given.
+const DEFAULT_FEE_ESTIMATE_PROBABILITY: f64 = 0.95;
+
+/// Determines the status of a transaction from the mempool and the history store.
+fn get_transaction_status(
+    blockchain: &BlockchainProxy,
+    mempool: &Mempool,
+    hash: &Blake2bHash,
+) -> RPCResult<TransactionStatus, BlockchainState, Error> {
+    let blockchain_proxy = blockchain.read();
+    let BlockchainReadProxy::Full(ref blockchain) = blockchain_proxy else {
+        return Err(Error::NotSupportedForLightBlockchain);
+    };
+
+    // Transactions in the mempool are pending, the history store is only searched for the others.
+    if mempool.contains_transaction_by_hash(hash) {
+        return Ok(RPCData::with_blockchain(
+            TransactionStatus::Pending,
+            &blockchain_proxy,
+        ));
+    }
+
+    // The history store only contains the transactions of the main chain.
+    let hist_tx = blockchain
+        .history_store
+        .history_index()
+        .ok_or(Error::RequiresHistoryIndex)?
+        .get_hist_tx_by_hash(hash, None);
+
+    let status = if let Some(hist_tx) = hist_tx {
+        let block_number = hist_tx.block_number;
+        let confirmations = blockchain.block_number() - block_number + 1;
+        let succeeded = !matches!(
+            hist_tx.data,
+            HistoricTransactionData::Basic(ExecutedTransaction::Err(_))
+        );
+        if block_number <= blockchain.macro_head().block_number() {
+            TransactionStatus::Finalized {
+                block_number,
+                confirmations,
+                succeeded,
+            }
+        } else {
+            TransactionStatus::Included {
+                block_number,
+                confirmations,
+                succeeded,
+            }
+        }
+    } else {
+        match mempool.get_drop_reason(hash) {
+            Some(DropReason::Expired) => TransactionStatus::Expired,
+            Some(DropReason::Invalid(reason)) => TransactionStatus::Invalid { reason },
+            Some(DropReason::Replaced) => TransactionStatus::Dropped {
+                reason: TransactionDropReason::Replaced,
+            },
+            Some(DropReason::TooFull) => TransactionStatus::Dropped {
+                reason: TransactionDropReason::MempoolFull,
+            },
+            None => TransactionStatus::Unknown,
+        }
+    };
+
+    Ok(RPCData::with_blockchain(status, &blockchain_proxy))
+}
+
+/// Follows the status of a transaction for a subscription and decides which changes are emitted.
+struct TransactionStatusTracker {
+    last_status: TransactionStatus,
+    /// The block number after which the transaction can't be included anymore, unless it is
+    /// pending or included again.
+    deadline: u32,
+}
+
+impl TransactionStatusTracker {
+    fn new(initial: &RPCData<TransactionStatus, BlockchainState>) -> Self {
+        TransactionStatusTracker {
+            last_status: initial.data.clone(),
+            deadline: Self::deadline(initial.metadata.block_number),
+        }
+    }
+
+    /// A transaction that is valid after the given block can be included at most a validity
+    /// window after its validity start height, which is at most a batch ahead.
+    fn deadline(block_number: u32) -> u32 {
+        block_number + Policy::blocks_per_batch() + Policy::transaction_validity_window_blocks()
+    }
+
+    /// Returns the update if the status changed. Transactions that are neither pending nor
+    /// included are reported as expired once the deadline passed.
+    fn update(
+        &mut self,
+        mut update: RPCData<TransactionStatus, BlockchainState>,
+    ) -> Option<RPCData<TransactionStatus, BlockchainState>> {
+        if matches!(
+            update.data,
+            TransactionStatus::Pending | TransactionStatus::Included { .. }
+        ) {
+            self.deadline = Self::deadline(update.metadata.block_number);
+        } else if !update.data.is_final() && update.metadata.block_number > self.deadline {
+            update.data = TransactionStatus::Expired;
+        }
+
+        if update.data == self.last_status {
+            return None;
+        }
+        self.last_status = update.data.clone();
+        Some(update)
+    }
+}
+
 pub struct MempoolDispatcher {
     consensus: ConsensusProxy<Network>,
     mempool: Arc<Mempool>,
@@ -34,16 +161,16 @@ impl MempoolInterface for MempoolDispatcher {
     async fn push_transaction(
         &mut self,
         raw_tx: String,
-    ) -> RPCResult<Blake2bHash, (), Self::Error> {
+    ) -> RPCResult<Blake2bHash, Option<Blake2bHash>, Self::Error> {
         let tx = Transaction::deserialize_from_vec(&hex::decode(&raw_tx)?)?;
         let txid = tx.hash::<Blake2bHash>();
 
         match self.mempool.add_transaction(tx.clone(), None) {
-            Ok(_) => self
+            Ok(replaced_txid) => self
                 .consensus
                 .send_transaction(tx)
                 .await
-                .map(|_| txid.into())
+                .map(|_| RPCData::new(txid, replaced_txid))
                 .map_err(Error::NetworkError),
             Err(e) => Err(Error::MempoolError(e)),
         }
@@ -52,7 +179,7 @@ impl MempoolInterface for MempoolDispatcher {
     async fn push_high_priority_transaction(
         &mut self,
         raw_tx: String,
-    ) -> RPCResult<Blake2bHash, (), Self::Error> {
+    ) -> RPCResult<Blake2bHash, Option<Blake2bHash>, Self::Error> {
         let tx = Transaction::deserialize_from_vec(&hex::decode(&raw_tx)?)?;
         let txid = tx.hash::<Blake2bHash>();
 
@@ -60,11 +187,11 @@ impl MempoolInterface for MempoolDispatcher {
             .mempool
             .add_transaction(tx.clone(), Some(TxPriority::High))
         {
-            Ok(_) => self
+            Ok(replaced_txid) => self
                 .consensus
                 .send_transaction(tx)
                 .await
-                .map(|_| txid.into())
+                .map(|_| RPCData::new(txid, replaced_txid))
                 .map_err(Error::NetworkError),
             Err(e) => Err(Error::MempoolError(e)),
         }
@@ -100,6 +227,27 @@ impl MempoolInterface for MempoolDispatcher {
         Ok(self.mempool.get_rules().tx_fee_per_byte.into())
     }
 
+    async fn estimate_fee(
+        &mut self,
+        target_blocks: u32,
+        probability: Option<f64>,
+    ) -> RPCResult<f64, (), Self::Error> {
+        if target_blocks == 0 {
+            return Err(Error::InvalidArgument(
+                "Target blocks must be at least 1".to_string(),
+            ));
+        }
+
+        let probability = probability.unwrap_or(DEFAULT_FEE_ESTIMATE_PROBABILITY);
+        if !(probability > 0.0 && probability <= 1.0) {
+            return Err(Error::InvalidArgument(
+                "Probability must be in the range (0, 1]".to_string(),
+            ));
+        }
+
+        Ok(self.mempool.estimate_fee(target_blocks, probability).into())
+    }
+
     async fn get_transaction_from_mempool(
         &mut self,
         hash: Blake2bHash,
@@ -110,4 +258,243 @@ impl MempoolInterface for MempoolDispatcher {
             return Err(Error::TransactionNotFound(hash));
         }
     }
+
+    async fn get_transaction_status(
+        &mut self,
+        hash: Blake2bHash,
+    ) -> RPCResult<TransactionStatus, BlockchainState, Self::Error> {
+        get_transaction_status(&self.consensus.blockchain, &self.mempool, &hash)
+    }
+
+    #[stream]
+    async fn subscribe_for_transaction_status(
+        &mut self,
+        hash: Blake2bHash,
+    ) -> Result<BoxStream<'static, RPCData<TransactionStatus, BlockchainState>>, Self::Error> {
+        let blockchain = self.consensus.blockchain.clone();
+        let mempool = Arc::clone(&self.mempool);
+
+        // Subscribe before determining the current status, such that no change is missed.
+        let notifications = blockchain.read().notifier_as_stream();
+        let initial = get_transaction_status(&blockchain, &mempool, &hash)?;
+        if initial.data.is_final() {
+            return Ok(stream::once(future::ready(initial)).boxed());
+        }
+
+        // The mempool and the history store are updated with every new block, so the status is
+        // checked again after every change of the blockchain.
+        let mut tracker = TransactionStatusTracker::new(&initial);
+        let updates = notifications.filter_map(move |_| {
+            let update = get_transaction_status(&blockchain, &mempool, &hash)
+                .ok()
+                .and_then(|update| tracker.update(update));
+            future::ready(update)
+        });
+        // The stream ends right after the final status, without waiting for another block.
+        let updates = stream::unfold(Some(updates), |updates| async move {
+            let mut updates = updates?;
+            let update = updates.next().await?;
+            let finished = update.data.is_final();
+            Some((update, (!finished).then_some(updates)))
+        });
+
+        Ok(stream::once(future::ready(initial)).chain(updates).boxed())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use nimiq_blockchain_interface::AbstractBlockchain;
+    use nimiq_blockchain_proxy::BlockchainProxy;
+    use nimiq_hash::{Blake2bHash, Hash};
+    use nimiq_keys::{Address, KeyPair, PrivateKey};
+    use nimiq_mempool::{config::MempoolConfig, mempool::Mempool};
+    use nimiq_primitives::{account::FailReason, coin::Coin, policy::Policy};
+    use nimiq_rpc_interface::types::{
+        BlockchainState, RPCData, TransactionDropReason, TransactionStatus,
+    };
+    use nimiq_test_log::test;
+    use nimiq_test_utils::{block_production::TemporaryBlockProducer, blockchain::REWARD_KEY};
+    use nimiq_transaction::Transaction;
+    use nimiq_transaction_builder::TransactionBuilder;
+
+    use super::{get_transaction_status, TransactionStatusTracker};
+
+    fn setup() -> (TemporaryBlockProducer, Mempool) {
+        let producer = TemporaryBlockProducer::new();
+        let mempool = Mempool::new(producer.blockchain.clone(), MempoolConfig::default());
+        (producer, mempool)
+    }
+
+    fn key_pair() -> KeyPair {
+        KeyPair::from(PrivateKey::from_str(REWARD_KEY).unwrap())
+    }
+
+    /// Creates a transaction from the funded genesis account that is valid from the current head.
+    fn transaction(producer: &TemporaryBlockProducer, value: Coin, fee: Coin) -> Transaction {
+        let blockchain = producer.blockchain.read();
+        TransactionBuilder::new_basic(
+            &key_pair(),
+            Address::from([1; 20]),
+            value,
+            fee,
+            blockchain.block_number(),
+            blockchain.network_id(),
+        )
+        .unwrap()
+    }
+
+    fn status(
+        producer: &TemporaryBlockProducer,
+        mempool: &Mempool,
+        tx: &Transaction,
+    ) -> TransactionStatus {
+        let blockchain = BlockchainProxy::from(&producer.blockchain);
+        get_transaction_status(&blockchain, mempool, &tx.hash())
+            .unwrap()
+            .data
+    }
+
+    #[test]
+    fn included_transactions_are_finalized_with_the_next_macro_block() {
+        let (producer, mempool) = setup();
+        let tx = transaction(&producer, Coin::from_u64_unchecked(1), Coin::ZERO);
+        mempool.add_transaction(tx.clone(), None).unwrap();
+        assert_eq!(status(&producer, &mempool, &tx), TransactionStatus::Pending);
+
+        let block = producer.next_block_with_txs(vec![], false, vec![tx.clone()]);
+        let block_number = block.block_number();
+        mempool.update(&[(block.hash(), block)], &[]);
+        assert_eq!(
+            status(&producer, &mempool, &tx),
+            TransactionStatus::Included {
+                block_number,
+                confirmations: 1,
+                succeeded: true,
+            }
+        );
+
+        while !Policy::is_macro_block_at(producer.blockchain.read().block_number()) {
+            producer.next_block(vec![], false);
+        }
+        let head = producer.blockchain.read().block_number();
+        assert_eq!(
+            status(&producer, &mempool, &tx),
+            TransactionStatus::Finalized {
+                block_number,
+                confirmations: head - block_number + 1,
+                succeeded: true,
+            }
+        );
+    }
+
+    #[test]
+    fn transactions_expire_after_the_validity_window() {
+        let (producer, mempool) = setup();
+        let tx = transaction(&producer, Coin::from_u64_unchecked(1), Coin::ZERO);
+        mempool.add_transaction(tx.clone(), None).unwrap();
+
+        for _ in 0..=Policy::transaction_validity_window_blocks() {
+            producer.next_block(vec![], false);
+        }
+        mempool.update(&[], &[]);
+
+        assert_eq!(status(&producer, &mempool, &tx), TransactionStatus::Expired);
+    }
+
+    #[test]
+    fn transactions_become_invalid_if_the_sender_cant_pay() {
+        let (producer, mempool) = setup();
+        let tx = transaction(&producer, Coin::from_u64_unchecked(1), Coin::ZERO);
+        mempool.add_transaction(tx.clone(), None).unwrap();
+
+        // Another transaction that spends the whole balance is included without the mempool.
+        let balance = producer
+            .blockchain
+            .read()
+            .get_account_if_complete(&Address::from(&key_pair()))
+            .unwrap()
+            .balance();
+        let spend_all = transaction(&producer, balance, Coin::ZERO);
+        let block = producer.next_block_with_txs(vec![], false, vec![spend_all]);
+        mempool.update(&[(block.hash(), block)], &[]);
+
+        assert_eq!(
+            status(&producer, &mempool, &tx),
+            TransactionStatus::Invalid {
+                reason: FailReason::InsufficientFunds,
+            }
+        );
+        // Another node might still include it.
+        assert!(!status(&producer, &mempool, &tx).is_final());
+    }
+
+    fn update(
+        status: TransactionStatus,
+        block_number: u32,
+    ) -> RPCData<TransactionStatus, BlockchainState> {
+        RPCData::new(
+            status,
+            BlockchainState::new(block_number, Blake2bHash::default()),
+        )
+    }
+
+    #[test]
+    fn unknown_transactions_are_followed_until_they_expire() {
+        let initial = update(TransactionStatus::Unknown, 10);
+        assert!(!initial.data.is_final());
+        let mut tracker = TransactionStatusTracker::new(&initial);
+        let deadline = TransactionStatusTracker::deadline(10);
+
+        assert!(tracker
+            .update(update(TransactionStatus::Unknown, 11))
+            .is_none());
+        assert!(tracker
+            .update(update(TransactionStatus::Unknown, deadline))
+            .is_none());
+        assert_eq!(
+            tracker
+                .update(update(TransactionStatus::Unknown, deadline + 1))
+                .unwrap()
+                .data,
+            TransactionStatus::Expired
+        );
+    }
+
+    #[test]
+    fn dropped_transactions_expire_a_validity_window_after_they_were_pending() {
+        let mut tracker = TransactionStatusTracker::new(&update(TransactionStatus::Unknown, 10));
+
+        assert_eq!(
+            tracker
+                .update(update(TransactionStatus::Pending, 20))
+                .unwrap()
+                .data,
+            TransactionStatus::Pending
+        );
+        let dropped = TransactionStatus::Dropped {
+            reason: TransactionDropReason::MempoolFull,
+        };
+        assert_eq!(
+            tracker.update(update(dropped.clone(), 21)).unwrap().data,
+            dropped
+        );
+
+        // The deadline moved with the pending status.
+        assert!(tracker
+            .update(update(
+                dropped.clone(),
+                TransactionStatusTracker::deadline(10) + 1
+            ))
+            .is_none());
+        assert_eq!(
+            tracker
+                .update(update(dropped, TransactionStatusTracker::deadline(20) + 1))
+                .unwrap()
+                .data,
+            TransactionStatus::Expired
+        );
+    }
 }
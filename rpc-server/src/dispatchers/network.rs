@@ -1,9 +1,21 @@
-use std::sync::Arc;
+use std::{
+    str::FromStr,
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
 
 use async_trait::async_trait;
-use nimiq_network_interface::network::Network as InterfaceNetwork;
-use nimiq_network_libp2p::Network;
-use nimiq_rpc_interface::{network::NetworkInterface, types::RPCResult};
+use nimiq_network_interface::{
+    network::{CloseReason as NetworkCloseReason, Network as InterfaceNetwork},
+    peer_info::{NodeType as NetworkNodeType, Services},
+};
+use nimiq_network_libp2p::{
+    libp2p::Multiaddr, BanReason as NetworkBanReason, BanTarget, Network, PeerId, PeerReputation,
+};
+use nimiq_rpc_interface::{
+    network::NetworkInterface,
+    types::{BanReason, CloseReason, NodeType, PeerBan, PeerDetails, RPCResult},
+};
 
 use crate::error::Error;
 
@@ -15,6 +27,52 @@ impl NetworkDispatcher {
     pub fn new(network: Arc<Network>) -> Self {
         NetworkDispatcher { network }
     }
+
+    fn peer_details(&self, peer_id: PeerId, reputation: PeerReputation) -> Option<PeerDetails> {
+        let peer_info = self.network.get_peer_info(peer_id)?;
+        let connected_since = self.network.get_peer_connected_since(&peer_id)?;
+        let services = peer_info.get_services();
+
+        let node_type = if services.contains(Services::provided(NetworkNodeType::History)) {
+            NodeType::History
+        } else if services.contains(Services::provided(NetworkNodeType::Full)) {
+            NodeType::Full
+        } else {
+            NodeType::Light
+        };
+
+        Some(PeerDetails {
+            peer_id: peer_id.to_string(),
+            address: peer_info.get_address().to_string(),
+            addresses: self
+                .network
+                .get_peer_addresses(&peer_id)
+                .iter()
+                .map(|address| address.to_string())
+                .collect(),
+            node_type,
+            services: services
+                .iter_names()
+                .map(|(name, _)| name.to_lowercase().replace('_', "-"))
+                .collect(),
+            connected_since: connected_since
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_millis() as u64,
+            connection_duration: connected_since.elapsed().unwrap_or_default().as_secs(),
+            reputation: reputation.score,
+            rate_limit_hits: reputation.rate_limit_hits,
+        })
+    }
+}
+
+fn parse_peer_id(peer_id: &str) -> Result<PeerId, Error> {
+    PeerId::from_str(peer_id)
+        .map_err(|_| Error::InvalidArgument(format!("Invalid peer ID: {peer_id}")))
+}
+
+fn parse_ban_target(target: &str) -> Result<BanTarget, Error> {
+    BanTarget::from_str(target).map_err(|error| Error::InvalidArgument(error.to_string()))
 }
 
 #[nimiq_jsonrpc_derive::service(rename_all = "camelCase")]
@@ -39,4 +97,90 @@ impl NetworkInterface for NetworkDispatcher {
             .collect::<Vec<_>>()
             .into())
     }
+
+    async fn get_peer_info(&mut self, peer_id: String) -> RPCResult<PeerDetails, (), Self::Error> {
+        let peer_id = parse_peer_id(&peer_id)?;
+        let reputation = self
+            .network
+            .get_peer_reputations()
+            .await?
+            .remove(&peer_id)
+            .unwrap_or_default();
+        self.peer_details(peer_id, reputation)
+            .map(Into::into)
+            .ok_or(Error::PeerNotFound(peer_id.to_string()))
+    }
+
+    async fn get_peer_info_list(&mut self) -> RPCResult<Vec<PeerDetails>, (), Self::Error> {
+        let reputations = self.network.get_peer_reputations().await?;
+        Ok(self
+            .network
+            .get_peers()
+            .into_iter()
+            .filter_map(|peer_id| {
+                let reputation = reputations.get(&peer_id).copied().unwrap_or_default();
+                self.peer_details(peer_id, reputation)
+            })
+            .collect::<Vec<_>>()
+            .into())
+    }
+
+    async fn dial_address(&mut self, address: String) -> RPCResult<(), (), Self::Error> {
+        let address = Multiaddr::from_str(&address)
+            .map_err(|_| Error::InvalidArgument(format!("Invalid multiaddress: {address}")))?;
+        self.network.dial_address(address).await?;
+        Ok(().into())
+    }
+
+    async fn disconnect_peer(
+        &mut self,
+        peer_id: String,
+        reason: CloseReason,
+    ) -> RPCResult<(), (), Self::Error> {
+        let peer_id = parse_peer_id(&peer_id)?;
+        if !self.network.has_peer(peer_id) {
+            return Err(Error::PeerNotFound(peer_id.to_string()));
+        }
+
+        let reason = match reason {
+            CloseReason::Other => NetworkCloseReason::Other,
+            CloseReason::GoingOffline => NetworkCloseReason::GoingOffline,
+            CloseReason::Error => NetworkCloseReason::Error,
+            CloseReason::MaliciousPeer => NetworkCloseReason::MaliciousPeer,
+        };
+        self.network.disconnect_peer(peer_id, reason).await;
+        Ok(().into())
+    }
+
+    async fn ban(&mut self, target: String, duration: u64) -> RPCResult<(), (), Self::Error> {
+        let target = parse_ban_target(&target)?;
+        self.network
+            .ban(target, Duration::from_secs(duration))
+            .await?;
+        Ok(().into())
+    }
+
+    async fn unban(&mut self, target: String) -> RPCResult<bool, (), Self::Error> {
+        let target = parse_ban_target(&target)?;
+        Ok(self.network.unban(target).await?.into())
+    }
+
+    async fn get_bans(&mut self) -> RPCResult<Vec<PeerBan>, (), Self::Error> {
+        Ok(self
+            .network
+            .get_bans()
+            .await?
+            .iter()
+            .map(|ban| PeerBan {
+                target: ban.target.to_string(),
+                reason: match ban.reason {
+                    NetworkBanReason::MaliciousPeer => BanReason::MaliciousPeer,
+                    NetworkBanReason::Manual => BanReason::Manual,
+                    NetworkBanReason::DenyList => BanReason::DenyList,
+                },
+                expires_in: ban.expires_in.map(|expires_in| expires_in.as_secs()),
+            })
+            .collect::<Vec<_>>()
+            .into())
+    }
 }
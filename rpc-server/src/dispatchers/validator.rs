@@ -1,13 +1,17 @@
-use std::sync::atomic::Ordering;
+use std::{sync::atomic::Ordering, time::SystemTime};
 
 use async_trait::async_trait;
 use nimiq_bls::{KeyPair as BlsKeyPair, SecretKey as BlsSecretKey};
 use nimiq_consensus::ConsensusProxy;
 use nimiq_keys::Address;
 use nimiq_network_libp2p::Network;
-use nimiq_rpc_interface::{types::RPCResult, validator::ValidatorInterface};
+use nimiq_rpc_interface::{
+    types::{PeerIdResolution, RPCResult, ValidatorConnectionStatus},
+    validator::ValidatorInterface,
+};
 use nimiq_serde::{Deserialize, Serialize};
 use nimiq_validator::validator::ValidatorProxy;
+use nimiq_validator_network::status::PeerIdResolution as NetworkPeerIdResolution;
 
 use crate::error::Error;
 
@@ -90,4 +94,40 @@ impl ValidatorInterface for ValidatorDispatcher {
         let is_synced = self.consensus.is_ready_for_validation();
         Ok(is_synced.into())
     }
+
+    async fn get_validator_network_status(
+        &mut self,
+    ) -> RPCResult<Vec<ValidatorConnectionStatus>, (), Self::Error> {
+        let now = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as u64;
+
+        Ok(self
+            .validator
+            .network_status
+            .connection_statuses()
+            .into_iter()
+            .map(|status| ValidatorConnectionStatus {
+                validator_id: status.validator_id,
+                address: status.address,
+                peer_id: status.peer_id,
+                resolution: match status.resolution {
+                    NetworkPeerIdResolution::Unknown => PeerIdResolution::Unknown,
+                    NetworkPeerIdResolution::InProgress => PeerIdResolution::InProgress,
+                    NetworkPeerIdResolution::Resolved => PeerIdResolution::Resolved,
+                    NetworkPeerIdResolution::Failed => PeerIdResolution::Failed,
+                    NetworkPeerIdResolution::Outdated => PeerIdResolution::Outdated,
+                },
+                connected: status.connected,
+                last_resolved: status.last_resolved,
+                record_age: status
+                    .record_timestamp
+                    .map(|timestamp| now.saturating_sub(timestamp) / 1000),
+                resolution_failures: status.resolution_failures,
+                rtt: status.rtt.map(|rtt| rtt.as_millis() as u64),
+            })
+            .collect::<Vec<_>>()
+            .into())
+    }
 }
@@ -1,10 +1,10 @@
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 
 use async_trait::async_trait;
 use nimiq_database::traits::WriteTransaction;
 use nimiq_keys::{Address, Ed25519PublicKey, Ed25519Signature, KeyPair, PrivateKey};
 use nimiq_rpc_interface::{
-    types::{RPCResult, ReturnAccount, ReturnSignature},
+    types::{RPCData, RPCResult, ReturnAccount, ReturnSignature},
     wallet::WalletInterface,
 };
 use nimiq_serde::Deserialize;
@@ -29,9 +29,12 @@ pub struct WalletDispatcher {
 
 impl WalletDispatcher {
     pub fn new(wallet_store: Arc<WalletStore>) -> Self {
+        let unlocked_wallets = Arc::new(RwLock::new(UnlockedWallets::default()));
+        UnlockedWallets::spawn_sweeper(&unlocked_wallets);
+
         Self {
             wallet_store,
-            unlocked_wallets: Arc::new(RwLock::new(UnlockedWallets::default())),
+            unlocked_wallets,
         }
     }
 }
@@ -99,12 +102,11 @@ impl WalletInterface for WalletDispatcher {
         .into())
     }
 
-    // # TODO The duration parameter is ignored.
     async fn unlock_account(
         &mut self,
         address: Address,
         passphrase: Option<String>,
-        _duration: Option<u64>,
+        duration: Option<u64>,
     ) -> RPCResult<bool, (), Self::Error> {
         let passphrase = passphrase.unwrap_or_default();
         let account = self
@@ -116,16 +118,24 @@ impl WalletInterface for WalletDispatcher {
             .unlock(passphrase.as_bytes())
             .map_err(|_locked| Error::WrongPassphrase)?;
 
-        self.unlocked_wallets.write().insert(unlocked_account);
+        self.unlocked_wallets
+            .write()
+            .insert(unlocked_account, duration.map(Duration::from_secs));
 
         Ok(true.into())
     }
 
-    async fn is_account_unlocked(&mut self, address: Address) -> RPCResult<bool, (), Self::Error> {
+    async fn is_account_unlocked(
+        &mut self,
+        address: Address,
+    ) -> RPCResult<bool, Option<u64>, Self::Error> {
         let unlocked_wallets = self.unlocked_wallets.read();
-        let is_unlocked = unlocked_wallets.get(&address).is_some();
+        let time_remaining = unlocked_wallets.time_remaining(&address);
 
-        Ok(is_unlocked.into())
+        Ok(RPCData::new(
+            time_remaining.is_some(),
+            time_remaining.flatten().map(|duration| duration.as_secs()),
+        ))
     }
 
     async fn remove_account(&mut self, address: Address) -> RPCResult<bool, (), Self::Error> {
@@ -57,6 +57,9 @@ pub enum Error {
     #[error("Validator with address {0} is retired thus cannot be reactivated")]
     ValidatorRetired(Address),
 
+    #[error("No connected peer with ID: {0}")]
+    PeerNotFound(String),
+
     #[error("No staker with address: {0}")]
     StakerNotFound(Address),
 
@@ -87,6 +90,12 @@ pub enum Error {
     #[error("No consensus")]
     NoConsensus,
 
+    #[error("{0}")]
+    Blockchain(#[from] nimiq_blockchain_interface::BlockchainError),
+
+    #[error("{0}")]
+    StateProof(#[from] nimiq_blockchain_interface::StateProofError),
+
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
 }
@@ -1,28 +1,210 @@
-use std::collections::HashMap;
+use std::{
+    collections::HashMap,
+    sync::{Arc, Weak},
+    time::Duration,
+};
 
+use futures::StreamExt;
 use nimiq_keys::Address;
-use nimiq_utils::otp::Unlocked;
+use nimiq_time::{interval, Instant};
+use nimiq_utils::{otp::Unlocked, spawn};
 use nimiq_wallet::WalletAccount;
+use parking_lot::RwLock;
+
+/// The interval in which expired wallets are locked again.
+const SWEEP_INTERVAL: Duration = Duration::from_secs(1);
+
+/// An unlocked wallet account together with the point in time at which it expires.
+pub struct UnlockedWallet {
+    pub wallet: Unlocked<WalletAccount>,
+    /// The instant at which the wallet gets locked again.
+    /// `None` if the wallet stays unlocked until it is explicitly locked.
+    pub expires_at: Option<Instant>,
+}
+
+impl UnlockedWallet {
+    /// Returns whether the wallet has expired at the given instant.
+    pub fn is_expired(&self, now: Instant) -> bool {
+        self.expires_at.is_some_and(|expires_at| expires_at <= now)
+    }
+
+    /// Returns the time remaining until the wallet expires, if it has an expiry.
+    pub fn time_remaining(&self, now: Instant) -> Option<Duration> {
+        self.expires_at
+            .map(|expires_at| expires_at.saturating_duration_since(now))
+    }
+}
 
 #[derive(Default)]
 pub struct UnlockedWallets {
-    pub unlocked_wallets: HashMap<Address, Unlocked<WalletAccount>>,
+    pub unlocked_wallets: HashMap<Address, UnlockedWallet>,
 }
 
 impl UnlockedWallets {
-    pub fn insert(&mut self, wallet: Unlocked<WalletAccount>) {
-        log::info!("Unlocking {:?}", &wallet.address);
-        self.unlocked_wallets.insert(wallet.address.clone(), wallet);
+    /// Inserts an unlocked wallet. If a `duration` is given, the wallet is locked again once it
+    /// elapsed, otherwise it stays unlocked until it is explicitly locked.
+    pub fn insert(&mut self, wallet: Unlocked<WalletAccount>, duration: Option<Duration>) {
+        log::info!("Unlocking {:?} (duration: {:?})", &wallet.address, duration);
+        let expires_at = duration.and_then(|duration| Instant::now().checked_add(duration));
+        self.unlocked_wallets.insert(
+            wallet.address.clone(),
+            UnlockedWallet { wallet, expires_at },
+        );
     }
 
     pub fn get(&self, address: &Address) -> Option<&WalletAccount> {
         log::info!("Accessing {:?}", address);
+        self.get_unexpired(address)
+            .map(|unlocked| unlocked.wallet.unlocked_data())
+    }
+
+    /// Returns `None` if the wallet is locked, or `Some` with the time remaining until the wallet
+    /// is locked again. The inner value is `None` if the wallet does not expire.
+    pub fn time_remaining(&self, address: &Address) -> Option<Option<Duration>> {
+        self.get_unexpired(address)
+            .map(|unlocked| unlocked.time_remaining(Instant::now()))
+    }
+
+    pub fn remove(&mut self, address: &Address) -> Option<Unlocked<WalletAccount>> {
+        self.unlocked_wallets
+            .remove(address)
+            .map(|unlocked| unlocked.wallet)
+    }
+
+    /// Locks all wallets whose unlock duration has elapsed. Returns the number of locked wallets.
+    pub fn remove_expired(&mut self) -> usize {
+        let now = Instant::now();
+        let num_wallets = self.unlocked_wallets.len();
+        self.unlocked_wallets.retain(|address, unlocked| {
+            let expired = unlocked.is_expired(now);
+            if expired {
+                log::info!("Locking expired {:?}", address);
+            }
+            !expired
+        });
+        num_wallets - self.unlocked_wallets.len()
+    }
+
+    /// Spawns a background task that periodically locks expired wallets.
+    /// The task stops once the given wallets are dropped.
+    pub fn spawn_sweeper(unlocked_wallets: &Arc<RwLock<Self>>) {
+        let unlocked_wallets = Arc::downgrade(unlocked_wallets);
+        spawn(Self::sweep(unlocked_wallets));
+    }
+
+    async fn sweep(unlocked_wallets: Weak<RwLock<Self>>) {
+        let mut interval = interval(SWEEP_INTERVAL);
+        while interval.next().await.is_some() {
+            let Some(unlocked_wallets) = unlocked_wallets.upgrade() else {
+                break;
+            };
+            unlocked_wallets.write().remove_expired();
+        }
+    }
+
+    fn get_unexpired(&self, address: &Address) -> Option<&UnlockedWallet> {
         self.unlocked_wallets
             .get(address)
-            .map(Unlocked::unlocked_data)
+            .filter(|unlocked| !unlocked.is_expired(Instant::now()))
     }
+}
 
-    pub fn remove(&mut self, address: &Address) -> Option<Unlocked<WalletAccount>> {
-        self.unlocked_wallets.remove(address)
+#[cfg(test)]
+mod tests {
+    use std::{sync::Arc, time::Duration};
+
+    use nimiq_test_log::test;
+    use nimiq_time::{sleep, Instant};
+    use nimiq_utils::otp::Unlocked;
+    use nimiq_wallet::WalletAccount;
+    use parking_lot::RwLock;
+
+    use super::{UnlockedWallet, UnlockedWallets, SWEEP_INTERVAL};
+
+    fn unlocked_wallet() -> Unlocked<WalletAccount> {
+        Unlocked::with_defaults(WalletAccount::generate(), b"").unwrap()
+    }
+
+    #[test]
+    fn it_computes_expiry_and_time_remaining() {
+        let now = Instant::now();
+        let unlocked = UnlockedWallet {
+            wallet: unlocked_wallet(),
+            expires_at: Some(now + Duration::from_secs(10)),
+        };
+
+        assert!(!unlocked.is_expired(now));
+        assert_eq!(unlocked.time_remaining(now), Some(Duration::from_secs(10)));
+
+        let later = now + Duration::from_secs(4);
+        assert!(!unlocked.is_expired(later));
+        assert_eq!(unlocked.time_remaining(later), Some(Duration::from_secs(6)));
+
+        let expiry = now + Duration::from_secs(10);
+        assert!(unlocked.is_expired(expiry));
+        assert_eq!(unlocked.time_remaining(expiry), Some(Duration::ZERO));
+
+        let unlimited = UnlockedWallet {
+            wallet: unlocked_wallet(),
+            expires_at: None,
+        };
+        assert!(!unlimited.is_expired(expiry));
+        assert_eq!(unlimited.time_remaining(expiry), None);
+    }
+
+    #[test]
+    fn expired_wallets_are_reported_as_locked() {
+        let mut wallets = UnlockedWallets::default();
+
+        let expired = unlocked_wallet();
+        let expired_address = expired.address.clone();
+        wallets.insert(expired, Some(Duration::ZERO));
+
+        let unlimited = unlocked_wallet();
+        let unlimited_address = unlimited.address.clone();
+        wallets.insert(unlimited, None);
+
+        let limited = unlocked_wallet();
+        let limited_address = limited.address.clone();
+        wallets.insert(limited, Some(Duration::from_secs(3600)));
+
+        assert!(wallets.get(&expired_address).is_none());
+        assert_eq!(wallets.time_remaining(&expired_address), None);
+
+        assert!(wallets.get(&unlimited_address).is_some());
+        assert_eq!(wallets.time_remaining(&unlimited_address), Some(None));
+
+        assert!(wallets.get(&limited_address).is_some());
+        let remaining = wallets.time_remaining(&limited_address).flatten().unwrap();
+        assert!(remaining > Duration::from_secs(3590) && remaining <= Duration::from_secs(3600));
+
+        assert_eq!(wallets.remove_expired(), 1);
+        assert!(!wallets.unlocked_wallets.contains_key(&expired_address));
+        assert_eq!(wallets.unlocked_wallets.len(), 2);
+        assert_eq!(wallets.remove_expired(), 0);
+    }
+
+    #[test(tokio::test)]
+    async fn sweeper_removes_expired_wallets() {
+        tokio::time::pause();
+
+        let wallets = Arc::new(RwLock::new(UnlockedWallets::default()));
+
+        let expired = unlocked_wallet();
+        let expired_address = expired.address.clone();
+        let unlimited = unlocked_wallet();
+        let unlimited_address = unlimited.address.clone();
+        {
+            let mut wallets = wallets.write();
+            wallets.insert(expired, Some(Duration::ZERO));
+            wallets.insert(unlimited, None);
+        }
+
+        UnlockedWallets::spawn_sweeper(&wallets);
+        sleep(SWEEP_INTERVAL * 2).await;
+
+        let wallets = wallets.read();
+        assert!(!wallets.unlocked_wallets.contains_key(&expired_address));
+        assert!(wallets.unlocked_wallets.contains_key(&unlimited_address));
     }
 }
@@ -151,6 +151,7 @@ async fn main_inner() -> Result<(), Error> {
         sync_mode: None,
         network: None,
         prove: false,
+        command: None,
     };
 
     // Parse config file - this will obey the `--config` command line option.
@@ -247,6 +248,9 @@ async fn main_inner() -> Result<(), Error> {
             client.mempool(),
             client.consensus_proxy(),
             client.network(),
+            client
+                .validator_proxy()
+                .map(|validator| validator.network_status),
             &[],
         )
     }
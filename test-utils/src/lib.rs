@@ -4,6 +4,7 @@ pub mod blockchain;
 pub mod blockchain_with_rng;
 pub mod mock_node;
 pub mod node;
+pub mod replay;
 pub mod test_custom_block;
 pub mod test_network;
 pub mod test_rng;
@@ -0,0 +1,210 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    io,
+    path::Path,
+    sync::Arc,
+    time::Duration,
+};
+
+use futures::{stream::BoxStream, StreamExt};
+use nimiq_network_interface::request::{peek_type, RequestType};
+use nimiq_network_libp2p::{CaptureEvent, CaptureReader, CaptureRecord, PeerId};
+use nimiq_network_mock::{MockHub, MockNetwork, MockPeerId, MockRequestId};
+use nimiq_time::{sleep, sleep_until, Instant};
+use nimiq_utils::spawn;
+use parking_lot::Mutex;
+
+/// A captured response to one of the requests of the captured node.
+struct CapturedResponse {
+    /// The time the captured node waited for the response.
+    latency: Duration,
+    /// The serialized `Result<Response, InboundRequestError>`, `None` if the request failed.
+    data: Option<Vec<u8>>,
+}
+
+type ResponseQueues = HashMap<(PeerId, RequestType), VecDeque<CapturedResponse>>;
+
+/// Replays network traffic captured by a libp2p node against a node on a mock network.
+///
+/// Every remote peer of the capture is played by its own [`MockNetwork`] on the given hub:
+///  - Gossipsub messages and requests received by the captured node are sent by the respective
+///    peer in the order they were captured.
+///  - Requests of the node under test are answered with the responses the captured node received
+///    from the same peer for the same request type, in the order they were captured. Requests
+///    that failed in the capture, or for which no more responses are left, time out.
+///
+/// Messages and responses sent by the captured node itself are not replayed.
+pub struct TrafficReplay {
+    records: Vec<CaptureRecord>,
+    peers: HashMap<PeerId, Arc<MockNetwork>>,
+    responses: Arc<Mutex<ResponseQueues>>,
+    receivers: Vec<(
+        PeerId,
+        BoxStream<'static, (Vec<u8>, MockRequestId, MockPeerId)>,
+    )>,
+}
+
+impl TrafficReplay {
+    /// Reads the capture file at `path` and creates the mock networks of its peers on `hub`.
+    pub fn open<P: AsRef<Path>>(path: P, hub: &mut MockHub) -> io::Result<Self> {
+        let records = CaptureReader::open(path)?.collect::<io::Result<_>>()?;
+        Ok(Self::new(records, hub))
+    }
+
+    /// Creates the mock networks of the peers of the captured `records` on `hub`.
+    pub fn new(records: Vec<CaptureRecord>, hub: &mut MockHub) -> Self {
+        let mut peers = HashMap::new();
+        let mut requests = HashMap::new();
+        let mut responses = ResponseQueues::new();
+
+        for record in &records {
+            match &record.event {
+                // Published messages carry the peer ID of the captured node.
+                CaptureEvent::GossipPublished { .. } => continue,
+                CaptureEvent::RequestSent { id, data } => {
+                    if let Ok(request_type) = peek_type(data) {
+                        requests.insert(*id, (request_type, record.timestamp));
+                    }
+                }
+                CaptureEvent::ResponseReceived { id, data } => {
+                    if let Some((request_type, sent_at)) = requests.remove(id) {
+                        responses
+                            .entry((record.peer_id, request_type))
+                            .or_default()
+                            .push_back(CapturedResponse {
+                                latency: Duration::from_millis(
+                                    record.timestamp.saturating_sub(sent_at),
+                                ),
+                                data: data.clone(),
+                            });
+                    }
+                }
+                _ => {}
+            }
+
+            peers
+                .entry(record.peer_id)
+                .or_insert_with(|| Arc::new(hub.new_network()));
+        }
+
+        // Register the request handlers right away, such that requests of the node under test
+        // are queued until the replay starts.
+        let receivers = responses
+            .keys()
+            .map(|&(peer_id, request_type)| {
+                (peer_id, peers[&peer_id].receive_requests_raw(request_type))
+            })
+            .collect();
+
+        TrafficReplay {
+            records,
+            peers,
+            responses: Arc::new(Mutex::new(responses)),
+            receivers,
+        }
+    }
+
+    /// Returns the mock network playing the given captured peer.
+    pub fn network(&self, peer_id: &PeerId) -> Option<&Arc<MockNetwork>> {
+        self.peers.get(peer_id)
+    }
+
+    /// Connects all captured peers to `network`.
+    pub fn connect(&self, network: &MockNetwork) {
+        for peer in self.peers.values() {
+            peer.dial_mock(network);
+        }
+    }
+
+    /// Replays the capture against `target`, which must be connected to the captured peers.
+    /// If `preserve_timing` is set, the original delays between messages and the latencies of
+    /// responses are kept. Otherwise, everything is replayed as fast as possible.
+    ///
+    /// Returns once all gossipsub messages and requests have been sent. Requests of the node
+    /// under test keep being answered in the background.
+    pub async fn run(mut self, target: &MockNetwork, preserve_timing: bool) {
+        for (peer_id, receiver) in self.receivers.drain(..) {
+            spawn(Self::respond(
+                Arc::clone(&self.peers[&peer_id]),
+                peer_id,
+                receiver,
+                Arc::clone(&self.responses),
+                preserve_timing,
+            ));
+        }
+
+        let start = self.records.first().map(|record| record.timestamp);
+        let started_at = Instant::now();
+        for record in self.records.drain(..) {
+            if preserve_timing {
+                let offset = record.timestamp.saturating_sub(start.unwrap());
+                sleep_until(started_at + Duration::from_millis(offset)).await;
+            }
+
+            let Some(network) = self.peers.get(&record.peer_id) else {
+                continue;
+            };
+            match record.event {
+                CaptureEvent::GossipReceived { topic, data } => {
+                    if let Err(error) = network.publish_raw(topic.clone(), data).await {
+                        log::warn!(%topic, %error, "Failed to replay gossipsub message");
+                    }
+                }
+                CaptureEvent::RequestReceived { data, .. } => {
+                    let network = Arc::clone(network);
+                    let target = target.peer_id();
+                    spawn(async move {
+                        if let Err(error) = network.request_raw(data, target).await {
+                            log::debug!(%error, "Replayed request failed");
+                        }
+                    });
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Answers the requests of one type sent to one captured peer.
+    async fn respond(
+        network: Arc<MockNetwork>,
+        peer_id: PeerId,
+        mut receiver: BoxStream<'static, (Vec<u8>, MockRequestId, MockPeerId)>,
+        responses: Arc<Mutex<ResponseQueues>>,
+        preserve_timing: bool,
+    ) {
+        while let Some((data, request_id, _)) = receiver.next().await {
+            let Ok(request_type) = peek_type(&data) else {
+                continue;
+            };
+            if !request_type.requires_response() {
+                continue;
+            }
+
+            let Some(response) = responses
+                .lock()
+                .get_mut(&(peer_id, request_type))
+                .and_then(VecDeque::pop_front)
+            else {
+                log::debug!(%peer_id, %request_type, "No captured response left");
+                continue;
+            };
+
+            // Over libp2p, responses are wrapped in a `Result`, while the mock network only
+            // transmits successful responses. A serialized `Ok` starts with a zero byte.
+            let Some(data) = response.data.filter(|data| data.first() == Some(&0)) else {
+                continue;
+            };
+
+            let network = Arc::clone(&network);
+            spawn(async move {
+                if preserve_timing {
+                    sleep(response.latency).await;
+                }
+                network
+                    .respond_raw(request_id, data[1..].to_vec())
+                    .await
+                    .ok();
+            });
+        }
+    }
+}
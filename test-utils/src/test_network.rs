@@ -81,6 +81,7 @@ impl TestNetwork for Network {
             false,
             true,
             NonZeroU8::new(1).unwrap(),
+            None,
         );
         let network = Arc::new(Network::new(config, ()).await);
         network.listen_on(vec![peer_address]).await;
@@ -569,6 +569,7 @@ impl<R: Rng + CryptoRng> TransactionsGenerator<R> {
                     time_step: 1,
                     step_amount: balance,
                     total_amount: balance,
+                    schedule: None,
                 },
             },
             IncomingType::CreateHTLC => IncomingAccountData::Htlc {
@@ -3,11 +3,15 @@ use nimiq_hash::Blake2bHash;
 use nimiq_keys::{Address, Ed25519PublicKey, KeyPair};
 use nimiq_primitives::{coin::Coin, networks::NetworkId, policy::Policy};
 use nimiq_transaction::{
-    account::htlc_contract::{AnyHash, PreImage},
+    account::{
+        htlc_contract::{AnyHash, PreImage},
+        vesting_contract::VestingTranche,
+    },
     SignatureProof, Transaction,
 };
 use thiserror::Error;
 
+use crate::recipient::vesting_contract::VestingRecipientBuilderError;
 pub use crate::{proof::TransactionProofBuilder, recipient::Recipient, sender::Sender};
 
 pub mod proof;
@@ -68,6 +72,9 @@ pub enum TransactionBuilderError {
     /// [`signaling transaction`]: struct.TransactionBuilder.html#method.with_value
     #[error("The value must be zero for signaling transactions and cannot be zero for others.")]
     InvalidValue,
+    /// The vesting contract to be created is invalid, e.g. because its release schedule is invalid.
+    #[error("Invalid vesting contract: {0}")]
+    InvalidVestingRecipient(#[from] VestingRecipientBuilderError),
 }
 
 /// A helper to build arbitrary transactions.
@@ -562,6 +569,145 @@ impl TransactionBuilder {
         }
     }
 
+    /// Creates a transaction that creates a new vesting contract with a cliff.
+    ///
+    /// # Arguments
+    ///
+    ///  - `key_pair`:              The key pair used to sign the outgoing transaction. The vesting
+    ///                             contract value is sent from the basic account belonging to this
+    ///                             key pair.
+    ///  - `owner`:                 The address of the owner of the vesting contract.
+    ///  - `start_time`,
+    ///    `cliff_duration`,
+    ///    `cliff_amount`:          Nothing is released before `start_time + cliff_duration`, at
+    ///                             which point `cliff_amount` is released.
+    ///  - `time_step`,
+    ///    `num_steps`:             The remaining value is released in `num_steps` payouts every
+    ///                             `time_step` after the cliff.
+    ///  - `value`:                 The value for the vesting contract. This is sent from the
+    ///                             account belonging to `key_pair`.
+    ///  - `fee`:                   Transaction fee.
+    ///  - `validity_start_height`: Block height from which this transaction is valid.
+    ///  - `network_id`:            ID of network for which the transaction is meant.
+    ///
+    /// # Returns
+    ///
+    /// The finalized transaction.
+    ///
+    pub fn new_create_vesting_with_cliff(
+        key_pair: &KeyPair,
+        owner: Address,
+        start_time: u64,
+        cliff_duration: u64,
+        cliff_amount: Coin,
+        time_step: u64,
+        num_steps: u32,
+        value: Coin,
+        fee: Coin,
+        validity_start_height: u32,
+        network_id: NetworkId,
+    ) -> Result<Transaction, TransactionBuilderError> {
+        let mut recipient = Recipient::new_vesting_builder(owner);
+        recipient.with_cliff(
+            value,
+            start_time,
+            cliff_duration,
+            cliff_amount,
+            time_step,
+            num_steps,
+        )?;
+
+        Self::new_create_vesting_from_recipient(
+            key_pair,
+            recipient.generate()?,
+            value,
+            fee,
+            validity_start_height,
+            network_id,
+        )
+    }
+
+    /// Creates a transaction that creates a new vesting contract which releases its funds in
+    /// several tranches.
+    ///
+    /// # Arguments
+    ///
+    ///  - `key_pair`:              The key pair used to sign the outgoing transaction. The vesting
+    ///                             contract value is sent from the basic account belonging to this
+    ///                             key pair.
+    ///  - `owner`:                 The address of the owner of the vesting contract.
+    ///  - `tranches`:              The tranches of the release schedule. Each tranche releases
+    ///                             its funds independently of the other tranches.
+    ///  - `value`:                 The value for the vesting contract. This is sent from the
+    ///                             account belonging to `key_pair`.
+    ///  - `fee`:                   Transaction fee.
+    ///  - `validity_start_height`: Block height from which this transaction is valid.
+    ///  - `network_id`:            ID of network for which the transaction is meant.
+    ///
+    /// # Returns
+    ///
+    /// The finalized transaction. Fails with a
+    /// [`VestingRecipientBuilderError::ScheduleTotalMismatch`] if the tranches don't add up to
+    /// `value`.
+    ///
+    pub fn new_create_vesting_with_schedule(
+        key_pair: &KeyPair,
+        owner: Address,
+        tranches: Vec<VestingTranche>,
+        value: Coin,
+        fee: Coin,
+        validity_start_height: u32,
+        network_id: NetworkId,
+    ) -> Result<Transaction, TransactionBuilderError> {
+        let total_amount = tranches.iter().try_fold(Coin::ZERO, |sum, tranche| {
+            sum.checked_add(tranche.total_amount)
+        });
+        if total_amount != Some(value) {
+            return Err(VestingRecipientBuilderError::ScheduleTotalMismatch.into());
+        }
+
+        let mut recipient = Recipient::new_vesting_builder(owner);
+        for tranche in tranches {
+            recipient.with_tranche(tranche);
+        }
+
+        Self::new_create_vesting_from_recipient(
+            key_pair,
+            recipient.generate()?,
+            value,
+            fee,
+            validity_start_height,
+            network_id,
+        )
+    }
+
+    fn new_create_vesting_from_recipient(
+        key_pair: &KeyPair,
+        recipient: Recipient,
+        value: Coin,
+        fee: Coin,
+        validity_start_height: u32,
+        network_id: NetworkId,
+    ) -> Result<Transaction, TransactionBuilderError> {
+        let mut builder = Self::new();
+        builder
+            .with_sender(Sender::new_basic(Address::from(key_pair)))
+            .with_recipient(recipient)
+            .with_value(value)
+            .with_fee(fee)
+            .with_validity_start_height(validity_start_height)
+            .with_network_id(network_id);
+
+        let proof_builder = builder.generate()?;
+        match proof_builder {
+            TransactionProofBuilder::Basic(mut builder) => {
+                builder.sign_with_key_pair(key_pair);
+                Ok(builder.generate().unwrap())
+            }
+            _ => unreachable!(),
+        }
+    }
+
     /// Creates a transaction that redeems funds from a vesting contract.
     ///
     /// # Arguments
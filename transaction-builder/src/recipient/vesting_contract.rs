@@ -1,8 +1,10 @@
-use std::ops::Div;
+use std::ops::{Div, Rem};
 
 use nimiq_keys::Address;
 use nimiq_primitives::coin::Coin;
-use nimiq_transaction::account::vesting_contract::CreationTransactionData as VestingCreationData;
+use nimiq_transaction::account::vesting_contract::{
+    CreationTransactionData as VestingCreationData, VestingSchedule, VestingTranche,
+};
 use thiserror::Error;
 
 use crate::recipient::Recipient;
@@ -46,6 +48,32 @@ pub enum VestingRecipientBuilderError {
     /// [`with_total_amount`]: struct.VestingRecipientBuilder.html#method.with_total_amount
     #[error("The vesting total amount is missing.")]
     NoTotalAmount,
+    /// The tranches added to the [`VestingRecipientBuilder`] do not form a valid schedule.
+    /// Each tranche must release a non-zero amount in non-zero steps and there can be at most
+    /// [`MAX_VESTING_TRANCHES`] tranches.
+    ///
+    /// [`VestingRecipientBuilder`]: struct.VestingRecipientBuilder.html
+    /// [`MAX_VESTING_TRANCHES`]: nimiq_transaction::account::vesting_contract::MAX_VESTING_TRANCHES
+    #[error("The vesting schedule is invalid.")]
+    InvalidSchedule,
+    /// The release schedule passed to [`with_cliff`] has no steps after the cliff.
+    ///
+    /// [`with_cliff`]: struct.VestingRecipientBuilder.html#method.with_cliff
+    #[error("The vesting schedule needs at least one step after the cliff.")]
+    NoSteps,
+    /// The cliff amount passed to [`with_cliff`] exceeds the total amount.
+    ///
+    /// [`with_cliff`]: struct.VestingRecipientBuilder.html#method.with_cliff
+    #[error("The vesting cliff amount exceeds the total amount.")]
+    CliffExceedsTotal,
+    /// The cliff passed to [`with_cliff`] releases a non-zero amount but has a duration of zero.
+    ///
+    /// [`with_cliff`]: struct.VestingRecipientBuilder.html#method.with_cliff
+    #[error("The vesting cliff duration is zero.")]
+    NoCliffDuration,
+    /// The tranches of the release schedule don't add up to the value of the contract.
+    #[error("The vesting schedule total amount doesn't match the contract value.")]
+    ScheduleTotalMismatch,
 }
 
 /// A `VestingRecipientBuilder` can be used to create new vesting contracts.
@@ -58,6 +86,13 @@ pub enum VestingRecipientBuilderError {
 ///
 /// That means that the first funds can be withdrawn at blockchain height
 /// `start_block + time_step`.
+///
+/// Alternatively, the funds can be released in several tranches, e.g. a cliff followed by a
+/// linear release (see [`with_cliff`] and [`with_tranche`]). In that case, the fields above are
+/// ignored.
+///
+/// [`with_cliff`]: struct.VestingRecipientBuilder.html#method.with_cliff
+/// [`with_tranche`]: struct.VestingRecipientBuilder.html#method.with_tranche
 #[derive(Default)]
 pub struct VestingRecipientBuilder {
     owner: Option<Address>,
@@ -65,6 +100,7 @@ pub struct VestingRecipientBuilder {
     time_step: Option<u64>,
     step_amount: Option<Coin>,
     total_amount: Option<Coin>,
+    tranches: Vec<VestingTranche>,
 }
 
 impl VestingRecipientBuilder {
@@ -137,6 +173,86 @@ impl VestingRecipientBuilder {
         self
     }
 
+    /// Adds a tranche to the release schedule.
+    /// The contract releases the funds of all its tranches independently of each other.
+    pub fn with_tranche(&mut self, tranche: VestingTranche) -> &mut Self {
+        self.tranches.push(tranche);
+        self
+    }
+
+    /// This convenience function allows to quickly create a release schedule with a cliff:
+    /// Nothing is released before `start_time + cliff_duration`, at which point `cliff_amount`
+    /// is released. The remaining funds are then released in `num_steps` payouts every `time_step`.
+    /// If the remaining funds can't be split evenly, the last payout includes the remainder.
+    ///
+    /// Returns a [`VestingRecipientBuilderError::NoSteps`] if `num_steps` is zero, a
+    /// [`VestingRecipientBuilderError::CliffExceedsTotal`] if `cliff_amount` exceeds
+    /// `total_amount` and a [`VestingRecipientBuilderError::NoCliffDuration`] if there is a
+    /// cliff amount but `cliff_duration` is zero.
+    pub fn with_cliff(
+        &mut self,
+        total_amount: Coin,
+        start_time: u64,
+        cliff_duration: u64,
+        cliff_amount: Coin,
+        time_step: u64,
+        num_steps: u32,
+    ) -> Result<&mut Self, VestingRecipientBuilderError> {
+        if num_steps == 0 {
+            return Err(VestingRecipientBuilderError::NoSteps);
+        }
+        if cliff_amount > total_amount {
+            return Err(VestingRecipientBuilderError::CliffExceedsTotal);
+        }
+        if !cliff_amount.is_zero() && cliff_duration == 0 {
+            return Err(VestingRecipientBuilderError::NoCliffDuration);
+        }
+
+        if !cliff_amount.is_zero() {
+            self.with_tranche(VestingTranche {
+                start_time,
+                time_step: cliff_duration,
+                step_amount: cliff_amount,
+                total_amount: cliff_amount,
+            });
+        }
+
+        let linear_start_time = start_time.saturating_add(cliff_duration);
+        let linear_amount = total_amount - cliff_amount;
+        let step_amount = linear_amount.div(u64::from(num_steps));
+        let remainder = linear_amount.rem(u64::from(num_steps));
+
+        // If the funds can't be split evenly, all but the last step are released by one tranche
+        // and the last step, which includes the remainder, by another one.
+        if remainder.is_zero() {
+            if !linear_amount.is_zero() {
+                self.with_tranche(VestingTranche {
+                    start_time: linear_start_time,
+                    time_step,
+                    step_amount,
+                    total_amount: linear_amount,
+                });
+            }
+        } else {
+            let last_step_amount = step_amount + remainder;
+            if linear_amount > last_step_amount {
+                self.with_tranche(VestingTranche {
+                    start_time: linear_start_time,
+                    time_step,
+                    step_amount,
+                    total_amount: linear_amount - last_step_amount,
+                });
+            }
+            self.with_tranche(VestingTranche {
+                start_time: linear_start_time,
+                time_step: time_step.saturating_mul(u64::from(num_steps)),
+                step_amount: last_step_amount,
+                total_amount: last_step_amount,
+            });
+        }
+        Ok(self)
+    }
+
     /// This method tries putting together the contract creation,
     /// returning a [`Recipient`] in case of success.
     /// In case of a failure, it returns a [`VestingRecipientBuilderError`].
@@ -163,9 +279,19 @@ impl VestingRecipientBuilder {
     /// [`Recipient`]: ../enum.Recipient.html
     /// [`VestingRecipientBuilderError`]: enum.VestingRecipientBuilderError.html
     pub fn generate(self) -> Result<Recipient, VestingRecipientBuilderError> {
+        let owner = self.owner.ok_or(VestingRecipientBuilderError::NoOwner)?;
+
+        if !self.tranches.is_empty() {
+            let schedule = VestingSchedule::new(self.tranches)
+                .map_err(|_| VestingRecipientBuilderError::InvalidSchedule)?;
+            return Ok(Recipient::VestingCreation {
+                data: VestingCreationData::with_schedule(owner, schedule),
+            });
+        }
+
         Ok(Recipient::VestingCreation {
             data: VestingCreationData {
-                owner: self.owner.ok_or(VestingRecipientBuilderError::NoOwner)?,
+                owner,
                 start_time: self
                     .start_time
                     .ok_or(VestingRecipientBuilderError::NoStartBlock)?,
@@ -178,6 +304,7 @@ impl VestingRecipientBuilder {
                 total_amount: self
                     .total_amount
                     .ok_or(VestingRecipientBuilderError::NoTotalAmount)?,
+                schedule: None,
             },
         })
     }
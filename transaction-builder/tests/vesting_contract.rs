@@ -4,8 +4,14 @@ use nimiq_keys::{Address, KeyPair, PrivateKey};
 use nimiq_primitives::{account::AccountType, coin::Coin, networks::NetworkId};
 use nimiq_serde::{Deserialize, Serialize};
 use nimiq_test_log::test;
-use nimiq_transaction::{SignatureProof, Transaction};
-use nimiq_transaction_builder::{Recipient, Sender, TransactionBuilder};
+use nimiq_transaction::{
+    account::vesting_contract::{CreationTransactionData, VestingSchedule, VestingTranche},
+    SignatureProof, Transaction,
+};
+use nimiq_transaction_builder::{
+    recipient::vesting_contract::VestingRecipientBuilderError, Recipient, Sender,
+    TransactionBuilder, TransactionBuilderError,
+};
 
 #[test]
 #[allow(unused_must_use)]
@@ -143,3 +149,196 @@ fn it_can_create_outgoing_transactions() {
     proof_builder.sign_with_key_pair(&key_pair);
     assert_eq!(proof_builder.generate().unwrap(), tx);
 }
+
+#[test]
+fn it_can_create_creation_transaction_with_cliff() {
+    let owner = Address::from([0u8; 20]);
+    let schedule = VestingSchedule::new(vec![
+        VestingTranche {
+            start_time: 100,
+            time_step: 1000,
+            step_amount: Coin::from_u64_unchecked(400),
+            total_amount: Coin::from_u64_unchecked(400),
+        },
+        VestingTranche {
+            start_time: 1100,
+            time_step: 100,
+            step_amount: Coin::from_u64_unchecked(200),
+            total_amount: Coin::from_u64_unchecked(600),
+        },
+    ])
+    .unwrap();
+
+    let mut transaction = Transaction::new_contract_creation(
+        owner.clone(),
+        AccountType::Basic,
+        vec![],
+        AccountType::Vesting,
+        CreationTransactionData::with_schedule(owner.clone(), schedule).to_tx_data(),
+        1000.try_into().unwrap(),
+        0.try_into().unwrap(),
+        0,
+        NetworkId::UnitAlbatross,
+    );
+    transaction.recipient = transaction.contract_creation_address();
+
+    let mut recipient = Recipient::new_vesting_builder(owner.clone());
+    recipient
+        .with_cliff(
+            Coin::from_u64_unchecked(1000),
+            100,
+            1000,
+            Coin::from_u64_unchecked(400),
+            100,
+            3,
+        )
+        .unwrap();
+
+    let mut builder = TransactionBuilder::new();
+    builder
+        .with_sender(Sender::new_basic(owner.clone()))
+        .with_recipient(recipient.generate().unwrap())
+        .with_value(1000.try_into().unwrap())
+        .with_validity_start_height(0)
+        .with_network_id(NetworkId::UnitAlbatross);
+    let proof_builder = builder
+        .generate()
+        .expect("Builder should be able to create transaction");
+    let proof_builder = proof_builder.unwrap_basic();
+    assert_eq!(proof_builder.transaction, transaction);
+
+    // Tranches that don't release anything are rejected.
+    let mut recipient = Recipient::new_vesting_builder(owner.clone());
+    recipient.with_tranche(VestingTranche::default());
+    assert!(recipient.generate().is_err());
+
+    // There must be at least one step after the cliff.
+    let mut recipient = Recipient::new_vesting_builder(owner.clone());
+    assert!(matches!(
+        recipient.with_cliff(
+            Coin::from_u64_unchecked(1000),
+            100,
+            1000,
+            Coin::from_u64_unchecked(400),
+            100,
+            0,
+        ),
+        Err(VestingRecipientBuilderError::NoSteps)
+    ));
+
+    // The cliff can't release more than the total amount.
+    let mut recipient = Recipient::new_vesting_builder(owner.clone());
+    assert!(matches!(
+        recipient.with_cliff(
+            Coin::from_u64_unchecked(1000),
+            100,
+            1000,
+            Coin::from_u64_unchecked(1001),
+            100,
+            3,
+        ),
+        Err(VestingRecipientBuilderError::CliffExceedsTotal)
+    ));
+
+    // A cliff that releases funds must have a duration.
+    let mut recipient = Recipient::new_vesting_builder(owner);
+    assert!(matches!(
+        recipient.with_cliff(
+            Coin::from_u64_unchecked(1000),
+            100,
+            0,
+            Coin::from_u64_unchecked(400),
+            100,
+            3,
+        ),
+        Err(VestingRecipientBuilderError::NoCliffDuration)
+    ));
+}
+
+#[test]
+fn it_rejects_schedules_that_do_not_match_the_value() {
+    let key_pair = KeyPair::from(
+        PrivateKey::deserialize_from_vec(
+            &hex::decode("9d5bd02379e7e45cf515c788048f5cf3c454ffabd3e83bd1d7667716c325c3c0")
+                .unwrap(),
+        )
+        .unwrap(),
+    );
+    let tranches = vec![VestingTranche {
+        start_time: 100,
+        time_step: 100,
+        step_amount: Coin::from_u64_unchecked(200),
+        total_amount: Coin::from_u64_unchecked(600),
+    }];
+
+    for value in [599, 601] {
+        assert!(matches!(
+            TransactionBuilder::new_create_vesting_with_schedule(
+                &key_pair,
+                Address::from([0u8; 20]),
+                tranches.clone(),
+                Coin::from_u64_unchecked(value),
+                Coin::ZERO,
+                0,
+                NetworkId::UnitAlbatross,
+            ),
+            Err(TransactionBuilderError::InvalidVestingRecipient(
+                VestingRecipientBuilderError::ScheduleTotalMismatch
+            ))
+        ));
+    }
+
+    assert!(TransactionBuilder::new_create_vesting_with_schedule(
+        &key_pair,
+        Address::from([0u8; 20]),
+        tranches,
+        Coin::from_u64_unchecked(600),
+        Coin::ZERO,
+        0,
+        NetworkId::UnitAlbatross,
+    )
+    .is_ok());
+}
+
+#[test]
+fn it_releases_the_remainder_with_the_last_step_after_the_cliff() {
+    let owner = Address::from([0u8; 20]);
+
+    // 700 can't be split evenly into 3 steps: 233 are released with each of the first two steps
+    // and the remaining 234 with the last step.
+    let mut recipient = Recipient::new_vesting_builder(owner);
+    recipient
+        .with_cliff(
+            Coin::from_u64_unchecked(1000),
+            100,
+            1000,
+            Coin::from_u64_unchecked(300),
+            100,
+            3,
+        )
+        .unwrap();
+
+    let schedule = match recipient.generate().unwrap() {
+        Recipient::VestingCreation { data } => data.schedule.unwrap(),
+        _ => unreachable!(),
+    };
+    assert_eq!(schedule.total_amount(), Coin::from_u64_unchecked(1000));
+    assert_eq!(schedule.end_time(), 1400);
+
+    let expected_min_caps = [
+        (1099, 1000),
+        (1100, 700),
+        (1199, 700),
+        (1200, 467),
+        (1300, 234),
+        (1399, 234),
+        (1400, 0),
+    ];
+    for (time, min_cap) in expected_min_caps {
+        assert_eq!(
+            schedule.min_cap(time),
+            Coin::from_u64_unchecked(min_cap),
+            "time {time}"
+        );
+    }
+}
@@ -1,6 +1,7 @@
 pub mod error;
 pub mod network_impl;
 pub mod single_response_requester;
+pub mod status;
 pub mod validator_record;
 
 use async_trait::async_trait;
@@ -12,7 +13,7 @@ use nimiq_network_interface::{
 };
 use nimiq_primitives::slots_allocation::Validators;
 
-pub use crate::error::NetworkError;
+pub use crate::{error::NetworkError, status::ValidatorNetworkStatus};
 
 pub type MessageStream<TMessage> = BoxStream<'static, (TMessage, u16)>;
 pub type PubsubId<TValidatorNetwork> =
@@ -21,7 +22,7 @@ pub type PubsubId<TValidatorNetwork> =
 /// Fixed upper bound network.
 /// Peers are denoted by a usize identifier which deterministically identifies them.
 #[async_trait]
-pub trait ValidatorNetwork: Send + Sync {
+pub trait ValidatorNetwork: ValidatorNetworkStatus + Send + Sync {
     type Error: std::error::Error + Send + 'static;
     type NetworkType: Network;
 
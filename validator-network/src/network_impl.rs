@@ -1,4 +1,10 @@
-use std::{collections::BTreeMap, error::Error, fmt::Debug, future, sync::Arc};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    error::Error,
+    fmt::Debug,
+    future,
+    sync::Arc,
+};
 
 use async_trait::async_trait;
 use futures::{future::BoxFuture, stream::BoxStream, FutureExt, StreamExt, TryFutureExt};
@@ -15,7 +21,10 @@ use parking_lot::RwLock;
 use time::OffsetDateTime;
 
 use super::{MessageStream, NetworkError, PubsubId, ValidatorNetwork};
-use crate::validator_record::ValidatorRecord;
+use crate::{
+    status::{PeerIdResolution, ValidatorConnectionStatus, ValidatorNetworkStatus},
+    validator_record::ValidatorRecord,
+};
 
 /// Validator `PeerId` cache state
 #[derive(Clone, Copy)]
@@ -49,6 +58,39 @@ impl<TPeerId: Clone> CacheState<TPeerId> {
             CacheState::Empty(peer_id) => Some(peer_id.clone()),
         }
     }
+    fn resolution(&self) -> PeerIdResolution {
+        match self {
+            CacheState::Resolved(..) => PeerIdResolution::Resolved,
+            CacheState::Error(..) => PeerIdResolution::Failed,
+            CacheState::InProgress(..) => PeerIdResolution::InProgress,
+            CacheState::Empty(..) => PeerIdResolution::Outdated,
+        }
+    }
+}
+
+/// Statistics about the peer ID lookups of a validator.
+#[derive(Clone, Copy, Default)]
+struct ResolutionStats {
+    /// Unix time in milliseconds of the last successful lookup.
+    last_resolved: Option<u64>,
+    /// Timestamp of the validator record found by the last successful lookup.
+    record_timestamp: Option<u64>,
+    /// Number of failed lookups.
+    failures: u64,
+}
+
+/// The result of a successful peer ID lookup.
+struct ResolvedPeer<TPeerId> {
+    peer_id: TPeerId,
+    /// Timestamp of the validator record, unknown if the peer ID was obtained from the fallback.
+    record_timestamp: Option<u64>,
+    /// Peers relaying the validator's traffic if it runs in private mode.
+    relays: Vec<TPeerId>,
+}
+
+/// Returns the current unix time in milliseconds.
+fn unix_time_millis() -> u64 {
+    (OffsetDateTime::now_utc().unix_timestamp_nanos() / 1_000_000) as u64
 }
 
 pub type DhtFallback<N> =
@@ -68,6 +110,10 @@ where
     validators: Arc<RwLock<Option<Validators>>>,
     /// Cache for mapping validator public keys to peer IDs
     validator_peer_id_cache: Arc<RwLock<BTreeMap<Address, CacheState<N::PeerId>>>>,
+    /// Lookup statistics per validator address, reset when the validator leaves the active set
+    resolution_stats: Arc<RwLock<BTreeMap<Address, ResolutionStats>>>,
+    /// Relays of the validators running in private mode, as found in their validator records
+    validator_relays: Arc<RwLock<BTreeMap<Address, Vec<N::PeerId>>>>,
     dht_fallback: Arc<DhtFallback<N>>,
 }
 
@@ -87,6 +133,8 @@ where
             own_validator_id: Arc::new(RwLock::new(None)),
             validators: Arc::new(RwLock::new(None)),
             validator_peer_id_cache: Arc::new(RwLock::new(BTreeMap::new())),
+            resolution_stats: Arc::new(RwLock::new(BTreeMap::new())),
+            validator_relays: Arc::new(RwLock::new(BTreeMap::new())),
             dht_fallback,
         }
     }
@@ -98,6 +146,8 @@ where
             own_validator_id: Arc::clone(&self.own_validator_id),
             validators: Arc::clone(&self.validators),
             validator_peer_id_cache: Arc::clone(&self.validator_peer_id_cache),
+            resolution_stats: Arc::clone(&self.resolution_stats),
+            validator_relays: Arc::clone(&self.validator_relays),
             dht_fallback: Arc::clone(&self.dht_fallback),
         }
     }
@@ -122,11 +172,15 @@ where
         network: &N,
         validator_address: &Address,
         fallback: Arc<DhtFallback<N>>,
-    ) -> Result<Option<N::PeerId>, NetworkError<N::Error>> {
+    ) -> Result<Option<ResolvedPeer<N::PeerId>>, NetworkError<N::Error>> {
         let result = Self::resolve_peer_id_dht(network, validator_address).await;
         if !matches!(result, Ok(Some(_))) {
             if let Some(peer_id) = fallback(validator_address.clone()).await {
-                return Ok(Some(peer_id));
+                return Ok(Some(ResolvedPeer {
+                    peer_id,
+                    record_timestamp: None,
+                    relays: vec![],
+                }));
             }
         }
         result
@@ -135,12 +189,16 @@ where
     async fn resolve_peer_id_dht(
         network: &N,
         validator_address: &Address,
-    ) -> Result<Option<N::PeerId>, NetworkError<N::Error>> {
+    ) -> Result<Option<ResolvedPeer<N::PeerId>>, NetworkError<N::Error>> {
         if let Some(record) = network
             .dht_get::<_, ValidatorRecord<N::PeerId>, KeyPair>(validator_address)
             .await?
         {
-            Ok(Some(record.peer_id))
+            Ok(Some(ResolvedPeer {
+                peer_id: record.peer_id,
+                record_timestamp: Some(record.timestamp),
+                relays: record.relays,
+            }))
         } else {
             Ok(None)
         }
@@ -161,14 +219,21 @@ where
         )
         .await
         {
-            Ok(Some(peer_id)) => {
+            Ok(Some(resolved)) => {
                 log::trace!(
-                    %peer_id,
+                    peer_id = %resolved.peer_id,
                     validator_id,
                     %validator_address,
+                    num_relays = resolved.relays.len(),
                     "Resolved validator peer ID"
                 );
-                Ok(peer_id)
+                // Tell the network how to reach validators that don't accept connections.
+                self.network
+                    .set_peer_relays(resolved.peer_id, resolved.relays.clone());
+                self.validator_relays
+                    .write()
+                    .insert(validator_address.clone(), resolved.relays);
+                Ok((resolved.peer_id, resolved.record_timestamp))
             }
             Ok(None) => {
                 log::debug!(validator_id, %validator_address, "Unable to resolve validator peer ID: Entry not found in DHT");
@@ -185,6 +250,20 @@ where
             }
         };
 
+        {
+            let mut resolution_stats = self.resolution_stats.write();
+            let stats = resolution_stats
+                .entry(validator_address.clone())
+                .or_default();
+            match cache_value {
+                Ok((_, record_timestamp)) => {
+                    stats.last_resolved = Some(unix_time_millis());
+                    stats.record_timestamp = record_timestamp;
+                }
+                Err(()) => stats.failures += 1,
+            }
+        }
+
         match self
             .validator_peer_id_cache
             .write()
@@ -193,7 +272,7 @@ where
             Some(cache_entry) => {
                 if let CacheState::InProgress(prev_peer_id) = *cache_entry {
                     *cache_entry = match cache_value {
-                        Ok(peer_id) => CacheState::Resolved(peer_id),
+                        Ok((peer_id, _)) => CacheState::Resolved(peer_id),
                         Err(()) => CacheState::Error(prev_peer_id),
                     };
                 } else {
@@ -265,6 +344,28 @@ where
         new_cache_state
     }
 
+    /// Checks that a message claiming to come from `validator_id` was received from the
+    /// validator's peer or, if it runs in private mode, from one of its relays.
+    fn is_from_validator(&self, validator_id: u16, peer_id: &N::PeerId) -> bool {
+        let Some(validator_peer_id) = self
+            .get_validator_cache(validator_id)
+            .potentially_outdated_peer_id()
+        else {
+            return false;
+        };
+        if validator_peer_id == *peer_id {
+            return true;
+        }
+
+        let validators = self.validators.read();
+        Self::get_validator(validators.as_ref(), validator_id).is_some_and(|validator| {
+            self.validator_relays
+                .read()
+                .get(&validator.address)
+                .is_some_and(|relays| relays.contains(peer_id))
+        })
+    }
+
     /// Clears the validator->peer_id cache on a `RequestError`.
     /// The cached entry should be cleared when the peer id might have changed.
     fn clear_validator_peer_id_cache_on_error(
@@ -372,6 +473,18 @@ where
                 Some(key) == cur_key
             });
 
+        let addresses: BTreeSet<_> = validators
+            .validators
+            .iter()
+            .map(|validator| &validator.address)
+            .collect();
+        self.resolution_stats
+            .write()
+            .retain(|address, _| addresses.contains(address));
+        self.validator_relays
+            .write()
+            .retain(|address, _| addresses.contains(address));
+
         *self.validators.write() = Some(validators.clone());
     }
 
@@ -439,14 +552,10 @@ where
                 .filter_map(move |(message, peer_id)| {
                     let self_ = self_.arc_clone();
                     async move {
-                        let validator_peer_id = self_.get_validator_cache(message.validator_id).potentially_outdated_peer_id();
                         // Check that each message actually comes from the peer that it
                         // claims it comes from. Reject it otherwise.
-                        if validator_peer_id
-                            .as_ref()
-                            .map(|pid| *pid != peer_id)
-                            .unwrap_or(true)
-                        {
+                        if !self_.is_from_validator(message.validator_id, &peer_id) {
+                            let validator_peer_id = self_.get_peer_id(message.validator_id);
                             warn!(%peer_id, ?validator_peer_id, claimed_validator_id = message.validator_id, "Dropping validator message");
                             return None;
                         }
@@ -466,14 +575,10 @@ where
             .filter_map(move |(message, request_id, peer_id)| {
                 let self_ = self_.arc_clone();
                 async move {
-                    let validator_peer_id = self_.get_validator_cache(message.validator_id).potentially_outdated_peer_id();
                     // Check that each message actually comes from the peer that it
                     // claims it comes from. Reject it otherwise.
-                    if validator_peer_id
-                        .as_ref()
-                        .map(|pid| *pid != peer_id)
-                        .unwrap_or(true)
-                    {
+                    if !self_.is_from_validator(message.validator_id, &peer_id) {
+                        let validator_peer_id = self_.get_peer_id(message.validator_id);
                         warn!(%peer_id, ?validator_peer_id, claimed_validator_id = message.validator_id, "Dropping validator request");
                         return None;
                     }
@@ -524,7 +629,8 @@ where
         let record = ValidatorRecord::new(
             peer_id,
             validator_address.clone(),
-            (OffsetDateTime::now_utc().unix_timestamp_nanos() / 1_000_000) as u64,
+            unix_time_millis(),
+            self.network.get_local_relays(),
         );
         self.network
             .dht_put(validator_address, &record, signing_key_pair)
@@ -549,3 +655,43 @@ where
             .potentially_outdated_peer_id()
     }
 }
+
+impl<N> ValidatorNetworkStatus for ValidatorNetworkImpl<N>
+where
+    N: Network,
+    N::PeerId: Serialize + Deserialize,
+{
+    fn connection_statuses(&self) -> Vec<ValidatorConnectionStatus> {
+        let validators = self.validators.read();
+        let Some(validators) = validators.as_ref() else {
+            return vec![];
+        };
+        let own_validator_id = *self.own_validator_id.read();
+        let validator_peer_id_cache = self.validator_peer_id_cache.read();
+        let resolution_stats = self.resolution_stats.read();
+
+        (0..validators.num_validators() as u16)
+            .filter(|&validator_id| Some(validator_id) != own_validator_id)
+            .map(|validator_id| {
+                let address = &validators.get_validator_by_slot_band(validator_id).address;
+                let cache_state = validator_peer_id_cache.get(address);
+                let peer_id = cache_state.and_then(CacheState::potentially_outdated_peer_id);
+                let stats = resolution_stats.get(address).copied().unwrap_or_default();
+
+                ValidatorConnectionStatus {
+                    validator_id,
+                    address: address.clone(),
+                    peer_id: peer_id.map(|peer_id| peer_id.to_string()),
+                    resolution: cache_state
+                        .map(CacheState::resolution)
+                        .unwrap_or(PeerIdResolution::Unknown),
+                    connected: peer_id.is_some_and(|peer_id| self.network.has_peer(peer_id)),
+                    last_resolved: stats.last_resolved,
+                    record_timestamp: stats.record_timestamp,
+                    resolution_failures: stats.failures,
+                    rtt: peer_id.and_then(|peer_id| self.network.get_peer_rtt(peer_id)),
+                }
+            })
+            .collect()
+    }
+}
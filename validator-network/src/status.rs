@@ -0,0 +1,49 @@
+use std::time::Duration;
+
+use nimiq_keys::Address;
+
+/// The state of the lookup of a validator's peer ID.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PeerIdResolution {
+    /// The peer ID hasn't been looked up yet.
+    Unknown,
+    /// The peer ID is currently being looked up.
+    InProgress,
+    /// The peer ID was resolved.
+    Resolved,
+    /// The last lookup failed.
+    Failed,
+    /// A request to the resolved peer ID failed, so it might be outdated.
+    Outdated,
+}
+
+/// The connection health of an elected validator, as seen by the local node.
+#[derive(Clone, Debug)]
+pub struct ValidatorConnectionStatus {
+    /// The ID (slot band) of the validator.
+    pub validator_id: u16,
+    pub address: Address,
+    /// The last known peer ID of the validator, which might be outdated.
+    pub peer_id: Option<String>,
+    pub resolution: PeerIdResolution,
+    /// Whether we are connected to the last known peer ID.
+    pub connected: bool,
+    /// The time at which the peer ID was last resolved, in milliseconds since the unix epoch.
+    pub last_resolved: Option<u64>,
+    /// The timestamp of the last resolved validator record, in milliseconds since the unix
+    /// epoch. `None` if the peer ID was obtained from the DHT fallback.
+    pub record_timestamp: Option<u64>,
+    /// The number of failed peer ID lookups during the current epoch.
+    pub resolution_failures: u64,
+    /// The round-trip time of the last ping to the validator.
+    pub rtt: Option<Duration>,
+}
+
+/// Reports the connection health of the elected validators.
+///
+/// Unlike [`ValidatorNetwork`](crate::ValidatorNetwork), this trait is object safe, so it can be
+/// handed to the RPC and metrics servers without knowing the network type.
+pub trait ValidatorNetworkStatus: Send + Sync {
+    /// Returns the status of every elected validator except ourselves, ordered by validator ID.
+    fn connection_statuses(&self) -> Vec<ValidatorConnectionStatus>;
+}
@@ -22,17 +22,26 @@ where
     pub validator_address: Address,
     /// Record timestamp in milliseconds since 1970-01-01 00:00:00 UTC, excluding leap seconds (Unix time)
     pub timestamp: u64,
+    /// Peers relaying the validator's traffic if it runs in private mode and can't be reached
+    /// directly. These are also allowed to publish the record on its behalf.
+    pub relays: Vec<TPeerId>,
 }
 
 impl<TPeerId> ValidatorRecord<TPeerId>
 where
     TPeerId: Serialize + Deserialize,
 {
-    pub fn new(peer_id: TPeerId, validator_address: Address, timestamp: u64) -> Self {
+    pub fn new(
+        peer_id: TPeerId,
+        validator_address: Address,
+        timestamp: u64,
+        relays: Vec<TPeerId>,
+    ) -> Self {
         Self {
             peer_id,
             validator_address,
             timestamp,
+            relays,
         }
     }
 }
@@ -36,7 +36,7 @@ use nimiq_network_interface::{
 use nimiq_primitives::{coin::Coin, policy::Policy};
 use nimiq_transaction_builder::TransactionBuilder;
 use nimiq_utils::spawn;
-use nimiq_validator_network::{PubsubId, ValidatorNetwork};
+use nimiq_validator_network::{PubsubId, ValidatorNetwork, ValidatorNetworkStatus};
 use parking_lot::RwLock;
 #[cfg(feature = "metrics")]
 use tokio_metrics::TaskMonitor;
@@ -76,6 +76,7 @@ pub struct ValidatorProxy {
     pub automatic_reactivate: Arc<AtomicBool>,
     pub slot_band: Arc<RwLock<Option<u16>>>,
     pub consensus_state: Arc<RwLock<ConsensusState>>,
+    pub network_status: Arc<dyn ValidatorNetworkStatus>,
 }
 
 impl Clone for ValidatorProxy {
@@ -88,6 +89,7 @@ impl Clone for ValidatorProxy {
             automatic_reactivate: Arc::clone(&self.automatic_reactivate),
             slot_band: Arc::clone(&self.slot_band),
             consensus_state: Arc::clone(&self.consensus_state),
+            network_status: Arc::clone(&self.network_status),
         }
     }
 }
@@ -184,7 +186,12 @@ where
             consensus.proxy(),
         );
 
-        let mempool = MempoolTask::new(consensus, Arc::clone(&blockchain), mempool_config);
+        let mempool = MempoolTask::new(
+            consensus,
+            Arc::clone(&blockchain),
+            env.clone(),
+            mempool_config,
+        );
 
         let automatic_reactivate = Arc::new(AtomicBool::new(automatic_reactivate));
 
@@ -737,6 +744,7 @@ where
             automatic_reactivate: Arc::clone(&self.automatic_reactivate),
             slot_band: Arc::clone(&self.slot_band),
             consensus_state: Arc::clone(&self.consensus_state),
+            network_status: Arc::clone(&self.network) as Arc<dyn ValidatorNetworkStatus>,
         }
     }
 
@@ -28,6 +28,7 @@ use nimiq_utils::spawn;
 use nimiq_validator::aggregation::{
     skip_block::SignedSkipBlockMessage, update::SerializableLevelUpdate,
 };
+use nimiq_validator_network::{status::PeerIdResolution, ValidatorNetworkStatus};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -138,6 +139,49 @@ async fn four_validators_can_create_micro_blocks() {
     assert!(blockchain.read().block_number() >= 30 + Policy::genesis_block_number());
 }
 
+#[test(tokio::test)]
+async fn validators_report_connection_status() {
+    let hub = MockHub::default();
+    let env =
+        MdbxDatabase::new_volatile(Default::default()).expect("Could not open a volatile database");
+
+    let validators = build_validators::<MockNetwork>(
+        env,
+        &(1u64..=4u64).collect::<Vec<_>>(),
+        &mut Some(hub),
+        false,
+    )
+    .await;
+
+    let blockchain = Arc::clone(&validators.first().unwrap().blockchain);
+    let validator_network = Arc::clone(&validators.first().unwrap().network);
+
+    for validator in validators {
+        spawn(validator);
+    }
+
+    // The first macro block requires the validators to talk to each other.
+    let macro_block_number = Policy::genesis_block_number() + Policy::blocks_per_batch();
+    let events = blockchain.read().notifier_as_stream();
+    timeout(
+        Duration::from_secs(60),
+        events
+            .filter(|_| future::ready(blockchain.read().block_number() >= macro_block_number))
+            .next(),
+    )
+    .await
+    .unwrap();
+
+    let statuses = validator_network.connection_statuses();
+    assert_eq!(statuses.len(), 3);
+    for status in statuses {
+        assert_ne!(status.resolution, PeerIdResolution::Unknown);
+        assert!(status.peer_id.is_some());
+        assert!(status.connected);
+        assert!(status.record_timestamp.is_some());
+    }
+}
+
 #[test(tokio::test)]
 async fn validators_can_do_skip_block() {
     let env =
@@ -1,6 +1,6 @@
 use nimiq_database::{
     declare_table,
-    mdbx::{MdbxDatabase, MdbxReadTransaction, MdbxWriteTransaction, OptionalTransaction},
+    mdbx::MdbxDatabase,
     traits::{Database, ReadCursor, ReadTransaction, WriteTransaction},
 };
 use nimiq_keys::Address;
@@ -10,14 +10,16 @@ use crate::wallet_account::WalletAccount;
 
 declare_table!(WalletTable, "Wallet", Address => Locked<WalletAccount>);
 
+/// Stores the locked wallet accounts. It works with any database backend, e.g. an in-memory
+/// database in tests, and uses MDBX by default.
 #[derive(Debug)]
-pub struct WalletStore {
-    env: MdbxDatabase,
+pub struct WalletStore<D: Database = MdbxDatabase> {
+    env: D,
     table: WalletTable,
 }
 
-impl WalletStore {
-    pub fn new(env: MdbxDatabase) -> Self {
+impl<D: Database> WalletStore<D> {
+    pub fn new(env: D) -> Self {
         let wallet_table = WalletTable;
         env.create_regular_table(&wallet_table);
         WalletStore {
@@ -26,43 +28,50 @@ impl WalletStore {
         }
     }
 
-    pub fn create_read_transaction(&self) -> MdbxReadTransaction {
+    pub fn create_read_transaction(&self) -> D::ReadTransaction<'_> {
         self.env.read_transaction()
     }
 
-    pub fn create_write_transaction(&self) -> MdbxWriteTransaction {
+    pub fn create_write_transaction(&self) -> D::WriteTransaction<'_> {
         self.env.write_transaction()
     }
 
-    pub fn list(&self, txn_option: Option<&MdbxReadTransaction>) -> Vec<Address> {
-        let txn = txn_option.or_new(&self.env);
-
-        let cursor = txn.cursor(&self.table);
-        cursor
-            .into_iter_start()
-            .map(|(address, _)| address)
-            .collect()
+    pub fn list<'db>(&'db self, txn_option: Option<&D::ReadTransaction<'db>>) -> Vec<Address> {
+        match txn_option {
+            Some(txn) => self.list_in(txn),
+            None => self.list_in(&self.env.read_transaction()),
+        }
     }
 
-    pub fn get(
-        &self,
+    pub fn get<'db>(
+        &'db self,
         address: &Address,
-        txn_option: Option<&MdbxReadTransaction>,
+        txn_option: Option<&D::ReadTransaction<'db>>,
     ) -> Option<Locked<WalletAccount>> {
-        let txn = txn_option.or_new(&self.env);
-        txn.get(&self.table, address)
+        match txn_option {
+            Some(txn) => txn.get(&self.table, address),
+            None => self.env.read_transaction().get(&self.table, address),
+        }
     }
 
-    pub fn put(
-        &self,
+    pub fn put<'db>(
+        &'db self,
         address: &Address,
         wallet: &Locked<WalletAccount>,
-        txn: &mut MdbxWriteTransaction,
+        txn: &mut D::WriteTransaction<'db>,
     ) {
         txn.put_reserve(&self.table, address, wallet);
     }
 
-    pub fn remove(&self, address: &Address, txn: &mut MdbxWriteTransaction) {
+    pub fn remove<'db>(&'db self, address: &Address, txn: &mut D::WriteTransaction<'db>) {
         txn.remove(&self.table, address);
     }
+
+    fn list_in<'db>(&self, txn: &impl ReadTransaction<'db>) -> Vec<Address> {
+        let cursor = txn.cursor(&self.table);
+        cursor
+            .into_iter_start()
+            .map(|(address, _)| address)
+            .collect()
+    }
 }
@@ -1,8 +1,10 @@
+use nimiq_database::{memory::MemoryDatabase, traits::WriteTransaction};
 use nimiq_keys::{Address, KeyPair, PrivateKey};
 use nimiq_primitives::{coin::Coin, networks::NetworkId};
 use nimiq_serde::{Deserialize, Serialize};
 use nimiq_test_log::test;
-use nimiq_wallet::WalletAccount;
+use nimiq_utils::otp::Locked;
+use nimiq_wallet::{WalletAccount, WalletStore};
 
 /// This is an example for using doc comment attributes
 ///
@@ -43,3 +45,42 @@ fn test_serialize_deserialize() {
         }
     }
 }
+
+#[test]
+fn test_wallet_store_in_memory() {
+    let store = WalletStore::new(MemoryDatabase::new());
+    let wallet = wallet();
+    let address = wallet.address.clone();
+    assert!(store.list(None).is_empty());
+
+    // Uncommitted changes are discarded.
+    let mut txn = store.create_write_transaction();
+    store.put(
+        &address,
+        &Locked::with_defaults(wallet.clone(), b"password").unwrap(),
+        &mut txn,
+    );
+    txn.abort();
+    assert!(store.get(&address, None).is_none());
+
+    let mut txn = store.create_write_transaction();
+    store.put(
+        &address,
+        &Locked::with_defaults(wallet.clone(), b"password").unwrap(),
+        &mut txn,
+    );
+    txn.commit();
+    assert_eq!(store.list(None), vec![address.clone()]);
+
+    let unlocked = store
+        .get(&address, None)
+        .unwrap()
+        .unlock(b"password")
+        .unwrap_or_else(|_| panic!("Wrong password"));
+    assert_eq!(*unlocked, wallet);
+
+    let mut txn = store.create_write_transaction();
+    store.remove(&address, &mut txn);
+    txn.commit();
+    assert!(store.list(None).is_empty());
+}
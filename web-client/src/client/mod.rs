@@ -2,3 +2,4 @@ pub mod account;
 pub mod block;
 pub mod lib;
 pub mod peer_info;
+pub mod state_proof;
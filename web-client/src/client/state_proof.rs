@@ -0,0 +1,80 @@
+use std::str::FromStr;
+
+use nimiq_hash::Blake2bHash;
+use nimiq_serde::Deserialize;
+use wasm_bindgen::prelude::*;
+
+use crate::{
+    client::account::{
+        PlainAccount, PlainAccountType, PlainStaker, PlainStakerType, PlainValidator,
+        PlainValidatorType,
+    },
+    common::address::{Address, AddressAnyType},
+};
+
+/// Utility class to verify the proofs returned by the `getAccountProofByAddress`,
+/// `getStakerProofByAddress` and `getValidatorProofByAddress` RPC methods of a full node.
+///
+/// A proof is only as trustworthy as the block hash it is verified against, so the hash must come
+/// from a source the caller already trusts, e.g. the head of this client or its latest election
+/// block. Proofs against past blocks can only be requested from history nodes, and only as long as
+/// the block hasn't been pruned.
+#[wasm_bindgen]
+pub struct StateProof;
+
+#[wasm_bindgen]
+impl StateProof {
+    /// Verifies the hex-encoded proof of an account against a trusted block hash and returns the
+    /// proven account.
+    ///
+    /// Throws if the proof is invalid or was created against a different block.
+    #[wasm_bindgen(js_name = verifyAccount)]
+    pub fn verify_account(
+        proof: &str,
+        block_hash: &str,
+        address: &AddressAnyType,
+    ) -> Result<PlainAccountType, JsError> {
+        let address = Address::from_any(address)?.take_native();
+        let account = StateProof::parse(proof)?
+            .verify_account(&Blake2bHash::from_str(block_hash)?, &address)?;
+        Ok(serde_wasm_bindgen::to_value(&PlainAccount::from(&account))?.into())
+    }
+
+    /// Verifies the hex-encoded proof of a staker against a trusted block hash and returns the
+    /// proven staker, or `undefined` if the proof shows that the staker doesn't exist.
+    ///
+    /// Throws if the proof is invalid or was created against a different block.
+    #[wasm_bindgen(js_name = verifyStaker)]
+    pub fn verify_staker(
+        proof: &str,
+        block_hash: &str,
+        address: &AddressAnyType,
+    ) -> Result<PlainStakerType, JsError> {
+        let address = Address::from_any(address)?.take_native();
+        let staker = StateProof::parse(proof)?
+            .verify_staker(&Blake2bHash::from_str(block_hash)?, &address)?;
+        Ok(serde_wasm_bindgen::to_value(&staker.as_ref().map(PlainStaker::from))?.into())
+    }
+
+    /// Verifies the hex-encoded proof of a validator against a trusted block hash and returns the
+    /// proven validator, or `undefined` if the proof shows that the validator doesn't exist.
+    ///
+    /// Throws if the proof is invalid or was created against a different block.
+    #[wasm_bindgen(js_name = verifyValidator)]
+    pub fn verify_validator(
+        proof: &str,
+        block_hash: &str,
+        address: &AddressAnyType,
+    ) -> Result<PlainValidatorType, JsError> {
+        let address = Address::from_any(address)?.take_native();
+        let validator = StateProof::parse(proof)?
+            .verify_validator(&Blake2bHash::from_str(block_hash)?, &address)?;
+        Ok(serde_wasm_bindgen::to_value(&validator.as_ref().map(PlainValidator::from))?.into())
+    }
+}
+
+impl StateProof {
+    fn parse(proof: &str) -> Result<nimiq_blockchain_interface::StateProof, JsError> {
+        Ok(nimiq_blockchain_interface::StateProof::deserialize_from_vec(&hex::decode(proof)?)?)
+    }
+}
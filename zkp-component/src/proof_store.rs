@@ -1,12 +1,20 @@
+#[cfg(feature = "database-storage")]
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
 #[cfg(feature = "database-storage")]
 use nimiq_database::{
     declare_table,
     mdbx::MdbxDatabase,
-    traits::{Database, ReadTransaction, WriteTransaction},
+    traits::{Database, ReadCursor, ReadTransaction, WriteCursor, WriteTransaction},
 };
+use parking_lot::Mutex;
 
 use crate::types::*;
 
+/// A proof store that is shared between the ZKP component and its request handlers.
+pub type SharedProofStore = Arc<Mutex<Box<dyn ProofStore>>>;
+
 /// Defines an interface for storing and retrieving ZK proofs.
 pub trait ProofStore: Send {
     /// Gets a ZK proof.
@@ -14,11 +22,29 @@ pub trait ProofStore: Send {
 
     /// Sets or stores a ZK proof.
     fn set_zkp(&self, zk_proof: &ZKProof);
+
+    /// Gets the most recent stored ZK proof for an election block at or below the given block
+    /// number. Stores that only keep the latest proof return it if it is old enough.
+    fn get_zkp_up_to(&self, block_number: u32) -> Option<ZKProof> {
+        self.get_zkp()
+            .filter(|zk_proof| zk_proof.block_number <= block_number)
+    }
+
+    /// Returns the election block numbers of all stored ZK proofs in ascending order.
+    fn get_zkp_block_numbers(&self) -> Vec<u32> {
+        self.get_zkp()
+            .map(|zk_proof| zk_proof.block_number)
+            .into_iter()
+            .collect()
+    }
 }
 
 #[cfg(feature = "database-storage")]
 declare_table!(ZKProofTable, "ZKPState", () => ZKProof);
 
+#[cfg(feature = "database-storage")]
+declare_table!(ZKProofArchiveTable, "ZKPArchive", u32 => ZKProof);
+
 #[cfg(feature = "database-storage")]
 /// DB implementation of a ProofStore meant for persistent storage
 #[derive(Debug)]
@@ -48,3 +74,95 @@ impl ProofStore for DBProofStore {
         tx.commit();
     }
 }
+
+#[cfg(feature = "database-storage")]
+/// DB implementation of a ProofStore that keeps the proofs of past election blocks in addition to
+/// the latest one. This allows peers to bootstrap to an election block other than the latest one.
+#[derive(Debug)]
+pub struct DBProofArchive {
+    /// Environment for the DB creation and transaction handling.
+    env: MdbxDatabase,
+    /// The maximum number of proofs that are kept. The oldest proofs are pruned first.
+    /// All proofs are kept if this is `None`.
+    max_proofs: Option<usize>,
+    /// The number of proofs in the archive table.
+    num_proofs: AtomicUsize,
+}
+
+#[cfg(feature = "database-storage")]
+impl DBProofArchive {
+    pub fn new(env: MdbxDatabase, max_proofs: Option<usize>) -> Self {
+        env.create_regular_table(&ZKProofTable);
+        env.create_regular_table(&ZKProofArchiveTable);
+
+        // Count the stored proofs once, afterwards the count is maintained when storing proofs.
+        let num_proofs = env
+            .read_transaction()
+            .cursor(&ZKProofArchiveTable)
+            .into_iter_start()
+            .count();
+
+        Self {
+            env,
+            max_proofs,
+            num_proofs: AtomicUsize::new(num_proofs),
+        }
+    }
+}
+
+#[cfg(feature = "database-storage")]
+impl ProofStore for DBProofArchive {
+    fn get_zkp(&self) -> Option<ZKProof> {
+        self.env.read_transaction().get(&ZKProofTable, &())
+    }
+
+    fn set_zkp(&self, zk_proof: &ZKProof) {
+        let mut tx = self.env.write_transaction();
+        tx.put(&ZKProofTable, &(), zk_proof);
+
+        let mut num_proofs = self.num_proofs.load(Ordering::Acquire);
+        if tx
+            .get(&ZKProofArchiveTable, &zk_proof.block_number)
+            .is_none()
+        {
+            num_proofs += 1;
+        }
+        tx.put(&ZKProofArchiveTable, &zk_proof.block_number, zk_proof);
+
+        // Prune the oldest proofs. Proofs are stored in ascending order, so the latest proof is
+        // never pruned.
+        if let Some(max_proofs) = self.max_proofs {
+            let mut cursor = WriteTransaction::cursor(&tx, &ZKProofArchiveTable);
+            let mut entry = cursor.first();
+            while entry.is_some() && num_proofs > max_proofs.max(1) {
+                cursor.remove();
+                num_proofs -= 1;
+                entry = cursor.next();
+            }
+        }
+
+        tx.commit();
+        self.num_proofs.store(num_proofs, Ordering::Release);
+    }
+
+    fn get_zkp_up_to(&self, block_number: u32) -> Option<ZKProof> {
+        let tx = self.env.read_transaction();
+        let mut cursor = tx.cursor(&ZKProofArchiveTable);
+
+        // Position the cursor after the given block number and step back.
+        let entry = match block_number.checked_add(1) {
+            Some(next) if cursor.set_lowerbound_key(&next).is_some() => cursor.prev(),
+            _ => cursor.last(),
+        };
+        entry.map(|(_, zk_proof)| zk_proof)
+    }
+
+    fn get_zkp_block_numbers(&self) -> Vec<u32> {
+        self.env
+            .read_transaction()
+            .cursor(&ZKProofArchiveTable)
+            .into_iter_start()
+            .map(|(block_number, _)| block_number)
+            .collect()
+    }
+}
@@ -11,12 +11,13 @@ use nimiq_network_interface::{
     network::{Network, Topic},
     request::{Handle, RequestCommon, RequestError, RequestMarker},
 };
+use nimiq_primitives::policy::Policy;
 use nimiq_serde::{Deserialize, DeserializeError, Serialize};
 use nimiq_zkp_primitives::NanoZKPError;
 use parking_lot::RwLock;
 use thiserror::Error;
 
-use crate::ZKPComponent;
+use crate::{proof_store::SharedProofStore, ZKPComponent};
 
 pub const PROOF_GENERATION_OUTPUT_DELIMITER: [u8; 2] = [242, 208];
 
@@ -161,6 +162,9 @@ pub enum Error {
     #[error("Response was invalid: requested_block: {0}")]
     InvalidResponse(bool),
 
+    #[error("Proof's block is more recent than the requested block: {0}")]
+    UnrequestedBlock(u32),
+
     #[error("Outdated proof")]
     OutdatedProof,
 
@@ -228,6 +232,25 @@ impl RequestCommon for RequestZKP {
     const MAX_REQUESTS: u32 = MAX_REQUEST_RESPONSE_ZKP;
 }
 
+/// The request of a zkp for an election block up to a given block height. This allows to bootstrap to an election block
+/// other than the latest one, given that the peer keeps historical proofs.
+/// The response should either have the most recent proof that is more recent than block_number and not more recent
+/// than up_to_block_number or None.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RequestZKPUpTo {
+    pub(crate) block_number: u32,
+    pub(crate) up_to_block_number: u32,
+    pub(crate) request_election_block: bool,
+}
+
+impl RequestCommon for RequestZKPUpTo {
+    type Kind = RequestMarker;
+    const TYPE_ID: u16 = 219;
+    type Response = RequestZKPResponse;
+
+    const MAX_REQUESTS: u32 = MAX_REQUEST_RESPONSE_ZKP;
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[repr(u8)]
 pub enum RequestZKPResponse {
@@ -239,6 +262,7 @@ pub enum RequestZKPResponse {
 pub(crate) struct ZKPStateEnvironment {
     pub(crate) zkp_state: Arc<RwLock<ZKPState>>,
     pub(crate) blockchain: BlockchainProxy,
+    pub(crate) proof_storage: Option<SharedProofStore>,
 }
 
 impl<N: Network> From<&ZKPComponent<N>> for ZKPStateEnvironment {
@@ -246,6 +270,7 @@ impl<N: Network> From<&ZKPComponent<N>> for ZKPStateEnvironment {
         ZKPStateEnvironment {
             zkp_state: Arc::clone(&component.zkp_state),
             blockchain: component.blockchain.clone(),
+            proof_storage: component.proof_storage.clone(),
         }
     }
 }
@@ -275,6 +300,43 @@ impl<N: Network> Handle<N, Arc<ZKPStateEnvironment>> for RequestZKP {
     }
 }
 
+impl<N: Network> Handle<N, Arc<ZKPStateEnvironment>> for RequestZKPUpTo {
+    fn handle(&self, _peer_id: N::PeerId, env: &Arc<ZKPStateEnvironment>) -> RequestZKPResponse {
+        // First retrieve the ZKP proof and release the lock again.
+        let zkp_state = env.zkp_state.read();
+        let latest_proof: ZKProof = (*zkp_state).clone().into();
+        drop(zkp_state);
+
+        // Proofs older than our current state are served from the proof storage.
+        let zkp_proof = if self.up_to_block_number < latest_proof.block_number {
+            env.proof_storage
+                .as_ref()
+                .and_then(|proof_storage| {
+                    proof_storage.lock().get_zkp_up_to(self.up_to_block_number)
+                })
+                .unwrap_or_else(|| ZKProof::new(Policy::genesis_block_number(), None))
+        } else {
+            latest_proof
+        };
+        let proof_block_number = zkp_proof.block_number;
+        if proof_block_number <= self.block_number {
+            return RequestZKPResponse::Outdated(proof_block_number);
+        }
+
+        // Then get the corresponding block if necessary.
+        let block = if self.request_election_block {
+            env.blockchain
+                .read()
+                .get_block_at(proof_block_number, false)
+                .ok()
+                .map(|block| block.unwrap_macro())
+        } else {
+            None
+        };
+        RequestZKPResponse::Proof(zkp_proof, block)
+    }
+}
+
 mod serde_derive {
 
     use std::fmt;
@@ -22,7 +22,12 @@ use tokio_stream::wrappers::BroadcastStream;
 
 #[cfg(feature = "zkp-prover")]
 use crate::zkp_prover::ZKProver;
-use crate::{proof_store::ProofStore, proof_utils::*, types::*, zkp_requests::ZKPRequests};
+use crate::{
+    proof_store::{ProofStore, SharedProofStore},
+    proof_utils::*,
+    types::*,
+    zkp_requests::ZKPRequests,
+};
 
 pub type ZKProofsStream<N> = BoxStream<'static, (ZKProof, <N as Network>::PubsubId)>;
 
@@ -31,6 +36,7 @@ const BROADCAST_MAX_CAPACITY: usize = 256;
 pub struct ZKPComponentProxy<N: Network> {
     network: Arc<N>,
     zkp_state: Arc<RwLock<ZKPState>>,
+    proof_storage: Option<SharedProofStore>,
     zkp_requests: Arc<Mutex<ZKPRequests<N>>>,
     pub(crate) zkp_events_notifier: broadcast::Sender<ZKPEvent<N>>,
 }
@@ -40,6 +46,7 @@ impl<N: Network> Clone for ZKPComponentProxy<N> {
         Self {
             network: Arc::clone(&self.network),
             zkp_state: Arc::clone(&self.zkp_state),
+            proof_storage: self.proof_storage.clone(),
             zkp_requests: Arc::clone(&self.zkp_requests),
             zkp_events_notifier: self.zkp_events_notifier.clone(),
         }
@@ -77,13 +84,52 @@ impl<N: Network> ZKPComponentProxy<N> {
         request_election_block: bool,
     ) -> (Result<Result<ZKPRequestEvent, Error>, RecvError>, N::PeerId) {
         let block_number = self.zkp_state.read().latest_block.block_number();
-        let request =
-            self.zkp_requests
-                .lock()
-                .request_zkp(peer_id, block_number, request_election_block);
+        let request = self.zkp_requests.lock().request_zkp(
+            peer_id,
+            block_number,
+            request_election_block,
+            None,
+        );
+        (request.await, peer_id)
+    }
+
+    /// Requests the most recent zkp for an election block at or below `up_to_block_number` from the given peer.
+    /// This allows to bootstrap to an election block other than the latest one, given that the peer keeps
+    /// historical proofs.
+    pub async fn request_zkp_up_to_from_peer(
+        &self,
+        peer_id: N::PeerId,
+        up_to_block_number: u32,
+        request_election_block: bool,
+    ) -> (Result<Result<ZKPRequestEvent, Error>, RecvError>, N::PeerId) {
+        let block_number = self.zkp_state.read().latest_block.block_number();
+        let request = self.zkp_requests.lock().request_zkp(
+            peer_id,
+            block_number,
+            request_election_block,
+            Some(up_to_block_number),
+        );
         (request.await, peer_id)
     }
 
+    /// Returns the election block numbers of all proofs in our proof storage in ascending order.
+    /// Unless historical proofs are kept, this contains only the latest proof.
+    pub fn get_stored_zkp_block_numbers(&self) -> Vec<u32> {
+        self.proof_storage
+            .as_ref()
+            .map(|proof_storage| proof_storage.lock().get_zkp_block_numbers())
+            .unwrap_or_default()
+    }
+
+    /// Gets the proof for the given election block from our proof storage.
+    pub fn get_stored_zkp(&self, block_number: u32) -> Option<ZKProof> {
+        self.proof_storage
+            .as_ref()?
+            .lock()
+            .get_zkp_up_to(block_number)
+            .filter(|zk_proof| zk_proof.block_number == block_number)
+    }
+
     pub fn subscribe_zkps(&self) -> BroadcastStream<ZKPEvent<N>> {
         BroadcastStream::new(self.zkp_events_notifier.subscribe())
     }
@@ -99,7 +145,7 @@ impl<N: Network> ZKPComponentProxy<N> {
 /// - The current zkp state
 /// - The proof generating component that can be activated by a client configuration
 /// - The zkp gossip stream
-/// - The db storage for the current proof
+/// - The db storage for the current proof and, optionally, historical proofs
 /// - The zkp requests component to fetch an up to date proof from our peers
 /// - The zkp events notifies newly stored proofs.
 ///
@@ -111,7 +157,7 @@ pub struct ZKPComponent<N: Network> {
     #[cfg(feature = "zkp-prover")]
     zk_prover: Option<ZKProver<N>>,
     zk_proofs_stream: ZKProofsStream<N>,
-    proof_storage: Option<Box<dyn ProofStore>>,
+    pub(crate) proof_storage: Option<SharedProofStore>,
     zkp_requests: Arc<Mutex<ZKPRequests<N>>>,
     zkp_events_notifier: broadcast::Sender<ZKPEvent<N>>,
 }
@@ -139,7 +185,7 @@ impl<N: Network> ZKPComponent<N> {
             #[cfg(feature = "zkp-prover")]
             zk_prover: None,
             zk_proofs_stream,
-            proof_storage,
+            proof_storage: proof_storage.map(|proof_storage| Arc::new(Mutex::new(proof_storage))),
             zkp_requests: Arc::new(Mutex::new(ZKPRequests::new(network))),
             zkp_events_notifier: broadcast::Sender::new(BROADCAST_MAX_CAPACITY),
         };
@@ -187,8 +233,12 @@ impl<N: Network> ZKPComponent<N> {
 
     /// Launches thread that processes the zkp requests and replies to them.
     fn launch_request_handler(&self) {
-        let stream = self.network.receive_requests::<RequestZKP>();
         let env = Arc::new(ZKPStateEnvironment::from(self));
+
+        let stream = self.network.receive_requests::<RequestZKP>();
+        spawn(Box::pin(request_handler(&self.network, stream, &env)));
+
+        let stream = self.network.receive_requests::<RequestZKPUpTo>();
         spawn(Box::pin(request_handler(&self.network, stream, &env)));
     }
 
@@ -197,6 +247,7 @@ impl<N: Network> ZKPComponent<N> {
         ZKPComponentProxy {
             network: Arc::clone(&self.network),
             zkp_state: Arc::clone(&self.zkp_state),
+            proof_storage: self.proof_storage.clone(),
             zkp_requests: Arc::clone(&self.zkp_requests),
             zkp_events_notifier: self.zkp_events_notifier.clone(),
         }
@@ -214,7 +265,8 @@ impl<N: Network> ZKPComponent<N> {
     /// our state. In case of failure, it replaces the db proof with the current state.
     fn load_proof_from_db(&mut self) {
         if let Some(proof_storage) = &self.proof_storage {
-            if let Some(loaded_proof) = proof_storage.get_zkp() {
+            let loaded_proof = proof_storage.lock().get_zkp();
+            if let Some(loaded_proof) = loaded_proof {
                 let mut this = Pin::new(self);
 
                 if let Err(e) = this.as_mut().push_proof_from_peers(
@@ -227,6 +279,7 @@ impl<N: Network> ZKPComponent<N> {
                     this.proof_storage
                         .as_ref()
                         .unwrap()
+                        .lock()
                         .set_zkp(&this.zkp_state.read().clone().into());
                 } else {
                     log::info!("The zk proof was successfully load from disk");
@@ -263,7 +316,7 @@ impl<N: Network> ZKPComponent<N> {
         // Adds the new proof to storage.
         if let Some(proof_storage) = &self.proof_storage {
             if add_to_storage {
-                proof_storage.set_zkp(&zkp_state_lock.clone().into())
+                proof_storage.lock().set_zkp(&zkp_state_lock.clone().into())
             }
         }
         drop(zkp_state_lock);
@@ -361,7 +414,9 @@ impl<N: Network> Future for ZKPComponent<N> {
                 Poll::Ready(Some((zk_proof, block))) => {
                     log::info!("New ZK Proof generated by us");
                     if let Some(proof_storage) = &self.proof_storage {
-                        proof_storage.set_zkp(&self.zkp_state.read().clone().into());
+                        proof_storage
+                            .lock()
+                            .set_zkp(&self.zkp_state.read().clone().into());
                     }
 
                     _ = self.zkp_events_notifier.send(ZKPEvent::new(
@@ -36,6 +36,7 @@ pub struct ZKPRequests<N: Network + 'static> {
             (
                 N::PeerId,
                 bool,
+                Option<u32>,
                 Option<Sender<Result<ZKPRequestEvent, Error>>>,
                 Result<RequestZKPResponse, RequestError>,
             ),
@@ -64,19 +65,28 @@ impl<N: Network + 'static> ZKPRequests<N> {
         request_election_block: bool,
     ) {
         for peer_id in peers {
-            self.push_request(peer_id, block_number, request_election_block, None);
+            self.push_request(peer_id, block_number, request_election_block, None, None);
         }
     }
 
     /// Requests a ZKP from a single peer and return future with verification result.
+    /// If `up_to_block_number` is given, a `RequestZKPUpTo` is sent and the peer responds with its most recent proof for
+    /// an election block at or below that block number.
     pub fn request_zkp(
         &mut self,
         peer_id: N::PeerId,
         block_number: u32,
         request_election_block: bool,
+        up_to_block_number: Option<u32>,
     ) -> Receiver<Result<ZKPRequestEvent, Error>> {
         let (tx, rx) = channel();
-        self.push_request(peer_id, block_number, request_election_block, Some(tx));
+        self.push_request(
+            peer_id,
+            block_number,
+            request_election_block,
+            up_to_block_number,
+            Some(tx),
+        );
 
         rx
     }
@@ -86,24 +96,43 @@ impl<N: Network + 'static> ZKPRequests<N> {
         peer_id: N::PeerId,
         block_number: u32,
         request_election_block: bool,
+        up_to_block_number: Option<u32>,
         response_channel: Option<Sender<Result<ZKPRequestEvent, Error>>>,
     ) {
         let network = Arc::clone(&self.network);
         self.zkp_request_results.push(
             async move {
+                let result = match up_to_block_number {
+                    Some(up_to_block_number) => {
+                        network
+                            .request::<RequestZKPUpTo>(
+                                RequestZKPUpTo {
+                                    block_number,
+                                    up_to_block_number,
+                                    request_election_block,
+                                },
+                                peer_id,
+                            )
+                            .await
+                    }
+                    None => {
+                        network
+                            .request::<RequestZKP>(
+                                RequestZKP {
+                                    block_number,
+                                    request_election_block,
+                                },
+                                peer_id,
+                            )
+                            .await
+                    }
+                };
                 (
                     peer_id,
                     request_election_block,
+                    up_to_block_number,
                     response_channel,
-                    network
-                        .request::<RequestZKP>(
-                            RequestZKP {
-                                block_number,
-                                request_election_block,
-                            },
-                            peer_id,
-                        )
-                        .await,
+                    result,
                 )
             }
             .boxed(),
@@ -118,7 +147,13 @@ impl<N: Network + 'static> Stream for ZKPRequests<N> {
         // We poll the zkp requests and return the proof.
         while let Poll::Ready(result) = self.zkp_request_results.poll_next_unpin(cx) {
             match result {
-                Some((peer_id, request_election_block, response_channel, result)) => match result {
+                Some((
+                    peer_id,
+                    request_election_block,
+                    up_to_block_number,
+                    response_channel,
+                    result,
+                )) => match result {
                     Ok(RequestZKPResponse::Proof(proof, election_block)) => {
                         // Check that the response is in-line with whether we asked for the election block or not.
                         if (request_election_block && election_block.is_none())
@@ -131,6 +166,14 @@ impl<N: Network + 'static> Stream for ZKPRequests<N> {
                             continue;
                         }
 
+                        // Check that the proof is not more recent than requested.
+                        if up_to_block_number.is_some_and(|up_to| proof.block_number > up_to) {
+                            if let Some(tx) = response_channel {
+                                let _ = tx.send(Err(Error::UnrequestedBlock(proof.block_number)));
+                            }
+                            continue;
+                        }
+
                         return Poll::Ready(Some(ZKPRequestsItem {
                             peer_id,
                             proof,
@@ -14,7 +14,7 @@ use nimiq_test_utils::{
 use nimiq_utils::time::OffsetTime;
 use nimiq_zkp::ZKP_VERIFYING_DATA;
 use nimiq_zkp_component::{
-    proof_store::{DBProofStore, ProofStore},
+    proof_store::{DBProofArchive, DBProofStore, ProofStore},
     proof_utils::validate_proof,
     types::ZKProof,
 };
@@ -182,3 +182,58 @@ async fn can_store_and_load_zkp_state_from_db() {
         "Load from db was not successful"
     );
 }
+
+#[test(tokio::test)]
+async fn can_store_and_prune_historical_zkps() {
+    let env = MdbxDatabase::new_volatile(DatabaseConfig {
+        max_tables: Some(2),
+        ..Default::default()
+    })
+    .unwrap();
+
+    let proof_store = DBProofArchive::new(env.clone(), Some(2));
+    let proofs: Vec<_> = (1..=4)
+        .map(|epoch| ZKProof {
+            block_number: Policy::genesis_block_number() + epoch * Policy::blocks_per_epoch(),
+            proof: Some(Proof::default()),
+        })
+        .collect();
+
+    for proof in &proofs[..3] {
+        proof_store.set_zkp(proof);
+    }
+
+    // The oldest proof has been pruned.
+    assert_eq!(proof_store.get_zkp().unwrap(), proofs[2]);
+    assert_eq!(
+        proof_store.get_zkp_block_numbers(),
+        vec![proofs[1].block_number, proofs[2].block_number]
+    );
+
+    // Historical proofs are returned for any block number up to the next election block.
+    assert_eq!(
+        proof_store.get_zkp_up_to(proofs[2].block_number - 1),
+        Some(proofs[1].clone())
+    );
+    assert_eq!(
+        proof_store.get_zkp_up_to(proofs[2].block_number),
+        Some(proofs[2].clone())
+    );
+    assert_eq!(proof_store.get_zkp_up_to(proofs[1].block_number - 1), None);
+    assert_eq!(proof_store.get_zkp_up_to(u32::MAX), Some(proofs[2].clone()));
+
+    // Storing the same proof again doesn't prune anything.
+    proof_store.set_zkp(&proofs[2]);
+    assert_eq!(
+        proof_store.get_zkp_block_numbers(),
+        vec![proofs[1].block_number, proofs[2].block_number]
+    );
+
+    // The stored proofs are counted when reopening the archive, so pruning continues.
+    let proof_store = DBProofArchive::new(env, Some(2));
+    proof_store.set_zkp(&proofs[3]);
+    assert_eq!(
+        proof_store.get_zkp_block_numbers(),
+        vec![proofs[2].block_number, proofs[3].block_number]
+    );
+}
@@ -61,6 +61,8 @@ fn initialize(network_id: NetworkId) {
     };
     // The genesis block number must be set accordingly
     policy_config.genesis_block_number = genesis_block.block_number();
+    policy_config.vesting_schedule_activation_block =
+        network_info.vesting_schedule_activation_block();
 
     let _ = Policy::get_or_init(policy_config);
 }